@@ -4,14 +4,19 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync;
-use std::{env, ffi, fs, io, mem};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, ffi, fs, io, mem, panic, process, thread};
 
 use snapbox::cmd::{Command, OutputAssert};
 use snapbox::{Assert, Substitutions};
 use thiserror::Error;
 
-/// Used to ensure the build task is only run once.
-static BUILD: sync::Once = sync::Once::new();
+/// Used to ensure the build task is only run once, even when formulas are run in parallel.
+/// Holds the build error, if any, so that every caller fails the same way.
+static BUILD: sync::OnceLock<Result<(), String>> = sync::OnceLock::new();
+
+/// Used to name the temporary homes of formulas run in parallel.
+static WORKERS: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -198,7 +203,8 @@ impl TestFormula {
         self.bins.insert(0, bins);
 
         // We don't need to re-build everytime the `build` function is called. Once is enough.
-        BUILD.call_once(|| {
+        // Other threads calling this function block until the build is done.
+        let result = BUILD.get_or_init(|| {
             use escargot::format::Message;
             use radicle::logger::env_level;
             use radicle::logger::test as logger;
@@ -214,7 +220,7 @@ impl TestFormula {
                     .manifest_path(&manifest.join("Cargo.toml"))
                     .target_dir(&target_dir)
                     .exec()
-                    .unwrap();
+                    .map_err(|e| format!("building package `{package}` failed: {e}"))?;
 
                 for result in results {
                     match result {
@@ -231,7 +237,11 @@ impl TestFormula {
                     }
                 }
             }
+            Ok(())
         });
+        if let Err(e) = result {
+            panic!("TestFormula::build: {e}");
+        }
         self
     }
 
@@ -506,6 +516,53 @@ impl TestFormula {
     }
 }
 
+/// Run independent test formulas in parallel, each in its own worker thread.
+///
+/// The blocks of a formula are still run in order, since they share the formula's working
+/// directory and homes, which must therefore not be shared between formulas. Each formula is
+/// given its own temporary `HOME`, unless its environment already sets one.
+pub fn run_parallel(formulas: impl IntoIterator<Item = TestFormula>) -> Result<bool, io::Error> {
+    thread::scope(|s| {
+        let workers = formulas
+            .into_iter()
+            .map(|mut formula| {
+                let worker = WORKERS.fetch_add(1, Ordering::SeqCst);
+                let home =
+                    env::temp_dir().join(format!("radicle-cli-test-{}-{worker}", process::id()));
+
+                thread::Builder::new()
+                    .name(format!("test-{worker}"))
+                    .spawn_scoped(s, move || {
+                        fs::create_dir_all(&home)?;
+                        formula
+                            .env
+                            .entry(String::from("HOME"))
+                            .or_insert_with(|| home.to_string_lossy().to_string());
+
+                        let result = formula.run();
+                        fs::remove_dir_all(&home).ok();
+
+                        result
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Wait for all workers before reporting the first failure.
+        let mut results = Vec::new();
+        for worker in workers {
+            results.push(worker.join());
+        }
+        let mut success = true;
+        for result in results {
+            match result {
+                Ok(result) => success &= result?,
+                Err(e) => panic::resume_unwind(e),
+            }
+        }
+        Ok(success)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -624,6 +681,41 @@ name = "radicle-cli-test"
         formula.run().unwrap();
     }
 
+    #[test]
+    fn test_run_parallel() {
+        let inputs = [
+            r#"
+```
+$ head -n 2 Cargo.toml
+[package]
+name = "radicle-cli-test"
+$ echo $HOME
+[..]/radicle-cli-test-[..]
+```
+"#,
+            r#"
+```
+$ echo "hello"
+hello
+$ echo $HOME
+[..]/radicle-cli-test-[..]
+```
+"#,
+        ];
+        let formulas = inputs.map(|input| {
+            let mut formula =
+                TestFormula::new(PathBuf::from_str(env!("CARGO_MANIFEST_DIR")).unwrap());
+            formula
+                .read(
+                    Path::new("test.md"),
+                    io::BufReader::new(io::Cursor::new(input.trim().as_bytes().to_owned())),
+                )
+                .unwrap();
+            formula
+        });
+        assert!(run_parallel(formulas).unwrap());
+    }
+
     #[test]
     fn test_example_spaced_brackets() {
         let input = r#"
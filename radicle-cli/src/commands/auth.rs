@@ -2,6 +2,7 @@
 use std::env;
 use std::ffi::OsString;
 use std::ops::Not as _;
+use std::path::PathBuf;
 use std::str::FromStr;
 
 use anyhow::anyhow;
@@ -14,6 +15,7 @@ use radicle::{profile, Profile};
 
 use crate::terminal as term;
 use crate::terminal::args::{Args, Error, Help};
+use crate::terminal::io::PassphraseSource;
 
 pub const HELP: Help = Help {
     name: "auth",
@@ -24,21 +26,26 @@ Usage
 
     rad auth [<option>...]
 
-    A passphrase may be given via the environment variable `RAD_PASSPHRASE` or
-    via the standard input stream if `--stdin` is used. Using either of these
-    methods disables the passphrase prompt.
+    A passphrase may be given via the environment variable `RAD_PASSPHRASE`,
+    read from a file or from the output of a command, or read from the standard
+    input stream if `--stdin` is used. Using any of these methods disables the
+    passphrase prompt. A file or command may also be configured via the
+    `RAD_PASSPHRASE_FILE` and `RAD_PASSPHRASE_COMMAND` environment variables.
 
 Options
 
-    --alias                 When initializing an identity, sets the node alias
-    --stdin                 Read passphrase from stdin (default: false)
-    --help                  Print help
+    --alias                         When initializing an identity, sets the node alias
+    --stdin                         Read passphrase from stdin (default: false)
+    --passphrase-file <path>        Read passphrase from the first line of a file
+    --passphrase-command <cmd>      Read passphrase from the output of a shell command
+    --help                          Print help
 "#,
 };
 
 #[derive(Debug)]
 pub struct Options {
     pub stdin: bool,
+    pub passphrase: Option<PassphraseSource>,
     pub alias: Option<Alias>,
 }
 
@@ -47,6 +54,7 @@ impl Args for Options {
         use lexopt::prelude::*;
 
         let mut stdin = false;
+        let mut passphrase = None;
         let mut alias = None;
         let mut parser = lexopt::Parser::from_args(args);
 
@@ -61,6 +69,17 @@ impl Args for Options {
                 Long("stdin") => {
                     stdin = true;
                 }
+                Long("passphrase-file") => {
+                    let val = parser.value()?;
+
+                    passphrase = Some(PassphraseSource::File(PathBuf::from(val)));
+                }
+                Long("passphrase-command") => {
+                    let val = parser.value()?;
+                    let val = term::args::string(&val);
+
+                    passphrase = Some(PassphraseSource::Command(val));
+                }
                 Long("help") | Short('h') => {
                     return Err(Error::Help.into());
                 }
@@ -68,7 +87,20 @@ impl Args for Options {
             }
         }
 
-        Ok((Options { alias, stdin }, vec![]))
+        if stdin && passphrase.is_some() {
+            anyhow::bail!(
+                "`--stdin` cannot be used with `--passphrase-file` or `--passphrase-command`"
+            );
+        }
+
+        Ok((
+            Options {
+                alias,
+                stdin,
+                passphrase,
+            },
+            vec![],
+        ))
     }
 }
 
@@ -105,11 +137,10 @@ pub fn init(options: Options) -> anyhow::Result<()> {
         )?
     };
     let home = profile::home()?;
-    let passphrase = if options.stdin {
-        term::passphrase_stdin()
-    } else {
-        term::passphrase_confirm("Enter a passphrase:", RAD_PASSPHRASE)
-    }?;
+    let passphrase = match passphrase(&options)? {
+        Some(passphrase) => passphrase,
+        None => term::passphrase_confirm("Enter a passphrase:", RAD_PASSPHRASE)?,
+    };
     let passphrase = passphrase.trim().is_empty().not().then_some(passphrase);
     let spinner = term::spinner("Creating your Ed25519 keypair...");
     let profile = Profile::init(home, alias, passphrase.clone())?;
@@ -163,8 +194,8 @@ pub fn init(options: Options) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Try loading the identity's key into SSH Agent, falling back to verifying `RAD_PASSPHRASE` for
-/// use.
+/// Try loading the identity's key into SSH Agent, falling back to verifying the given passphrase
+/// for use.
 pub fn authenticate(options: Options, profile: &Profile) -> anyhow::Result<()> {
     if !profile.keystore.is_encrypted()? {
         term::success!("Authenticated as {}", term::format::tertiary(profile.id()));
@@ -179,12 +210,11 @@ pub fn authenticate(options: Options, profile: &Profile) -> anyhow::Result<()> {
                 term::success!("Radicle key already in ssh-agent");
                 return Ok(());
             }
-            let passphrase = if let Some(phrase) = profile::env::passphrase() {
-                phrase
-            } else if options.stdin {
-                term::passphrase_stdin()?
-            } else {
-                term::io::passphrase(term::io::PassphraseValidator::new(profile.keystore.clone()))?
+            let passphrase = match passphrase(&options)? {
+                Some(passphrase) => passphrase,
+                None => term::io::passphrase(term::io::PassphraseValidator::new(
+                    profile.keystore.clone(),
+                ))?,
             };
             register(&mut agent, profile, passphrase)?;
 
@@ -196,10 +226,10 @@ pub fn authenticate(options: Options, profile: &Profile) -> anyhow::Result<()> {
         Err(e) => Err(e)?,
     };
 
-    // Try RAD_PASSPHRASE fallback.
-    if let Some(passphrase) = profile::env::passphrase() {
+    // Try verifying a passphrase given via the options or the environment.
+    if let Some(passphrase) = passphrase(&options)? {
         ssh::keystore::MemorySigner::load(&profile.keystore, Some(passphrase))
-            .map_err(|_| anyhow!("`{RAD_PASSPHRASE}` is invalid"))?;
+            .map_err(|_| anyhow!("passphrase is invalid"))?;
         return Ok(());
     }
 
@@ -213,6 +243,25 @@ pub fn authenticate(options: Options, profile: &Profile) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Get the passphrase without prompting, if one was given via the options or the environment.
+///
+/// A passphrase file or command given on the command line takes precedence over `--stdin`,
+/// which takes precedence over the environment.
+fn passphrase(options: &Options) -> anyhow::Result<Option<Passphrase>> {
+    if let Some(source) = &options.passphrase {
+        return source.read().map(Some);
+    }
+    if options.stdin {
+        return term::passphrase_stdin().map(Some);
+    }
+    if let Some(passphrase) = profile::env::passphrase() {
+        return Ok(Some(passphrase));
+    }
+    term::io::passphrase_source()
+        .map(|source| source.read())
+        .transpose()
+}
+
 /// Register key with ssh-agent.
 pub fn register(
     agent: &mut ssh::agent::Agent,
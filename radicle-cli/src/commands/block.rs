@@ -1,6 +1,10 @@
 use std::ffi::OsString;
+use std::fs;
+use std::path::PathBuf;
 
-use radicle::node::policy::Policy;
+use anyhow::Context as _;
+
+use radicle::node::policy::{BlockList, Policy};
 use radicle::prelude::{NodeId, RepoId};
 
 use crate::terminal as term;
@@ -16,16 +20,24 @@ Usage
 
     rad block <rid> [<option>...]
     rad block <nid> [<option>...]
+    rad block --export <path> [<option>...]
+    rad block --import <path> [<option>...]
 
     Blocks a repository from being seeded or a node from being followed.
 
+    Block lists can be shared between nodes: `--export` writes all blocked
+    nodes and repositories to a JSON file, and `--import` blocks all the
+    entries of such a file, in addition to the existing ones.
+
 Options
 
-    --help          Print help
+    --export <path>     Export the block list to the given file
+    --import <path>     Import a block list from the given file
+    --help              Print help
 "#,
 };
 
-enum Target {
+pub enum Target {
     Node(NodeId),
     Repo(RepoId),
 }
@@ -39,8 +51,14 @@ impl std::fmt::Display for Target {
     }
 }
 
+pub enum Operation {
+    Block(Target),
+    Export(PathBuf),
+    Import(PathBuf),
+}
+
 pub struct Options {
-    target: Target,
+    op: Operation,
 }
 
 impl Args for Options {
@@ -49,12 +67,19 @@ impl Args for Options {
 
         let mut parser = lexopt::Parser::from_args(args);
         let mut target = None;
+        let mut op = None;
 
         while let Some(arg) = parser.next()? {
             match arg {
                 Long("help") | Short('h') => {
                     return Err(Error::Help.into());
                 }
+                Long("export") if op.is_none() => {
+                    op = Some(Operation::Export(parser.value()?.into()));
+                }
+                Long("import") if op.is_none() => {
+                    op = Some(Operation::Import(parser.value()?.into()));
+                }
                 Value(val) if target.is_none() => {
                     if let Ok(rid) = args::rid(&val) {
                         target = Some(Target::Repo(rid));
@@ -70,14 +95,18 @@ impl Args for Options {
             }
         }
 
-        Ok((
-            Options {
-                target: target.ok_or(anyhow::anyhow!(
-                    "a repository or node to block must be specified, see `rad block --help`"
-                ))?,
-            },
-            vec![],
-        ))
+        let op = match (op, target) {
+            (Some(_), Some(_)) => {
+                anyhow::bail!("a block list cannot be used with a repository or node")
+            }
+            (Some(op), None) => op,
+            (None, Some(target)) => Operation::Block(target),
+            (None, None) => anyhow::bail!(
+                "a repository or node to block must be specified, see `rad block --help`"
+            ),
+        };
+
+        Ok((Options { op }, vec![]))
     }
 }
 
@@ -85,12 +114,39 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
     let profile = ctx.profile()?;
     let mut policies = profile.policies_mut()?;
 
-    let updated = match options.target {
-        Target::Node(nid) => policies.set_follow_policy(&nid, Policy::Block)?,
-        Target::Repo(rid) => policies.set_seed_policy(&rid, Policy::Block)?,
-    };
-    if updated {
-        term::success!("Policy for {} set to 'block'", options.target);
+    match options.op {
+        Operation::Block(target) => {
+            let updated = match target {
+                Target::Node(nid) => policies.set_follow_policy(&nid, Policy::Block)?,
+                Target::Repo(rid) => policies.set_seed_policy(&rid, Policy::Block)?,
+            };
+            if updated {
+                term::success!("Policy for {target} set to 'block'");
+            }
+        }
+        Operation::Export(path) => {
+            let list = policies.block_list()?;
+
+            fs::write(&path, format!("{list}\n"))
+                .with_context(|| format!("failed to write block list to {}", path.display()))?;
+            term::success!(
+                "Exported {} block list entries to {}",
+                list.len(),
+                term::format::tertiary(path.display())
+            );
+        }
+        Operation::Import(path) => {
+            let list = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read block list from {}", path.display()))?
+                .parse::<BlockList>()?;
+            let updated = policies.import(&list)?;
+
+            term::success!(
+                "Imported {} block list entries from {}, {updated} policies updated",
+                list.len(),
+                term::format::tertiary(path.display())
+            );
+        }
     }
     Ok(())
 }
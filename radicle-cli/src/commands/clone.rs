@@ -1,5 +1,6 @@
 #![allow(clippy::or_fun_call)]
 use std::ffi::OsString;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time;
@@ -44,12 +45,19 @@ Usage
     For private repositories, use the `--private` and `--seed` options, to clone directly
     from known seeds in the privacy set.
 
+    With `--depth`, the working copy is a shallow clone, containing only the latest
+    commits of each branch. The repository is still fully replicated to local storage,
+    since issues, patches and the identity document require the full history. Shallow
+    working copies can't be used for operations that need the missing history, eg.
+    merging or rebasing onto older commits; run `git fetch --unshallow rad` to fetch it.
+
 Options
 
         --scope <scope>     Follow scope: `followed` or `all` (default: all)
     -s, --seed <nid>        Clone from this seed (may be specified multiple times)
         --private           Clone a private repository
         --timeout <secs>    Timeout for fetching repository (default: 9)
+        --depth <n>         Only check out the last <n> commits of each branch
         --help              Print help
 
 "#,
@@ -65,6 +73,8 @@ pub struct Options {
     scope: Scope,
     /// Sync settings.
     sync: SyncSettings,
+    /// History depth of the working copy, if shallow.
+    depth: Option<NonZeroUsize>,
 }
 
 impl Args for Options {
@@ -76,6 +86,7 @@ impl Args for Options {
         let mut scope = Scope::All;
         let mut sync = SyncSettings::default();
         let mut directory = None;
+        let mut depth = None;
 
         while let Some(arg) = parser.next()? {
             match arg {
@@ -100,6 +111,14 @@ impl Args for Options {
 
                     sync.timeout = time::Duration::from_secs(secs as u64);
                 }
+                Long("depth") => {
+                    let value = parser.value()?;
+                    let value = term::args::number(&value)?;
+                    let value = NonZeroUsize::new(value)
+                        .ok_or_else(|| anyhow!("depth must be greater than zero"))?;
+
+                    depth = Some(value);
+                }
                 Long("no-confirm") => {
                     // We keep this flag here for consistency though it doesn't have any effect,
                     // since the command is fully non-interactive.
@@ -130,6 +149,7 @@ impl Args for Options {
                 directory,
                 scope,
                 sync,
+                depth,
             },
             vec![],
         ))
@@ -152,6 +172,7 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
         options.directory.clone(),
         options.scope,
         options.sync.with_profile(&profile),
+        options.depth,
         &mut node,
         &signer,
         &profile.storage,
@@ -237,6 +258,7 @@ pub fn clone<G: Signer>(
     directory: Option<PathBuf>,
     scope: Scope,
     settings: SyncSettings,
+    depth: Option<NonZeroUsize>,
     node: &mut Node,
     signer: &G,
     storage: &Storage,
@@ -294,7 +316,7 @@ pub fn clone<G: Signer>(
         "Creating checkout in ./{}..",
         term::format::tertiary(path.display())
     ));
-    let working = rad::checkout(id, &me, path, &storage)?;
+    let working = rad::checkout_shallow(id, &me, path, &storage, depth)?;
 
     spinner.finish();
 
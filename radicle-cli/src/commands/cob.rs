@@ -1,13 +1,14 @@
 use std::ffi::OsString;
 use std::str::FromStr;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, Context as _};
 use chrono::prelude::*;
 use nonempty::NonEmpty;
 use radicle::cob;
 use radicle::prelude::RepoId;
 use radicle::storage::ReadStorage;
 use radicle_cob::object::collaboration::list;
+use serde::Serialize;
 
 use crate::git::Rev;
 use crate::terminal as term;
@@ -23,14 +24,21 @@ Usage
     rad cob <command> [<option>...]
     rad cob list --repo <rid> --type <typename>
     rad cob show --repo <rid> --type <typename> --object <oid>
+    rad cob log [<typename> <oid>] [--repo <rid>] [--json]
+
+    If `--repo` is not specified, the repository of the working
+    directory is used. The type and object can also be given as
+    positional arguments, eg. `rad cob log xyz.radicle.issue <oid>`.
 
 Commands
 
     list       List all COBs of a given type (--object is not needed)
     show       Show a COB as raw operations
+    log        Show the change history of a COB, parents before children
 
 Options
 
+    --json     Output changes as JSON, one per line (log only)
     --help     Print help
 "#,
 };
@@ -38,19 +46,32 @@ Options
 enum OperationName {
     List,
     Show,
+    Log,
 }
 
 enum Operation {
     List,
     Show(Rev),
+    Log { object: Rev, json: bool },
 }
 
 pub struct Options {
-    rid: RepoId,
+    rid: Option<RepoId>,
     op: Operation,
     type_name: cob::TypeName,
 }
 
+/// A change, as output by `rad cob log --json`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Change {
+    id: cob::EntryId,
+    author: cob::ActorId,
+    parents: Vec<cob::EntryId>,
+    timestamp: u64,
+    actions: Vec<serde_json::Value>,
+}
+
 impl Args for Options {
     fn from_args(args: Vec<OsString>) -> anyhow::Result<(Self, Vec<OsString>)> {
         use lexopt::prelude::*;
@@ -60,14 +81,27 @@ impl Args for Options {
         let mut type_name: Option<cob::TypeName> = None;
         let mut oid: Option<Rev> = None;
         let mut rid: Option<RepoId> = None;
+        let mut json = false;
 
         while let Some(arg) = parser.next()? {
             match arg {
                 Value(val) if op.is_none() => match val.to_string_lossy().as_ref() {
                     "l" | "list" => op = Some(OperationName::List),
                     "s" | "show" => op = Some(OperationName::Show),
+                    "log" => op = Some(OperationName::Log),
                     unknown => anyhow::bail!("unknown operation '{unknown}'"),
                 },
+                Value(val) if type_name.is_none() => {
+                    let v = term::args::string(&val);
+                    let v = cob::TypeName::from_str(&v)?;
+
+                    type_name = Some(v);
+                }
+                Value(val) if oid.is_none() => {
+                    let v = term::args::string(&val);
+
+                    oid = Some(Rev::from(v));
+                }
                 Long("type") | Short('t') => {
                     let v = parser.value()?;
                     let v = term::args::string(&v);
@@ -87,6 +121,7 @@ impl Args for Options {
 
                     rid = Some(v);
                 }
+                Long("json") => json = true,
                 Long("help") | Short('h') => {
                     return Err(Error::Help.into());
                 }
@@ -102,10 +137,15 @@ impl Args for Options {
                         OperationName::Show => Operation::Show(oid.ok_or_else(|| {
                             anyhow!("an object id must be specified with `--object")
                         })?),
+                        OperationName::Log => Operation::Log {
+                            object: oid.ok_or_else(|| {
+                                anyhow!("an object id must be specified with `--object")
+                            })?,
+                            json,
+                        },
                     }
                 },
-                rid: rid
-                    .ok_or_else(|| anyhow!("a repository id must be specified with `--repo`"))?,
+                rid,
                 type_name: type_name
                     .ok_or_else(|| anyhow!("an object type must be specified with `--type`"))?,
             },
@@ -117,7 +157,15 @@ impl Args for Options {
 pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
     let profile = ctx.profile()?;
     let storage = &profile.storage;
-    let repo = storage.repository(options.rid)?;
+    let rid = match options.rid {
+        Some(rid) => rid,
+        None => {
+            let (_, rid) =
+                radicle::rad::cwd().context("a repository id must be specified with `--repo`")?;
+            rid
+        }
+    };
+    let repo = storage.repository(rid)?;
 
     match options.op {
         Operation::List => {
@@ -161,7 +209,58 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
                 }
             }
         }
+        Operation::Log { object, json } => {
+            let oid = object.resolve(&repo.backend)?;
+            let ops = cob::store::log(&oid, &options.type_name, &repo)?;
+
+            if json {
+                log_json(ops)?;
+            } else {
+                log(ops)?;
+            }
+        }
     }
 
     Ok(())
 }
+
+fn log(ops: Vec<cob::Op<Vec<u8>>>) -> anyhow::Result<()> {
+    for op in ops {
+        let time = DateTime::<Utc>::from(
+            std::time::UNIX_EPOCH + std::time::Duration::from_secs(op.timestamp.as_secs()),
+        )
+        .to_rfc2822();
+
+        term::print(term::format::yellow(format!("commit   {}", op.id)));
+        for parent in op.parents {
+            term::print(format!("parent   {}", parent));
+        }
+        term::print(format!("author   {}", op.author));
+        term::print(format!("date     {}", time));
+
+        for action in op.actions {
+            let obj: serde_json::Value = serde_json::from_slice(&action)?;
+            term::print(format!("action   {obj}"));
+        }
+        term::blank();
+    }
+    Ok(())
+}
+
+fn log_json(ops: Vec<cob::Op<Vec<u8>>>) -> anyhow::Result<()> {
+    for op in ops {
+        let change = Change {
+            id: op.id,
+            author: op.author,
+            parents: op.parents,
+            timestamp: op.timestamp.as_secs(),
+            actions: op
+                .actions
+                .iter()
+                .map(|a| serde_json::from_slice(a))
+                .collect::<Result<_, _>>()?,
+        };
+        println!("{}", serde_json::to_string(&change)?);
+    }
+    Ok(())
+}
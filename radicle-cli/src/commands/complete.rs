@@ -0,0 +1,134 @@
+use std::ffi::OsString;
+
+use anyhow::anyhow;
+
+use radicle::issue::cache::Issues as _;
+use radicle::patch::cache::Patches as _;
+use radicle::storage::ReadStorage;
+
+use crate::terminal as term;
+use crate::terminal::args::{Args, Error, Help};
+
+pub const HELP: Help = Help {
+    name: "complete",
+    description: "Complete command-line arguments",
+    version: env!("RADICLE_VERSION"),
+    usage: r#"
+Usage
+
+    rad complete [<option>...] [--] <word>...
+
+    Print completion candidates for the last of the given words, which form
+    a `rad` command line, without the leading `rad`. This command is meant to
+    be called from shell completion scripts.
+
+    Issue and patch identifiers are completed from the repository in the
+    current working directory.
+
+Options
+
+    --limit <n>    Maximum number of candidates to print (default: 32)
+    --help         Print help
+"#,
+};
+
+/// Default maximum number of candidates.
+pub const DEFAULT_LIMIT: usize = 32;
+
+pub struct Options {
+    words: Vec<String>,
+    limit: usize,
+}
+
+impl Args for Options {
+    fn from_args(args: Vec<OsString>) -> anyhow::Result<(Self, Vec<OsString>)> {
+        use lexopt::prelude::*;
+
+        let mut parser = lexopt::Parser::from_args(args);
+        let mut words = Vec::new();
+        let mut limit = DEFAULT_LIMIT;
+
+        while let Some(arg) = parser.next()? {
+            match arg {
+                Long("limit") => {
+                    let val = parser.value()?;
+                    limit = term::args::number(&val)?;
+                }
+                Long("help") | Short('h') => {
+                    return Err(Error::Help.into());
+                }
+                Value(val) => {
+                    words.push(term::args::string(&val));
+                }
+                _ => return Err(anyhow!(arg.unexpected())),
+            }
+        }
+
+        Ok((Options { words, limit }, vec![]))
+    }
+}
+
+/// Kind of value to complete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Completion {
+    Issue,
+    Patch,
+}
+
+impl Completion {
+    /// Find out what is being completed from the words preceding the current one.
+    ///
+    /// Identifiers are completed as the first argument after an `issue` or `patch`
+    /// operation, eg. `rad patch show <id>`.
+    fn from_words(preceding: &[String]) -> Option<Self> {
+        match preceding {
+            [command, operation] if !operation.starts_with('-') => match command.as_str() {
+                "issue" if !matches!(operation.as_str(), "list" | "open") => Some(Self::Issue),
+                "patch" if !matches!(operation.as_str(), "list") => Some(Self::Patch),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
+    let Some((current, preceding)) = options.words.split_last() else {
+        return Ok(());
+    };
+    if current.starts_with('-') {
+        return Ok(());
+    }
+    let Some(completion) = Completion::from_words(preceding) else {
+        return Ok(());
+    };
+    // Completion runs on every key press, so we stay silent outside of a repository.
+    let Ok((_, rid)) = radicle::rad::cwd() else {
+        return Ok(());
+    };
+    let profile = ctx.profile()?;
+    let repo = profile.storage.repository(rid)?;
+    let candidates = match completion {
+        Completion::Issue => profile
+            .issues(&repo)?
+            .list()?
+            .filter_map(|r| r.ok())
+            .map(|(id, _)| id.to_string())
+            .filter(|id| id.starts_with(current.as_str()))
+            .take(options.limit)
+            .collect::<Vec<_>>(),
+        Completion::Patch => profile
+            .patches(&repo)?
+            .list()?
+            .filter_map(|r| r.ok())
+            .map(|(id, _)| id.to_string())
+            .filter(|id| id.starts_with(current.as_str()))
+            .take(options.limit)
+            .collect::<Vec<_>>(),
+    };
+
+    for candidate in candidates {
+        println!("{candidate}");
+    }
+    Ok(())
+}
@@ -5,6 +5,7 @@ use anyhow::anyhow;
 use radicle::git;
 use radicle::rad;
 use radicle_surf as surf;
+use radicle_surf::diff::FileDiff;
 
 use crate::git::pretty_diff::ToPretty as _;
 use crate::git::Rev;
@@ -26,9 +27,12 @@ Usage
     This command is meant to operate as closely as possible to `git diff`,
     except its output is optimized for human-readability.
 
+    When no commit is given, the working tree is compared with `HEAD`.
+
 Options
 
     --staged        View staged changes
+    --stat          Show a summary of the changes per file
     --color         Force color output
     --help          Print help
 "#,
@@ -37,6 +41,7 @@ Options
 pub struct Options {
     pub commits: Vec<Rev>,
     pub staged: bool,
+    pub stat: bool,
     pub unified: usize,
     pub color: bool,
 }
@@ -48,6 +53,7 @@ impl Args for Options {
         let mut parser = lexopt::Parser::from_args(args);
         let mut commits = Vec::new();
         let mut staged = false;
+        let mut stat = false;
         let mut unified = 5;
         let mut color = false;
 
@@ -58,6 +64,7 @@ impl Args for Options {
                     unified = term::args::number(&val)?;
                 }
                 Long("staged") | Long("cached") => staged = true,
+                Long("stat") => stat = true,
                 Long("color") => color = true,
                 Long("help") | Short('h') => return Err(Error::Help.into()),
                 Value(val) => {
@@ -73,6 +80,7 @@ impl Args for Options {
             Options {
                 commits,
                 staged,
+                stat,
                 unified,
                 color,
             },
@@ -107,13 +115,13 @@ pub fn run(options: Options, _ctx: impl term::Context) -> anyhow::Result<()> {
 
     let mut diff = match oids.as_slice() {
         [] => {
+            let head = repo.head()?.peel_to_tree()?;
             if options.staged {
-                let head = repo.head()?.peel_to_tree()?;
                 // HEAD vs. index.
                 repo.diff_tree_to_index(Some(&head), None, Some(&mut opts))
             } else {
-                // Working tree vs. index.
-                repo.diff_index_to_workdir(None, None)
+                // HEAD vs. working tree.
+                repo.diff_tree_to_workdir_with_index(Some(&head), Some(&mut opts))
             }
         }
         [commit] => {
@@ -142,6 +150,11 @@ pub fn run(options: Options, _ctx: impl term::Context) -> anyhow::Result<()> {
     term::Paint::force(options.color);
 
     let diff = surf::diff::Diff::try_from(diff)?;
+    if options.stat {
+        pager::run(stat(&diff))?;
+
+        return Ok(());
+    }
     let mut hi = Highlighter::default();
     let pretty = diff.pretty(&mut hi, &(), &repo);
 
@@ -149,3 +162,90 @@ pub fn run(options: Options, _ctx: impl term::Context) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Render a per-file summary of the diff.
+/// Should match the default output of `git diff --stat`.
+fn stat(diff: &surf::diff::Diff) -> Vec<term::Line> {
+    /// Maximum width of the `+`/`-` graph.
+    const GRAPH_WIDTH: usize = 40;
+
+    let files = diff
+        .files()
+        .map(|file| {
+            let name = match file {
+                FileDiff::Added(f) => f.path.display().to_string(),
+                FileDiff::Deleted(f) => f.path.display().to_string(),
+                FileDiff::Modified(f) => f.path.display().to_string(),
+                FileDiff::Moved(f) => {
+                    format!("{} => {}", f.old_path.display(), f.new_path.display())
+                }
+                FileDiff::Copied(f) => {
+                    format!("{} => {}", f.old_path.display(), f.new_path.display())
+                }
+            };
+            let (additions, deletions) = file
+                .stats()
+                .map(|s| (s.additions, s.deletions))
+                .unwrap_or_default();
+
+            (name, additions, deletions)
+        })
+        .collect::<Vec<_>>();
+
+    if files.is_empty() {
+        return Vec::new();
+    }
+    let files_changed = files.len();
+    let insertions = files.iter().map(|(_, a, _)| a).sum::<usize>();
+    let deletions = files.iter().map(|(_, _, d)| d).sum::<usize>();
+    let name_width = files
+        .iter()
+        .map(|(name, _, _)| name.chars().count())
+        .max()
+        .unwrap_or_default();
+    let max_changes = files
+        .iter()
+        .map(|(_, a, d)| a + d)
+        .max()
+        .unwrap_or_default();
+    let count_width = max_changes.to_string().len();
+    // Scale the graph down if the largest change doesn't fit.
+    let scale = |n: usize| {
+        if max_changes > GRAPH_WIDTH {
+            (n * GRAPH_WIDTH).div_ceil(max_changes)
+        } else {
+            n
+        }
+    };
+
+    let mut lines = files
+        .into_iter()
+        .map(|(name, additions, deletions)| {
+            term::Line::new(format!(
+                " {name:<name_width$} | {:>count_width$} ",
+                additions + deletions
+            ))
+            .item(term::format::positive("+".repeat(scale(additions))))
+            .item(term::format::negative("-".repeat(scale(deletions))))
+        })
+        .collect::<Vec<_>>();
+
+    let mut summary = vec![format!(
+        "{files_changed} file{} changed",
+        if files_changed == 1 { "" } else { "s" }
+    )];
+    if insertions > 0 || deletions == 0 {
+        summary.push(format!(
+            "{insertions} insertion{}(+)",
+            if insertions == 1 { "" } else { "s" }
+        ));
+    }
+    if deletions > 0 || insertions == 0 {
+        summary.push(format!(
+            "{deletions} deletion{}(-)",
+            if deletions == 1 { "" } else { "s" }
+        ));
+    }
+    lines.push(term::Line::new(format!(" {}", summary.join(", "))));
+    lines
+}
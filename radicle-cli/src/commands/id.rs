@@ -37,10 +37,16 @@ Usage
     rad id edit <revision-id> [--title <string>] [--description <string>] [<option>...]
     rad id show <revision-id> [<option>...]
     rad id <accept | reject | redact> <revision-id> [<option>...]
+    rad id sign <blob> [<option>...]
 
     The *rad id* command is used to manage and propose changes to the
     identity of a Radicle repository.
 
+    When an update requires the signatures of multiple delegates, the
+    proposed document can be shared by its blob id, as printed by
+    *rad id update*. Other delegates may then sign the proposal with
+    *rad id sign*, until the threshold is met.
+
     See the rad-id(1) man page for more information.
 
 Options
@@ -65,6 +71,9 @@ pub enum Operation {
     AcceptRevision {
         revision: Rev,
     },
+    SignRevision {
+        blob: Rev,
+    },
     RejectRevision {
         revision: Rev,
     },
@@ -86,6 +95,7 @@ pub enum Operation {
 #[derive(Default, PartialEq, Eq)]
 pub enum OperationName {
     Accept,
+    Sign,
     Reject,
     Edit,
     Update,
@@ -150,6 +160,7 @@ impl Args for Options {
                     "l" | "list" => op = Some(OperationName::List),
                     "s" | "show" => op = Some(OperationName::Show),
                     "a" | "accept" => op = Some(OperationName::Accept),
+                    "sign" => op = Some(OperationName::Sign),
                     "r" | "reject" => op = Some(OperationName::Reject),
                     "d" | "redact" => op = Some(OperationName::Redact),
 
@@ -222,6 +233,9 @@ impl Args for Options {
             OperationName::Accept => Operation::AcceptRevision {
                 revision: revision.ok_or_else(|| anyhow!("a revision must be provided"))?,
             },
+            OperationName::Sign => Operation::SignRevision {
+                blob: revision.ok_or_else(|| anyhow!("a proposal blob must be provided"))?,
+            },
             OperationName::Reject => Operation::RejectRevision {
                 revision: revision.ok_or_else(|| anyhow!("a revision must be provided"))?,
             },
@@ -278,31 +292,13 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
     match options.op {
         Operation::AcceptRevision { revision } => {
             let revision = get(revision, &identity, &repo)?.clone();
-            let id = revision.id;
 
-            if !revision.is_active() {
-                anyhow::bail!("cannot vote on revision that is {}", revision.state);
-            }
-
-            if options
-                .interactive
-                .confirm(format!("Accept revision {}?", term::format::tertiary(id)))
-            {
-                identity.accept(&revision.id, &signer)?;
-
-                if let Some(revision) = identity.revision(&id) {
-                    // Update the canonical head to point to the latest accepted revision.
-                    if revision.is_accepted() && revision.id == identity.current {
-                        repo.set_identity_head_to(revision.id)?;
-                    }
-                    // TODO: Different output if canonical changed?
+            accept(&revision, &mut identity, &current, &repo, &options, &profile, &signer)?;
+        }
+        Operation::SignRevision { blob } => {
+            let revision = get_by_blob(blob, &identity, &repo)?.clone();
 
-                    if !options.quiet {
-                        term::success!("Revision {id} accepted");
-                        print_meta(revision, &current, &profile)?;
-                    }
-                }
-            }
+            accept(&revision, &mut identity, &current, &repo, &options, &profile, &signer)?;
         }
         Operation::RejectRevision { revision } => {
             let revision = get(revision, &identity, &repo)?.clone();
@@ -408,6 +404,13 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
                     term::format::tertiary(revision.id)
                 );
                 print(&revision, &current, &repo, &profile)?;
+
+                if revision.is_active() && profile.hints() {
+                    term::hint(format!(
+                        "to sign this proposal, other delegates may run `rad id sign {}`",
+                        revision.blob
+                    ));
+                }
             }
         }
         Operation::ListRevisions => {
@@ -482,6 +485,59 @@ fn get<'a>(
     Ok(revision)
 }
 
+/// Get the active revision proposing the given identity document blob.
+fn get_by_blob<'a>(
+    blob: Rev,
+    identity: &'a Identity,
+    repo: &radicle::storage::git::Repository,
+) -> anyhow::Result<&'a Revision> {
+    let blob = blob.resolve::<radicle::git::Oid>(&repo.backend)?;
+    let revision = identity
+        .revisions()
+        .find(|r| r.is_active() && r.blob == blob)
+        .ok_or(anyhow!("no active revision found proposing blob `{blob}`"))?;
+
+    Ok(revision)
+}
+
+/// Accept a revision with the local delegate's signature.
+fn accept<G: Signer>(
+    revision: &Revision,
+    identity: &mut IdentityMut<radicle::storage::git::Repository>,
+    current: &Revision,
+    repo: &radicle::storage::git::Repository,
+    options: &Options,
+    profile: &Profile,
+    signer: &G,
+) -> anyhow::Result<()> {
+    let id = revision.id;
+
+    if !revision.is_active() {
+        anyhow::bail!("cannot vote on revision that is {}", revision.state);
+    }
+
+    if options
+        .interactive
+        .confirm(format!("Accept revision {}?", term::format::tertiary(id)))
+    {
+        identity.accept(&revision.id, signer)?;
+
+        if let Some(revision) = identity.revision(&id) {
+            // Update the canonical head to point to the latest accepted revision.
+            if revision.is_accepted() && revision.id == identity.current {
+                repo.set_identity_head_to(revision.id)?;
+            }
+            // TODO: Different output if canonical changed?
+
+            if !options.quiet {
+                term::success!("Revision {id} accepted");
+                print_meta(revision, current, profile)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 fn print_meta(
     revision: &Revision,
     previous: &Doc<Verified>,
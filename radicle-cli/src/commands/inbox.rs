@@ -42,12 +42,18 @@ Usage
     the `list` command) and displays the information related to that
     notification. This will mark the notification as read.
 
-    The `rad inbox clear` command will delete all notifications in the inbox.
+    The `rad inbox clear` command will delete all notifications in the inbox,
+    or only the ones matching the given filters. With `--mark-read`, the
+    notifications are marked as read instead of being deleted.
 
 Options
 
     --all                Operate on all repositories
     --repo <rid>         Operate on the given repository (default: rad .)
+    --unread             Only operate on unread notifications
+    --type <type>        Only operate on notifications of the given type:
+                         `issue`, `patch`, `branch` or `id`
+    --mark-read          Mark notifications as read instead of deleting them (clear)
     --sort-by <field>    Sort by `id` or `timestamp` (default: timestamp)
     --reverse, -r        Reverse the list
     --show-unknown       Show any updates that were not recognized
@@ -72,6 +78,46 @@ enum Mode {
     ByRepo(RepoId),
 }
 
+/// Type of notification to filter on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Issue,
+    Patch,
+    Branch,
+    Identity,
+}
+
+/// Notification filter.
+#[derive(Clone, Copy, Debug, Default)]
+struct Filter {
+    /// Only match unread notifications.
+    unread: bool,
+    /// Only match notifications of this type.
+    kind: Option<Kind>,
+}
+
+impl Filter {
+    /// Whether the filter matches every notification.
+    fn is_empty(&self) -> bool {
+        !self.unread && self.kind.is_none()
+    }
+
+    /// Whether the notification matches the filter.
+    fn matches(&self, n: &Notification) -> bool {
+        if self.unread && n.status.is_read() {
+            return false;
+        }
+        match (self.kind, &n.kind) {
+            (None, _) => true,
+            (Some(Kind::Branch), NotificationKind::Branch { .. }) => true,
+            (Some(Kind::Issue), NotificationKind::Cob { typed_id }) => typed_id.is_issue(),
+            (Some(Kind::Patch), NotificationKind::Cob { typed_id }) => typed_id.is_patch(),
+            (Some(Kind::Identity), NotificationKind::Cob { typed_id }) => typed_id.is_identity(),
+            (Some(_), _) => false,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 struct SortBy {
     reverse: bool,
@@ -82,6 +128,8 @@ pub struct Options {
     op: Operation,
     mode: Mode,
     sort_by: SortBy,
+    filter: Filter,
+    mark_read: bool,
     show_unknown: bool,
 }
 
@@ -95,6 +143,8 @@ impl Args for Options {
         let mut ids = Vec::new();
         let mut reverse = None;
         let mut field = None;
+        let mut filter = Filter::default();
+        let mut mark_read = false;
         let mut show_unknown = false;
 
         while let Some(arg) = parser.next()? {
@@ -111,6 +161,27 @@ impl Args for Options {
                 Long("show-unknown") => {
                     show_unknown = true;
                 }
+                Long("unread") => {
+                    filter.unread = true;
+                }
+                Long("type") => {
+                    let val = parser.value()?;
+
+                    filter.kind = match term::args::string(&val).as_str() {
+                        "issue" => Some(Kind::Issue),
+                        "patch" => Some(Kind::Patch),
+                        "branch" => Some(Kind::Branch),
+                        "id" => Some(Kind::Identity),
+                        other => {
+                            return Err(anyhow!(
+                                "unknown notification type `{other}`, see `rad inbox --help`"
+                            ))
+                        }
+                    };
+                }
+                Long("mark-read") if op == Some(Operation::Clear) => {
+                    mark_read = true;
+                }
                 Long("sort-by") => {
                     let val = parser.value()?;
 
@@ -124,7 +195,7 @@ impl Args for Options {
                         }
                     }
                 }
-                Long("repo") if mode.is_none() && op.is_some() => {
+                Long("repo") if mode.is_none() => {
                     let val = parser.value()?;
                     let repo = args::rid(&val)?;
 
@@ -167,6 +238,8 @@ impl Args for Options {
                 op,
                 mode,
                 sort_by,
+                filter,
+                mark_read,
                 show_unknown,
             },
             vec![],
@@ -182,6 +255,8 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
         op,
         mode,
         sort_by,
+        filter,
+        mark_read,
         show_unknown,
     } = options;
 
@@ -189,12 +264,13 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
         Operation::List => list(
             mode,
             sort_by,
+            filter,
             show_unknown,
             &notifs.read_only(),
             storage,
             &profile,
         ),
-        Operation::Clear => clear(mode, &mut notifs),
+        Operation::Clear => clear(mode, filter, mark_read, &mut notifs),
         Operation::Show => show(mode, &mut notifs, storage, &profile),
     }
 }
@@ -202,6 +278,7 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
 fn list(
     mode: Mode,
     sort_by: SortBy,
+    filter: Filter,
     show_unknown: bool,
     notifs: &notifications::StoreReader,
     storage: &Storage,
@@ -210,17 +287,19 @@ fn list(
     let repos: Vec<term::VStack<'_>> = match mode {
         Mode::Contextual => {
             if let Ok((_, rid)) = radicle::rad::cwd() {
-                list_repo(rid, sort_by, show_unknown, notifs, storage, profile)?
+                list_repo(rid, sort_by, filter, show_unknown, notifs, storage, profile)?
                     .into_iter()
                     .collect()
             } else {
-                list_all(sort_by, show_unknown, notifs, storage, profile)?
+                list_all(sort_by, filter, show_unknown, notifs, storage, profile)?
             }
         }
-        Mode::ByRepo(rid) => list_repo(rid, sort_by, show_unknown, notifs, storage, profile)?
-            .into_iter()
-            .collect(),
-        Mode::All => list_all(sort_by, show_unknown, notifs, storage, profile)?,
+        Mode::ByRepo(rid) => {
+            list_repo(rid, sort_by, filter, show_unknown, notifs, storage, profile)?
+                .into_iter()
+                .collect()
+        }
+        Mode::All => list_all(sort_by, filter, show_unknown, notifs, storage, profile)?,
         Mode::ById(_) => anyhow::bail!("the `list` command does not take IDs"),
     };
 
@@ -236,6 +315,7 @@ fn list(
 
 fn list_all<'a>(
     sort_by: SortBy,
+    filter: Filter,
     show_unknown: bool,
     notifs: &notifications::StoreReader,
     storage: &Storage,
@@ -246,7 +326,15 @@ fn list_all<'a>(
 
     let mut vstacks = Vec::new();
     for repo in repos {
-        let vstack = list_repo(repo.rid, sort_by, show_unknown, notifs, storage, profile)?;
+        let vstack = list_repo(
+            repo.rid,
+            sort_by,
+            filter,
+            show_unknown,
+            notifs,
+            storage,
+            profile,
+        )?;
         vstacks.extend(vstack.into_iter());
     }
     Ok(vstacks)
@@ -255,6 +343,7 @@ fn list_all<'a>(
 fn list_repo<'a, R: ReadStorage>(
     rid: RepoId,
     sort_by: SortBy,
+    filter: Filter,
     show_unknown: bool,
     notifs: &notifications::StoreReader,
     storage: &R,
@@ -282,6 +371,9 @@ where
 
     for n in notifs {
         let n: Notification = n?;
+        if !filter.matches(&n) {
+            continue;
+        }
 
         let seen = if n.status.is_read() {
             term::Label::blank()
@@ -487,14 +579,16 @@ impl NotificationRow {
     }
 }
 
-fn clear(mode: Mode, notifs: &mut notifications::StoreWriter) -> anyhow::Result<()> {
-    let cleared = match mode {
-        Mode::All => notifs.clear_all()?,
-        Mode::ById(ids) => notifs.clear(&ids)?,
-        Mode::ByRepo(rid) => notifs.clear_by_repo(&rid)?,
+fn clear(
+    mode: Mode,
+    filter: Filter,
+    mark_read: bool,
+    notifs: &mut notifications::StoreWriter,
+) -> anyhow::Result<()> {
+    let mode = match mode {
         Mode::Contextual => {
             if let Ok((_, rid)) = radicle::rad::cwd() {
-                notifs.clear_by_repo(&rid)?
+                Mode::ByRepo(rid)
             } else {
                 return Err(Error::WithHint {
                     err: anyhow!("not a radicle repository"),
@@ -503,6 +597,34 @@ fn clear(mode: Mode, notifs: &mut notifications::StoreWriter) -> anyhow::Result<
                 .into());
             }
         }
+        mode => mode,
+    };
+
+    if mark_read {
+        // Notifications that are already read are left as-is.
+        let filter = Filter {
+            unread: true,
+            ..filter
+        };
+        let ids = select(mode, filter, notifs)?;
+        notifs.set_status(NotificationStatus::ReadAt(LocalTime::now()), &ids)?;
+
+        if ids.is_empty() {
+            term::print(term::format::italic("Your inbox has no unread items."));
+        } else {
+            term::success!("Marked {} item(s) as read", ids.len());
+        }
+        return Ok(());
+    }
+
+    let cleared = match mode {
+        Mode::All if filter.is_empty() => notifs.clear_all()?,
+        Mode::ById(ids) if filter.is_empty() => notifs.clear(&ids)?,
+        Mode::ByRepo(rid) if filter.is_empty() => notifs.clear_by_repo(&rid)?,
+        mode => {
+            let ids = select(mode, filter, notifs)?;
+            notifs.clear(&ids)?
+        }
     };
     if cleared > 0 {
         term::success!("Cleared {cleared} item(s) from your inbox");
@@ -512,6 +634,39 @@ fn clear(mode: Mode, notifs: &mut notifications::StoreWriter) -> anyhow::Result<
     Ok(())
 }
 
+/// Select the IDs of the notifications matching the mode and filter.
+fn select(
+    mode: Mode,
+    filter: Filter,
+    notifs: &notifications::StoreWriter,
+) -> anyhow::Result<Vec<NotificationId>> {
+    let mut ids = Vec::new();
+    let mut select = |n: Notification| {
+        if filter.matches(&n) {
+            ids.push(n.id);
+        }
+    };
+
+    match mode {
+        Mode::All | Mode::Contextual => {
+            for n in notifs.all()? {
+                select(n?);
+            }
+        }
+        Mode::ByRepo(rid) => {
+            for n in notifs.by_repo(&rid, "rowid")? {
+                select(n?);
+            }
+        }
+        Mode::ById(given) => {
+            for id in given {
+                select(notifs.get(id)?);
+            }
+        }
+    }
+    Ok(ids)
+}
+
 fn show(
     mode: Mode,
     notifs: &mut notifications::StoreWriter,
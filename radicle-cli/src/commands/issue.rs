@@ -10,6 +10,7 @@ use anyhow::{anyhow, Context as _};
 use radicle::cob::common::{Label, Reaction};
 use radicle::cob::issue;
 use radicle::cob::issue::{CloseReason, State};
+use radicle::cob::query::Query;
 use radicle::cob::thread;
 use radicle::crypto::Signer;
 use radicle::issue::cache::Issues as _;
@@ -46,7 +47,8 @@ Usage
     rad issue label <issue-id> [--add <label>] [--delete <label>] [<option>...]
     rad issue comment <issue-id> [--message <message>] [--reply-to <comment-id>] [<option>...]
     rad issue show <issue-id> [<option>...]
-    rad issue state <issue-id> [--closed | --open | --solved] [<option>...]
+    rad issue state <issue-id> [--closed | --open | --solved] [--reason <reason>] [<option>...]
+    rad issue close <issue-id> [--reason <reason>] [<option>...]
     rad issue cache [<issue-id>] [<option>...]
 
 Assign options
@@ -63,8 +65,15 @@ Label options
 
     Note: --add takes precedence over --delete
 
+State options
+
+        --reason <reason>      Why the issue is closed: `resolved`, `wontfix` or `duplicate`
+                               (default: resolved)
+
 Show options
 
+        --format <format>      Show the issue in the given format:
+                               `full`, `header`, `md` or `json` (default: full)
         --debug                Show the issue as Rust debug output
 
 Options
@@ -245,9 +254,12 @@ impl Args for Options {
 
                 // State options.
                 Long("closed") if op == Some(OperationName::State) => {
-                    state = Some(State::Closed {
-                        reason: CloseReason::Other,
-                    });
+                    // Keep the reason if one was already given.
+                    if !matches!(state, Some(State::Closed { .. })) {
+                        state = Some(State::Closed {
+                            reason: CloseReason::Solved,
+                        });
+                    }
                 }
                 Long("open") if op == Some(OperationName::State) => {
                     state = Some(State::Open);
@@ -257,6 +269,12 @@ impl Args for Options {
                         reason: CloseReason::Solved,
                     });
                 }
+                Long("reason") if op == Some(OperationName::State) => {
+                    let val = parser.value()?;
+                    let reason = close_reason(&val)?;
+
+                    state = Some(State::Closed { reason });
+                }
 
                 // React options.
                 Long("emoji") if op == Some(OperationName::React) => {
@@ -278,6 +296,8 @@ impl Args for Options {
                     match val.as_str() {
                         "header" => format = Format::Header,
                         "full" => format = Format::Full,
+                        "md" | "markdown" => format = Format::Markdown,
+                        "json" => format = Format::Json,
                         _ => anyhow::bail!("unknown format '{val}'"),
                     }
                 }
@@ -356,6 +376,12 @@ impl Args for Options {
                     "o" | "open" => op = Some(OperationName::Open),
                     "r" | "react" => op = Some(OperationName::React),
                     "s" | "state" => op = Some(OperationName::State),
+                    "close" => {
+                        op = Some(OperationName::State);
+                        state = Some(State::Closed {
+                            reason: CloseReason::Solved,
+                        });
+                    }
                     "assign" => op = Some(OperationName::Assign),
                     "label" => op = Some(OperationName::Label),
                     "cache" => op = Some(OperationName::Cache),
@@ -605,6 +631,18 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Parse the reason for closing an issue.
+fn close_reason(val: &OsString) -> anyhow::Result<CloseReason> {
+    match term::args::string(val).as_str() {
+        "resolved" => Ok(CloseReason::Solved),
+        "wontfix" => Ok(CloseReason::WontFix),
+        "duplicate" => Ok(CloseReason::Duplicate),
+        other => Err(anyhow!(
+            "invalid close reason '{other}', expected one of 'resolved', 'wontfix' or 'duplicate'"
+        )),
+    }
+}
+
 fn list<C>(
     cache: C,
     assigned: &Option<Assigned>,
@@ -619,11 +657,15 @@ where
         return Ok(());
     }
 
-    let assignee = match assigned {
-        Some(Assigned::Me) => Some(*profile.id()),
-        Some(Assigned::Peer(id)) => Some((*id).into()),
-        None => None,
-    };
+    let mut query = Query::new();
+    match assigned {
+        Some(Assigned::Me) => query = query.assignee(profile.did()),
+        Some(Assigned::Peer(id)) => query = query.assignee(*id),
+        None => {}
+    }
+    if let Some(s) = state {
+        query = query.state(*s);
+    }
 
     let mut all = Vec::new();
     let issues = cache.list()?;
@@ -633,15 +675,8 @@ where
             continue;
         };
 
-        if let Some(a) = assignee {
-            if !issue.assignees().any(|v| v == &Did::from(a)) {
-                continue;
-            }
-        }
-        if let Some(s) = state {
-            if s != issue.state() {
-                continue;
-            }
+        if !query.matches(&issue) {
+            continue;
         }
         all.push((id, issue))
     }
@@ -686,7 +721,13 @@ where
         table.push([
             match issue.state() {
                 State::Open => term::format::positive("●").into(),
-                State::Closed { .. } => term::format::negative("●").into(),
+                State::Closed {
+                    reason: CloseReason::Other,
+                } => term::format::negative("●").into(),
+                State::Closed { reason } => term::Line::spaced([
+                    term::format::negative("●").into(),
+                    term::format::dim(reason.to_string()).italic().into(),
+                ]),
             },
             term::format::tertiary(term::format::cob(&id))
                 .to_owned()
@@ -1,6 +1,12 @@
 use std::ffi::OsString;
+use std::fmt;
 
+use radicle::node::policy::{Policy, SeedPolicy};
+use radicle::node::routing::Store as _;
+use radicle::node::{Handle as _, Node, State};
+use radicle::prelude::RepoId;
 use radicle::storage::{ReadStorage, RepositoryInfo};
+use serde::Serialize;
 
 use crate::terminal as term;
 use crate::terminal::args::{Args, Error, Help};
@@ -25,14 +31,61 @@ Options
     --public        Show only public repositories
     --seeded, -s    Show all seeded repositories
     --all, -a       Show all repositories in storage
-    --verbose, -v   Verbose output
+    --json          Output repositories as JSON, one per line
+    --verbose, -v   Verbose output, including the replication status
     --help          Print help
+
+Status
+
+    seeding         The repository is seeded by the local node
+    fetching        The repository is currently being fetched by the local node
+    blocked         The repository is explicitly blocked
+    local           The repository is in local storage, but not seeded
 "#,
 };
 
+/// Replication status of a repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Status {
+    /// Repository is seeded.
+    Seeding,
+    /// Repository is seeded and is being fetched.
+    Fetching,
+    /// Repository is blocked.
+    Blocked,
+    /// Repository is in storage, but not seeded.
+    Local,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Seeding => write!(f, "seeding"),
+            Self::Fetching => write!(f, "fetching"),
+            Self::Blocked => write!(f, "blocked"),
+            Self::Local => write!(f, "local"),
+        }
+    }
+}
+
+/// A repository, as output by `rad ls --json`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Repo {
+    name: String,
+    rid: RepoId,
+    visibility: &'static str,
+    head: radicle::git::Oid,
+    description: String,
+    status: Status,
+    /// Number of seeds for this repository in our routing table.
+    seeds: usize,
+}
+
 pub struct Options {
-    #[allow(dead_code)]
     verbose: bool,
+    json: bool,
     public: bool,
     private: bool,
     all: bool,
@@ -45,6 +98,7 @@ impl Args for Options {
 
         let mut parser = lexopt::Parser::from_args(args);
         let mut verbose = false;
+        let mut json = false;
         let mut private = false;
         let mut public = false;
         let mut all = false;
@@ -68,6 +122,7 @@ impl Args for Options {
                     public = true;
                 }
                 Long("verbose") | Short('v') => verbose = true,
+                Long("json") => json = true,
                 _ => return Err(anyhow::anyhow!(arg.unexpected())),
             }
         }
@@ -75,6 +130,7 @@ impl Args for Options {
         Ok((
             Options {
                 verbose,
+                json,
                 private,
                 public,
                 all,
@@ -90,12 +146,29 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
     let storage = &profile.storage;
     let repos = storage.repositories()?;
     let policy = profile.policies()?;
-    let mut table = term::Table::new(term::TableOptions::bordered());
+    let routing = profile.database()?;
     let mut rows = Vec::new();
+    let mut output = Vec::new();
 
     if repos.is_empty() {
         return Ok(());
     }
+    // Repositories currently being fetched by our node, if it's running.
+    let fetching = {
+        let node = Node::new(profile.socket());
+        let sessions = if node.is_running() {
+            node.sessions().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        sessions
+            .into_iter()
+            .flat_map(|s| match s.state {
+                State::Connected { fetching, .. } => fetching.into_iter().collect(),
+                _ => Vec::new(),
+            })
+            .collect::<Vec<_>>()
+    };
 
     for RepositoryInfo {
         rid,
@@ -121,26 +194,95 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
         if !seeded && options.seeded {
             continue;
         }
+        let status = if fetching.contains(&rid) {
+            Status::Fetching
+        } else if seeded {
+            Status::Seeding
+        } else if matches!(
+            (*policy).seed_policy(&rid)?,
+            Some(SeedPolicy {
+                policy: Policy::Block,
+                ..
+            })
+        ) {
+            Status::Blocked
+        } else {
+            Status::Local
+        };
         let proj = doc.project()?;
+
+        if options.json {
+            output.push(Repo {
+                name: proj.name().to_owned(),
+                rid,
+                visibility: if doc.visibility.is_public() {
+                    "public"
+                } else {
+                    "private"
+                },
+                head,
+                description: proj.description().to_owned(),
+                status,
+                seeds: routing.count(&rid)?,
+            });
+            continue;
+        }
         let head = term::format::oid(head).into();
 
-        rows.push([
-            term::format::bold(proj.name().to_owned()),
-            term::format::tertiary(rid.urn()),
-            if seeded {
-                term::format::visibility(&doc.visibility).into()
-            } else {
-                term::format::dim("local").into()
-            },
-            term::format::secondary(head),
-            term::format::italic(proj.description().to_owned()),
-        ]);
+        rows.push((
+            [
+                term::format::bold(proj.name().to_owned()),
+                term::format::tertiary(rid.urn()),
+                if seeded {
+                    term::format::visibility(&doc.visibility).into()
+                } else {
+                    term::format::dim("local").into()
+                },
+                term::format::secondary(head),
+                term::format::italic(proj.description().to_owned()),
+            ],
+            status,
+        ));
     }
-    rows.sort();
+
+    if options.json {
+        output.sort_by(|a, b| a.name.cmp(&b.name).then(a.rid.cmp(&b.rid)));
+
+        for repo in output {
+            println!("{}", serde_json::to_string(&repo)?);
+        }
+        return Ok(());
+    }
+    rows.sort_by(|(a, _), (b, _)| a.cmp(b));
 
     if rows.is_empty() {
         term::print(term::format::italic("Nothing to show."));
+    } else if options.verbose {
+        let mut table = term::Table::new(term::TableOptions::bordered());
+        table.push([
+            "Name".into(),
+            "RID".into(),
+            "Visibility".into(),
+            "Head".into(),
+            "Description".into(),
+            "Status".into(),
+        ]);
+        table.divider();
+        table.extend(
+            rows.into_iter()
+                .map(|([name, rid, visibility, head, description], status)| {
+                    let status = match status {
+                        Status::Seeding => term::format::positive(status.to_string()),
+                        Status::Fetching => term::format::yellow(status.to_string()),
+                        Status::Blocked => term::format::negative(status.to_string()),
+                        Status::Local => term::format::dim(status.to_string()),
+                    };
+                    [name, rid, visibility, head, description, status]
+                }),
+        );
+        table.print();
     } else {
+        let mut table = term::Table::new(term::TableOptions::bordered());
         table.push([
             "Name".into(),
             "RID".into(),
@@ -149,7 +291,7 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
             "Description".into(),
         ]);
         table.divider();
-        table.extend(rows);
+        table.extend(rows.into_iter().map(|(row, _)| row));
         table.print();
     }
 
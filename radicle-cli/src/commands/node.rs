@@ -34,7 +34,9 @@ Usage
     rad node stop [<option>...]
     rad node logs [-n <lines>]
     rad node connect <nid>@<addr> [<option>...]
+    rad node disconnect <nid> [<option>...]
     rad node routing [--rid <rid>] [--nid <nid>] [--json] [<option>...]
+    rad node policy-log [--json] [<option>...]
     rad node events [--timeout <secs>] [-n <count>] [<option>...]
     rad node config [--addresses]
     rad node db <command> [<option>..]
@@ -53,6 +55,10 @@ Routing options
     --nid <nid>          Show the routing table entries for the given NID
     --json               Output the routing table as json
 
+Policy log options
+
+    --json               Output the policy log as json, one change per line
+
 Events options
 
     --timeout <secs>     How long to wait to receive an event before giving up
@@ -76,6 +82,9 @@ pub enum Operation {
     Config {
         addresses: bool,
     },
+    Disconnect {
+        nid: NodeId,
+    },
     Db {
         args: Vec<OsString>,
     },
@@ -83,6 +92,9 @@ pub enum Operation {
         timeout: time::Duration,
         count: usize,
     },
+    PolicyLog {
+        json: bool,
+    },
     Routing {
         json: bool,
         rid: Option<RepoId>,
@@ -107,7 +119,9 @@ pub enum OperationName {
     Connect,
     Config,
     Db,
+    Disconnect,
     Events,
+    PolicyLog,
     Routing,
     Logs,
     Start,
@@ -144,8 +158,10 @@ impl Args for Options {
                 Value(val) if op.is_none() => match val.to_string_lossy().as_ref() {
                     "connect" => op = Some(OperationName::Connect),
                     "db" => op = Some(OperationName::Db),
+                    "disconnect" => op = Some(OperationName::Disconnect),
                     "events" => op = Some(OperationName::Events),
                     "logs" => op = Some(OperationName::Logs),
+                    "policy-log" => op = Some(OperationName::PolicyLog),
                     "config" => op = Some(OperationName::Config),
                     "routing" => op = Some(OperationName::Routing),
                     "start" => op = Some(OperationName::Start),
@@ -158,6 +174,9 @@ impl Args for Options {
                 Value(val) if matches!(op, Some(OperationName::Connect)) => {
                     addr = Some(val.parse()?);
                 }
+                Value(val) if matches!(op, Some(OperationName::Disconnect)) => {
+                    nid = Some(term::args::nid(&val)?);
+                }
                 Long("rid") if matches!(op, Some(OperationName::Routing)) => {
                     let val = parser.value()?;
                     rid = term::args::rid(&val).ok();
@@ -166,7 +185,14 @@ impl Args for Options {
                     let val = parser.value()?;
                     nid = term::args::nid(&val).ok();
                 }
-                Long("json") if matches!(op, Some(OperationName::Routing)) => json = true,
+                Long("json")
+                    if matches!(
+                        op,
+                        Some(OperationName::Routing) | Some(OperationName::PolicyLog)
+                    ) =>
+                {
+                    json = true
+                }
                 Long("timeout")
                     if op == Some(OperationName::Events) || op == Some(OperationName::Connect) =>
                 {
@@ -212,7 +238,11 @@ impl Args for Options {
             },
             OperationName::Config => Operation::Config { addresses },
             OperationName::Db => Operation::Db { args: options },
+            OperationName::Disconnect => Operation::Disconnect {
+                nid: nid.ok_or_else(|| anyhow!("a Node ID must be provided"))?,
+            },
             OperationName::Events => Operation::Events { timeout, count },
+            OperationName::PolicyLog => Operation::PolicyLog { json },
             OperationName::Routing => Operation::Routing { rid, nid, json },
             OperationName::Logs => Operation::Logs { lines },
             OperationName::Start => Operation::Start {
@@ -250,6 +280,7 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
         Operation::Db { args } => {
             commands::db(&profile, args)?;
         }
+        Operation::Disconnect { nid } => control::disconnect(&mut node, nid)?,
         Operation::Sessions => {
             let sessions = control::sessions(&node)?;
             if let Some(table) = sessions {
@@ -259,6 +290,9 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
         Operation::Events { timeout, count } => {
             events::run(node, count, timeout)?;
         }
+        Operation::PolicyLog { json } => {
+            control::policy_log(&profile, json)?;
+        }
         Operation::Routing { rid, nid, json } => {
             let store = profile.database()?;
             routing::run(&store, rid, nid, json)?;
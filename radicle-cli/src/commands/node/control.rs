@@ -7,6 +7,7 @@ use anyhow::anyhow;
 use localtime::LocalTime;
 
 use radicle::node;
+use radicle::node::policy::PolicyChange;
 use radicle::node::{Address, ConnectResult, Handle as _, NodeId};
 use radicle::Node;
 use radicle::{profile, Profile};
@@ -190,6 +191,92 @@ pub fn connect(
     Ok(())
 }
 
+pub fn disconnect(node: &mut Node, nid: NodeId) -> anyhow::Result<()> {
+    // Nb. The node only replies once the peer is disconnected, so make sure we are connected.
+    if !node
+        .sessions()?
+        .iter()
+        .any(|s| s.nid == nid && s.state.is_connected())
+    {
+        anyhow::bail!("not connected to {}", term::format::node(&nid));
+    }
+    let spinner = term::spinner(format!(
+        "Disconnecting from {}...",
+        term::format::node(&nid)
+    ));
+    match node.disconnect(nid) {
+        Ok(()) => spinner.finish(),
+        Err(err) => return Err(err.into()),
+    }
+    Ok(())
+}
+
+pub fn policy_log(profile: &Profile, json: bool) -> anyhow::Result<()> {
+    let log = profile.policies()?.policy_log()?;
+
+    if json {
+        for entry in log {
+            term::print(serde_json::to_string(&entry)?);
+        }
+        return Ok(());
+    }
+    let mut table = term::Table::new(term::table::TableOptions::bordered());
+
+    table.push([
+        term::format::bold("Change").into(),
+        term::format::bold("Target").into(),
+        term::format::bold("Value").into(),
+        term::format::bold("Source").into(),
+        term::format::bold("Time").into(),
+    ]);
+    table.divider();
+
+    for entry in log {
+        let (change, target, value): (&str, term::Label, term::Label) = match entry.change {
+            PolicyChange::Follow { nid, alias } => (
+                "follow",
+                term::format::tertiary(nid).into(),
+                alias.map(|a| a.to_string()).unwrap_or_default().into(),
+            ),
+            PolicyChange::Unfollow { nid } => (
+                "unfollow",
+                term::format::tertiary(nid).into(),
+                term::Label::blank(),
+            ),
+            PolicyChange::Seed { rid, scope } => (
+                "seed",
+                term::format::tertiary(rid).into(),
+                scope.to_string().into(),
+            ),
+            PolicyChange::Unseed { rid } => (
+                "unseed",
+                term::format::tertiary(rid).into(),
+                term::Label::blank(),
+            ),
+            PolicyChange::FollowPolicy { nid, policy } => (
+                "policy",
+                term::format::tertiary(nid).into(),
+                term::format::policy(&policy).into(),
+            ),
+            PolicyChange::SeedPolicy { rid, policy } => (
+                "policy",
+                term::format::tertiary(rid).into(),
+                term::format::policy(&policy).into(),
+            ),
+        };
+        table.push([
+            change.into(),
+            target,
+            value,
+            term::format::dim(entry.source).into(),
+            term::format::dim(term::format::timestamp(entry.timestamp)).into(),
+        ]);
+    }
+    table.print();
+
+    Ok(())
+}
+
 pub fn status(node: &Node, profile: &Profile) -> anyhow::Result<()> {
     if node.is_running() {
         let listen = node
@@ -6,6 +6,8 @@ mod assign;
 mod cache;
 #[path = "patch/checkout.rs"]
 mod checkout;
+#[path = "patch/cherry_pick.rs"]
+mod cherry_pick;
 #[path = "patch/comment.rs"]
 mod comment;
 #[path = "patch/delete.rs"]
@@ -24,8 +26,12 @@ mod ready;
 mod redact;
 #[path = "patch/review.rs"]
 mod review;
+#[path = "patch/set_base.rs"]
+mod set_base;
 #[path = "patch/show.rs"]
 mod show;
+#[path = "patch/stack.rs"]
+mod stack;
 #[path = "patch/update.rs"]
 mod update;
 
@@ -57,10 +63,12 @@ Usage
     rad patch list [--all|--merged|--open|--archived|--draft|--authored] [--author <did>]... [<option>...]
     rad patch show <patch-id> [<option>...]
     rad patch diff <patch-id> [<option>...]
-    rad patch archive <patch-id> [--undo] [<option>...]
+    rad patch archive <patch-id>... [--undo] [<option>...]
+    rad patch unarchive <patch-id>... [<option>...]
     rad patch update <patch-id> [<option>...]
     rad patch checkout <patch-id> [<option>...]
-    rad patch review <patch-id> [--accept | --reject] [-m [<string>]] [-d | --delete] [<option>...]
+    rad patch cherry-pick <patch-id> [--revision <id>] [<option>...]
+    rad patch review <patch-id> [--accept | --reject | --comment] [-m [<string>]] [-d | --delete] [<option>...]
     rad patch delete <patch-id> [<option>...]
     rad patch redact <revision-id> [<option>...]
     rad patch assign <revision-id> [--add <did>] [--delete <did>] [<option>...]
@@ -68,8 +76,10 @@ Usage
     rad patch ready <patch-id> [--undo] [<option>...]
     rad patch edit <patch-id> [<option>...]
     rad patch set <patch-id> [<option>...]
+    rad patch set-base <patch-id> <revspec> [<option>...]
     rad patch comment <patch-id | revision-id> [<option>...]
     rad patch cache [<patch-id>] [<option>...]
+    rad patch stack [<option>...]
 
 Show options
 
@@ -97,6 +107,7 @@ Review options
         --hunk <index>         Only review a specific hunk
         --accept               Accept a patch or set of hunks
         --reject               Reject a patch or set of hunks
+        --comment              Review without a verdict, only leaving a comment
     -U, --unified <n>          Generate diffs with <n> lines of context instead of the usual three
     -d, --delete               Delete a review draft
     -m, --message [<string>]   Provide a comment with the review (default: prompt)
@@ -111,7 +122,7 @@ Assign options
 
 Archive options
 
-        --undo                 Unarchive a patch
+        --undo                 Unarchive the patches, same as `rad patch unarchive`
 
 Label options
 
@@ -127,6 +138,11 @@ Update options
     -m, --message [<string>]   Provide a comment message to the patch or revision (default: prompt)
         --no-message           Leave the patch or revision comment message blank
 
+Set-base options
+
+    -m, --message [<string>]   Provide a comment message to the new revision (default: prompt)
+        --no-message           Leave the revision comment message blank
+
 List options
 
         --all                  Show all patches, including merged and archived patches
@@ -148,6 +164,10 @@ Checkout options
         --name <string>        Provide a name for the branch to checkout
     -f, --force                Checkout the head of the revision, even if the branch already exists
 
+Cherry-pick options
+
+    -r, --revision <id>        Cherry-pick the commits of the given revision (default: latest)
+
 Other options
 
         --repo <rid>           Operate on the given repository (default: cwd)
@@ -166,6 +186,7 @@ pub enum OperationName {
     Archive,
     Delete,
     Checkout,
+    CherryPick,
     Comment,
     Ready,
     Review,
@@ -175,7 +196,9 @@ pub enum OperationName {
     Edit,
     Redact,
     Set,
+    SetBase,
     Cache,
+    Stack,
 }
 
 #[derive(Debug, Default, PartialEq, Eq)]
@@ -207,7 +230,7 @@ pub enum Operation {
         message: Message,
     },
     Archive {
-        patch_id: Rev,
+        patch_ids: Vec<Rev>,
         undo: bool,
     },
     Ready {
@@ -222,6 +245,10 @@ pub enum Operation {
         revision_id: Option<Rev>,
         opts: checkout::Options,
     },
+    CherryPick {
+        patch_id: Rev,
+        revision_id: Option<Rev>,
+    },
     Comment {
         revision_id: Rev,
         message: Message,
@@ -254,9 +281,15 @@ pub enum Operation {
     Set {
         patch_id: Rev,
     },
+    SetBase {
+        patch_id: Rev,
+        base_id: Rev,
+        message: Message,
+    },
     Cache {
         patch_id: Option<Rev>,
     },
+    Stack,
 }
 
 impl Operation {
@@ -272,12 +305,15 @@ impl Operation {
             | Operation::Label { .. }
             | Operation::Edit { .. }
             | Operation::Redact { .. }
-            | Operation::Set { .. } => true,
+            | Operation::Set { .. }
+            | Operation::SetBase { .. } => true,
             Operation::Show { .. }
             | Operation::Diff { .. }
             | Operation::Checkout { .. }
+            | Operation::CherryPick { .. }
             | Operation::List { .. }
-            | Operation::Cache { .. } => false,
+            | Operation::Cache { .. }
+            | Operation::Stack => false,
         }
     }
 }
@@ -305,6 +341,7 @@ impl Args for Options {
         let mut authors = vec![];
         let mut announce = true;
         let mut patch_id = None;
+        let mut patch_ids = Vec::new();
         let mut revision_id = None;
         let mut message = Message::default();
         let mut filter = Some(patch::Status::Open);
@@ -316,6 +353,7 @@ impl Args for Options {
         let mut assign_opts = AssignOptions::default();
         let mut label_opts = LabelOptions::default();
         let mut review_op = review::Operation::default();
+        let mut review_comment = false;
         let mut base_id = None;
         let mut repo = None;
 
@@ -383,7 +421,9 @@ impl Args for Options {
 
                 // Review/diff options.
                 Long("revision") | Short('r')
-                    if op == Some(OperationName::Review) || op == Some(OperationName::Diff) =>
+                    if op == Some(OperationName::Review)
+                        || op == Some(OperationName::Diff)
+                        || op == Some(OperationName::CherryPick) =>
                 {
                     let val = parser.value()?;
                     let rev = term::args::rev(&val)?;
@@ -419,7 +459,7 @@ impl Args for Options {
                 Long("delete") | Short('d') if op == Some(OperationName::Review) => {
                     review_op = review::Operation::Delete;
                 }
-                Long("accept") if op == Some(OperationName::Review) => {
+                Long("accept") if op == Some(OperationName::Review) && !review_comment => {
                     if let review::Operation::Review {
                         verdict: verdict @ None,
                         ..
@@ -430,7 +470,7 @@ impl Args for Options {
                         return Err(arg.unexpected().into());
                     }
                 }
-                Long("reject") if op == Some(OperationName::Review) => {
+                Long("reject") if op == Some(OperationName::Review) && !review_comment => {
                     if let review::Operation::Review {
                         verdict: verdict @ None,
                         ..
@@ -441,6 +481,13 @@ impl Args for Options {
                         return Err(arg.unexpected().into());
                     }
                 }
+                Long("comment") if op == Some(OperationName::Review) => {
+                    if let review::Operation::Review { verdict: None, .. } = review_op {
+                        review_comment = true;
+                    } else {
+                        return Err(arg.unexpected().into());
+                    }
+                }
 
                 // Checkout options
                 Long("revision") if op == Some(OperationName::Checkout) => {
@@ -536,7 +583,12 @@ impl Args for Options {
                     "u" | "update" => op = Some(OperationName::Update),
                     "d" | "delete" => op = Some(OperationName::Delete),
                     "c" | "checkout" => op = Some(OperationName::Checkout),
+                    "cherry-pick" => op = Some(OperationName::CherryPick),
                     "a" | "archive" => op = Some(OperationName::Archive),
+                    "unarchive" => {
+                        op = Some(OperationName::Archive);
+                        undo = true;
+                    }
                     "y" | "ready" => op = Some(OperationName::Ready),
                     "e" | "edit" => op = Some(OperationName::Edit),
                     "r" | "redact" => op = Some(OperationName::Redact),
@@ -546,13 +598,26 @@ impl Args for Options {
                     "comment" => op = Some(OperationName::Comment),
                     "review" => op = Some(OperationName::Review),
                     "set" => op = Some(OperationName::Set),
+                    "set-base" => op = Some(OperationName::SetBase),
                     "cache" => op = Some(OperationName::Cache),
+                    "stack" => op = Some(OperationName::Stack),
                     unknown => anyhow::bail!("unknown operation '{}'", unknown),
                 },
+                Value(val) if op == Some(OperationName::Archive) => {
+                    patch_ids.push(Rev::from(string(&val)));
+                }
                 Value(val) if op == Some(OperationName::Redact) => {
                     let rev = term::args::rev(&val)?;
                     revision_id = Some(rev);
                 }
+                Value(val)
+                    if op == Some(OperationName::SetBase)
+                        && patch_id.is_some()
+                        && base_id.is_none() =>
+                {
+                    let rev = term::args::rev(&val)?;
+                    base_id = Some(rev);
+                }
                 Value(val)
                     if patch_id.is_none()
                         && [
@@ -560,13 +625,14 @@ impl Args for Options {
                             Some(OperationName::Diff),
                             Some(OperationName::Update),
                             Some(OperationName::Delete),
-                            Some(OperationName::Archive),
                             Some(OperationName::Ready),
                             Some(OperationName::Checkout),
+                            Some(OperationName::CherryPick),
                             Some(OperationName::Comment),
                             Some(OperationName::Review),
                             Some(OperationName::Edit),
                             Some(OperationName::Set),
+                            Some(OperationName::SetBase),
                             Some(OperationName::Assign),
                             Some(OperationName::Label),
                             Some(OperationName::Cache),
@@ -599,15 +665,21 @@ impl Args for Options {
                 base_id,
                 message,
             },
-            OperationName::Archive => Operation::Archive {
-                patch_id: patch_id.ok_or_else(|| anyhow!("a patch id must be provided"))?,
-                undo,
-            },
+            OperationName::Archive => {
+                if patch_ids.is_empty() {
+                    anyhow::bail!("a patch id must be provided");
+                }
+                Operation::Archive { patch_ids, undo }
+            }
             OperationName::Checkout => Operation::Checkout {
                 patch_id: patch_id.ok_or_else(|| anyhow!("a patch must be provided"))?,
                 revision_id,
                 opts: checkout_opts,
             },
+            OperationName::CherryPick => Operation::CherryPick {
+                patch_id: patch_id.ok_or_else(|| anyhow!("a patch must be provided"))?,
+                revision_id,
+            },
             OperationName::Comment => Operation::Comment {
                 revision_id: patch_id
                     .ok_or_else(|| anyhow!("a patch or revision must be provided"))?,
@@ -646,7 +718,13 @@ impl Args for Options {
             OperationName::Set => Operation::Set {
                 patch_id: patch_id.ok_or_else(|| anyhow!("a patch must be provided"))?,
             },
+            OperationName::SetBase => Operation::SetBase {
+                patch_id: patch_id.ok_or_else(|| anyhow!("a patch must be provided"))?,
+                base_id: base_id.ok_or_else(|| anyhow!("a base revision must be provided"))?,
+                message,
+            },
             OperationName::Cache => Operation::Cache { patch_id },
+            OperationName::Stack => Operation::Stack,
         };
 
         Ok((
@@ -737,9 +815,14 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
                 &workdir,
             )?;
         }
-        Operation::Archive { ref patch_id, undo } => {
-            let patch_id = patch_id.resolve::<PatchId>(&repository.backend)?;
-            archive::run(&patch_id, undo, &profile, &repository)?;
+        Operation::Archive {
+            ref patch_ids,
+            undo,
+        } => {
+            for patch_id in patch_ids {
+                let patch_id = patch_id.resolve::<PatchId>(&repository.backend)?;
+                archive::run(&patch_id, undo, &profile, &repository)?;
+            }
         }
         Operation::Ready { ref patch_id, undo } => {
             let patch_id = patch_id.resolve::<PatchId>(&repository.backend)?;
@@ -778,6 +861,26 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
                 opts,
             )?;
         }
+        Operation::CherryPick {
+            patch_id,
+            revision_id,
+        } => {
+            let patch_id = patch_id.resolve::<radicle::git::Oid>(&repository.backend)?;
+            let revision_id = revision_id
+                .map(|rev| rev.resolve::<radicle::git::Oid>(&repository.backend))
+                .transpose()?
+                .map(patch::RevisionId::from);
+            let workdir = workdir.ok_or(anyhow!(
+                "this command must be run from a repository checkout"
+            ))?;
+            cherry_pick::run(
+                &patch::PatchId::from(patch_id),
+                revision_id,
+                &repository,
+                &workdir,
+                &profile,
+            )?;
+        }
         Operation::Comment {
             revision_id,
             message,
@@ -844,12 +947,25 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
             ))?;
             radicle::rad::setup_patch_upstream(&patch_id, *patch.head(), &workdir, true)?;
         }
+        Operation::SetBase {
+            patch_id,
+            base_id,
+            message,
+        } => {
+            let patch_id = patch_id.resolve(&repository.backend)?;
+            let base_id = base_id.resolve::<radicle::git::Oid>(&repository.backend)?;
+
+            set_base::run(&patch_id, base_id, message, &profile, &repository)?;
+        }
         Operation::Cache { patch_id } => {
             let patch_id = patch_id
                 .map(|id| id.resolve(&repository.backend))
                 .transpose()?;
             cache::run(patch_id, &repository, &profile)?;
         }
+        Operation::Stack => {
+            stack::run(&repository, &profile)?;
+        }
     }
 
     if announce {
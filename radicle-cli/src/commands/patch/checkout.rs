@@ -112,7 +112,7 @@ pub fn run(
 
 /// Try to find the patch head in our working copy, and if we don't find it,
 /// fetch it from storage first.
-fn find_patch_commit<'a>(
+pub(super) fn find_patch_commit<'a>(
     revision: &patch::Revision,
     stored: &Repository,
     working: &'a git::raw::Repository,
@@ -0,0 +1,68 @@
+use anyhow::anyhow;
+
+use radicle::cob::patch;
+use radicle::cob::patch::RevisionId;
+use radicle::patch::cache::Patches as _;
+use radicle::patch::PatchId;
+use radicle::storage::git::Repository;
+use radicle::{git, Profile};
+
+use super::checkout::find_patch_commit;
+use crate::terminal as term;
+
+pub fn run(
+    patch_id: &PatchId,
+    revision_id: Option<RevisionId>,
+    stored: &Repository,
+    working: &git::raw::Repository,
+    profile: &Profile,
+) -> anyhow::Result<()> {
+    let patches = profile.patches(stored)?;
+    let patch = patches
+        .get(patch_id)?
+        .ok_or_else(|| anyhow!("Patch `{patch_id}` not found"))?;
+
+    let (revision_id, revision) = match revision_id {
+        Some(id) => (
+            id,
+            patch
+                .revision(&id)
+                .ok_or_else(|| anyhow!("Patch revision `{id}` not found"))?,
+        ),
+        None => patch.latest(),
+    };
+    // Make sure the revision's commits are in the working copy.
+    find_patch_commit(revision, stored, working)?;
+
+    let spinner = term::spinner(format!(
+        "Cherry-picking revision {}...",
+        term::format::dim(term::format::oid(revision_id))
+    ));
+    let picked = patch.cherry_pick(revision_id, working)?;
+
+    match picked.preview {
+        patch::MergePreview::Clean => {
+            spinner.finish();
+        }
+        patch::MergePreview::Conflicting { files } => {
+            spinner.failed();
+
+            for file in files {
+                term::warning(format!("conflict in {}", file.display()));
+            }
+        }
+    }
+    for oid in picked.commits {
+        let commit = working.find_commit(oid.into())?;
+
+        term::info!(
+            "{} {}",
+            term::format::secondary(term::format::oid(commit.id())),
+            commit.summary().unwrap_or_default()
+        );
+    }
+    if !picked.preview.is_clean() {
+        anyhow::bail!("revision {revision_id} could not be fully applied");
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use radicle::cob::patch;
+use radicle::git;
+use radicle::prelude::*;
+use radicle::storage::git::Repository;
+
+use crate::terminal as term;
+
+const HELP: &str = r#"<!--
+Please enter a comment describing the change of base for this patch.
+Lines starting with '<!--' and ending with '-->' will be ignored.
+-->"#;
+
+/// Run patch set-base.
+pub fn run(
+    patch_id: &patch::PatchId,
+    base: git::Oid,
+    message: term::patch::Message,
+    profile: &Profile,
+    repository: &Repository,
+) -> anyhow::Result<()> {
+    let mut patches = profile.patches_mut(repository)?;
+    let Ok(mut patch) = patches.get_mut(patch_id) else {
+        anyhow::bail!("Patch `{patch_id}` not found");
+    };
+    let head = *patch.head();
+
+    if *patch.base() == base {
+        term::info!("Patch {patch_id} is already based on {base}");
+        return Ok(());
+    }
+    // If the new base contains the patch head, the patch has nothing left to merge.
+    if base == head || repository.backend.merge_base(*base, *head)? == *head {
+        term::warning(format!(
+            "patch is empty: all of its commits are contained in the new base {}",
+            term::format::oid(base)
+        ));
+    }
+
+    let message = message.get(HELP)?;
+    let signer = term::signer(profile)?;
+    let revision = patch.update(message, base, head, &signer)?;
+
+    term::print(revision);
+
+    Ok(())
+}
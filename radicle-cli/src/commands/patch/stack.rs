@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+
+use radicle::cob::patch::PatchStack;
+use radicle::patch::cache::Patches as _;
+use radicle::profile::Profile;
+use radicle::storage::git::Repository;
+
+use crate::terminal as term;
+
+/// Show open patches in stacking order, with each patch indented under the patch
+/// it is stacked on.
+pub fn run(repository: &Repository, profile: &Profile) -> anyhow::Result<()> {
+    let patches = profile.patches(repository)?;
+    let opened = patches
+        .opened()?
+        .filter_map(|p| p.ok())
+        .collect::<BTreeMap<_, _>>();
+    let stack = PatchStack::detect(repository, opened.iter().map(|(id, p)| (*id, p)))?;
+
+    if stack.is_empty() {
+        term::print(term::format::italic("Nothing to show."));
+        return Ok(());
+    }
+
+    for id in stack.iter() {
+        let Some(patch) = opened.get(id) else {
+            continue;
+        };
+        let prefix = match stack.depth(id) {
+            0 => String::new(),
+            n => format!("{}└─ ", "   ".repeat(n - 1)),
+        };
+        term::print(format!(
+            "{prefix}{} {}",
+            term::format::tertiary(term::format::cob(id)),
+            patch.title()
+        ));
+    }
+    Ok(())
+}
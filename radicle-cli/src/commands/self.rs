@@ -1,7 +1,15 @@
 use std::ffi::OsString;
+use std::fs;
+use std::io::Write as _;
+use std::os::unix::fs::OpenOptionsExt as _;
+use std::path::PathBuf;
 
-use radicle::crypto::ssh;
-use radicle::Profile;
+use anyhow::{anyhow, Context as _};
+
+use radicle::crypto::ssh::{self, Keystore};
+use radicle::node::Alias;
+use radicle::profile::env::RAD_PASSPHRASE;
+use radicle::{profile, Profile};
 
 use crate::terminal as term;
 use crate::terminal::args::{Args, Error, Help};
@@ -15,6 +23,12 @@ pub const HELP: Help = Help {
 Usage
 
     rad self [<option>...]
+    rad self --export <file>
+    rad self --import <file> [--force]
+
+    The exported file contains your secret key, encrypted with your passphrase,
+    and your alias. If your key isn't encrypted, a passphrase to encrypt it with
+    is asked for, or read from `RAD_PASSPHRASE`.
 
 Options
 
@@ -25,6 +39,9 @@ Options
     --config             Show the location of your configuration file
     --ssh-key            Show your public key in OpenSSH format
     --ssh-fingerprint    Show your public key fingerprint in OpenSSH format
+    --export <file>      Export your identity to a file
+    --import <file>      Import an identity from a file, creating your profile
+    --force              Overwrite an existing key when importing
     --help               Show help
 "#,
 };
@@ -41,9 +58,16 @@ enum Show {
     All,
 }
 
+#[derive(Debug)]
+enum Operation {
+    Show(Show),
+    Export { file: PathBuf },
+    Import { file: PathBuf, force: bool },
+}
+
 #[derive(Debug)]
 pub struct Options {
-    show: Show,
+    op: Operation,
 }
 
 impl Args for Options {
@@ -52,6 +76,9 @@ impl Args for Options {
 
         let mut parser = lexopt::Parser::from_args(args);
         let mut show: Option<Show> = None;
+        let mut export: Option<PathBuf> = None;
+        let mut import: Option<PathBuf> = None;
+        let mut force = false;
 
         while let Some(arg) = parser.next()? {
             match arg {
@@ -76,6 +103,15 @@ impl Args for Options {
                 Long("ssh-fingerprint") if show.is_none() => {
                     show = Some(Show::SshFingerprint);
                 }
+                Long("export") if export.is_none() && import.is_none() => {
+                    export = Some(parser.value()?.into());
+                }
+                Long("import") if export.is_none() && import.is_none() => {
+                    import = Some(parser.value()?.into());
+                }
+                Long("force") => {
+                    force = true;
+                }
                 Long("help") | Short('h') => {
                     return Err(Error::Help.into());
                 }
@@ -83,19 +119,29 @@ impl Args for Options {
             }
         }
 
-        Ok((
-            Options {
-                show: show.unwrap_or(Show::All),
-            },
-            vec![],
-        ))
+        let op = match (export, import) {
+            (Some(file), None) if show.is_none() => Operation::Export { file },
+            (None, Some(file)) if show.is_none() => Operation::Import { file, force },
+            (None, None) => Operation::Show(show.unwrap_or(Show::All)),
+            _ => anyhow::bail!("`--export` and `--import` can't be combined with other options"),
+        };
+        if force && !matches!(op, Operation::Import { .. }) {
+            anyhow::bail!("`--force` can only be used with `--import`");
+        }
+
+        Ok((Options { op }, vec![]))
     }
 }
 
 pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
+    let show = match options.op {
+        Operation::Show(show) => show,
+        Operation::Export { file } => return export(&ctx.profile()?, file),
+        Operation::Import { file, force } => return import(file, force),
+    };
     let profile = ctx.profile()?;
 
-    match options.show {
+    match show {
         Show::Alias => {
             term::print(profile.config.alias());
         }
@@ -123,6 +169,67 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Export the identity key and alias of the profile to `file`.
+fn export(profile: &Profile, file: PathBuf) -> anyhow::Result<()> {
+    let passphrase = if profile.keystore.is_encrypted()? {
+        None
+    } else {
+        Some(term::passphrase_confirm(
+            "Enter a passphrase to encrypt your key with:",
+            RAD_PASSPHRASE,
+        )?)
+    };
+    let secret = profile.keystore.export(passphrase)?;
+    let mut f = fs::OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .mode(0o600)
+        .open(&file)
+        .with_context(|| format!("failed to create '{}'", file.display()))?;
+
+    writeln!(f, "alias {}", profile.config.alias())?;
+    f.write_all(secret.as_bytes())?;
+    f.sync_all()?;
+
+    term::success!(
+        "Exported {} to {}",
+        term::format::tertiary(profile.id()),
+        term::format::highlight(file.display())
+    );
+
+    Ok(())
+}
+
+/// Import an identity exported with [`export`], initializing the profile if needed.
+fn import(file: PathBuf, force: bool) -> anyhow::Result<()> {
+    let contents = fs::read_to_string(&file)
+        .with_context(|| format!("failed to read '{}'", file.display()))?;
+    let (alias, secret) = contents
+        .split_once('\n')
+        .and_then(|(header, secret)| Some((header.strip_prefix("alias ")?, secret)))
+        .ok_or_else(|| anyhow!("'{}' is not a valid identity export", file.display()))?;
+    let alias = alias.parse::<Alias>()?;
+    let home = profile::home()?;
+
+    if !force {
+        if let Some(existing) = Keystore::new(&home.keys()).public_key()? {
+            anyhow::bail!(
+                "a key for {existing} already exists in {}; use `--force` to overwrite it",
+                home.path().display()
+            );
+        }
+    }
+    let profile = Profile::import(home, alias, secret, force)?;
+
+    term::success!(
+        "Imported {} ({})",
+        term::format::tertiary(profile.id()),
+        term::format::primary(profile.config.alias())
+    );
+
+    Ok(())
+}
+
 fn all(profile: &Profile) -> anyhow::Result<()> {
     let mut table = term::Table::<2, term::Label>::default();
 
@@ -1,5 +1,6 @@
 use std::ffi::OsString;
 use std::path::Path;
+use std::{fs, io};
 
 use localtime::LocalDuration;
 use localtime::LocalTime;
@@ -7,8 +8,11 @@ use radicle::git;
 use radicle::issue::cache::Issues as _;
 use radicle::node::address;
 use radicle::node::routing;
+use radicle::node::routing::Store as _;
 use radicle::patch::cache::Patches as _;
+use radicle::prelude::RepoId;
 use radicle::storage::{ReadRepository, ReadStorage, WriteRepository};
+use radicle::Profile;
 use radicle_term::Element;
 use serde::Serialize;
 
@@ -23,9 +27,16 @@ pub const HELP: Help = Help {
 Usage
 
     rad stats [<option>...]
+    rad stats <rid> [<option>...]
+
+    Without a repository, displays metrics aggregated over all the
+    repositories in local storage, and all known nodes.
+
+    With a repository, displays metrics of that repository only.
 
 Options
 
+    --json       Output the repository metrics as JSON
     --help       Print help
 "#,
 };
@@ -60,31 +71,65 @@ struct Stats {
     nodes: NodeStats,
 }
 
+/// Metrics of a single repository.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RepoMetrics {
+    rid: RepoId,
+    commits: usize,
+    contributors: usize,
+    open_issues: usize,
+    open_patches: usize,
+    /// Size of the repository on disk, in bytes.
+    size: u64,
+    /// Number of nodes known to seed the repository.
+    seeds: usize,
+}
+
 #[derive(Default, Debug, Eq, PartialEq)]
-pub struct Options {}
+pub struct Options {
+    rid: Option<RepoId>,
+    json: bool,
+}
 
 impl Args for Options {
     fn from_args(args: Vec<OsString>) -> anyhow::Result<(Self, Vec<OsString>)> {
         use lexopt::prelude::*;
 
         let mut parser = lexopt::Parser::from_args(args);
+        let mut rid = None;
+        let mut json = false;
 
-        #[allow(clippy::never_loop)]
         while let Some(arg) = parser.next()? {
             match arg {
+                Long("json") => json = true,
                 Long("help") | Short('h') => {
                     return Err(Error::Help.into());
                 }
+                Value(val) if rid.is_none() => {
+                    rid = Some(term::args::rid(&val)?);
+                }
                 _ => return Err(anyhow::anyhow!(arg.unexpected())),
             }
         }
 
-        Ok((Options {}, vec![]))
+        Ok((Options { rid, json }, vec![]))
     }
 }
 
-pub fn run(_options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
+pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
     let profile = ctx.profile()?;
+
+    if let Some(rid) = options.rid {
+        let metrics = repo(rid, &profile)?;
+
+        if options.json {
+            println!("{}", serde_json::to_string_pretty(&metrics)?);
+        } else {
+            print(&metrics);
+        }
+        return Ok(());
+    }
     let storage = &profile.storage;
     let mut stats = Stats::default();
 
@@ -110,20 +155,11 @@ pub fn run(_options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
 
     let db = profile.database()?;
     stats.nodes.all = address::Store::nodes(&db)?;
-    stats.repos.replicas = routing::Store::len(&db)?;
 
-    {
-        let row = db
-            .db
-            .prepare("SELECT COUNT(DISTINCT repo) FROM routing")?
-            // SAFETY: `COUNT` always returns a row.
-            .into_iter()
-            .next()
-            .unwrap()?;
-        let count = row.read::<i64, _>(0) as usize;
-
-        stats.repos.unique = count;
-    }
+    let routing = routing::Store::stats(&db)?;
+    stats.repos.replicas = routing.entries;
+    stats.repos.unique = routing.repos;
+    stats.nodes.seeding = routing.nodes;
 
     {
         let now = LocalTime::now();
@@ -155,21 +191,71 @@ pub fn run(_options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
         stats.nodes.public = count;
     }
 
-    {
-        let row = db
-            .db
-            .prepare("SELECT COUNT(DISTINCT node) FROM routing")?
-            .into_iter()
-            .next()
-            // SAFETY: `COUNT` always returns a row.
-            .unwrap()?;
-        let count = row.read::<i64, _>(0) as usize;
-
-        stats.nodes.seeding = count;
-    }
-
     let output = term::json::to_pretty(&stats, Path::new("stats.json"))?;
     output.print();
 
     Ok(())
 }
+
+/// Compute the metrics of a repository.
+fn repo(rid: RepoId, profile: &Profile) -> anyhow::Result<RepoMetrics> {
+    let repo = profile.storage.repository(rid)?;
+    let (_, head) = repo.head()?;
+    let stats = radicle_surf::Repository::open(repo.path())?.stats_from(&head)?;
+    let issues = profile.issues(&repo)?.counts()?;
+    let patches = profile.patches(&repo)?.counts()?;
+    let seeds = profile.database()?.count(&rid)?;
+
+    Ok(RepoMetrics {
+        rid,
+        commits: stats.commits,
+        contributors: stats.contributors,
+        open_issues: issues.open,
+        open_patches: patches.open,
+        size: disk_usage(repo.path())?,
+        seeds,
+    })
+}
+
+/// Print the metrics of a repository as a table.
+fn print(metrics: &RepoMetrics) {
+    let mut t = term::Table::new(term::table::TableOptions::bordered());
+    let rows = [
+        ("Commits", metrics.commits.to_string()),
+        ("Contributors", metrics.contributors.to_string()),
+        ("Open issues", metrics.open_issues.to_string()),
+        ("Open patches", metrics.open_patches.to_string()),
+        ("Size", format!("{} KiB", metrics.size.div_ceil(1024))),
+        ("Seeds", metrics.seeds.to_string()),
+    ];
+    t.push([
+        term::format::default(String::from("Repository")),
+        term::format::tertiary(metrics.rid.urn()),
+    ]);
+    t.divider();
+
+    for (name, value) in rows {
+        t.push([
+            term::format::default(name.to_owned()),
+            term::format::highlight(value),
+        ]);
+    }
+    t.print();
+}
+
+/// Total size of the files under a directory, in bytes.
+fn disk_usage(path: &Path) -> io::Result<u64> {
+    let mut size = 0;
+
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let meta = entry.metadata()?;
+
+        if meta.is_dir() {
+            size += disk_usage(&entry.path())?;
+        } else {
+            size += meta.len();
+        }
+    }
+    Ok(size)
+}
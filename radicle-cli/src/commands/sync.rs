@@ -30,6 +30,7 @@ pub const HELP: Help = Help {
 Usage
 
     rad sync [--fetch | --announce] [<rid>] [<option>...]
+    rad sync [--fetch-only | --announce-only] [<rid>] [<option>...]
     rad sync --inventory [<option>...]
     rad sync status [<rid>] [<option>...]
 
@@ -52,6 +53,9 @@ Usage
     When `--fetch` or `--announce` are specified on their own, this command
     will only fetch or announce.
 
+    The `--fetch-only` and `--announce-only` flags make this explicit: they
+    select a single direction and cannot be combined with the opposite flag.
+
     If `--inventory` is specified, the node's inventory is announced to
     the network. This mode does not take an `<rid>`.
 
@@ -64,6 +68,8 @@ Options
         --sort-by   <field>   Sort the table by column (options: nid, alias, status)
     -f, --fetch               Turn on fetching (default: true)
     -a, --announce            Turn on ref announcing (default: true)
+        --fetch-only          Only fetch from seeds, don't announce refs
+        --announce-only       Only announce refs, don't fetch from seeds
     -i, --inventory           Turn on inventory announcing (default: false)
         --force               Force fetches from unknown seeds (default: false)
         --timeout   <secs>    How many seconds to wait while syncing
@@ -148,6 +154,7 @@ impl Args for Options {
         let mut rid = None;
         let mut fetch = false;
         let mut announce = false;
+        let mut only: Option<SyncDirection> = None;
         let mut inventory = false;
         let mut force = false;
         let mut debug = false;
@@ -188,6 +195,18 @@ impl Args for Options {
                 Long("announce") | Short('a') => {
                     announce = true;
                 }
+                Long("fetch-only") => {
+                    if only == Some(SyncDirection::Announce) {
+                        anyhow::bail!("`--fetch-only` cannot be used with `--announce-only`");
+                    }
+                    only = Some(SyncDirection::Fetch);
+                }
+                Long("announce-only") => {
+                    if only == Some(SyncDirection::Fetch) {
+                        anyhow::bail!("`--announce-only` cannot be used with `--fetch-only`");
+                    }
+                    only = Some(SyncDirection::Announce);
+                }
                 Long("inventory") | Short('i') => {
                     inventory = true;
                 }
@@ -218,17 +237,24 @@ impl Args for Options {
             }
         }
 
-        let sync = if inventory && (fetch || announce || force) {
+        let sync = if inventory && (fetch || announce || force || only.is_some()) {
             anyhow::bail!(
                 "`--inventory` cannot be used with `--fetch` or `--announce` or `--force`"
             );
         } else if inventory {
             SyncMode::Inventory
         } else {
-            let direction = match (fetch, announce) {
-                (true, true) | (false, false) => SyncDirection::Both,
-                (true, false) => SyncDirection::Fetch,
-                (false, true) => SyncDirection::Announce,
+            let direction = match (only, fetch, announce) {
+                (Some(SyncDirection::Fetch), _, true) => {
+                    anyhow::bail!("`--fetch-only` cannot be used with `--announce`");
+                }
+                (Some(SyncDirection::Announce), true, _) => {
+                    anyhow::bail!("`--announce-only` cannot be used with `--fetch`");
+                }
+                (Some(direction), _, _) => direction,
+                (None, true, true) | (None, false, false) => SyncDirection::Both,
+                (None, true, false) => SyncDirection::Fetch,
+                (None, false, true) => SyncDirection::Announce,
             };
             if direction == SyncDirection::Announce && force {
                 anyhow::bail!("`--force` cannot be used without `--fetch`");
@@ -1,10 +1,15 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::OsString;
+use std::str::FromStr;
 use std::{thread, time};
 
 use anyhow::{anyhow, Context as _};
 
+use radicle::cob;
+use radicle::cob::object::Storage as _;
 use radicle::git;
 use radicle::prelude::{NodeId, RepoId};
+use radicle::storage::git::Repository;
 use radicle::storage::{ReadRepository, ReadStorage};
 
 use crate::terminal as term;
@@ -18,10 +23,16 @@ pub const HELP: Help = Help {
 Usage
 
     rad watch -r <ref> [-t <oid>] [--repo <rid>] [<option>...]
+    rad watch [--type <type>...] [--repo <rid>] [--json] [<option>...]
 
     Watches a Git reference, and optionally exits when it reaches a target value.
     If no target value is passed, exits when the target changes.
 
+    When no reference is given, watches the repository's collaborative objects
+    instead, and prints a notification every time one is created or updated,
+    eg. when a new issue is opened or a comment is added to a patch.
+    By default, issues and patches are watched.
+
 Options
 
         --repo      <rid>       The repository to watch (default: `rad .`)
@@ -34,15 +45,28 @@ Options
     -i, --interval  <millis>    How often, in milliseconds, to check the reference target
                                 (default: 1000)
         --timeout   <millis>    Timeout, in milliseconds (default: none)
+
+COB options
+
+        --type      <type>      The type of collaborative object to watch, eg. 'xyz.radicle.issue'
+                                (may be specified multiple times)
+    -n, --count     <n>         Exit after this many notifications (default: none)
+        --json                  Print notifications as JSON lines
+
+Other options
+
     -h, --help                  Print help
 "#,
 };
 
 pub struct Options {
     rid: Option<RepoId>,
-    refstr: git::RefString,
+    refstr: Option<git::RefString>,
     target: Option<git::Oid>,
     nid: Option<NodeId>,
+    types: BTreeSet<cob::TypeName>,
+    count: Option<usize>,
+    json: bool,
     interval: time::Duration,
     timeout: time::Duration,
 }
@@ -58,6 +82,9 @@ impl Args for Options {
         let mut refstr: Option<git::RefString> = None;
         let mut interval: Option<time::Duration> = None;
         let mut timeout: time::Duration = time::Duration::MAX;
+        let mut types = BTreeSet::new();
+        let mut count = None;
+        let mut json = false;
 
         while let Some(arg) = parser.next()? {
             match arg {
@@ -97,6 +124,22 @@ impl Args for Options {
 
                     timeout = value;
                 }
+                Long("type") => {
+                    let value = parser.value()?;
+                    let value = term::args::string(&value);
+                    let value = cob::TypeName::from_str(&value)?;
+
+                    types.insert(value);
+                }
+                Long("count") | Short('n') => {
+                    let value = parser.value()?;
+                    let value = term::args::number(&value)?;
+
+                    count = Some(value);
+                }
+                Long("json") => {
+                    json = true;
+                }
                 Long("help") | Short('h') => {
                     return Err(Error::Help.into());
                 }
@@ -104,12 +147,22 @@ impl Args for Options {
             }
         }
 
+        if refstr.is_none() && target.is_some() {
+            anyhow::bail!("a reference must be provided with `--target`");
+        }
+        if refstr.is_some() && (!types.is_empty() || count.is_some() || json) {
+            anyhow::bail!("`--type`, `--count` and `--json` can't be used with `--ref`");
+        }
+
         Ok((
             Options {
                 rid,
-                refstr: refstr.ok_or_else(|| anyhow!("a reference must be provided"))?,
+                refstr,
                 nid,
                 target,
+                types,
+                count,
+                json,
                 interval: interval.unwrap_or(time::Duration::from_secs(1)),
                 timeout,
             },
@@ -121,11 +174,6 @@ impl Args for Options {
 pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
     let profile = ctx.profile()?;
     let storage = &profile.storage;
-    let qualified = options
-        .refstr
-        .qualified()
-        .ok_or_else(|| anyhow!("reference must be fully-qualified, eg. 'refs/heads/master'"))?;
-    let nid = options.nid.unwrap_or(profile.public_key);
     let rid = match options.rid {
         Some(rid) => rid,
         None => {
@@ -135,6 +183,14 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
         }
     };
     let repo = storage.repository(rid)?;
+
+    let Some(refstr) = &options.refstr else {
+        return watch_cobs(&repo, &options);
+    };
+    let qualified = refstr
+        .qualified()
+        .ok_or_else(|| anyhow!("reference must be fully-qualified, eg. 'refs/heads/master'"))?;
+    let nid = options.nid.unwrap_or(profile.public_key);
     let now = time::SystemTime::now();
 
     if let Some(target) = options.target {
@@ -173,3 +229,90 @@ fn reference<R: ReadRepository>(
         Err(e) => Err(e),
     }
 }
+
+/// A change to a collaborative object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+enum Event {
+    /// The object was created.
+    Created,
+    /// The object was updated, eg. with a new comment.
+    Updated,
+}
+
+impl std::fmt::Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Created => write!(f, "created"),
+            Self::Updated => write!(f, "updated"),
+        }
+    }
+}
+
+/// The heads of every collaborative object of the watched types.
+type Heads = BTreeMap<(cob::TypeName, cob::ObjectId), BTreeSet<git::Oid>>;
+
+fn watch_cobs(repo: &Repository, options: &Options) -> anyhow::Result<()> {
+    let types = if options.types.is_empty() {
+        BTreeSet::from([cob::issue::TYPENAME.clone(), cob::patch::TYPENAME.clone()])
+    } else {
+        options.types.clone()
+    };
+    let now = time::SystemTime::now();
+    let mut heads = cob_heads(repo, &types)?;
+    let mut count = 0;
+
+    loop {
+        thread::sleep(options.interval);
+
+        let latest = cob_heads(repo, &types)?;
+        for ((typename, id), tips) in &latest {
+            let event = match heads.get(&(typename.clone(), *id)) {
+                None => Event::Created,
+                Some(previous) if previous != tips => Event::Updated,
+                Some(_) => continue,
+            };
+            if options.json {
+                println!(
+                    "{}",
+                    serde_json::json!({ "event": event, "type": typename, "id": id })
+                );
+            } else {
+                let kind = if *typename == *cob::issue::TYPENAME {
+                    String::from("issue")
+                } else if *typename == *cob::patch::TYPENAME {
+                    String::from("patch")
+                } else {
+                    typename.to_string()
+                };
+                term::info!(
+                    "{} {kind} {}",
+                    term::format::tertiary(event),
+                    term::format::cob(id)
+                );
+            }
+            count += 1;
+
+            if options.count.is_some_and(|n| count >= n) {
+                return Ok(());
+            }
+        }
+        heads = latest;
+
+        if now.elapsed()? >= options.timeout {
+            anyhow::bail!("timed out after {}ms", options.timeout.as_millis());
+        }
+    }
+}
+
+fn cob_heads(repo: &Repository, types: &BTreeSet<cob::TypeName>) -> anyhow::Result<Heads> {
+    let mut heads = Heads::new();
+
+    for typename in types {
+        for (id, objects) in repo.types(typename)? {
+            let tips = objects.iter().map(|r| r.target.id).collect();
+            heads.insert((typename.clone(), id), tips);
+        }
+    }
+    Ok(heads)
+}
@@ -4,6 +4,7 @@ use std::{io::ErrorKind, iter, process};
 
 use anyhow::anyhow;
 
+use radicle::profile::env;
 use radicle::version::Version;
 use radicle_cli::commands::*;
 use radicle_cli::terminal as term;
@@ -64,6 +65,12 @@ fn parse_args() -> anyhow::Result<Command> {
             Long("version") => {
                 command = Some(Command::Version { json: false });
             }
+            Long("profile") if command.is_none() => {
+                let name = parser.value()?;
+                // N.b. this is picked up by `radicle::profile::home`, when loading
+                // the profile of the command.
+                env::set_var(env::RAD_PROFILE, name);
+            }
             Value(val) if command.is_none() => {
                 if val == *"." {
                     command = Some(Command::Other(vec![OsString::from("inspect")]));
@@ -175,6 +182,13 @@ fn run_other(exe: &str, args: &[OsString]) -> Result<(), Option<anyhow::Error>>
                 args.to_vec(),
             );
         }
+        "complete" => {
+            term::run_command_args::<rad_complete::Options, _>(
+                rad_complete::HELP,
+                rad_complete::run,
+                args.to_vec(),
+            );
+        }
         "debug" => {
             term::run_command_args::<rad_debug::Options, _>(
                 rad_debug::HELP,
@@ -8,6 +8,7 @@ use crate::terminal;
 
 /// Output the given element through a pager, if necessary.
 /// If it fits within the screen, don't run it through a pager.
+/// If no pager is configured, the built-in pager is used.
 pub fn run(elem: impl Element) -> io::Result<()> {
     let Some(constraint) = Constraint::from_env() else {
         return elem.write(Constraint::UNBOUNDED);
@@ -19,7 +20,7 @@ pub fn run(elem: impl Element) -> io::Result<()> {
         return elem.write(Constraint::UNBOUNDED);
     }
     let Some(pager) = radicle::profile::env::pager() else {
-        return elem.write(Constraint::UNBOUNDED);
+        return radicle_term::pager::page(elem, false);
     };
     let Some(parts) = shlex::split(&pager) else {
         return elem.write(Constraint::UNBOUNDED);
@@ -129,7 +129,10 @@ impl Context for DefaultContext {
 
     fn profile(&self) -> Result<Profile, anyhow::Error> {
         match Profile::load() {
-            Ok(profile) => Ok(profile),
+            Ok(profile) => {
+                set_theme(&profile.config.cli.theme)?;
+                Ok(profile)
+            }
             Err(radicle::profile::Error::NotFound(path)) => Err(args::Error::WithHint {
                 err: anyhow::anyhow!("Radicle profile not found in '{}'.", path.display()),
                 hint: "To setup your radicle profile, run `rad auth`.",
@@ -141,21 +144,43 @@ impl Context for DefaultContext {
     }
 }
 
-pub fn fail(_name: &str, error: &anyhow::Error) {
-    let err = error.to_string();
-    let err = err.trim_end();
+/// Set the terminal color theme from the CLI configuration.
+pub fn set_theme(config: &radicle::cli::Theme) -> anyhow::Result<()> {
+    use radicle_term::theme::{self, Role};
 
-    for line in err.lines() {
-        io::error(line);
+    if config.no_color {
+        theme::set(Theme::no_color());
+        return Ok(());
+    }
+    let mut t = Theme::default();
+
+    for (role, color) in [
+        (Role::Success, &config.success),
+        (Role::Error, &config.error),
+        (Role::Warning, &config.warning),
+        (Role::Hint, &config.hint),
+        (Role::Highlight, &config.highlight),
+    ] {
+        if let Some(color) = color {
+            t = t.with(role, theme::color(color)?);
+        }
     }
+    theme::set(t);
+
+    Ok(())
+}
+
+pub fn fail(_name: &str, error: &anyhow::Error) {
+    let mut hint = None;
 
     // Catch common node errors, and offer a hint.
     if let Some(e) = error.downcast_ref::<radicle::node::Error>() {
         if e.is_connection_err() {
-            io::hint("to start your node, run `rad node start`.");
+            hint = Some("to start your node, run `rad node start`.");
         }
     }
-    if let Some(Error::WithHint { hint, .. }) = error.downcast_ref::<Error>() {
-        io::hint(hint);
+    if let Some(Error::WithHint { hint: h, .. }) = error.downcast_ref::<Error>() {
+        hint = Some(*h);
     }
+    io::error_chain(error.as_ref(), hint);
 }
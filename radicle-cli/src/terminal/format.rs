@@ -18,11 +18,7 @@ use crate::terminal as term;
 
 /// Format a node id to be more compact.
 pub fn node(node: &NodeId) -> Paint<String> {
-    let node = node.to_human();
-    let start = node.chars().take(7).collect::<String>();
-    let end = node.chars().skip(node.len() - 7).collect::<String>();
-
-    Paint::new(format!("{start}…{end}"))
+    Paint::new(node.short())
 }
 
 /// Format a git Oid.
@@ -52,8 +48,7 @@ pub fn cob(id: &ObjectId) -> Paint<String> {
 
 /// Format a DID.
 pub fn did(did: &Did) -> Paint<String> {
-    let nid = did.as_key().to_human();
-    Paint::new(format!("{}…{}", &nid[..7], &nid[nid.len() - 7..]))
+    Paint::new(did.as_key().short())
 }
 
 /// Format a Visibility.
@@ -255,7 +250,7 @@ pub mod issue {
                 reason: CloseReason::Other,
             } => term::format::negative(s.to_string()),
             State::Closed {
-                reason: CloseReason::Solved,
+                reason: CloseReason::Solved | CloseReason::WontFix | CloseReason::Duplicate,
             } => term::format::secondary(s.to_string()),
         }
     }
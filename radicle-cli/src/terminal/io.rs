@@ -2,7 +2,7 @@ use radicle::cob::issue::Issue;
 use radicle::cob::thread::{Comment, CommentId};
 use radicle::crypto::ssh::keystore::MemorySigner;
 use radicle::crypto::{ssh::Keystore, Signer};
-use radicle::profile::env::RAD_PASSPHRASE;
+use radicle::profile::env::{self, RAD_PASSPHRASE, RAD_PASSPHRASE_COMMAND, RAD_PASSPHRASE_FILE};
 use radicle::profile::Profile;
 
 pub use radicle_term::io::*;
@@ -39,21 +39,36 @@ impl inquire::validator::StringValidator for PassphraseValidator {
     }
 }
 
-/// Get the signer. First we try getting it from ssh-agent, otherwise we prompt the user,
-/// if we're connected to a TTY.
+/// Get the passphrase source configured via `RAD_PASSPHRASE_FILE` or
+/// `RAD_PASSPHRASE_COMMAND`, if any.
+pub fn passphrase_source() -> Option<PassphraseSource> {
+    if let Some(path) = env::var_os(RAD_PASSPHRASE_FILE) {
+        Some(PassphraseSource::File(path.into()))
+    } else {
+        env::var(RAD_PASSPHRASE_COMMAND)
+            .ok()
+            .map(PassphraseSource::Command)
+    }
+}
+
+/// Get the signer. First we try getting it from ssh-agent, then from a configured
+/// passphrase source, otherwise we prompt the user, if we're connected to a TTY.
 pub fn signer(profile: &Profile) -> anyhow::Result<Box<dyn Signer>> {
     if let Ok(signer) = profile.signer() {
         return Ok(signer);
     }
     let validator = PassphraseValidator::new(profile.keystore.clone());
-    let passphrase = match passphrase(validator) {
-        Ok(p) => p,
-        Err(inquire::InquireError::NotTTY) => {
-            return Err(anyhow::anyhow!(
-                "running in non-interactive mode, please set `{RAD_PASSPHRASE}` to unseal your key",
-            ));
-        }
-        Err(e) => return Err(e.into()),
+    let passphrase = match passphrase_source() {
+        Some(source) => source.read()?,
+        None => match passphrase(validator) {
+            Ok(p) => p,
+            Err(inquire::InquireError::NotTTY) => {
+                return Err(anyhow::anyhow!(
+                    "running in non-interactive mode, please set `{RAD_PASSPHRASE}` to unseal your key",
+                ));
+            }
+            Err(e) => return Err(e.into()),
+        },
     };
     let spinner = spinner("Unsealing key...");
     let signer = MemorySigner::load(&profile.keystore, Some(passphrase))?;
@@ -1,11 +1,16 @@
+use std::fmt::Write as _;
 use std::io;
 
 use radicle_term::table::TableOptions;
 use radicle_term::{Table, VStack};
+use serde::Serialize;
 
 use radicle::cob;
 use radicle::cob::issue;
 use radicle::cob::issue::CloseReason;
+use radicle::cob::thread;
+use radicle::node::{AliasStore as _, NodeId};
+use radicle::prelude::Did;
 use radicle::Profile;
 
 use crate::terminal as term;
@@ -29,6 +34,10 @@ pub enum Format {
     #[default]
     Full,
     Header,
+    /// Markdown, including the full discussion.
+    Markdown,
+    /// JSON, including the full discussion.
+    Json,
 }
 
 pub fn get_title_description(
@@ -44,6 +53,25 @@ pub fn show(
     format: Format,
     profile: &Profile,
 ) -> anyhow::Result<()> {
+    match format {
+        Format::Markdown => {
+            term::markdown(&markdown(issue, id, profile));
+            return Ok(());
+        }
+        Format::Json => {
+            #[derive(Serialize)]
+            struct Export<'a> {
+                id: &'a cob::ObjectId,
+                #[serde(flatten)]
+                issue: &'a issue::Issue,
+            }
+            println!("{}", serde_json::to_string_pretty(&Export { id, issue })?);
+
+            return Ok(());
+        }
+        Format::Full | Format::Header => {}
+    }
+
     let labels: Vec<String> = issue.labels().cloned().map(|t| t.into()).collect();
     let assignees: Vec<String> = issue
         .assignees()
@@ -91,15 +119,16 @@ pub fn show(
         term::format::tertiary("Status".to_owned()).into(),
         match issue.state() {
             issue::State::Open => term::format::positive("open".to_owned()).into(),
-            issue::State::Closed {
-                reason: CloseReason::Solved,
-            } => term::Line::spaced([
-                term::format::negative("closed").into(),
-                term::format::negative("(solved)").italic().dim().into(),
-            ]),
             issue::State::Closed {
                 reason: CloseReason::Other,
             } => term::Line::spaced([term::format::negative("closed").into()]),
+            issue::State::Closed { reason } => term::Line::spaced([
+                term::format::negative("closed").into(),
+                term::format::negative(format!("({reason})"))
+                    .italic()
+                    .dim()
+                    .into(),
+            ]),
         },
     ]);
 
@@ -129,3 +158,84 @@ pub fn show(
 
     Ok(())
 }
+
+/// Render an issue and its discussion as Markdown.
+pub fn markdown(issue: &issue::Issue, id: &cob::ObjectId, profile: &Profile) -> String {
+    let author = |nid: &NodeId| -> String {
+        let did = Did::from(*nid);
+        match profile.alias(nid) {
+            Some(alias) => format!("{alias} ({did})"),
+            None => did.to_string(),
+        }
+    };
+    let reactions = |comment: &thread::Comment| -> Option<String> {
+        let reactions = comment
+            .reactions()
+            .into_iter()
+            .map(|(reaction, authors)| format!("{} {}", reaction.emoji(), authors.len()))
+            .collect::<Vec<_>>();
+
+        (!reactions.is_empty()).then(|| reactions.join(" "))
+    };
+    let short = |id: &thread::CommentId| id.to_string()[..7].to_owned();
+    let labels = issue.labels().map(|l| l.to_string()).collect::<Vec<_>>();
+    let assignees = issue.assignees().map(|a| a.to_string()).collect::<Vec<_>>();
+    let status = match issue.state() {
+        issue::State::Closed {
+            reason: CloseReason::Other,
+        } => String::from("closed"),
+        issue::State::Closed { reason } => format!("closed ({reason})"),
+        state => state.to_string(),
+    };
+    let mut md = String::new();
+
+    // Writing to a `String` is infallible.
+    writeln!(md, "# {}", issue.title()).ok();
+    writeln!(md).ok();
+    writeln!(md, "- Issue: {id}").ok();
+    writeln!(md, "- Author: {}", author(issue.author().public_key())).ok();
+    writeln!(md, "- Status: {status}").ok();
+    if !labels.is_empty() {
+        writeln!(md, "- Labels: {}", labels.join(", ")).ok();
+    }
+    if !assignees.is_empty() {
+        writeln!(md, "- Assignees: {}", assignees.join(", ")).ok();
+    }
+
+    let (_, root) = issue.root();
+    if !root.body().trim().is_empty() {
+        writeln!(md).ok();
+        writeln!(md, "{}", root.body().trim()).ok();
+    }
+    if let Some(reactions) = reactions(root) {
+        writeln!(md).ok();
+        writeln!(md, "Reactions: {reactions}").ok();
+    }
+
+    let mut replies = issue.replies().peekable();
+    if replies.peek().is_some() {
+        writeln!(md).ok();
+        writeln!(md, "## Discussion").ok();
+    }
+    for (id, comment) in replies {
+        writeln!(md).ok();
+        let heading = match comment.reply_to() {
+            Some(to) if &to != issue.root().0 => format!(
+                "{} replied to {} ({})",
+                author(&comment.author()),
+                short(&to),
+                short(id)
+            ),
+            _ => format!("{} ({})", author(&comment.author()), short(id)),
+        };
+        writeln!(md, "### {heading}").ok();
+        writeln!(md).ok();
+        writeln!(md, "{}", comment.body().trim()).ok();
+
+        if let Some(reactions) = reactions(comment) {
+            writeln!(md).ok();
+            writeln!(md, "Reactions: {reactions}").ok();
+        }
+    }
+    md
+}
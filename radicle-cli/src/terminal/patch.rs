@@ -3,6 +3,7 @@ mod timeline;
 
 use std::fmt;
 use std::fmt::Write;
+use std::fs;
 use std::io;
 use std::io::IsTerminal as _;
 
@@ -13,6 +14,7 @@ use radicle::cob::patch;
 use radicle::git;
 use radicle::patch::{Patch, PatchId};
 use radicle::prelude::Profile;
+use radicle::profile::Config;
 use radicle::storage::git::Repository;
 use radicle::storage::WriteRepository as _;
 
@@ -191,35 +193,75 @@ pub fn patch_commits<'a>(
     Ok(commits)
 }
 
+/// Load the patch template of a working copy, if there is one.
+///
+/// The template path is configured in the profile, or in the repository's configuration
+/// overrides, and is relative to the root of the working copy.
+pub fn template(repo: &git::raw::Repository, config: &Config) -> Result<Option<String>, Error> {
+    let Some(workdir) = repo.workdir() else {
+        return Ok(None);
+    };
+    match fs::read_to_string(workdir.join(config.cli.patch_template())) {
+        Ok(template) => Ok(Some(template)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Remove the comment lines, ie. lines starting with `# `, from a patch message.
+///
+/// Lines starting with `##` are kept, so that templates can use Markdown sub-headings.
+pub fn strip_comment_lines(message: &str) -> String {
+    message
+        .lines()
+        .filter(|line| *line != "#" && !line.starts_with("# "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// The message shown in the editor when creating a `Patch`.
 fn create_display_message(
     repo: &git::raw::Repository,
     base: &git::Oid,
     head: &git::Oid,
+    template: Option<&str>,
 ) -> Result<String, Error> {
     let commits = patch_commits(repo, base, head)?;
-    if commits.is_empty() {
-        return Ok(PATCH_MSG.trim_start().to_string());
-    }
-
     let summary = message_from_commits("patch", commits)?;
     let summary = summary.trim();
 
-    Ok(format!("{summary}\n{PATCH_MSG}"))
+    let msg = match template.map(str::trim) {
+        Some(template) if summary.is_empty() => template.to_owned(),
+        Some(template) => format!("{summary}\n\n{template}"),
+        None => summary.to_owned(),
+    };
+    if msg.is_empty() {
+        return Ok(PATCH_MSG.trim_start().to_string());
+    }
+    Ok(format!("{msg}\n{PATCH_MSG}"))
 }
 
 /// Get the Patch title and description from the command line arguments, or request it from the
 /// user.
 ///
+/// If a patch template is given, it is appended to the message shown in the editor, and
+/// comment lines are stripped from the result.
+///
 /// The user can bail out if an empty title is entered.
 pub fn get_create_message(
     message: term::patch::Message,
     repo: &git::raw::Repository,
     base: &git::Oid,
     head: &git::Oid,
+    template: Option<&str>,
 ) -> Result<(String, String), Error> {
-    let display_msg = create_display_message(repo, base, head)?;
+    let display_msg = create_display_message(repo, base, head, template)?;
     let message = message.get(&display_msg)?;
+    let message = if template.is_some() {
+        strip_comment_lines(&message)
+    } else {
+        message
+    };
 
     let (title, description) = message.split_once('\n').unwrap_or((&message, ""));
     let (title, description) = (title.trim().to_string(), description.trim().to_string());
@@ -518,7 +560,7 @@ mod test {
             "Commit 2\n\nDescription\n",
         );
 
-        let res = create_display_message(&repo, &commit_0, &commit_0).unwrap();
+        let res = create_display_message(&repo, &commit_0, &commit_0, None).unwrap();
         assert_eq!(
             "\
             <!--\n\
@@ -534,7 +576,7 @@ mod test {
             res
         );
 
-        let res = create_display_message(&repo, &commit_0, &commit_1).unwrap();
+        let res = create_display_message(&repo, &commit_0, &commit_1, None).unwrap();
         assert_eq!(
             "\
             Commit 1\n\
@@ -554,7 +596,7 @@ mod test {
             res
         );
 
-        let res = create_display_message(&repo, &commit_0, &commit_2).unwrap();
+        let res = create_display_message(&repo, &commit_0, &commit_2, None).unwrap();
         assert_eq!(
             "\
             <!--\n\
@@ -128,6 +128,24 @@ fn rad_auth() {
     test("examples/rad-auth.md", Path::new("."), None, []).unwrap();
 }
 
+#[test]
+fn rad_auth_passphrase_file() {
+    use std::fs;
+
+    let working = tempfile::tempdir().unwrap();
+
+    fs::write(working.path().join("passphrase"), "hunter2\n").unwrap();
+    fs::write(working.path().join("wrong-passphrase"), "hunter3\n").unwrap();
+
+    test(
+        "examples/rad-auth-passphrase-file.md",
+        working.path(),
+        None,
+        [],
+    )
+    .unwrap();
+}
+
 #[test]
 fn rad_auth_errors() {
     test("examples/rad-auth-errors.md", Path::new("."), None, []).unwrap();
@@ -147,6 +165,34 @@ fn rad_issue() {
     test("examples/rad-issue.md", &working, Some(home), []).unwrap();
 }
 
+#[test]
+fn rad_issue_close() {
+    let mut environment = Environment::new();
+    let profile = environment.profile(config::profile("alice"));
+    let home = &profile.home;
+    let working = environment.tmp().join("working");
+
+    // Setup a test repository.
+    fixtures::repository(&working);
+
+    test("examples/rad-init.md", &working, Some(home), []).unwrap();
+    test("examples/rad-issue-close.md", &working, Some(home), []).unwrap();
+}
+
+#[test]
+fn rad_issue_export() {
+    let mut environment = Environment::new();
+    let profile = environment.profile(config::profile("alice"));
+    let home = &profile.home;
+    let working = environment.tmp().join("working");
+
+    // Setup a test repository.
+    fixtures::repository(&working);
+
+    test("examples/rad-init.md", &working, Some(home), []).unwrap();
+    test("examples/rad-issue-export.md", &working, Some(home), []).unwrap();
+}
+
 #[test]
 fn rad_cob() {
     let mut environment = Environment::new();
@@ -212,6 +258,44 @@ fn rad_init_no_git() {
     .unwrap();
 }
 
+#[test]
+fn rad_stats_repo() {
+    let mut environment = Environment::new();
+    let profile = environment.profile(config::profile("alice"));
+    let working = tempfile::tempdir().unwrap();
+    let bob = "z6Mkt67GdsW7715MEfRuP4pSZxJRJh6kj6Y48WRqVv4N1tRk".parse().unwrap();
+    let acme = "rad:z42hL2jL4XNk6K8oHQaSWfMgCL7ji".parse().unwrap();
+
+    // Setup a test repository.
+    fixtures::repository(working.path());
+
+    test(
+        "examples/rad-init.md",
+        working.path(),
+        Some(&profile.home),
+        [],
+    )
+    .unwrap();
+
+    // Alice knows of one other seed for the repository.
+    let mut db = profile.database_mut().unwrap();
+    radicle::node::routing::Store::insert(
+        &mut db,
+        [&acme],
+        bob,
+        localtime::LocalTime::now().into(),
+    )
+    .unwrap();
+
+    test(
+        "examples/rad-stats-repo.md",
+        working.path(),
+        Some(&profile.home),
+        [],
+    )
+    .unwrap();
+}
+
 #[test]
 fn rad_inspect() {
     let mut environment = Environment::new();
@@ -468,6 +552,52 @@ fn rad_id_multi_delegate() {
         .unwrap();
 }
 
+#[test]
+fn rad_id_sign() {
+    let mut environment = Environment::new();
+    let alice = environment.node(Config::test(Alias::new("alice")));
+    let bob = environment.node(Config::test(Alias::new("bob")));
+    let working = tempfile::tempdir().unwrap();
+    let working = working.path();
+    let acme = RepoId::from_str("z42hL2jL4XNk6K8oHQaSWfMgCL7ji").unwrap();
+
+    // Setup a test repository.
+    fixtures::repository(working.join("alice"));
+
+    test(
+        "examples/rad-init.md",
+        working.join("alice"),
+        Some(&alice.home),
+        [],
+    )
+    .unwrap();
+
+    let mut alice = alice.spawn();
+    let mut bob = bob.spawn();
+
+    alice.handle.seed(acme, Scope::All).unwrap();
+    alice.connect(&bob).converge([&bob]);
+
+    bob.fork(acme, working.join("bob")).unwrap();
+    bob.has_remote_of(&acme, &alice.id);
+    alice.has_remote_of(&acme, &bob.id);
+
+    formula(&environment.tmp(), "examples/rad-id-sign.md")
+        .unwrap()
+        .home(
+            "alice",
+            working.join("alice"),
+            [("RAD_HOME", alice.home.path().display())],
+        )
+        .home(
+            "bob",
+            working.join("bob"),
+            [("RAD_HOME", bob.home.path().display())],
+        )
+        .run()
+        .unwrap();
+}
+
 #[test]
 #[ignore = "slow"]
 fn rad_id_collaboration() {
@@ -644,6 +774,44 @@ fn rad_node_connect() {
     assert!(session.state.is_connected());
 }
 
+#[test]
+fn rad_node_disconnect() {
+    let mut environment = Environment::new();
+    let alice = environment.node(Config::test(Alias::new("alice")));
+    let bob = environment.node(Config::test(Alias::new("bob")));
+    let working = tempfile::tempdir().unwrap();
+    let alice = alice.spawn();
+    let bob = bob.spawn();
+
+    alice
+        .rad(
+            "node",
+            &["connect", format!("{}@{}", bob.id, bob.addr).as_str()],
+            working.path(),
+        )
+        .unwrap();
+    assert!(alice
+        .handle
+        .sessions()
+        .unwrap()
+        .iter()
+        .any(|s| s.nid == bob.id && s.state.is_connected()));
+
+    alice
+        .rad(
+            "node",
+            &["disconnect", bob.id.to_string().as_str()],
+            working.path(),
+        )
+        .unwrap();
+    assert!(!alice
+        .handle
+        .sessions()
+        .unwrap()
+        .iter()
+        .any(|s| s.nid == bob.id && s.state.is_connected()));
+}
+
 #[test]
 fn rad_node() {
     let mut environment = Environment::new();
@@ -691,6 +859,20 @@ fn rad_patch() {
     test("examples/rad-patch.md", working.path(), Some(home), []).unwrap();
 }
 
+#[test]
+fn rad_complete() {
+    let mut environment = Environment::new();
+    let profile = environment.profile(config::profile("alice"));
+    let working = tempfile::tempdir().unwrap();
+    let home = &profile.home;
+
+    // Setup a test repository.
+    fixtures::repository(working.path());
+
+    test("examples/rad-init.md", working.path(), Some(home), []).unwrap();
+    test("examples/rad-complete.md", working.path(), Some(home), []).unwrap();
+}
+
 #[test]
 fn rad_patch_diff() {
     let mut environment = Environment::new();
@@ -719,6 +901,20 @@ fn rad_patch_edit() {
     test("examples/rad-patch-edit.md", working.path(), Some(home), []).unwrap();
 }
 
+#[test]
+fn rad_patch_review() {
+    let mut environment = Environment::new();
+    let profile = environment.profile(config::profile("alice"));
+    let working = tempfile::tempdir().unwrap();
+    let home = &profile.home;
+
+    // Setup a test repository.
+    fixtures::repository(working.path());
+
+    test("examples/rad-init.md", working.path(), Some(home), []).unwrap();
+    test("examples/rad-patch-review.md", working.path(), Some(home), []).unwrap();
+}
+
 #[test]
 fn rad_patch_checkout() {
     let mut environment = Environment::new();
@@ -739,6 +935,33 @@ fn rad_patch_checkout() {
     .unwrap();
 }
 
+#[test]
+fn rad_patch_cherry_pick() {
+    let mut environment = Environment::new();
+    let profile = environment.profile(config::profile("alice"));
+    let working = tempfile::tempdir().unwrap();
+    let home = &profile.home;
+
+    // Setup a test repository.
+    fixtures::repository(working.path());
+
+    test("examples/rad-init.md", working.path(), Some(home), []).unwrap();
+    test(
+        "examples/rad-patch-checkout.md",
+        working.path(),
+        Some(home),
+        [],
+    )
+    .unwrap();
+    test(
+        "examples/rad-patch-cherry-pick.md",
+        working.path(),
+        Some(home),
+        [],
+    )
+    .unwrap();
+}
+
 #[test]
 fn rad_patch_checkout_revision() {
     let mut environment = Environment::new();
@@ -881,6 +1104,26 @@ fn rad_patch_change_base() {
     .unwrap();
 }
 
+#[test]
+fn rad_patch_set_base() {
+    let mut environment = Environment::new();
+    let profile = environment.profile(config::profile("alice"));
+    let working = tempfile::tempdir().unwrap();
+    let home = &profile.home;
+
+    // Setup a test repository.
+    fixtures::repository(working.path());
+
+    test("examples/rad-init.md", working.path(), Some(home), []).unwrap();
+    test(
+        "examples/rad-patch-set-base.md",
+        working.path(),
+        Some(home),
+        [],
+    )
+    .unwrap();
+}
+
 #[test]
 fn rad_patch_draft() {
     let mut environment = Environment::new();
@@ -901,6 +1144,57 @@ fn rad_patch_draft() {
     .unwrap();
 }
 
+#[test]
+fn rad_patch_archive() {
+    let mut environment = Environment::new();
+    let profile = environment.profile(config::profile("alice"));
+    let working = tempfile::tempdir().unwrap();
+    let home = &profile.home;
+
+    // Setup a test repository.
+    fixtures::repository(working.path());
+
+    test("examples/rad-init.md", working.path(), Some(home), []).unwrap();
+    test(
+        "examples/rad-patch-archive.md",
+        working.path(),
+        Some(home),
+        [],
+    )
+    .unwrap();
+}
+
+#[test]
+fn rad_patch_template() {
+    let mut environment = Environment::new();
+    let profile = environment.profile(config::profile("alice"));
+    let working = tempfile::tempdir().unwrap();
+    let home = &profile.home;
+    let acme = RepoId::from_str("z42hL2jL4XNk6K8oHQaSWfMgCL7ji").unwrap();
+
+    // Setup a test repository.
+    fixtures::repository(working.path());
+
+    test("examples/rad-init.md", working.path(), Some(home), []).unwrap();
+    test(
+        "examples/rad-patch-template.md",
+        working.path(),
+        Some(home),
+        [],
+    )
+    .unwrap();
+
+    let repo = profile.storage.repository(acme).unwrap();
+    let patches = radicle::cob::patch::Patches::open(&repo).unwrap();
+    let (_, patch) = patches.all().unwrap().next().unwrap().unwrap();
+
+    assert_eq!(patch.title(), "Add a feature");
+    assert_eq!(
+        patch.description(),
+        "## Checklist\n\n- [ ] Tests were added or updated\n- [ ] Documentation was updated"
+    );
+}
+
 #[test]
 fn rad_patch_via_push() {
     let mut environment = Environment::new();
@@ -1105,6 +1399,19 @@ fn rad_unseed() {
     test("examples/rad-unseed.md", working, Some(&alice.home), []).unwrap();
 }
 
+#[test]
+fn rad_ls() {
+    let mut environment = Environment::new();
+    let mut alice = environment.node(Config::test(Alias::new("alice")));
+    let working = tempfile::tempdir().unwrap();
+
+    // Setup a test project.
+    alice.project("heartwood", "Radicle Heartwood Protocol & Stack");
+    let alice = alice.spawn();
+
+    test("examples/rad-ls.md", working, Some(&alice.home), []).unwrap();
+}
+
 #[test]
 fn rad_block() {
     let mut environment = Environment::new();
@@ -1163,6 +1470,26 @@ fn rad_clone_directory() {
     .unwrap();
 }
 
+#[test]
+fn rad_clone_depth() {
+    let mut environment = Environment::new();
+    let mut alice = environment.node(Config::test(Alias::new("alice")));
+    let bob = environment.node(Config::test(Alias::new("bob")));
+    let working = environment.tmp().join("working");
+
+    // Setup a test project.
+    let acme = alice.project("heartwood", "Radicle Heartwood Protocol & Stack");
+
+    let mut alice = alice.spawn();
+    let mut bob = bob.spawn();
+    // Prevent Alice from fetching Bob's fork, as we're not testing that and it may cause errors.
+    alice.handle.seed(acme, Scope::Followed).unwrap();
+
+    bob.connect(&alice).converge([&alice]);
+
+    test("examples/rad-clone-depth.md", working, Some(&bob.home), []).unwrap();
+}
+
 #[test]
 fn rad_clone_all() {
     let mut environment = Environment::new();
@@ -1372,6 +1699,23 @@ fn rad_self() {
     test("examples/rad-self.md", working, Some(&alice.home), []).unwrap();
 }
 
+#[test]
+fn rad_self_export_import() {
+    let mut environment = Environment::new();
+    let alice = environment.profile(config::profile("alice"));
+    let working = tempfile::tempdir().unwrap();
+
+    test(
+        "examples/rad-self-export.md",
+        working.path(),
+        Some(&alice.home),
+        [],
+    )
+    .unwrap();
+    // Import into a fresh home.
+    test("examples/rad-self-import.md", working.path(), None, []).unwrap();
+}
+
 #[test]
 fn rad_clone_unknown() {
     let mut environment = Environment::new();
@@ -1778,6 +2122,43 @@ fn rad_sync() {
     .unwrap();
 }
 
+#[test]
+fn rad_sync_only() {
+    let mut environment = Environment::new();
+    let working = environment.tmp().join("working");
+    let alice = environment.node(config::node("alice"));
+    let bob = environment.node(config::node("bob"));
+    let acme = RepoId::from_str("z42hL2jL4XNk6K8oHQaSWfMgCL7ji").unwrap();
+
+    fixtures::repository(working.join("acme"));
+
+    test(
+        "examples/rad-init.md",
+        working.join("acme"),
+        Some(&alice.home),
+        [],
+    )
+    .unwrap();
+
+    let mut alice = alice.spawn();
+    let mut bob = bob.spawn();
+
+    bob.handle.seed(acme, Scope::All).unwrap();
+    alice.connect(&bob);
+
+    bob.routes_to(&[(acme, alice.id)]);
+    alice.routes_to(&[(acme, alice.id), (acme, bob.id)]);
+    alice.is_synced_with(&acme, &bob.id);
+
+    test(
+        "examples/rad-sync-only.md",
+        working.join("acme"),
+        Some(&alice.home),
+        [],
+    )
+    .unwrap();
+}
+
 #[test]
 //
 //     alice -- seed -- bob
@@ -2389,6 +2770,38 @@ fn rad_watch() {
         .unwrap();
 }
 
+#[test]
+fn rad_watch_cobs() {
+    let mut environment = Environment::new();
+    let mut alice = environment.node(Config::test(Alias::new("alice")));
+    let working = environment.tmp().join("working");
+    let (repo, _) = fixtures::repository(working.join("alice"));
+    let rid = alice.project_from("heartwood", "Radicle Heartwood Protocol & Stack", &repo);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            // Give `rad watch` time to take a snapshot of the existing objects.
+            thread::sleep(time::Duration::from_secs(1));
+
+            let repo = alice.storage.repository(rid).unwrap();
+            let mut issues = radicle::issue::Cache::no_cache(&repo).unwrap();
+            issues
+                .create("Flux capacitor", "It's broken.", &[], &[], [], &alice.signer)
+                .unwrap();
+        });
+
+        formula(&environment.tmp(), "examples/rad-watch-cobs.md")
+            .unwrap()
+            .home(
+                "alice",
+                working.join("alice"),
+                [("RAD_HOME", alice.home.path().display())],
+            )
+            .run()
+            .unwrap();
+    });
+}
+
 #[test]
 fn rad_inbox() {
     let mut environment = Environment::new();
@@ -2423,6 +2836,40 @@ fn rad_inbox() {
         .unwrap();
 }
 
+#[test]
+fn rad_inbox_filter() {
+    let mut environment = Environment::new();
+    let mut alice = environment.node(Config::test(Alias::new("alice")));
+    let bob = environment.node(Config::test(Alias::new("bob")));
+    let working = environment.tmp().join("working");
+    let (repo1, _) = fixtures::repository(working.join("alice").join("heartwood"));
+    let (repo2, _) = fixtures::repository(working.join("alice").join("radicle-git"));
+    let rid1 = alice.project_from("heartwood", "Radicle Heartwood Protocol & Stack", &repo1);
+    let rid2 = alice.project_from("radicle-git", "Radicle Git", &repo2);
+
+    let alice = alice.spawn();
+    let mut bob = bob.spawn();
+
+    bob.connect(&alice).converge([&alice]);
+    bob.clone(rid1, working.join("bob")).unwrap();
+    bob.clone(rid2, working.join("bob")).unwrap();
+
+    formula(&environment.tmp(), "examples/rad-inbox-filter.md")
+        .unwrap()
+        .home(
+            "alice",
+            working.join("alice"),
+            [("RAD_HOME", alice.home.path().display())],
+        )
+        .home(
+            "bob",
+            working.join("bob"),
+            [("RAD_HOME", bob.home.path().display())],
+        )
+        .run()
+        .unwrap();
+}
+
 #[test]
 fn rad_patch_fetch_2() {
     let mut environment = Environment::new();
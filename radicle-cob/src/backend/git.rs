@@ -1,8 +1,38 @@
 // Copyright © 2022 The Radicle Link Contributors
 
+#[cfg(debug_assertions)]
+use std::cell::Cell;
+
 pub mod change;
 
 /// Environment variable to set to overwrite the commit date for both the author and the committer.
 ///
 /// The format must be a unix timestamp.
 pub const RAD_COMMIT_TIME: &str = "RAD_COMMIT_TIME";
+
+#[cfg(debug_assertions)]
+thread_local! {
+    /// Commit time override for the current thread. See [`with_commit_time`].
+    pub(crate) static COMMIT_TIME: Cell<Option<i64>> = const { Cell::new(None) };
+}
+
+/// Run `f` with the commit date of all changes created on the current thread fixed to
+/// `timestamp`. Takes precedence over [`RAD_COMMIT_TIME`].
+///
+/// **Test-only.** This has no effect in release builds.
+pub fn with_commit_time<T>(timestamp: i64, f: impl FnOnce() -> T) -> T {
+    #[cfg(debug_assertions)]
+    {
+        let previous = COMMIT_TIME.with(|t| t.replace(Some(timestamp)));
+        let result = f();
+
+        COMMIT_TIME.with(|t| t.set(previous));
+
+        result
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        let _ = timestamp;
+        f()
+    }
+}
@@ -321,6 +321,17 @@ fn write_commit(
     } else {
         (author, timestamp)
     };
+    #[cfg(debug_assertions)]
+    let (author, timestamp) = match crate::git::COMMIT_TIME.with(|t| t.get()) {
+        Some(timestamp) => {
+            let author = Author {
+                time: git_ext::author::Time::new(timestamp, 0),
+                ..author
+            };
+            (author, timestamp)
+        }
+        None => (author, timestamp),
+    };
 
     let oid = Commit::new(
         tree.id(),
@@ -18,6 +18,9 @@ pub trait Storage {
     type Signatures;
 
     /// Store a new change entry.
+    ///
+    /// The contents of the change are content-addressed: storing identical contents
+    /// more than once re-uses the same objects, and only adds a new signed entry.
     #[allow(clippy::type_complexity)]
     fn store<G>(
         &self,
@@ -64,6 +67,11 @@ pub struct Entry<Resource, Id, Signature> {
     /// The content address of the entry itself.
     pub id: Id,
     /// The content address of the tree of the entry.
+    ///
+    /// Since the tree only holds the manifest, contents and embeds of the change,
+    /// entries with identical contents share the same revision, and thus the same
+    /// stored objects. Only the commit, which carries the signature, parents and
+    /// timestamp, is specific to each entry.
     pub revision: Id,
     /// The cryptographic signature(s) and their public keys of the
     /// authors.
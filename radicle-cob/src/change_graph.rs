@@ -7,7 +7,9 @@ use git_ext::Oid;
 use radicle_dag::Dag;
 
 use crate::{
-    change, object, object::collaboration::Evaluate, signatures::ExtendedSignature,
+    change, object,
+    object::collaboration::{Evaluate, RateLimit, Tracker},
+    signatures::{cache::Cache, ExtendedSignature},
     CollaborativeObject, Entry, EntryId, History, ObjectId, TypeName,
 };
 
@@ -92,9 +94,15 @@ impl ChangeGraph {
 
     /// Given a graph evaluate it to produce a collaborative object. This will
     /// filter out branches of the graph which do not have valid signatures.
+    ///
+    /// Signatures of entries found in the `cache` are not verified again.
+    ///
+    /// Entries of authors exceeding the given rate `limit` are treated as invalid.
     pub(crate) fn evaluate<S, T: Evaluate<S>>(
         mut self,
         store: &S,
+        cache: &Cache,
+        limit: Option<RateLimit>,
     ) -> Result<CollaborativeObject<T>, EvaluateError> {
         let root = *self.object_id;
         let root = self
@@ -102,7 +110,7 @@ impl ChangeGraph {
             .get(&root)
             .ok_or(EvaluateError::MissingRoot(root))?;
 
-        if !root.valid_signatures() {
+        if !cache.verify(root) {
             return Err(EvaluateError::Signature(root.id));
         }
         // Evaluate the root separately, since we can't have a COB without a valid root.
@@ -111,20 +119,39 @@ impl ChangeGraph {
             T::init(&root.value, store).map_err(|e| EvaluateError::Init(Box::new(e)))?;
         let children = Vec::from_iter(root.dependents.iter().cloned());
         let manifest = root.manifest.clone();
+        let mut tracker = Tracker::new(limit);
+        // The root counts towards its author's limit, but is never rejected.
+        tracker.check(&root.value);
         let root = root.id;
 
         self.graph.prune(&children, |_, entry, siblings| {
             // Check the entry signatures are valid.
-            if !entry.valid_signatures() {
+            if !cache.verify(entry) {
                 return ControlFlow::Break(());
             }
+            // Check the entry author is within the rate limit.
+            if !tracker.check(entry) {
+                return ControlFlow::Break(());
+            }
+            let siblings = siblings
+                .map(|(k, n)| (k, &n.value))
+                .collect::<Vec<_>>();
+
             // Apply the entry to the state, and if there's an error, prune that branch.
             if object
-                .apply(entry, siblings.map(|(k, n)| (k, &n.value)), store)
+                .apply(entry, siblings.iter().copied(), store)
                 .is_err()
             {
                 return ControlFlow::Break(());
             }
+            // Give the object a chance to merge conflicting concurrent changes.
+            if !siblings.is_empty()
+                && object
+                    .resolve_conflict(entry, siblings.into_iter(), store)
+                    .is_err()
+            {
+                return ControlFlow::Break(());
+            }
             ControlFlow::Continue(())
         });
 
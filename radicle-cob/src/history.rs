@@ -57,6 +57,25 @@ impl History {
         self.graph.fold(roots, init, |acc, k, v| f(acc, k, v))
     }
 
+    /// Like [`History::traverse`], but without computing the full topological order
+    /// upfront: entries are visited as soon as their parents have been, so memory use is
+    /// bounded by the number of concurrent changes, rather than the size of the history.
+    ///
+    /// Concurrent changes may be visited in a different order than with
+    /// [`History::traverse`].
+    pub fn traverse_walk<F, A>(&self, init: A, roots: &[EntryId], mut f: F) -> A
+    where
+        F: for<'r> FnMut(A, &'r EntryId, &'r Entry) -> ControlFlow<A, A>,
+    {
+        self.graph.fold_walk(roots, init, |acc, k, v| f(acc, k, v))
+    }
+
+    /// Return a topologically-sorted iterator over history entries, starting from the given
+    /// roots. See [`History::traverse_walk`].
+    pub fn walk<'a>(&'a self, roots: &[EntryId]) -> impl Iterator<Item = &'a Entry> + 'a {
+        self.graph.walk(roots).map(|(_, node)| &node.value)
+    }
+
     /// Return a topologically-sorted list of history entries.
     pub fn sorted<F>(&self, compare: F) -> impl Iterator<Item = &Entry>
     where
@@ -85,7 +85,7 @@ pub use type_name::TypeName;
 pub mod object;
 pub use object::{
     create, get, info, list, remove, update, CollaborativeObject, Create, Evaluate, ObjectId,
-    Update, Updated,
+    RateLimit, Update, Updated,
 };
 
 #[cfg(test)]
@@ -10,7 +10,7 @@ use thiserror::Error;
 pub mod collaboration;
 pub use collaboration::{
     create, get, info, list, parse_refstr, remove, update, CollaborativeObject, Create, Evaluate,
-    Update, Updated,
+    RateLimit, Update, Updated,
 };
 
 pub mod storage;
@@ -18,6 +18,10 @@ pub use get::get;
 
 pub mod info;
 
+mod limit;
+pub(crate) use limit::Tracker;
+pub use limit::RateLimit;
+
 mod list;
 pub use list::list;
 
@@ -76,6 +80,36 @@ pub trait Evaluate<R>: Sized + Debug + 'static {
         concurrent: I,
         store: &R,
     ) -> Result<(), Self::Error>;
+
+    /// Resolve conflicts between a history entry and the entries concurrent to it.
+    ///
+    /// This is called right after [`Evaluate::apply`], for every entry that has
+    /// concurrent entries, ie. entries that are neither its ancestors nor its
+    /// descendants. Some of the concurrent entries may not have been applied yet.
+    /// Implementors can use it to merge conflicting concurrent changes, instead of
+    /// letting the last applied change win.
+    ///
+    /// Since every replica must converge on the same state, the resolution must be
+    /// deterministic: it may only depend on the current state and on the given
+    /// entries, and not on the order of the concurrent entries, on the order in
+    /// which concurrent entries are applied, or on anything local to the replica,
+    /// such as the current time. For example, picking the concurrent change with the
+    /// lowest entry id is deterministic.
+    ///
+    /// Returning an error prunes the entry and its descendants from the history,
+    /// just like an error returned by [`Evaluate::apply`].
+    ///
+    /// The default implementation does nothing.
+    fn resolve_conflict<'a, I: Iterator<Item = (&'a Oid, &'a Entry)>>(
+        &mut self,
+        entry: &Entry,
+        concurrent: I,
+        store: &R,
+    ) -> Result<(), Self::Error> {
+        let _ = (entry, concurrent, store);
+
+        Ok(())
+    }
 }
 
 impl<R> Evaluate<R> for NonEmpty<Entry> {
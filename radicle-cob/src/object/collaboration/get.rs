@@ -1,8 +1,11 @@
 // Copyright © 2022 The Radicle Link Contributors
 
-use crate::{change_graph::ChangeGraph, CollaborativeObject, Evaluate, ObjectId, Store, TypeName};
+use crate::{
+    change_graph::ChangeGraph, signatures::cache::Cache, CollaborativeObject, Evaluate, ObjectId,
+    Store, TypeName,
+};
 
-use super::error;
+use super::{error, RateLimit};
 
 /// Get a [`CollaborativeObject`], if it exists.
 ///
@@ -27,6 +30,10 @@ where
         .map_err(|err| error::Retrieve::Refs { err: Box::new(err) })?;
 
     ChangeGraph::load(storage, tip_refs.iter(), typename, oid)
-        .map(|graph| graph.evaluate(storage).map_err(error::Retrieve::evaluate))
+        .map(|graph| {
+            graph
+                .evaluate(storage, Cache::global(), RateLimit::global())
+                .map_err(error::Retrieve::evaluate)
+        })
         .transpose()
 }
@@ -0,0 +1,87 @@
+//! Per-object change-rate limiting.
+//!
+//! Any peer can add changes to a collaborative object, which makes it possible
+//! to flood an object with thousands of tiny edits. When a [`RateLimit`] is set,
+//! changes of an author that exceed it are treated as invalid during evaluation,
+//! just like changes with invalid signatures: they are pruned, along with the
+//! changes that depend on them.
+//!
+//! Limits are scoped to an author and an object: an author flooding an object
+//! doesn't affect changes of other authors, or changes made to other objects.
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+use radicle_crypto::PublicKey;
+
+use crate::change::store::Timestamp;
+use crate::Entry;
+
+/// The limit used when loading objects with [`crate::get`], [`crate::list`]
+/// and [`crate::update`]. There is no limit by default.
+static GLOBAL: Lazy<RwLock<Option<RateLimit>>> = Lazy::new(|| RwLock::new(None));
+
+/// Maximum number of changes an author can make to an object, within a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Maximum number of changes per author, within the window.
+    pub changes: usize,
+    /// Size of the window, in seconds.
+    pub window: u64,
+}
+
+impl RateLimit {
+    /// Create a new rate limit of `changes` per `window` seconds.
+    pub fn new(changes: usize, window: u64) -> Self {
+        Self { changes, window }
+    }
+
+    /// Get the limit used when loading objects, if any.
+    pub fn global() -> Option<Self> {
+        *GLOBAL.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Set the limit used when loading objects. Use [`None`] to disable rate limiting.
+    pub fn configure(limit: Option<Self>) {
+        *GLOBAL.write().unwrap_or_else(|e| e.into_inner()) = limit;
+    }
+}
+
+/// Tracks the changes of each author while an object is evaluated.
+#[derive(Debug)]
+pub(crate) struct Tracker {
+    limit: Option<RateLimit>,
+    changes: HashMap<PublicKey, Vec<Timestamp>>,
+}
+
+impl Tracker {
+    /// Create a new tracker for a single object.
+    pub fn new(limit: Option<RateLimit>) -> Self {
+        Self {
+            limit,
+            changes: HashMap::new(),
+        }
+    }
+
+    /// Check whether the entry is within the limit of its author, and if so, count it.
+    /// Entries are counted against the author's accepted entries whose timestamps
+    /// are less than a window apart.
+    pub fn check(&mut self, entry: &Entry) -> bool {
+        let Some(limit) = self.limit else {
+            return true;
+        };
+        let timestamps = self.changes.entry(*entry.author()).or_default();
+        let within = timestamps
+            .iter()
+            .filter(|t| t.abs_diff(entry.timestamp) < limit.window)
+            .count();
+
+        if within >= limit.changes {
+            return false;
+        }
+        timestamps.push(entry.timestamp);
+
+        true
+    }
+}
+
@@ -14,6 +14,7 @@ use git_ext::commit::{
 };
 
 pub use ssh::ExtendedSignature;
+pub mod cache;
 pub mod error;
 
 // FIXME(kim): This should really be a HashMap with a no-op Hasher -- PublicKey
@@ -0,0 +1,68 @@
+//! Cache of verified entry signatures.
+//!
+//! Entries are content-addressed, so the signatures of an entry can't change
+//! once they were verified. New entries arriving in an object always have new
+//! identifiers, and are therefore verified the first time they are loaded.
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use once_cell::sync::Lazy;
+
+use crate::{Entry, EntryId};
+
+/// The cache shared by all objects loaded in this process.
+static GLOBAL: Lazy<Cache> = Lazy::new(Cache::default);
+
+/// Set of entries whose signatures were verified.
+#[derive(Debug, Default)]
+pub struct Cache {
+    verified: Mutex<HashSet<EntryId>>,
+    checks: AtomicUsize,
+}
+
+impl Cache {
+    /// The cache used when loading objects with [`crate::get`], [`crate::list`]
+    /// and [`crate::update`].
+    pub fn global() -> &'static Self {
+        &GLOBAL
+    }
+
+    /// Check that the signatures of `entry` are valid. The check is skipped if
+    /// the entry was already verified.
+    pub fn verify(&self, entry: &Entry) -> bool {
+        if self.contains(entry.id()) {
+            return true;
+        }
+        self.checks.fetch_add(1, Ordering::Relaxed);
+
+        if !entry.valid_signatures() {
+            return false;
+        }
+        self.verified().insert(*entry.id());
+
+        true
+    }
+
+    /// Check whether the signatures of the given entry were verified.
+    pub fn contains(&self, id: &EntryId) -> bool {
+        self.verified().contains(id)
+    }
+
+    /// Number of signature checks performed, ie. not skipped, so far.
+    pub fn checks(&self) -> usize {
+        self.checks.load(Ordering::Relaxed)
+    }
+
+    /// Forget all verified entries, forcing their signatures to be verified
+    /// again the next time they are loaded.
+    pub fn clear(&self) {
+        self.verified().clear();
+    }
+
+    fn verified(&self) -> MutexGuard<'_, HashSet<EntryId>> {
+        // N.b. the set is never left in an inconsistent state, so we can
+        // recover from a poisoned lock.
+        self.verified.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
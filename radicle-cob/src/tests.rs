@@ -1,3 +1,4 @@
+use std::convert::Infallible;
 use std::ops::ControlFlow;
 
 use crypto::test::signer::MockSigner;
@@ -6,9 +7,12 @@ use git_ext::ref_format::{refname, Component, RefString};
 use nonempty::{nonempty, NonEmpty};
 use qcheck::Arbitrary;
 
+use crate::change_graph::ChangeGraph;
+use crate::object::Storage as _;
+use crate::signatures::cache::Cache;
 use crate::{
-    create, get, list, object, test::arbitrary::Invalid, update, Create, Entry, ObjectId, TypeName,
-    Update, Updated, Version,
+    create, get, list, object, test::arbitrary::Invalid, update, Create, Entry, EntryId, Evaluate,
+    ObjectId, RateLimit, TypeName, Update, Updated, Version,
 };
 
 use super::test;
@@ -154,6 +158,132 @@ fn update_cob() {
     assert_eq!(object, expected, "{object:#?} {expected:#?}");
 }
 
+#[test]
+fn store_identical_contents() {
+    use crate::change::{Storage as _, Template};
+
+    let storage = test::Storage::new();
+    let repo = storage.as_raw();
+    let alice = gen::<MockSigner>(1);
+    let bob = gen::<MockSigner>(1);
+    let template = || Template {
+        type_name: "xyz.rad.issue".parse::<TypeName>().unwrap(),
+        tips: vec![],
+        message: "labeling xyz.rad.issue".to_string(),
+        embeds: vec![],
+        contents: nonempty!(b"label bug".to_vec()),
+    };
+    let objects = || {
+        let mut count = 0;
+        repo.odb()
+            .unwrap()
+            .foreach(|_| {
+                count += 1;
+                true
+            })
+            .unwrap();
+        count
+    };
+
+    let first = repo.store(None, vec![], &alice, template()).unwrap();
+    let before = objects();
+    let second = repo.store(None, vec![], &bob, template()).unwrap();
+
+    // The contents are stored once, under the same revision, and only the
+    // signed commit of the second entry is added.
+    assert_eq!(first.revision, second.revision);
+    assert_ne!(first.id, second.id);
+    assert_eq!(objects(), before + 1);
+
+    // Each entry keeps its own author and signature.
+    let first = repo.load(first.id).unwrap();
+    let second = repo.load(second.id).unwrap();
+    assert_eq!(first.author(), alice.public_key());
+    assert_eq!(second.author(), bob.public_key());
+    assert!(first.valid_signatures());
+    assert!(second.valid_signatures());
+}
+
+#[test]
+fn rate_limit() {
+    let storage = test::Storage::new();
+    let alice = gen::<MockSigner>(1);
+    let bob = gen::<MockSigner>(2);
+    let terry = test::Person::new(&storage, "terry", *alice.public_key()).unwrap();
+    let proj = test::Project::new(&storage, "discworld", *alice.public_key()).unwrap();
+    let proj = test::RemoteProject {
+        project: proj,
+        person: terry,
+    };
+    let typename = "xyz.rad.issue".parse::<TypeName>().unwrap();
+    let cob = create::<NonEmpty<Entry>, _, _>(
+        &storage,
+        &alice,
+        Some(proj.project.content_id),
+        vec![],
+        alice.public_key(),
+        Create {
+            contents: nonempty!(b"issue 1".to_vec()),
+            type_name: typename.clone(),
+            message: "creating xyz.rad.issue".to_string(),
+            embeds: vec![],
+            version: Version::default(),
+        },
+    )
+    .unwrap();
+    let comment = |signer: &MockSigner, body: &[u8]| {
+        update::<NonEmpty<Entry>, _, _>(
+            &storage,
+            signer,
+            Some(proj.project.content_id),
+            vec![],
+            signer.public_key(),
+            Update {
+                changes: nonempty!(body.to_vec()),
+                object_id: *cob.id(),
+                type_name: typename.clone(),
+                embeds: vec![],
+                message: "commenting on xyz.rad.issue".to_string(),
+            },
+        )
+        .unwrap()
+    };
+    let load = |limit: Option<RateLimit>| {
+        let tips = storage.objects(&typename, cob.id()).unwrap();
+        ChangeGraph::load(&storage, tips.iter(), &typename, cob.id())
+            .unwrap()
+            .evaluate::<_, NonEmpty<Entry>>(&storage, &Cache::default(), limit)
+            .unwrap()
+    };
+    let authors = |object: &NonEmpty<Entry>, author: &MockSigner| {
+        object
+            .iter()
+            .filter(|e| e.author() == author.public_key())
+            .count()
+    };
+
+    comment(&alice, b"comment 1");
+    comment(&alice, b"comment 2");
+    // Bob floods the issue.
+    for i in 0..10 {
+        comment(&bob, format!("spam {i}").as_bytes());
+    }
+
+    let unlimited = load(None);
+    assert_eq!(unlimited.object.len(), 13);
+
+    // Limits are per author: Alice's changes all apply, while only the first three
+    // of Bob's changes do.
+    let limited = load(Some(RateLimit::new(3, 60 * 60)));
+    assert_eq!(limited.object.len(), 6);
+    assert_eq!(authors(&limited.object, &alice), 3);
+    assert_eq!(authors(&limited.object, &bob), 3);
+
+    // Changes outside of the window don't count towards the limit.
+    let limited = load(Some(RateLimit::new(3, 0)));
+    assert_eq!(limited.object.len(), 13);
+}
+
 #[test]
 fn traverse_cobs() {
     let storage = test::Storage::new();
@@ -235,6 +365,246 @@ fn traverse_cobs() {
     assert_eq!(contents, vec![b"issue 1".to_vec(), b"issue 2".to_vec()]);
 }
 
+#[test]
+fn signature_cache() {
+    let storage = test::Storage::new();
+    let signer = gen::<MockSigner>(1);
+    let terry = test::Person::new(&storage, "terry", *signer.public_key()).unwrap();
+    let proj = test::Project::new(&storage, "discworld", *signer.public_key()).unwrap();
+    let proj = test::RemoteProject {
+        project: proj,
+        person: terry,
+    };
+    let typename = "xyz.rad.issue".parse::<TypeName>().unwrap();
+    let cob = create::<NonEmpty<Entry>, _, _>(
+        &storage,
+        &signer,
+        Some(proj.project.content_id),
+        vec![],
+        signer.public_key(),
+        Create {
+            contents: nonempty!(b"issue 1".to_vec()),
+            type_name: typename.clone(),
+            message: "creating xyz.rad.issue".to_string(),
+            embeds: vec![],
+            version: Version::default(),
+        },
+    )
+    .unwrap();
+    let comment = |body: &[u8]| {
+        update::<NonEmpty<Entry>, _, _>(
+            &storage,
+            &signer,
+            Some(proj.project.content_id),
+            vec![],
+            signer.public_key(),
+            Update {
+                changes: nonempty!(body.to_vec()),
+                object_id: *cob.id(),
+                type_name: typename.clone(),
+                embeds: vec![],
+                message: "commenting on xyz.rad.issue".to_string(),
+            },
+        )
+        .unwrap()
+    };
+    let load = |cache: &Cache| {
+        let tips = storage.objects(&typename, cob.id()).unwrap();
+        ChangeGraph::load(&storage, tips.iter(), &typename, cob.id())
+            .unwrap()
+            .evaluate::<_, NonEmpty<Entry>>(&storage, cache, None)
+            .unwrap()
+    };
+    comment(b"comment 1");
+    comment(b"comment 2");
+
+    let cache = Cache::default();
+    let first = load(&cache);
+    assert_eq!(cache.checks(), 3);
+
+    let second = load(&cache);
+    assert_eq!(cache.checks(), 3, "Verified entries are not checked again");
+    assert_eq!(first, second);
+
+    // New entries are verified the first time they are loaded.
+    let Updated { head, .. } = comment(b"comment 3");
+    assert!(!cache.contains(&head));
+    load(&cache);
+    assert_eq!(cache.checks(), 4);
+    assert!(cache.contains(&head));
+
+    // Clearing the cache forces all entries to be verified again.
+    cache.clear();
+    load(&cache);
+    assert_eq!(cache.checks(), 8);
+}
+
+/// A job, with one history entry per run. When runs happen concurrently, the run
+/// with the lowest entry id wins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Job {
+    run: (EntryId, Vec<u8>),
+}
+
+impl Job {
+    fn run(entry: &Entry) -> (EntryId, Vec<u8>) {
+        (*entry.id(), entry.contents().head.clone())
+    }
+}
+
+impl<R> Evaluate<R> for Job {
+    type Error = Infallible;
+
+    fn init(entry: &Entry, _store: &R) -> Result<Self, Self::Error> {
+        Ok(Self {
+            run: Self::run(entry),
+        })
+    }
+
+    fn apply<'a, I: Iterator<Item = (&'a git_ext::Oid, &'a Entry)>>(
+        &mut self,
+        entry: &Entry,
+        _concurrent: I,
+        _store: &R,
+    ) -> Result<(), Self::Error> {
+        self.run = Self::run(entry);
+
+        Ok(())
+    }
+
+    fn resolve_conflict<'a, I: Iterator<Item = (&'a git_ext::Oid, &'a Entry)>>(
+        &mut self,
+        entry: &Entry,
+        concurrent: I,
+        _store: &R,
+    ) -> Result<(), Self::Error> {
+        if let Some(run) = concurrent
+            .map(|(_, e)| e)
+            .chain(Some(entry))
+            .map(Self::run)
+            .min_by_key(|(id, _)| *id)
+        {
+            self.run = run;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn concurrent_job_runs() {
+    let storage = test::Storage::new();
+    let neil_signer = gen::<MockSigner>(2);
+    let neil = test::Person::new(&storage, "gaiman", *neil_signer.public_key()).unwrap();
+    let terry_signer = gen::<MockSigner>(1);
+    let terry = test::Person::new(&storage, "pratchett", *terry_signer.public_key()).unwrap();
+    let proj = test::Project::new(&storage, "discworld", *terry_signer.public_key()).unwrap();
+    let terry_proj = test::RemoteProject {
+        project: proj.clone(),
+        person: terry,
+    };
+    let neil_proj = test::RemoteProject {
+        project: proj,
+        person: neil,
+    };
+    let typename = "xyz.radicle.job".parse::<TypeName>().unwrap();
+    let job = create::<Job, _, _>(
+        &storage,
+        &terry_signer,
+        Some(terry_proj.project.content_id),
+        vec![],
+        terry_signer.public_key(),
+        Create {
+            contents: nonempty!(b"queued".to_vec()),
+            type_name: typename.clone(),
+            message: "creating xyz.radicle.job".to_string(),
+            embeds: vec![],
+            version: Version::default(),
+        },
+    )
+    .unwrap();
+    let terry_ref = format!(
+        "refs/rad/{}/cobs/{}/{}",
+        terry_signer.public_key(),
+        typename,
+        job.id()
+    );
+
+    let Updated { head: terry_run, .. } = update::<Job, _, _>(
+        &storage,
+        &terry_signer,
+        Some(terry_proj.project.content_id),
+        vec![],
+        terry_signer.public_key(),
+        Update {
+            changes: nonempty!(b"run by terry".to_vec()),
+            object_id: *job.id(),
+            type_name: typename.clone(),
+            embeds: vec![],
+            message: "running xyz.radicle.job".to_string(),
+        },
+    )
+    .unwrap();
+
+    // Hide Terry's run from Neil, so that both runs only have the root as parent.
+    storage
+        .as_raw()
+        .reference(&terry_ref, git2::Oid::from(**job.id()), true, "hiding run")
+        .unwrap();
+    let Updated { head: neil_run, .. } = update::<Job, _, _>(
+        &storage,
+        &neil_signer,
+        Some(neil_proj.project.content_id),
+        vec![],
+        neil_signer.public_key(),
+        Update {
+            changes: nonempty!(b"run by neil".to_vec()),
+            object_id: *job.id(),
+            type_name: typename.clone(),
+            embeds: vec![],
+            message: "running xyz.radicle.job".to_string(),
+        },
+    )
+    .unwrap();
+    storage
+        .as_raw()
+        .reference(&terry_ref, terry_run.into(), true, "restoring run")
+        .unwrap();
+
+    let job = get::<Job, _>(&storage, &typename, job.id())
+        .unwrap()
+        .expect("BUG: cob was missing");
+    let expected = if terry_run < neil_run {
+        (terry_run, b"run by terry".to_vec())
+    } else {
+        (neil_run, b"run by neil".to_vec())
+    };
+    assert_eq!(job.history().tips().len(), 2);
+    assert_eq!(job.object().run, expected);
+
+    // The resolution doesn't depend on the order in which the runs are applied.
+    let root = job.history().root().id;
+    let entries = job
+        .history()
+        .traverse(Vec::new(), &[root], |mut acc, _, entry| {
+            acc.push(entry.clone());
+            ControlFlow::Continue(acc)
+        });
+    let [root, a, b] = entries.as_slice() else {
+        panic!("expected three entries, got {}", entries.len());
+    };
+    for (first, second) in [(a, b), (b, a)] {
+        let mut job = <Job as Evaluate<()>>::init(root, &()).unwrap();
+
+        for (entry, other) in [(first, second), (second, first)] {
+            let concurrent = [(other.id(), other)];
+            job.apply(entry, concurrent.into_iter(), &()).unwrap();
+            job.resolve_conflict(entry, concurrent.into_iter(), &())
+                .unwrap();
+        }
+        assert_eq!(job.run, expected);
+    }
+}
+
 #[quickcheck]
 fn parse_refstr(oid: ObjectId, typename: TypeName) {
     let suffix = refname!("refs/cobs")
@@ -1,9 +1,23 @@
 use std::collections::btree_map::{Entry, IntoIter, IntoKeys};
 use std::collections::BTreeMap;
+use std::iter::Peekable;
+use std::mem;
 use std::ops::Deref;
 
+use thiserror::Error;
+
 use crate::Semilattice;
 
+/// Error returned when a bounded merge would exceed the map capacity.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("map capacity of {capacity} entries exceeded: merge would result in {len} entries")]
+pub struct CapacityError {
+    /// Maximum number of entries allowed.
+    pub capacity: usize,
+    /// Number of entries the map would have after the merge.
+    pub len: usize,
+}
+
 /// Grow-only map.
 ///
 /// Conflicting elements are merged via the [`Semilattice`] instance.
@@ -33,6 +47,109 @@ impl<K: Ord, V: Semilattice> GMap<K, V> {
             }
         }
     }
+
+    /// Merge many entries into the map at once.
+    ///
+    /// This is equivalent to inserting each entry, but the incoming entries are sorted first
+    /// and then joined with the existing entries in a single pass, which is cheaper than
+    /// repeated inserts when importing large maps.
+    pub fn extend_merge<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let incoming = Self::from_iter(iter).inner;
+        let existing = mem::take(&mut self.inner);
+
+        self.inner = Self::join_sorted(existing, incoming);
+    }
+
+    /// Like [`GMap::extend_merge`], but fails if the resulting map would have more than
+    /// `capacity` entries. This guards against peers sending us arbitrarily large maps.
+    ///
+    /// On error, the map is left unchanged.
+    pub fn extend_merge_bounded<I: IntoIterator<Item = (K, V)>>(
+        &mut self,
+        iter: I,
+        capacity: usize,
+    ) -> Result<(), CapacityError> {
+        let mut incoming = BTreeMap::new();
+
+        for (k, v) in iter.into_iter() {
+            if let Some(existing) = incoming.get_mut(&k) {
+                existing.merge(v);
+                continue;
+            }
+            // Bail out early, without buffering more than the capacity.
+            if incoming.len() >= capacity {
+                return Err(CapacityError {
+                    capacity,
+                    len: incoming.len() + 1,
+                });
+            }
+            incoming.insert(k, v);
+        }
+        let len = self.inner.len()
+            + incoming
+                .keys()
+                .filter(|k| !self.inner.contains_key(k))
+                .count();
+        if len > capacity {
+            return Err(CapacityError { capacity, len });
+        }
+        let existing = mem::take(&mut self.inner);
+        self.inner = Self::join_sorted(existing, incoming);
+
+        Ok(())
+    }
+
+    /// Join two sorted maps into one, merging values with equal keys.
+    fn join_sorted(a: BTreeMap<K, V>, b: BTreeMap<K, V>) -> BTreeMap<K, V> {
+        if a.is_empty() {
+            return b;
+        }
+        if b.is_empty() {
+            return a;
+        }
+        // Nb. Building a `BTreeMap` from sorted input is linear.
+        JoinSorted {
+            a: a.into_iter().peekable(),
+            b: b.into_iter().peekable(),
+        }
+        .collect()
+    }
+}
+
+/// Iterator joining two sorted iterators of key-value pairs.
+struct JoinSorted<A: Iterator, B: Iterator> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<K, V, A, B> Iterator for JoinSorted<A, B>
+where
+    K: Ord,
+    V: Semilattice,
+    A: Iterator<Item = (K, V)>,
+    B: Iterator<Item = (K, V)>,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ordering = match (self.a.peek(), self.b.peek()) {
+            (Some((ka, _)), Some((kb, _))) => ka.cmp(kb),
+            (Some(_), None) => return self.a.next(),
+            (None, Some(_)) => return self.b.next(),
+            (None, None) => return None,
+        };
+        match ordering {
+            std::cmp::Ordering::Less => self.a.next(),
+            std::cmp::Ordering::Greater => self.b.next(),
+            std::cmp::Ordering::Equal => {
+                let (k, mut va) = self.a.next()?;
+                let (_, vb) = self.b.next()?;
+                va.merge(vb);
+
+                Some((k, va))
+            }
+        }
+    }
 }
 
 impl<K, V> GMap<K, V> {
@@ -115,4 +232,58 @@ mod tests {
 
         crate::test::assert_laws(&a, &b, &c);
     }
+
+    #[quickcheck]
+    fn prop_extend_merge(a: Vec<(u8, Max<u8>)>, b: Vec<(u8, Max<u8>)>) {
+        let mut expected = GMap::from_iter(a.clone());
+        let mut actual = GMap::from_iter(a);
+
+        expected.extend(b.clone());
+        actual.extend_merge(b);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_extend_merge() {
+        let mut map = GMap::from_iter([(1, Max::from(1)), (3, Max::from(3))]);
+        map.extend_merge([(2, Max::from(2)), (3, Max::from(1)), (1, Max::from(9))]);
+
+        assert_eq!(
+            map.into_iter().collect::<Vec<_>>(),
+            vec![(1, Max::from(9)), (2, Max::from(2)), (3, Max::from(3))]
+        );
+    }
+
+    #[test]
+    fn test_extend_merge_bounded() {
+        let mut map = GMap::from_iter([(1, Max::from(1)), (2, Max::from(2))]);
+
+        // Existing keys don't count towards the capacity.
+        map.extend_merge_bounded([(1, Max::from(5)), (3, Max::from(3))], 3)
+            .unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(&1), Some(&Max::from(5)));
+
+        // A merge exceeding the capacity leaves the map unchanged.
+        let before = map.clone();
+        assert_eq!(
+            map.extend_merge_bounded([(4, Max::from(4)), (1, Max::from(7))], 3),
+            Err(CapacityError {
+                capacity: 3,
+                len: 4
+            })
+        );
+        assert_eq!(map, before);
+
+        // Large inputs are rejected before being fully buffered.
+        assert_eq!(
+            map.extend_merge_bounded((0..u8::MAX).map(|i| (i, Max::from(i))), 8),
+            Err(CapacityError {
+                capacity: 8,
+                len: 9
+            })
+        );
+        assert_eq!(map, before);
+    }
 }
@@ -2,7 +2,7 @@ use std::collections::btree_map::{IntoKeys, Keys};
 use std::ops::Deref;
 
 use crate::GMap;
-use crate::Semilattice;
+use crate::{Delta, Semilattice};
 
 /// Grow-only set.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -77,6 +77,24 @@ impl<K> Deref for GSet<K> {
     }
 }
 
+/// Grow-only sets of timestamped elements can be synced via deltas.
+impl<K, C> Delta<C> for GSet<(C, K)>
+where
+    K: Ord + Clone,
+    C: Ord + Clone,
+{
+    /// The elements added after the given clock.
+    type DeltaState = Self;
+
+    fn delta_since(&self, clock: C) -> Self::DeltaState {
+        self.iter().filter(|(c, _)| *c > clock).cloned().collect()
+    }
+
+    fn apply_delta(&mut self, delta: Self::DeltaState) {
+        self.merge(delta);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use qcheck_macros::quickcheck;
@@ -94,4 +112,20 @@ mod tests {
 
         crate::test::assert_laws(&a, &b, &c);
     }
+
+    #[quickcheck]
+    fn prop_delta(a: Vec<(u16, u8)>, b: Vec<(u16, u8)>, clock: u16) {
+        let known = a.iter().filter(|(c, _)| *c <= clock).cloned();
+        let a = GSet::from_iter(a.clone());
+        // `b` already has all the elements of `a` up to the clock.
+        let b = GSet::from_iter(b).join(GSet::from_iter(known));
+
+        let delta = a.delta_since(clock);
+        assert!(delta.iter().all(|(c, _)| *c > clock));
+
+        let mut synced = b.clone();
+        synced.apply_delta(delta);
+
+        assert_eq!(synced, b.join(a));
+    }
 }
@@ -18,11 +18,11 @@ pub mod test;
 ////////////////////////////////////////////////////////////////////////////////
 
 pub use clock::Lamport;
-pub use gmap::GMap;
+pub use gmap::{CapacityError, GMap};
 pub use gset::GSet;
 pub use immutable::Immutable;
 pub use lwwmap::LWWMap;
-pub use lwwreg::LWWReg;
+pub use lwwreg::{LWWReg, Tiebreak};
 pub use lwwset::LWWSet;
 pub use ord::{Max, Min};
 pub use redactable::Redactable;
@@ -44,6 +44,22 @@ pub trait Semilattice: Sized {
     }
 }
 
+/// A semilattice which can be synced by exchanging deltas, ie. only the part of its state
+/// that changed after a given clock, instead of the full state.
+///
+/// Applying the delta since some clock to a state that already contains every change up to
+/// that clock must be equivalent to merging the full state.
+pub trait Delta<C = Lamport>: Semilattice {
+    /// The state changed after a given clock.
+    type DeltaState;
+
+    /// Get the changes made after the given clock.
+    fn delta_since(&self, clock: C) -> Self::DeltaState;
+
+    /// Apply a delta obtained via [`Delta::delta_since`].
+    fn apply_delta(&mut self, delta: Self::DeltaState);
+}
+
 impl<T: Semilattice> Semilattice for Option<T> {
     fn merge(&mut self, other: Self) {
         match (self, other) {
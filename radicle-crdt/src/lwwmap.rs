@@ -1,6 +1,7 @@
 use crate::gmap::GMap;
 use crate::lwwreg::LWWReg;
-use crate::{clock, Semilattice};
+use crate::ord::Max;
+use crate::{clock, Delta, Semilattice};
 
 /// Last-Write-Wins Map.
 ///
@@ -94,12 +95,38 @@ where
     }
 }
 
+impl<K, V, C> Delta<C> for LWWMap<K, V, C>
+where
+    K: Ord + Clone,
+    V: Semilattice + Clone,
+    C: Ord + Clone,
+{
+    /// The entries that were inserted or removed after the given clock.
+    type DeltaState = Self;
+
+    fn delta_since(&self, clock: C) -> Self::DeltaState {
+        let clock = Max::from(clock);
+
+        Self {
+            inner: self
+                .inner
+                .iter()
+                .filter(|(_, reg)| *reg.clock() > clock)
+                .map(|(k, reg)| (k.clone(), reg.clone()))
+                .collect(),
+        }
+    }
+
+    fn apply_delta(&mut self, delta: Self::DeltaState) {
+        self.merge(delta);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use qcheck_macros::quickcheck;
 
     use super::*;
-    use crate::ord::Max;
 
     #[quickcheck]
     fn prop_semilattice(
@@ -118,6 +145,22 @@ mod tests {
         crate::test::assert_laws(&a, &b, &c);
     }
 
+    #[quickcheck]
+    fn prop_delta(a: Vec<(u8, Max<u8>, u16)>, b: Vec<(u8, Max<u8>, u16)>, clock: u16) {
+        let known = a.iter().filter(|(_, _, c)| *c <= clock).cloned();
+        let a = LWWMap::from_iter(a.clone());
+        // `b` already has all the changes of `a` up to the clock.
+        let b = LWWMap::from_iter(b).join(LWWMap::from_iter(known));
+
+        let delta = a.delta_since(clock);
+        assert!(delta.inner.values().all(|reg| *reg.clock().get() > clock));
+
+        let mut synced = b.clone();
+        synced.apply_delta(delta);
+
+        assert_eq!(synced, b.join(a));
+    }
+
     #[test]
     fn test_insert() {
         let mut map = LWWMap::default();
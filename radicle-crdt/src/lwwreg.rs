@@ -1,16 +1,113 @@
+use std::cmp::Ordering;
+use std::fmt;
+
 use num_traits::Bounded;
 
 use crate::clock;
 use crate::ord::Max;
 use crate::Semilattice;
 
-/// Last-Write-Wins Register.
+/// Strategy used by [`LWWReg`] to resolve conflicts between values with equal clocks.
 ///
-/// In case of conflict, uses the [`Semilattice`] instance of `T` to merge.
+/// For the register to be a semilattice, the strategy must itself be idempotent,
+/// associative and commutative, ie. it must not depend on the order in which values
+/// are merged.
+pub trait Tiebreak<T> {
+    /// Resolve a conflict between two values, storing the winner in `a`.
+    fn tiebreak(&self, a: &mut T, b: T);
+}
+
+/// Resolve conflicts using the [`Semilattice`] instance of the value. This is the default.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Merge;
+
+impl<T: Semilattice> Tiebreak<T> for Merge {
+    fn tiebreak(&self, a: &mut T, b: T) {
+        a.merge(b);
+    }
+}
+
+/// Resolve conflicts by keeping the greatest value, according to its [`Ord`] instance.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ValueMax;
+
+impl<T: Ord> Tiebreak<T> for ValueMax {
+    fn tiebreak(&self, a: &mut T, b: T) {
+        if b > *a {
+            *a = b;
+        }
+    }
+}
+
+/// Resolve conflicts between values of the form `(actor, value)` by preferring the
+/// actor that comes first in the priority list. Actors that aren't in the list come
+/// last. If both actors have the same priority, the greatest pair wins.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct LWWReg<T, C = clock::Lamport> {
+pub struct ActorPriority<A> {
+    actors: Vec<A>,
+}
+
+impl<A> ActorPriority<A> {
+    /// Create a new strategy from a list of actors, by decreasing priority.
+    pub fn new(actors: impl IntoIterator<Item = A>) -> Self {
+        Self {
+            actors: actors.into_iter().collect(),
+        }
+    }
+
+    fn rank(&self, actor: &A) -> usize
+    where
+        A: PartialEq,
+    {
+        self.actors
+            .iter()
+            .position(|a| a == actor)
+            .unwrap_or(self.actors.len())
+    }
+}
+
+impl<A: Ord, V: Ord> Tiebreak<(A, V)> for ActorPriority<A> {
+    fn tiebreak(&self, a: &mut (A, V), b: (A, V)) {
+        let ordering = self
+            .rank(&b.0)
+            .cmp(&self.rank(&a.0))
+            .reverse()
+            .then_with(|| b.cmp(a));
+
+        if ordering == Ordering::Greater {
+            *a = b;
+        }
+    }
+}
+
+/// Resolve conflicts with a custom comparison function, keeping the greatest value.
+/// The function should define a total order for the register to remain a semilattice.
+#[derive(Clone, Copy)]
+pub struct Custom<F>(pub F);
+
+impl<F> fmt::Debug for Custom<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Custom").finish_non_exhaustive()
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering> Tiebreak<T> for Custom<F> {
+    fn tiebreak(&self, a: &mut T, b: T) {
+        if (self.0)(&b, a) == Ordering::Greater {
+            *a = b;
+        }
+    }
+}
+
+/// Last-Write-Wins Register.
+///
+/// In case of conflict, uses the [`Tiebreak`] strategy `S` to merge, which defaults
+/// to the [`Semilattice`] instance of `T`.
+#[derive(Clone)]
+pub struct LWWReg<T, C = clock::Lamport, S = Merge> {
     clock: Max<C>,
     value: T,
+    tiebreak: S,
 }
 
 impl<T: Semilattice, C: PartialOrd> LWWReg<T, C> {
@@ -18,16 +115,21 @@ impl<T: Semilattice, C: PartialOrd> LWWReg<T, C> {
     where
         C: Default,
     {
-        Self {
-            clock: Max::from(C::default()),
-            value,
-        }
+        Self::with_tiebreak(value, C::default(), Merge)
     }
 
     pub fn new(value: T, clock: C) -> Self {
+        Self::with_tiebreak(value, clock, Merge)
+    }
+}
+
+impl<T, C: PartialOrd, S: Tiebreak<T>> LWWReg<T, C, S> {
+    /// Create a new register with the given tiebreak strategy.
+    pub fn with_tiebreak(value: T, clock: C, tiebreak: S) -> Self {
         Self {
             clock: Max::from(clock),
             value,
+            tiebreak,
         }
     }
 
@@ -36,7 +138,7 @@ impl<T: Semilattice, C: PartialOrd> LWWReg<T, C> {
         let value = value.into();
 
         if clock == self.clock {
-            self.value.merge(value);
+            self.tiebreak.tiebreak(&mut self.value, value);
         } else if clock > self.clock {
             self.clock.merge(clock);
             self.value = value;
@@ -56,19 +158,40 @@ impl<T: Semilattice, C: PartialOrd> LWWReg<T, C> {
     }
 }
 
-impl<T: Default, C: Default + Bounded> Default for LWWReg<T, C> {
+impl<T: Default, C: Default + Bounded, S: Default> Default for LWWReg<T, C, S> {
     fn default() -> Self {
         Self {
             clock: Max::default(),
             value: T::default(),
+            tiebreak: S::default(),
         }
     }
 }
 
-impl<T, C> Semilattice for LWWReg<T, C>
+// Nb. The tiebreak strategy is not part of the register state, and is thus
+// ignored when comparing or printing registers.
+
+impl<T: fmt::Debug, C: fmt::Debug, S> fmt::Debug for LWWReg<T, C, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LWWReg")
+            .field("clock", &self.clock)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<T: PartialEq, C: PartialEq, S> PartialEq for LWWReg<T, C, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.clock == other.clock && self.value == other.value
+    }
+}
+
+impl<T: Eq, C: Eq, S> Eq for LWWReg<T, C, S> {}
+
+impl<T, C, S> Semilattice for LWWReg<T, C, S>
 where
-    T: Semilattice,
     C: PartialOrd,
+    S: Tiebreak<T>,
 {
     fn merge(&mut self, other: Self) {
         self.set(other.value, other.clock.into_inner());
@@ -91,6 +214,60 @@ mod tests {
         crate::test::assert_laws(&a, &b, &c);
     }
 
+    #[quickcheck]
+    fn prop_semilattice_value_max(a: (u8, u16), b: (u8, u16), c: (u8, u16)) {
+        let a = LWWReg::with_tiebreak(a.0, a.1, ValueMax);
+        let b = LWWReg::with_tiebreak(b.0, b.1, ValueMax);
+        let c = LWWReg::with_tiebreak(c.0, c.1, ValueMax);
+
+        crate::test::assert_laws(&a, &b, &c);
+    }
+
+    #[quickcheck]
+    fn prop_semilattice_actor_priority(
+        a: ((u8, u8), u16),
+        b: ((u8, u8), u16),
+        c: ((u8, u8), u16),
+        actors: Vec<u8>,
+    ) {
+        let priority = ActorPriority::new(actors);
+        let a = LWWReg::with_tiebreak(a.0, a.1, priority.clone());
+        let b = LWWReg::with_tiebreak(b.0, b.1, priority.clone());
+        let c = LWWReg::with_tiebreak(c.0, c.1, priority);
+
+        crate::test::assert_laws(&a, &b, &c);
+    }
+
+    #[quickcheck]
+    fn prop_semilattice_custom(a: (u8, u16), b: (u8, u16), c: (u8, u16)) {
+        let reverse = Custom(|a: &u8, b: &u8| b.cmp(a));
+        let a = LWWReg::with_tiebreak(a.0, a.1, reverse);
+        let b = LWWReg::with_tiebreak(b.0, b.1, reverse);
+        let c = LWWReg::with_tiebreak(c.0, c.1, reverse);
+
+        crate::test::assert_laws(&a, &b, &c);
+    }
+
+    #[test]
+    fn test_tiebreak() {
+        let a = LWWReg::with_tiebreak(0, 0, ValueMax);
+        let b = LWWReg::with_tiebreak(1, 0, ValueMax);
+        assert_eq!(a.join(b).get(), &1);
+
+        let priority = ActorPriority::new(['b', 'a']);
+        let a = LWWReg::with_tiebreak(('a', 1), 0, priority.clone());
+        let b = LWWReg::with_tiebreak(('b', 0), 0, priority.clone());
+        let c = LWWReg::with_tiebreak(('c', 2), 0, priority);
+        assert_eq!(a.clone().join(b.clone()).get(), &('b', 0));
+        assert_eq!(a.join(c.clone()).get(), &('a', 1));
+        assert_eq!(b.join(c).get(), &('b', 0));
+
+        let min = Custom(|a: &u8, b: &u8| b.cmp(a));
+        let a = LWWReg::with_tiebreak(0, 0, min);
+        let b = LWWReg::with_tiebreak(1, 0, min);
+        assert_eq!(a.join(b).get(), &0);
+    }
+
     #[test]
     fn test_merge() {
         let a = LWWReg::new(Max::from(0), 0);
@@ -0,0 +1,127 @@
+//! Signers whose secret key is held outside of the process, eg. by a PKCS#11 device or a KMS.
+//!
+//! An external signer is registered once per process with [`register`]. Wherever a signer
+//! is needed for its public key, eg. when a profile's signer is loaded, the registered
+//! external signer is then used in place of the keystore or the SSH agent.
+use std::collections::BTreeMap;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::{PublicKey, Signer};
+
+/// External signers registered with this process, by public key.
+static EXTERNAL: RwLock<BTreeMap<PublicKey, Arc<dyn ExternalSigner>>> =
+    RwLock::new(BTreeMap::new());
+
+/// A signer backed by an external device or service.
+///
+/// Signing failures of the backend, eg. because the device is unplugged or the service
+/// is unreachable, should be returned from [`Signer::try_sign`] as a [`crate::SignerError`].
+pub trait ExternalSigner: Signer {
+    /// Name of the backend, eg. `pkcs11`.
+    fn backend(&self) -> &str;
+}
+
+/// Register an external signer for its public key, replacing any signer previously
+/// registered for that key.
+pub fn register(signer: impl ExternalSigner + 'static) {
+    let key = *signer.public_key();
+
+    EXTERNAL
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(key, Arc::new(signer));
+}
+
+/// Unregister the external signer of a public key. Returns whether there was one.
+pub fn unregister(key: &PublicKey) -> bool {
+    EXTERNAL
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .remove(key)
+        .is_some()
+}
+
+/// Get the external signer registered for a public key, if any.
+pub fn signer(key: &PublicKey) -> Option<Arc<dyn ExternalSigner>> {
+    EXTERNAL
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(key)
+        .cloned()
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+
+    use super::*;
+    use crate::test::signer::MockSigner;
+    use crate::{Signature, SignerError};
+
+    /// A device holding a single key.
+    struct Device {
+        signer: MockSigner,
+        plugged: bool,
+    }
+
+    impl Signer for Device {
+        fn public_key(&self) -> &PublicKey {
+            self.signer.public_key()
+        }
+
+        fn sign(&self, msg: &[u8]) -> Signature {
+            self.try_sign(msg).unwrap()
+        }
+
+        fn try_sign(&self, msg: &[u8]) -> Result<Signature, SignerError> {
+            if !self.plugged {
+                return Err(SignerError::new(io::Error::new(
+                    io::ErrorKind::NotConnected,
+                    "device unplugged",
+                )));
+            }
+            self.signer.try_sign(msg)
+        }
+    }
+
+    impl ExternalSigner for Device {
+        fn backend(&self) -> &str {
+            "mock"
+        }
+    }
+
+    #[test]
+    fn test_external_signer() {
+        let device = Device {
+            signer: MockSigner::default(),
+            plugged: true,
+        };
+        let key = *device.public_key();
+        assert!(signer(&key).is_none());
+
+        register(device);
+
+        let signer = signer(&key).unwrap();
+        let sig = signer.try_sign(b"hello").unwrap();
+
+        assert_eq!(signer.backend(), "mock");
+        assert!(key.verify(b"hello", &sig).is_ok());
+        assert!(key.verify(b"goodbye", &sig).is_err());
+        assert!(unregister(&key));
+        assert!(super::signer(&key).is_none());
+    }
+
+    #[test]
+    fn test_external_signer_failure() {
+        let device = Device {
+            signer: MockSigner::default(),
+            plugged: false,
+        };
+        let key = *device.public_key();
+
+        register(device);
+
+        let err = signer(&key).unwrap().try_sign(b"hello").unwrap_err();
+        assert_eq!(err.to_string(), "device unplugged");
+    }
+}
@@ -8,6 +8,8 @@ use thiserror::Error;
 
 pub use ed25519::{edwards25519, Error, KeyPair, Seed};
 
+pub mod external;
+mod shamir;
 #[cfg(feature = "ssh")]
 pub mod ssh;
 #[cfg(any(test, feature = "test"))]
@@ -66,6 +68,23 @@ where
     }
 }
 
+impl<T> Signer for Arc<T>
+where
+    T: Signer + ?Sized,
+{
+    fn public_key(&self) -> &PublicKey {
+        self.deref().public_key()
+    }
+
+    fn sign(&self, msg: &[u8]) -> Signature {
+        self.deref().sign(msg)
+    }
+
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature, SignerError> {
+        self.deref().try_sign(msg)
+    }
+}
+
 /// Cryptographic signature.
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
 #[serde(into = "String", try_from = "String")]
@@ -335,6 +354,10 @@ impl TryFrom<&[u8]> for PublicKey {
 impl PublicKey {
     /// Multicodec key type for Ed25519 keys.
     pub const MULTICODEC_TYPE: [u8; 2] = [0xED, 0x1];
+    /// Number of digest bytes in a key fingerprint.
+    pub const FINGERPRINT_BYTES: usize = 8;
+    /// Number of characters kept on each side of a short key.
+    pub const SHORT_LEN: usize = 7;
 
     /// Encode public key in human-readable format.
     ///
@@ -348,6 +371,40 @@ impl PublicKey {
         multibase::encode(multibase::Base::Base58Btc, buf)
     }
 
+    /// Get a short, stable fingerprint of the key, for display purposes.
+    ///
+    /// The fingerprint is the first [`PublicKey::FINGERPRINT_BYTES`] bytes of the SHA-256
+    /// digest of the raw key bytes, encoded as lowercase hexadecimal, eg. `af822958f2d75afb`.
+    /// With 64 bits, collisions are unlikely among any realistic number of keys, but
+    /// they can be brute-forced, so the fingerprint should never be used in place of
+    /// the full key for identification.
+    pub fn fingerprint(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        let digest = Sha256::digest(self.0.deref());
+
+        digest[..Self::FINGERPRINT_BYTES]
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+
+    /// Get a short form of the key's human-readable encoding, consisting of its first
+    /// and last [`PublicKey::SHORT_LEN`] characters, eg. `z6MknSL…StBU8Vi`.
+    ///
+    /// Since all Ed25519 keys share the `z6Mk` prefix, only the last characters of the
+    /// prefix and the suffix tell keys apart. This is enough to recognize a key among a
+    /// handful of others, eg. the delegates of a repository, but it isn't unique.
+    pub fn short(&self) -> String {
+        let human = self.to_human();
+        let (start, end) = (
+            &human[..Self::SHORT_LEN],
+            &human[human.len() - Self::SHORT_LEN..],
+        );
+
+        format!("{start}…{end}")
+    }
+
     #[cfg(feature = "radicle-git-ext")]
     pub fn to_namespace(&self) -> radicle_git_ext::ref_format::RefString {
         use radicle_git_ext::ref_format::{refname, Component};
@@ -499,6 +556,18 @@ pub mod keypair {
         }
         KeyPair::generate()
     }
+
+    pub use crate::shamir::{Share, ShareError};
+
+    /// Split a seed into `shares` shares, such that any `threshold` of them can recover it.
+    pub fn split_seed(seed: &Seed, threshold: u8, shares: u8) -> Result<Vec<Share>, ShareError> {
+        crate::shamir::split(seed, threshold, shares)
+    }
+
+    /// Recover a seed from shares created with [`split_seed`].
+    pub fn recover_seed(shares: &[Share]) -> Result<Seed, ShareError> {
+        crate::shamir::recover(shares)
+    }
 }
 
 pub mod env {
@@ -556,6 +625,45 @@ mod tests {
         assert_eq!(key.to_string(), input);
     }
 
+    #[test]
+    fn test_fingerprint() {
+        let key = PublicKey::from_str("z6MknSLrJoTcukLrE435hVNQT4JUhbvWLX4kUzqkEStBU8Vi").unwrap();
+
+        assert_eq!(key.fingerprint(), "af822958f2d75afb");
+        assert_eq!(
+            PublicKey::from_str("z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK")
+                .unwrap()
+                .fingerprint(),
+            "c446d9bcf84d5e3e"
+        );
+    }
+
+    #[quickcheck]
+    fn prop_fingerprint(a: PublicKey, b: PublicKey) {
+        assert_eq!(a.fingerprint(), a.fingerprint());
+        assert_eq!(a.fingerprint().len(), PublicKey::FINGERPRINT_BYTES * 2);
+        assert_ne!(a.fingerprint(), b.fingerprint());
+    }
+
+    #[test]
+    fn test_short() {
+        let key = PublicKey::from_str("z6MknSLrJoTcukLrE435hVNQT4JUhbvWLX4kUzqkEStBU8Vi").unwrap();
+
+        assert_eq!(key.short(), "z6MknSL…StBU8Vi");
+    }
+
+    #[quickcheck]
+    fn prop_short(input: PublicKey) {
+        let human = input.to_human();
+        let short = input.short();
+        let (start, end) = short.split_once('…').unwrap();
+
+        assert!(human.starts_with(start));
+        assert!(human.ends_with(end));
+        assert_eq!(start.len(), PublicKey::SHORT_LEN);
+        assert_eq!(end.len(), PublicKey::SHORT_LEN);
+    }
+
     #[quickcheck]
     fn prop_key_equality(a: PublicKey, b: PublicKey) {
         use std::collections::HashSet;
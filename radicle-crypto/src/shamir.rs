@@ -0,0 +1,318 @@
+//! Shamir secret-sharing of key seeds, over GF(256).
+//!
+//! A seed is split into a number of shares, of which any `threshold` can be combined to
+//! recover it. Each byte of the seed is the constant term of its own random polynomial of
+//! degree `threshold - 1`, and a share holds the evaluations of all polynomials at one point.
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use zeroize::Zeroize;
+
+use crate::Seed;
+
+/// Length of a seed, in bytes.
+const SEED_LENGTH: usize = 32;
+
+/// Error splitting or recovering a seed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ShareError {
+    #[error("invalid threshold {threshold} for {shares} share(s)")]
+    InvalidThreshold { threshold: u8, shares: u8 },
+    #[error("not enough shares: {threshold} required, {count} given")]
+    NotEnoughShares { threshold: u8, count: usize },
+    #[error("shares have mismatching thresholds")]
+    MismatchingThreshold,
+    #[error("duplicate share with index {0}")]
+    DuplicateShare(u8),
+    #[error("invalid share: {0}")]
+    InvalidShare(&'static str),
+}
+
+/// One share of a seed.
+///
+/// Shares are printed as `<threshold>-<index>-<data>`, with the data hex-encoded,
+/// eg. `2-1-9f86d081..`, and are serialized in the same format.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Share {
+    /// Number of shares needed to recover the seed.
+    threshold: u8,
+    /// Point at which the polynomials were evaluated. Never zero.
+    index: u8,
+    /// Evaluations of the polynomials, one per seed byte.
+    data: [u8; SEED_LENGTH],
+}
+
+impl Share {
+    /// Number of shares needed to recover the seed.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Index of this share, starting at `1`.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("threshold", &self.threshold)
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}-", self.threshold, self.index)?;
+
+        for byte in self.data {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Share {
+    type Err = ShareError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, '-');
+        let (threshold, index, data) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(threshold), Some(index), Some(data)) => (threshold, index, data),
+            _ => return Err(ShareError::InvalidShare("expected `<threshold>-<index>-<data>`")),
+        };
+        let threshold = threshold
+            .parse::<u8>()
+            .ok()
+            .filter(|t| *t > 0)
+            .ok_or(ShareError::InvalidShare("invalid threshold"))?;
+        let index = index
+            .parse::<u8>()
+            .ok()
+            .filter(|i| *i > 0)
+            .ok_or(ShareError::InvalidShare("invalid index"))?;
+
+        if data.len() != SEED_LENGTH * 2 || !data.is_ascii() {
+            return Err(ShareError::InvalidShare("invalid data length"));
+        }
+        let mut share = Self {
+            threshold,
+            index,
+            data: [0; SEED_LENGTH],
+        };
+        for (i, byte) in share.data.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&data[i * 2..i * 2 + 2], 16)
+                .map_err(|_| ShareError::InvalidShare("invalid hexadecimal data"))?;
+        }
+        Ok(share)
+    }
+}
+
+impl From<Share> for String {
+    fn from(share: Share) -> Self {
+        share.to_string()
+    }
+}
+
+impl TryFrom<String> for Share {
+    type Error = ShareError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(&s)
+    }
+}
+
+/// Split a seed into `shares` shares, any `threshold` of which recover it.
+pub fn split(seed: &Seed, threshold: u8, shares: u8) -> Result<Vec<Share>, ShareError> {
+    if threshold == 0 || threshold > shares {
+        return Err(ShareError::InvalidThreshold { threshold, shares });
+    }
+    // The coefficients of every polynomial, by degree. The constant terms are the seed itself,
+    // and the others are random.
+    let mut coefficients = Vec::with_capacity(threshold as usize);
+    coefficients.push(**seed);
+    for _ in 1..threshold {
+        coefficients.push(*Seed::generate());
+    }
+
+    let shares = (1..=shares)
+        .map(|index| {
+            let mut data = [0; SEED_LENGTH];
+            for (i, byte) in data.iter_mut().enumerate() {
+                // Evaluate the polynomial using Horner's method.
+                *byte = coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, c| gf256::mul(acc, index) ^ c[i]);
+            }
+            Share {
+                threshold,
+                index,
+                data,
+            }
+        })
+        .collect();
+
+    coefficients.zeroize();
+
+    Ok(shares)
+}
+
+/// Recover a seed from its shares.
+///
+/// At least as many shares as the threshold they were split with must be given.
+/// Any shares in excess of the threshold are ignored.
+pub fn recover(shares: &[Share]) -> Result<Seed, ShareError> {
+    let Some(first) = shares.first() else {
+        return Err(ShareError::NotEnoughShares {
+            threshold: 1,
+            count: 0,
+        });
+    };
+    let threshold = first.threshold;
+
+    if shares.iter().any(|s| s.threshold != threshold) {
+        return Err(ShareError::MismatchingThreshold);
+    }
+    if shares.len() < threshold as usize {
+        return Err(ShareError::NotEnoughShares {
+            threshold,
+            count: shares.len(),
+        });
+    }
+    let shares = &shares[..threshold as usize];
+
+    for (i, share) in shares.iter().enumerate() {
+        if shares[..i].iter().any(|s| s.index == share.index) {
+            return Err(ShareError::DuplicateShare(share.index));
+        }
+    }
+
+    // Lagrange interpolation at zero. In GF(256), subtraction is the same as addition.
+    let mut seed = [0; SEED_LENGTH];
+    for share in shares {
+        let basis = shares
+            .iter()
+            .filter(|s| s.index != share.index)
+            .fold(1, |acc, s| gf256::mul(acc, gf256::div(s.index, s.index ^ share.index)));
+
+        for (byte, y) in seed.iter_mut().zip(share.data) {
+            *byte ^= gf256::mul(basis, y);
+        }
+    }
+    Ok(Seed::new(seed))
+}
+
+/// Arithmetic in GF(2^8), using the AES reduction polynomial.
+mod gf256 {
+    /// Multiply two field elements, without branching on their values.
+    pub fn mul(mut a: u8, mut b: u8) -> u8 {
+        let mut product = 0;
+
+        for _ in 0..8 {
+            product ^= a & 0u8.wrapping_sub(b & 1);
+            let carry = 0u8.wrapping_sub(a >> 7);
+            a = (a << 1) ^ (0x1b & carry);
+            b >>= 1;
+        }
+        product
+    }
+
+    /// Multiplicative inverse of a non-zero field element, ie. `a^254`.
+    pub fn inv(a: u8) -> u8 {
+        let mut result = 1;
+        let mut power = a;
+        let mut exp = 254u8;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = mul(result, power);
+            }
+            power = mul(power, power);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Divide `a` by the non-zero field element `b`.
+    pub fn div(a: u8, b: u8) -> u8 {
+        mul(a, inv(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gf256_inverse() {
+        for a in 1..=255 {
+            assert_eq!(gf256::mul(a, gf256::inv(a)), 1, "{a}");
+        }
+    }
+
+    #[test]
+    fn test_split_recover() {
+        let seed = Seed::generate();
+        let shares = split(&seed, 3, 5).unwrap();
+
+        assert_eq!(shares.len(), 5);
+        assert_eq!(*recover(&shares[..3]).unwrap(), *seed);
+        assert_eq!(*recover(&shares[2..]).unwrap(), *seed);
+        assert_eq!(
+            *recover(&[shares[4].clone(), shares[0].clone(), shares[2].clone()]).unwrap(),
+            *seed
+        );
+        assert_eq!(*recover(&shares).unwrap(), *seed);
+    }
+
+    #[test]
+    fn test_recover_not_enough_shares() {
+        let seed = Seed::generate();
+        let shares = split(&seed, 3, 5).unwrap();
+
+        assert_eq!(
+            recover(&shares[..2]).unwrap_err(),
+            ShareError::NotEnoughShares {
+                threshold: 3,
+                count: 2
+            }
+        );
+        assert_eq!(
+            recover(&[shares[0].clone(), shares[0].clone(), shares[1].clone()]).unwrap_err(),
+            ShareError::DuplicateShare(shares[0].index)
+        );
+    }
+
+    #[test]
+    fn test_split_invalid_threshold() {
+        let seed = Seed::generate();
+
+        assert!(split(&seed, 0, 3).is_err());
+        assert!(split(&seed, 4, 3).is_err());
+    }
+
+    #[test]
+    fn test_share_encode_decode() {
+        let seed = Seed::generate();
+        let shares = split(&seed, 2, 3).unwrap();
+
+        for share in shares {
+            let encoded = share.to_string();
+            let decoded = Share::from_str(&encoded).unwrap();
+            assert_eq!(decoded, share);
+        }
+        assert!(Share::from_str("2-0-00").is_err());
+        assert!(Share::from_str("2-1-zz").is_err());
+    }
+}
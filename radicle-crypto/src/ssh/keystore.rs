@@ -97,6 +97,50 @@ impl Keystore {
         Ok(keypair.pk.into())
     }
 
+    /// Export the secret key in OpenSSH format, for moving it to another keystore.
+    ///
+    /// If the stored key is already encrypted, it is exported as-is. Otherwise, it is
+    /// encrypted with the given `passphrase`, which is then required.
+    pub fn export(&self, passphrase: Option<Passphrase>) -> Result<Zeroizing<String>, Error> {
+        let path = self.path.join("radicle");
+        let secret = ssh_key::PrivateKey::read_openssh_file(&path)?;
+        let secret = if secret.is_encrypted() {
+            secret
+        } else if let Some(p) = passphrase {
+            secret.encrypt(&mut ssh_key::rand_core::OsRng, p)?
+        } else {
+            return Err(Error::PassphraseMissing);
+        };
+
+        Ok(secret.to_openssh(ssh_key::LineEnding::default())?)
+    }
+
+    /// Import a secret key in OpenSSH format, as returned by [`Keystore::export`].
+    /// The key is stored as-is, ie. it stays encrypted if it was.
+    ///
+    /// Returns an error if a key already exists, unless `force` is set, in which case
+    /// it is overwritten.
+    pub fn import(&self, secret: &str, force: bool) -> Result<PublicKey, Error> {
+        let secret = ssh_key::PrivateKey::from_openssh(secret)?;
+        let public = secret.public_key();
+        let key = PublicKey::try_from(public.clone()).map_err(|_| Error::InvalidKeyType)?;
+        let path = self.path.join("radicle");
+
+        if path.exists() && !force {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&self.path)?;
+
+        secret.write_openssh_file(&path, ssh_key::LineEnding::default())?;
+        public.write_openssh_file(&path.with_extension("pub"))?;
+
+        Ok(key)
+    }
+
     /// Load the public key from the store. Returns `None` if it wasn't found.
     pub fn public_key(&self) -> Result<Option<PublicKey>, Error> {
         let path = self.path.join("radicle.pub");
@@ -314,6 +358,38 @@ mod tests {
         assert_eq!(PublicKey::from(secret.public_key()), public);
     }
 
+    #[test]
+    fn test_export_import() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Keystore::new(&tmp.path().join("a"));
+        let other = Keystore::new(&tmp.path().join("b"));
+        let public = store.init("test", None).unwrap();
+
+        assert!(matches!(store.export(None), Err(Error::PassphraseMissing)));
+
+        let exported = store.export(Some("hunter".to_owned().into())).unwrap();
+        assert_eq!(other.import(&exported, false).unwrap(), public);
+        assert_eq!(other.public_key().unwrap().unwrap(), public);
+        assert!(other.is_encrypted().unwrap());
+        assert!(other
+            .is_valid_passphrase(&"hunter".to_owned().into())
+            .unwrap());
+
+        // Refuse to overwrite an existing key, unless forced.
+        let third = Keystore::new(&tmp.path().join("c"));
+        let replacement = third
+            .init("test", Some("blunder".to_owned().into()))
+            .unwrap();
+        let exported = third.export(None).unwrap();
+
+        assert!(matches!(
+            other.import(&exported, false),
+            Err(Error::AlreadyInitialized)
+        ));
+        assert_eq!(other.import(&exported, true).unwrap(), replacement);
+        assert_eq!(other.public_key().unwrap().unwrap(), replacement);
+    }
+
     #[test]
     fn test_signer() {
         let tmp = tempfile::tempdir().unwrap();
@@ -47,6 +47,27 @@ impl<K, V> Deref for Node<K, V> {
     }
 }
 
+/// Error returned when a dependency would introduce a cycle in the graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CycleError<K> {
+    /// The node depending on the other.
+    pub from: K,
+    /// The node depended on.
+    pub to: K,
+}
+
+impl<K: fmt::Debug> fmt::Display for CycleError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "dependency from {:?} to {:?} would introduce a cycle",
+            self.from, self.to
+        )
+    }
+}
+
+impl<K: fmt::Debug> std::error::Error for CycleError<K> {}
+
 /// A directed acyclic graph.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Dag<K, V> {
@@ -100,6 +121,8 @@ impl<K: Ord + Copy, V> Dag<K, V> {
     }
 
     /// Add a dependency from one node to the other.
+    ///
+    /// The dependency is not checked for cycles; see [`Dag::try_dependency`].
     pub fn dependency(&mut self, from: K, to: K) {
         if let Some(node) = self.graph.get_mut(&from) {
             node.dependencies.insert(to);
@@ -111,6 +134,27 @@ impl<K: Ord + Copy, V> Dag<K, V> {
         }
     }
 
+    /// Add a dependency from one node to the other, unless `to` already depends on `from`,
+    /// directly or not, in which case the dependency would introduce a cycle. The graph is
+    /// left unchanged if the dependency is rejected.
+    ///
+    /// Since this has to walk the dependencies of `to`, prefer [`Dag::dependency`] when the
+    /// dependency is known to be safe.
+    pub fn try_dependency(&mut self, from: K, to: K) -> Result<(), CycleError<K>> {
+        let cycle = from == to
+            || self
+                .graph
+                .get(&to)
+                .is_some_and(|node| self.ancestors_of(node).contains(&from));
+
+        if cycle {
+            return Err(CycleError { from, to });
+        }
+        self.dependency(from, to);
+
+        Ok(())
+    }
+
     /// Check if the graph contains a node.
     pub fn contains(&self, key: &K) -> bool {
         self.graph.contains_key(key)
@@ -279,6 +323,42 @@ impl<K: Ord + Copy, V> Dag<K, V> {
         acc
     }
 
+    /// Walk the graph in topological order, starting from the given roots.
+    ///
+    /// Unlike [`Dag::fold`], the traversal order isn't computed upfront: a node is yielded as
+    /// soon as all of its dependencies have been, so only the frontier of the traversal is kept
+    /// in memory. Dependencies on ancestors of the roots are considered satisfied.
+    pub fn walk(&self, roots: &[K]) -> Walk<'_, K, V> {
+        Walk::new(self, roots)
+    }
+
+    /// Fold over the graph in topological order, skipping certain branches, like [`Dag::fold`].
+    /// This is a breadth-first traversal using [`Dag::walk`], and hence may visit nodes that
+    /// don't depend on each other in a different order than [`Dag::fold`].
+    ///
+    /// To continue traversing a branch, return [`ControlFlow::Continue`] from the
+    /// filter function. To stop traversal of a branch, return [`ControlFlow::Break`].
+    pub fn fold_walk<A, F>(&self, roots: &[K], mut acc: A, mut filter: F) -> A
+    where
+        F: for<'r> FnMut(A, &'r K, &'r Node<K, V>) -> ControlFlow<A, A>,
+    {
+        let mut walk = self.walk(roots);
+
+        while let Some((key, node)) = walk.next() {
+            match filter(acc, key, node) {
+                ControlFlow::Continue(a) => {
+                    acc = a;
+                }
+                ControlFlow::Break(a) => {
+                    walk.skip_dependents();
+
+                    acc = a;
+                }
+            }
+        }
+        acc
+    }
+
     /// Remove a node from the graph, and all its dependents.
     pub fn remove(&mut self, key: &K) -> Option<Node<K, V>> {
         if let Some(node) = self.graph.remove(key) {
@@ -371,6 +451,125 @@ impl<K: Ord + Copy, V> Dag<K, V> {
     }
 }
 
+/// A topological traversal of a graph, which only keeps the traversal frontier in memory.
+/// See [`Dag::walk`].
+pub struct Walk<'a, K, V> {
+    dag: &'a Dag<K, V>,
+    /// Nodes whose dependencies have all been visited, and whether they should be skipped.
+    ready: BTreeMap<K, bool>,
+    /// Nodes that were reached, with the number of dependencies left to visit,
+    /// and whether they should be skipped.
+    blocked: BTreeMap<K, (usize, bool)>,
+    /// Ancestors of the roots, which aren't visited.
+    ancestors: BTreeSet<K>,
+    /// The last node visited, and whether it was skipped. Its dependents are reached
+    /// on the next iteration.
+    last: Option<(K, bool)>,
+}
+
+impl<'a, K: Ord + Copy, V> Walk<'a, K, V> {
+    fn new(dag: &'a Dag<K, V>, roots: &[K]) -> Self {
+        let mut ancestors = BTreeSet::new();
+        for node in roots.iter().filter_map(|k| dag.graph.get(k)) {
+            ancestors.extend(dag.ancestors_of(node));
+        }
+        // Roots, and ancestors reachable from other roots are visited.
+        let mut stack = roots.to_vec();
+        while let Some(key) = stack.pop() {
+            ancestors.remove(&key);
+
+            if let Some(node) = dag.graph.get(&key) {
+                stack.extend(node.dependents.iter().filter(|k| ancestors.contains(k)));
+            }
+        }
+        let mut walk = Self {
+            dag,
+            ready: BTreeMap::new(),
+            blocked: BTreeMap::new(),
+            ancestors,
+            last: None,
+        };
+        for node in roots.iter().filter_map(|k| dag.graph.get(k)) {
+            walk.schedule(node.key, walk.unvisited(node), false);
+        }
+        walk
+    }
+
+    /// Stop traversing the branch of the last node visited. None of its descendants
+    /// will be visited.
+    pub fn skip_dependents(&mut self) {
+        if let Some((_, skip)) = &mut self.last {
+            *skip = true;
+        }
+    }
+
+    /// Number of nodes reached but not yet visited, ie. the working set of the traversal.
+    pub fn pending(&self) -> usize {
+        self.ready.len() + self.blocked.len()
+    }
+
+    /// Number of dependencies of a node that are yet to be visited, when it is first reached.
+    fn unvisited(&self, node: &Node<K, V>) -> usize {
+        node.dependencies
+            .iter()
+            .filter(|k| !self.ancestors.contains(k))
+            .count()
+    }
+
+    /// Schedule a node for visiting, once its unvisited dependencies have been visited.
+    fn schedule(&mut self, key: K, unvisited: usize, skip: bool) {
+        if unvisited == 0 {
+            self.ready.insert(key, skip);
+        } else {
+            self.blocked.insert(key, (unvisited, skip));
+        }
+    }
+
+    /// Reach a node through one of its dependencies, which was just visited.
+    fn reach(&mut self, key: &K, skip: bool) {
+        if let Some((n, s)) = self.blocked.get_mut(key) {
+            *n -= 1;
+            *s |= skip;
+
+            if *n == 0 {
+                let skip = *s;
+
+                self.blocked.remove(key);
+                self.ready.insert(*key, skip);
+            }
+        } else if let Some(node) = self.dag.graph.get(key) {
+            self.schedule(*key, self.unvisited(node).saturating_sub(1), skip);
+        }
+    }
+}
+
+impl<'a, K: Ord + Copy, V> Iterator for Walk<'a, K, V> {
+    type Item = (&'a K, &'a Node<K, V>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let dag = self.dag;
+
+        loop {
+            if let Some((key, skip)) = self.last.take() {
+                if let Some(node) = dag.graph.get(&key) {
+                    for dependent in &node.dependents {
+                        self.reach(dependent, skip);
+                    }
+                }
+            }
+            let (key, skip) = self.ready.pop_first()?;
+            self.last = Some((key, skip));
+
+            if skip {
+                continue;
+            }
+            if let Some(node) = dag.graph.get(&key) {
+                return Some((&node.key, node));
+            }
+        }
+    }
+}
+
 impl<K: Ord + Copy + fmt::Display, V> Dag<K, V> {
     /// Return the graph in "dot" format.
     pub fn to_dot(&self) -> String {
@@ -433,6 +632,38 @@ mod tests {
         assert!(!dag.has_dependency(&1, &0));
     }
 
+    #[test]
+    fn test_try_dependency() {
+        let mut dag = Dag::new();
+
+        dag.node(0, ());
+        dag.node(1, ());
+        dag.node(2, ());
+
+        assert_eq!(dag.try_dependency(1, 0), Ok(()));
+        assert_eq!(dag.try_dependency(2, 1), Ok(()));
+        assert_eq!(dag.try_dependency(2, 0), Ok(()));
+        assert!(dag.has_dependency(&2, &0));
+    }
+
+    #[test]
+    fn test_try_dependency_cycle() {
+        let mut dag = Dag::new();
+
+        dag.node(0, ());
+        dag.node(1, ());
+        dag.node(2, ());
+        dag.dependency(1, 0);
+        dag.dependency(2, 1);
+
+        let before = dag.clone();
+
+        assert_eq!(dag.try_dependency(0, 2), Err(CycleError { from: 0, to: 2 }));
+        assert_eq!(dag.try_dependency(0, 1), Err(CycleError { from: 0, to: 1 }));
+        assert_eq!(dag.try_dependency(0, 0), Err(CycleError { from: 0, to: 0 }));
+        assert_eq!(dag, before);
+    }
+
     #[test]
     fn test_get() {
         let mut dag = Dag::new();
@@ -760,6 +991,133 @@ mod tests {
         assert_eq!(acc, vec!["R", "A1", "B1", "C1"]);
     }
 
+    #[test]
+    fn test_walk_reject() {
+        let mut dag = Dag::new();
+
+        dag.node("R", ());
+        dag.node("A1", ());
+        dag.node("A2", ());
+        dag.node("B1", ());
+        dag.node("C1", ());
+        dag.node("D1", ());
+
+        dag.dependency("A1", "R");
+        dag.dependency("A2", "R");
+        dag.dependency("B1", "A1");
+        dag.dependency("C1", "B1");
+        dag.dependency("D1", "C1");
+        dag.dependency("D1", "A2");
+
+        let acc = dag.fold_walk(&["R"], Vec::new(), |mut acc, key, _| {
+            acc.push(*key);
+            ControlFlow::Continue(acc)
+        });
+        assert_eq!(acc, vec!["R", "A1", "A2", "B1", "C1", "D1"]);
+
+        let acc = dag.fold_walk(&["R"], Vec::new(), |mut acc, key, _| {
+            if *key == "A1" {
+                ControlFlow::Break(acc)
+            } else {
+                acc.push(*key);
+                ControlFlow::Continue(acc)
+            }
+        });
+        assert_eq!(acc, vec!["R", "A2"]);
+
+        let acc = dag.fold_walk(&["R"], Vec::new(), |mut acc, key, _| {
+            if *key == "A2" {
+                ControlFlow::Break(acc)
+            } else {
+                acc.push(*key);
+                ControlFlow::Continue(acc)
+            }
+        });
+        assert_eq!(acc, vec!["R", "A1", "B1", "C1"]);
+    }
+
+    #[test]
+    fn test_walk_multiple_roots() {
+        let mut dag = Dag::new();
+
+        dag.node("R", ());
+        dag.node("A1", ());
+        dag.node("A2", ());
+        dag.node("B1", ());
+
+        dag.dependency("A1", "R");
+        dag.dependency("A2", "R");
+        dag.dependency("B1", "A1");
+        dag.dependency("B1", "R");
+
+        let acc = dag.fold_walk(&["A1", "A2"], Vec::new(), |mut acc, key, _| {
+            acc.push(*key);
+            ControlFlow::Continue(acc)
+        });
+        assert_eq!(acc, &["A1", "A2", "B1"]);
+    }
+
+    #[test]
+    fn test_walk_large() {
+        const NODES: usize = 10_000;
+        const WIDTH: usize = 16;
+
+        let mut rng = fastrand::Rng::with_seed(42);
+        let mut dag = Dag::new();
+
+        // A long history where each node depends on a few of the nodes just before it.
+        dag.node(0, ());
+        for i in 1..NODES {
+            dag.node(i, ());
+
+            for _ in 0..rng.usize(1..=3) {
+                dag.dependency(i, rng.usize(i.saturating_sub(WIDTH)..i));
+            }
+        }
+        let collect = |mut acc: Vec<usize>, key: &usize, _: &Node<usize, ()>| {
+            acc.push(*key);
+            ControlFlow::Continue(acc)
+        };
+        let reject = |mut acc: Vec<usize>, key: &usize, _: &Node<usize, ()>| {
+            if key % 101 == 50 {
+                ControlFlow::Break(acc)
+            } else {
+                acc.push(*key);
+                ControlFlow::Continue(acc)
+            }
+        };
+        let sorted = |mut keys: Vec<usize>| {
+            keys.sort();
+            keys
+        };
+
+        // The traversals visit the same nodes.
+        let expected = dag.fold(&[0], Vec::new(), collect);
+        let actual = dag.fold_walk(&[0], Vec::new(), collect);
+        assert_eq!(actual.len(), NODES);
+        assert_eq!(sorted(actual.clone()), sorted(expected));
+
+        let expected = dag.fold(&[0], Vec::new(), reject);
+        let actual = dag.fold_walk(&[0], Vec::new(), reject);
+        assert!(actual.len() < NODES);
+        assert_eq!(sorted(actual), sorted(expected));
+
+        // The walk is in topological order, and its working set is bounded by the width
+        // of the graph, rather than its size.
+        let mut walk = dag.walk(&[0]);
+        let mut visited = BTreeSet::new();
+        let mut pending = 0;
+
+        while let Some((key, node)) = walk.next() {
+            assert!(node.dependencies.is_subset(&visited));
+            assert!(visited.insert(*key));
+
+            pending = pending.max(walk.pending());
+        }
+        assert_eq!(visited.len(), NODES);
+        assert!(pending <= WIDTH);
+    }
+
     #[test]
     fn test_remove() {
         let mut dag = Dag::new();
@@ -0,0 +1,285 @@
+//! Checkpoints for resuming an interrupted [`crate::clone`].
+//!
+//! While cloning, the data references of each namespace are fetched
+//! in batches of [`CHECKPOINT_INTERVAL`] namespaces. After each batch,
+//! a [`Checkpoint`] recording the `rad/sigrefs` of the namespaces
+//! whose objects are now in the Odb is written to the repository's
+//! Git directory.
+//!
+//! If the clone is interrupted, [`crate::resume_clone`] loads the
+//! checkpoint and only negotiates the data references of the
+//! namespaces that are missing from it. The checkpoint is discarded
+//! entirely if the canonical `rad/id` of the remote has changed, and
+//! a namespace is fetched again if its `rad/sigrefs` moved since the
+//! checkpoint was written.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{fmt, fs, io};
+
+use radicle::crypto::PublicKey;
+use radicle::git::Oid;
+use radicle::storage::refs::RefsAt;
+
+/// Name of the checkpoint file, relative to the Git directory.
+pub const CHECKPOINT_FILE: &str = "FETCH_CHECKPOINT";
+/// Number of namespaces fetched between two checkpoints.
+pub const CHECKPOINT_INTERVAL: usize = 8;
+
+pub mod error {
+    use std::io;
+
+    use thiserror::Error;
+
+    #[derive(Debug, Error)]
+    pub enum Checkpoint {
+        #[error(transparent)]
+        Io(#[from] io::Error),
+        #[error("malformed checkpoint on line {line}: {reason}")]
+        Malformed { line: usize, reason: String },
+    }
+}
+
+/// The progress of a clone from a single remote.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// The node that is being cloned from.
+    pub remote: PublicKey,
+    /// The canonical `rad/id` the clone started from.
+    pub rad_id: Oid,
+    /// The `rad/sigrefs` of each namespace whose data references
+    /// were fetched.
+    pub fetched: BTreeMap<PublicKey, Oid>,
+}
+
+impl Checkpoint {
+    /// Start a new, empty, checkpoint.
+    pub fn new(remote: PublicKey, rad_id: Oid) -> Self {
+        Self {
+            remote,
+            rad_id,
+            fetched: BTreeMap::new(),
+        }
+    }
+
+    /// Whether this checkpoint can be used to resume a clone from
+    /// `remote`, which is now advertising `rad_id`.
+    pub fn is_compatible(&self, remote: &PublicKey, rad_id: &Oid) -> bool {
+        self.remote == *remote && self.rad_id == *rad_id
+    }
+
+    /// Whether the data references of `refs_at` were already fetched.
+    pub fn is_fetched(&self, refs_at: &RefsAt) -> bool {
+        self.fetched.get(&refs_at.remote) == Some(&refs_at.at)
+    }
+
+    /// Record that the data references of `refs_at` were fetched.
+    pub fn record(&mut self, refs_at: RefsAt) {
+        self.fetched.insert(refs_at.remote, refs_at.at);
+    }
+
+    /// Only keep the namespaces whose `rad/sigrefs` is still
+    /// advertised at the same commit.
+    pub fn retain(&mut self, advertised: impl IntoIterator<Item = RefsAt>) {
+        let advertised = advertised
+            .into_iter()
+            .map(|r| (r.remote, r.at))
+            .collect::<BTreeMap<_, _>>();
+
+        self.fetched.retain(|remote, at| advertised.get(remote) == Some(at));
+    }
+
+    /// The namespaces recorded in this checkpoint.
+    pub fn refs_at(&self) -> impl Iterator<Item = RefsAt> + '_ {
+        self.fetched.iter().map(|(remote, at)| RefsAt {
+            remote: *remote,
+            at: *at,
+        })
+    }
+
+    /// The path of the checkpoint file in `git_dir`.
+    pub fn path(git_dir: &Path) -> PathBuf {
+        git_dir.join(CHECKPOINT_FILE)
+    }
+
+    /// Load the checkpoint stored in `git_dir`, if any.
+    pub fn load(git_dir: &Path) -> Result<Option<Self>, error::Checkpoint> {
+        match fs::read_to_string(Self::path(git_dir)) {
+            Ok(s) => s.parse().map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Store the checkpoint in `git_dir`, replacing any previous one.
+    pub fn save(&self, git_dir: &Path) -> io::Result<()> {
+        let path = Self::path(git_dir);
+        let tmp = path.with_extension("tmp");
+
+        // N.b. write to a temporary file first, so that an
+        // interruption never leaves a truncated checkpoint behind.
+        fs::write(&tmp, self.to_string())?;
+        fs::rename(tmp, path)
+    }
+
+    /// Remove the checkpoint stored in `git_dir`, if any.
+    pub fn remove(git_dir: &Path) -> io::Result<()> {
+        match fs::remove_file(Self::path(git_dir)) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+}
+
+impl fmt::Display for Checkpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "remote {}", self.remote)?;
+        writeln!(f, "rad/id {}", self.rad_id)?;
+
+        for (remote, at) in &self.fetched {
+            writeln!(f, "{remote} {at}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Checkpoint {
+    type Err = error::Checkpoint;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn field<'a>(
+            line: usize,
+            s: Option<&'a str>,
+            name: &str,
+        ) -> Result<&'a str, error::Checkpoint> {
+            s.and_then(|s| s.strip_prefix(name))
+                .and_then(|s| s.strip_prefix(' '))
+                .ok_or_else(|| error::Checkpoint::Malformed {
+                    line,
+                    reason: format!("expected `{name}`"),
+                })
+        }
+        fn parse<T: FromStr>(line: usize, s: &str) -> Result<T, error::Checkpoint>
+        where
+            T::Err: fmt::Display,
+        {
+            s.parse().map_err(|e: T::Err| error::Checkpoint::Malformed {
+                line,
+                reason: e.to_string(),
+            })
+        }
+
+        let mut lines = s.lines();
+        let remote = parse(1, field(1, lines.next(), "remote")?)?;
+        let rad_id = parse(2, field(2, lines.next(), "rad/id")?)?;
+        let mut checkpoint = Self::new(remote, rad_id);
+
+        for (ix, line) in lines.enumerate() {
+            let n = ix + 3;
+            let (remote, at) = line.split_once(' ').ok_or_else(|| error::Checkpoint::Malformed {
+                line: n,
+                reason: String::from("expected `<remote> <oid>`"),
+            })?;
+            checkpoint.record(RefsAt {
+                remote: parse(n, remote)?,
+                at: parse(n, at)?,
+            });
+        }
+        Ok(checkpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(s: &str) -> PublicKey {
+        s.parse().unwrap()
+    }
+
+    fn oid(s: &str) -> Oid {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_resume_after_interruption() {
+        let tmp = tempfile::tempdir().unwrap();
+        let remote = key("z6MknSLrJoTcukLrE435hVNQT4JUhbvWLX4kUzqkEStBU8Vi");
+        let alice = RefsAt {
+            remote: key("z6Mkt67GdsW7715MEfRuP4pSZxJRJh6kj6Y48WRqVv4N1tRk"),
+            at: oid("f2de534b5e81d7c6e2dcaf58c3dd91573c0a0354"),
+        };
+        let bob = RefsAt {
+            remote: key("z6Mkux1aUQD2voWWukVb5nNUR7thrHveQG4pDQua8nVhib7Z"),
+            at: oid("7a2ac4e14e8fdc1e6cc4d6ebc68ea7ae0fd3bd34"),
+        };
+        let rad_id = oid("0f2e6d4a32b4fb1e86cd2f1e6e5c8d3e0b1a2c3d");
+
+        // The clone is interrupted after the first batch, which only
+        // contained Alice's namespace.
+        let mut checkpoint = Checkpoint::new(remote, rad_id);
+        checkpoint.record(alice);
+        checkpoint.save(tmp.path()).unwrap();
+        drop(checkpoint);
+
+        let mut resumed = Checkpoint::load(tmp.path()).unwrap().unwrap();
+        assert!(resumed.is_compatible(&remote, &rad_id));
+
+        resumed.retain([alice, bob]);
+        assert!(resumed.is_fetched(&alice));
+        assert!(!resumed.is_fetched(&bob));
+
+        // Only Bob's namespace is left to fetch.
+        let pending = [alice, bob]
+            .into_iter()
+            .filter(|r| !resumed.is_fetched(r))
+            .collect::<Vec<_>>();
+        assert_eq!(pending, vec![bob]);
+
+        resumed.record(bob);
+        resumed.save(tmp.path()).unwrap();
+        assert_eq!(
+            Checkpoint::load(tmp.path())
+                .unwrap()
+                .unwrap()
+                .refs_at()
+                .collect::<Vec<_>>(),
+            vec![alice, bob]
+        );
+
+        Checkpoint::remove(tmp.path()).unwrap();
+        assert_eq!(Checkpoint::load(tmp.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalidated_checkpoint() {
+        let remote = key("z6MknSLrJoTcukLrE435hVNQT4JUhbvWLX4kUzqkEStBU8Vi");
+        let alice = RefsAt {
+            remote: key("z6Mkt67GdsW7715MEfRuP4pSZxJRJh6kj6Y48WRqVv4N1tRk"),
+            at: oid("f2de534b5e81d7c6e2dcaf58c3dd91573c0a0354"),
+        };
+        let rad_id = oid("0f2e6d4a32b4fb1e86cd2f1e6e5c8d3e0b1a2c3d");
+        let mut checkpoint = Checkpoint::new(remote, rad_id);
+        checkpoint.record(alice);
+
+        // The identity changed, so the checkpoint can't be used.
+        assert!(!checkpoint.is_compatible(&remote, &alice.at));
+
+        // Alice's `rad/sigrefs` moved, so her namespace is fetched again.
+        let moved = RefsAt {
+            at: oid("7a2ac4e14e8fdc1e6cc4d6ebc68ea7ae0fd3bd34"),
+            ..alice
+        };
+        checkpoint.retain([moved]);
+        assert!(!checkpoint.is_fetched(&alice));
+        assert!(!checkpoint.is_fetched(&moved));
+    }
+
+    #[test]
+    fn test_malformed_checkpoint() {
+        assert!(matches!(
+            "remote foo\n".parse::<Checkpoint>(),
+            Err(error::Checkpoint::Malformed { line: 1, .. })
+        ));
+    }
+}
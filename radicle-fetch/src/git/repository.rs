@@ -1,5 +1,7 @@
 pub mod error;
 
+use std::collections::HashSet;
+
 use either::Either;
 use radicle::git::{self, Namespaced, Oid, Qualified};
 use radicle::storage::git::Repository;
@@ -97,10 +99,17 @@ where
     }
 }
 
+/// Apply the given updates to the repository.
+///
+/// All reference changes are staged in a single Git transaction, which is
+/// only committed once every update has been processed. If any update fails,
+/// the transaction is dropped and the references are left as they were
+/// before the call.
 pub fn update<'a, I>(repo: &Repository, updates: I) -> Result<Applied<'a>, error::Update>
 where
     I: IntoIterator<Item = Update<'a>>,
 {
+    let mut tx = Transaction::new(repo)?;
     let mut applied = Applied::default();
     for up in updates.into_iter() {
         match up {
@@ -108,26 +117,92 @@ where
                 name,
                 target,
                 no_ff,
-            } => match direct(repo, name, target, no_ff)? {
+            } => match direct(repo, &mut tx, name, target, no_ff)? {
                 Updated::Rejected(r) => applied.rejected.push(r),
                 Updated::Accepted(u) => applied.updated.push(u),
             },
-            Update::Prune { name, prev } => match prune(repo, name, prev)? {
+            Update::Prune { name, prev } => match prune(repo, &mut tx, name, prev)? {
                 Updated::Rejected(r) => applied.rejected.push(r),
                 Updated::Accepted(u) => applied.updated.push(u),
             },
         }
     }
+    tx.commit()?;
 
     Ok(applied)
 }
 
+/// A set of reference changes that are applied atomically.
+struct Transaction<'r> {
+    inner: git::raw::Transaction<'r>,
+    locked: HashSet<String>,
+}
+
+impl<'r> Transaction<'r> {
+    fn new(repo: &'r Repository) -> Result<Self, error::Update> {
+        Ok(Self {
+            inner: repo
+                .backend
+                .transaction()
+                .map_err(error::Update::Transaction)?,
+            locked: HashSet::new(),
+        })
+    }
+
+    /// Lock a reference, so that it can't be modified outside of this
+    /// transaction. Locking an already locked reference is a no-op.
+    fn lock(&mut self, name: &Namespaced) -> Result<(), error::Update> {
+        if self.locked.insert(name.to_string()) {
+            self.inner
+                .lock_ref(name.as_ref())
+                .map_err(|err| error::Update::Lock {
+                    name: name.to_owned(),
+                    err,
+                })?;
+        }
+        Ok(())
+    }
+
+    fn set_target(
+        &mut self,
+        name: &Namespaced,
+        target: Oid,
+        message: &str,
+    ) -> Result<(), error::Update> {
+        self.lock(name)?;
+        self.inner
+            .set_target(name.as_ref(), target.into(), None, message)
+            .map_err(|err| error::Update::Create {
+                name: name.to_owned(),
+                target,
+                err,
+            })
+    }
+
+    fn remove(&mut self, name: &Namespaced) -> Result<(), error::Update> {
+        self.lock(name)?;
+        self.inner
+            .remove(name.as_ref())
+            .map_err(|err| error::Update::Delete {
+                name: name.to_owned(),
+                err,
+            })
+    }
+
+    fn commit(self) -> Result<(), error::Update> {
+        self.inner.commit().map_err(error::Update::Transaction)
+    }
+}
+
 fn direct<'a>(
     repo: &Repository,
+    tx: &mut Transaction,
     name: Namespaced<'a>,
     target: Oid,
     no_ff: Policy,
 ) -> Result<Updated<'a>, error::Update> {
+    tx.lock(&name)?;
+
     let tip = refname_to_id(repo, name.clone())?;
     match tip {
         Some(prev) => {
@@ -140,27 +215,13 @@ fn direct<'a>(
                 }
                 .into()),
                 Ancestry::Ahead => {
-                    // N.b. the update is a fast-forward so we can safely
-                    // pass `force: true`.
-                    repo.backend
-                        .reference(name.as_ref(), target.into(), true, "radicle: update")
-                        .map_err(|err| error::Update::Create {
-                            name: name.to_owned(),
-                            target,
-                            err,
-                        })?;
+                    tx.set_target(&name, target, "radicle: update")?;
                     Ok(RefUpdate::from(name.to_ref_string(), prev, target).into())
                 }
                 Ancestry::Behind | Ancestry::Diverged if matches!(no_ff, Policy::Allow) => {
                     // N.b. the update is a non-fast-forward but
-                    // we allow it, so we pass `force: true`.
-                    repo.backend
-                        .reference(name.as_ref(), target.into(), true, "radicle: update")
-                        .map_err(|err| error::Update::Create {
-                            name: name.to_owned(),
-                            target,
-                            err,
-                        })?;
+                    // we allow it.
+                    tx.set_target(&name, target, "radicle: update")?;
                     Ok(RefUpdate::from(name.to_ref_string(), prev, target).into())
                 }
                 // N.b. if the target is behind, we simply reject the update
@@ -186,15 +247,9 @@ fn direct<'a>(
             }
         }
         None => {
-            // N.b. the reference didn't exist so we pass `force:
-            // false`.
-            repo.backend
-                .reference(name.as_ref(), target.into(), false, "radicle: create")
-                .map_err(|err| error::Update::Create {
-                    name: name.to_owned(),
-                    target,
-                    err,
-                })?;
+            // N.b. the reference is locked, so it can't be created
+            // concurrently.
+            tx.set_target(&name, target, "radicle: create")?;
             Ok(RefUpdate::Created {
                 name: name.to_ref_string(),
                 oid: target,
@@ -206,13 +261,16 @@ fn direct<'a>(
 
 fn prune<'a>(
     repo: &Repository,
+    tx: &mut Transaction,
     name: Namespaced<'a>,
     prev: Either<Oid, Qualified<'a>>,
 ) -> Result<Updated<'a>, error::Update> {
     use radicle::git::raw::ObjectType;
 
+    tx.lock(&name)?;
+
     match find(repo, &name)? {
-        Some(mut r) => {
+        Some(r) => {
             // N.b. peel this reference to whatever object it points to,
             // presumably a commit, and get its Oid
             let prev = r
@@ -220,10 +278,7 @@ fn prune<'a>(
                 .map_err(error::Update::Peel)?
                 .id()
                 .into();
-            r.delete().map_err(|err| error::Update::Delete {
-                name: name.to_owned(),
-                err,
-            })?;
+            tx.remove(&name)?;
             Ok(RefUpdate::Deleted {
                 name: name.to_ref_string(),
                 oid: prev,
@@ -247,3 +302,87 @@ fn find<'a>(
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use radicle::git::{raw, refname, refs::storage::branch_of};
+    use radicle::identity::RepoId;
+    use radicle::prelude::PublicKey;
+
+    use super::*;
+
+    fn commit(repo: &raw::Repository, msg: &str, parent: Option<Oid>) -> Oid {
+        let sig = raw::Signature::new("anonymous", "anonymous@radicle.xyz", &raw::Time::new(0, 0))
+            .unwrap();
+        let tree = repo.treebuilder(None).unwrap().write().unwrap();
+        let tree = repo.find_tree(tree).unwrap();
+        let parent = parent.map(|oid| repo.find_commit(*oid).unwrap());
+        let parents = parent.iter().collect::<Vec<_>>();
+
+        repo.commit(None, &sig, &sig, msg, &tree, &parents)
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn test_update_is_atomic() {
+        let tmp = tempfile::tempdir().unwrap();
+        let backend = raw::Repository::init_bare(tmp.path()).unwrap();
+        let rid = RepoId::from_urn("rad:z42hL2jL4XNk6K8oHQaSWfMgCL7ji").unwrap();
+        let remote = PublicKey::from([1; 32]);
+        let base = commit(&backend, "base", None);
+        let ahead = commit(&backend, "ahead", Some(base));
+        let diverged = commit(&backend, "diverged", Some(base));
+
+        let master = branch_of(&remote, &refname!("master"));
+        let dev = branch_of(&remote, &refname!("dev"));
+        let feature = branch_of(&remote, &refname!("feature"));
+        let old = branch_of(&remote, &refname!("old"));
+
+        for name in [&master, &dev, &old] {
+            backend.reference(name.as_ref(), *base, false, "").unwrap();
+        }
+        let repo = Repository { id: rid, backend };
+        let refs = |repo: &Repository| {
+            [&master, &dev, &feature, &old].map(|name| refname_to_id(repo, name.clone()).unwrap())
+        };
+        let before = refs(&repo);
+        let updates = [
+            Update::Direct {
+                name: dev.clone(),
+                target: ahead,
+                no_ff: Policy::Abort,
+            },
+            Update::Direct {
+                name: feature.clone(),
+                target: ahead,
+                no_ff: Policy::Abort,
+            },
+            Update::Prune {
+                name: old.clone(),
+                prev: Either::Left(base),
+            },
+        ];
+
+        // Fail mid-way through applying the updates, with a non-fast-forward.
+        let err = update(
+            &repo,
+            updates.clone().into_iter().chain([Update::Direct {
+                name: master.clone(),
+                target: diverged,
+                no_ff: Policy::Abort,
+            }]),
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, error::Update::NonFF { .. }));
+        assert_eq!(refs(&repo), before, "No refs were updated");
+
+        // Without the failing update, everything is applied.
+        let applied = update(&repo, updates).unwrap();
+
+        assert_eq!(applied.updated.len(), 3);
+        assert!(applied.rejected.is_empty());
+        assert_eq!(refs(&repo), [Some(base), Some(ahead), Some(ahead), None]);
+    }
+}
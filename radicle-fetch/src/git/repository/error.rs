@@ -63,6 +63,12 @@ pub enum Update {
         #[source]
         err: raw::Error,
     },
+    #[error("failed to lock reference {name}")]
+    Lock {
+        name: Namespaced<'static>,
+        #[source]
+        err: raw::Error,
+    },
     #[error("failed to find ref {name}")]
     Find {
         name: Namespaced<'static>,
@@ -79,4 +85,6 @@ pub enum Update {
     Peel(#[source] raw::Error),
     #[error(transparent)]
     Resolve(#[from] Resolve),
+    #[error("failed to apply reference transaction")]
+    Transaction(#[source] raw::Error),
 }
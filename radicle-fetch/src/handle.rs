@@ -3,13 +3,13 @@ use std::sync::Arc;
 
 use bstr::BString;
 use radicle::crypto::{PublicKey, Verified};
-use radicle::git::Oid;
+use radicle::git::{Oid, RefStr};
 use radicle::identity::DocError;
 use radicle::prelude::Doc;
 use radicle::storage::git::Repository;
 use radicle::storage::ReadRepository;
 
-use crate::policy::{Allowed, BlockList};
+use crate::policy::{Allowed, BlockList, RefFilter};
 use crate::transport::{ConnectionStream, Transport};
 
 /// The handle used for pulling or cloning changes from a remote peer.
@@ -26,6 +26,9 @@ pub struct Handle<S> {
     /// key in [`crate::pull`], however, we choose to allow the local
     /// peer's key in [`crate::clone`].
     pub(crate) blocked: BlockList,
+    /// The categories of references that are replicated from
+    /// remotes.
+    pub(crate) filter: RefFilter,
     // Signals to the pack writer to interrupt the process
     pub(crate) interrupt: Arc<AtomicBool>,
 }
@@ -36,6 +39,7 @@ impl<S> Handle<S> {
         repo: Repository,
         follow: Allowed,
         blocked: BlockList,
+        filter: RefFilter,
         connection: S,
     ) -> Result<Self, error::Init>
     where
@@ -50,6 +54,7 @@ impl<S> Handle<S> {
             allowed: follow,
             transport,
             blocked,
+            filter,
             interrupt: Arc::new(AtomicBool::new(false)),
         })
     }
@@ -58,6 +63,10 @@ impl<S> Handle<S> {
         self.blocked.is_blocked(key)
     }
 
+    pub fn is_replicated(&self, refname: &RefStr) -> bool {
+        self.filter.is_replicated(refname)
+    }
+
     pub fn repository(&self) -> &Repository {
         &self.repo
     }
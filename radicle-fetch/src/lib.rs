@@ -1,3 +1,4 @@
+pub mod checkpoint;
 pub mod git;
 pub mod handle;
 pub mod policy;
@@ -14,8 +15,9 @@ use std::time::Instant;
 
 use gix_protocol::handshake;
 
+pub use checkpoint::Checkpoint;
 pub use handle::Handle;
-pub use policy::{Allowed, BlockList, Scope};
+pub use policy::{Allowed, BlockList, RefFilter, Scope};
 pub use state::{FetchLimit, FetchResult};
 pub use transport::Transport;
 
@@ -39,12 +41,32 @@ pub enum Error {
     },
     #[error(transparent)]
     Protocol(#[from] state::error::Protocol),
+    #[error("failed to load clone checkpoint")]
+    Checkpoint(#[source] checkpoint::error::Checkpoint),
     #[error("missing `rad/id`")]
     MissingRadId,
     #[error("attempted to replicate from self")]
     ReplicateSelf,
 }
 
+impl Error {
+    /// Whether the fetch was rejected because a namespace exceeded its storage quota.
+    pub fn is_quota_exceeded(&self) -> bool {
+        matches!(self, Self::Protocol(state::error::Protocol::Quota(e)) if e.is_exceeded())
+    }
+
+    /// Whether the fetch was rejected because the refs advertised by the remote
+    /// don't match their signed refs.
+    pub fn is_sigrefs_mismatch(&self) -> bool {
+        matches!(
+            self,
+            Self::Protocol(state::error::Protocol::Step(state::error::Step::Layout(
+                stage::error::Layout::Sigrefs(_)
+            )))
+        )
+    }
+}
+
 /// Pull changes from the `remote`.
 ///
 /// It is expected that the local peer has a copy of the repository
@@ -86,11 +108,46 @@ where
 ///
 /// It is expected that the local peer has an empty repository which
 /// they want to populate with the `remote`'s view of the project.
+///
+/// The progress of the clone is written to a [`Checkpoint`] in the
+/// repository, so that it can be continued with [`resume_clone`] if
+/// it's interrupted.
 pub fn clone<S>(
     handle: &mut Handle<S>,
     limit: FetchLimit,
     remote: PublicKey,
 ) -> Result<FetchResult, Error>
+where
+    S: transport::ConnectionStream,
+{
+    clone_from(handle, limit, remote, None)
+}
+
+/// Resume an interrupted [`clone`] from the `remote`.
+///
+/// The namespaces recorded in the repository's [`Checkpoint`] are not
+/// fetched again, unless their `rad/sigrefs` changed. If there is no
+/// checkpoint, or it is no longer valid, this is equivalent to
+/// [`clone`].
+pub fn resume_clone<S>(
+    handle: &mut Handle<S>,
+    limit: FetchLimit,
+    remote: PublicKey,
+) -> Result<FetchResult, Error>
+where
+    S: transport::ConnectionStream,
+{
+    let checkpoint = Checkpoint::load(handle.repo.backend.path()).map_err(Error::Checkpoint)?;
+
+    clone_from(handle, limit, remote, checkpoint)
+}
+
+fn clone_from<S>(
+    handle: &mut Handle<S>,
+    limit: FetchLimit,
+    remote: PublicKey,
+    checkpoint: Option<Checkpoint>,
+) -> Result<FetchResult, Error>
 where
     S: transport::ConnectionStream,
 {
@@ -99,7 +156,7 @@ where
         return Err(Error::ReplicateSelf);
     }
     let handshake = perform_handshake(handle)?;
-    let state = FetchState::default();
+    let state = FetchState::checkpointed(checkpoint);
     let result = state
         .run(handle, &handshake, limit, remote, None)
         .map_err(Error::Protocol);
@@ -107,11 +164,18 @@ where
     let rid = handle.repo.id();
 
     match &result {
-        Ok(_) => {
+        Ok(fetched) => {
             log::debug!(
                 target: "fetch",
                 "Finished clone of {rid} from {remote} ({elapsed}ms)",
             );
+            // N.b. a failed clone keeps its checkpoint, since it can
+            // be resumed once the remote's delegates are valid.
+            if fetched.is_success() {
+                if let Err(e) = Checkpoint::remove(handle.repo.backend.path()) {
+                    log::warn!(target: "fetch", "Failed to remove checkpoint of {rid}: {e}");
+                }
+            }
         }
         Err(e) => {
             log::debug!(
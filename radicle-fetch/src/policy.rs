@@ -1,10 +1,12 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 use radicle::crypto::PublicKey;
+use radicle::git::{Qualified, RefStr};
 use radicle::node::policy::config::Config;
 use radicle::node::policy::store::Read;
 use radicle::prelude::RepoId;
 
+pub use radicle::node::config::RefCategory;
 pub use radicle::node::policy::{Policy, Scope};
 
 #[derive(Clone, Debug)]
@@ -69,6 +71,40 @@ impl BlockList {
     }
 }
 
+/// A filter on the categories of references that are replicated.
+///
+/// Note that `refs/rad` references are always replicated, since they
+/// are required for verifying a remote.
+#[derive(Clone, Debug, Default)]
+pub struct RefFilter(BTreeSet<RefCategory>);
+
+impl FromIterator<RefCategory> for RefFilter {
+    fn from_iter<T: IntoIterator<Item = RefCategory>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl RefFilter {
+    /// A filter that replicates all references.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Exclude the `category` of references from replication.
+    pub fn exclude(mut self, category: RefCategory) -> Self {
+        self.0.insert(category);
+        self
+    }
+
+    /// Check whether `refname`, which is not namespaced, should be
+    /// replicated.
+    pub fn is_replicated(&self, refname: &RefStr) -> bool {
+        Qualified::from_refstr(refname)
+            .and_then(|q| RefCategory::of(&q))
+            .map_or(true, |category| !self.0.contains(&category))
+    }
+}
+
 pub mod error {
     use radicle::node::policy;
     use radicle::prelude::RepoId;
@@ -106,3 +142,30 @@ pub mod error {
         Refs(#[from] storage::refs::Error),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use radicle::cob::TypeName;
+    use radicle::git::refname;
+
+    use super::*;
+
+    #[test]
+    fn test_ref_filter() {
+        let issues = "xyz.radicle.issue".parse::<TypeName>().unwrap();
+        let filter = RefFilter::all().exclude(RefCategory::Cob(issues));
+
+        assert!(!filter.is_replicated(&refname!(
+            "refs/cobs/xyz.radicle.issue/d96f425412c9f8ad5d9a9a05c9831d0728e2338d"
+        )));
+        assert!(filter.is_replicated(&refname!(
+            "refs/cobs/xyz.radicle.patch/d96f425412c9f8ad5d9a9a05c9831d0728e2338d"
+        )));
+        assert!(filter.is_replicated(&refname!("refs/heads/master")));
+        assert!(filter.is_replicated(&refname!("refs/rad/id")));
+
+        let filter = filter.exclude(RefCategory::Heads);
+        assert!(!filter.is_replicated(&refname!("refs/heads/master")));
+        assert!(filter.is_replicated(&refname!("refs/rad/sigrefs")));
+    }
+}
@@ -3,7 +3,9 @@ use std::ops::{Deref, Not as _};
 
 pub use radicle::storage::refs::SignedRefsAt;
 pub use radicle::storage::{git::Validation, Validations};
+use radicle::git::{Oid, RefString};
 use radicle::{crypto::PublicKey, storage::ValidateRepository};
+use thiserror::Error;
 
 use crate::state::Cached;
 
@@ -21,6 +23,34 @@ pub mod error {
     }
 
     pub type Load = radicle::storage::refs::Error;
+
+    /// The refs advertised by a remote don't match its signed refs.
+    #[derive(Debug, Error)]
+    #[error(
+        "refs advertised for {remote} don't match its signed refs: {}",
+        .mismatches.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(", ")
+    )]
+    pub struct Verify {
+        /// The remote whose refs were advertised.
+        pub remote: PublicKey,
+        /// The mismatching refs, ordered by name.
+        pub mismatches: Vec<super::Mismatch>,
+    }
+}
+
+/// An advertised reference that doesn't match the signed refs of its remote.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Mismatch {
+    #[error("`{refname}` is advertised at {advertised}, but signed at {signed}")]
+    Tip {
+        refname: RefString,
+        signed: Oid,
+        advertised: Oid,
+    },
+    #[error("`{refname}` is advertised at {advertised}, but not signed")]
+    Unsigned { refname: RefString, advertised: Oid },
+    #[error("`{refname}` is signed at {signed}, but not advertised")]
+    Missing { refname: RefString, signed: Oid },
 }
 
 /// A data carrier that associates that data with whether a given
@@ -91,6 +121,55 @@ pub(crate) fn validate(
     Ok(validations.is_empty().not().then_some(validations))
 }
 
+/// Strictly verify the refs advertised for `remote` against its `signed` refs,
+/// reporting every ref that doesn't match.
+///
+/// References under `refs/rad` are ignored on both sides, since they are
+/// verified separately.
+pub(crate) fn verify<'a>(
+    remote: PublicKey,
+    signed: impl IntoIterator<Item = (&'a RefString, &'a Oid)>,
+    advertised: impl IntoIterator<Item = (RefString, Oid)>,
+) -> Result<(), error::Verify> {
+    let is_rad = |refname: &RefString| refname.as_str().starts_with("refs/rad/");
+    let signed = signed
+        .into_iter()
+        .filter(|(refname, _)| !is_rad(refname))
+        .map(|(refname, oid)| (refname.clone(), *oid))
+        .collect::<BTreeMap<_, _>>();
+    let advertised = advertised
+        .into_iter()
+        .filter(|(refname, _)| !is_rad(refname))
+        .collect::<BTreeMap<_, _>>();
+
+    let refnames = signed.keys().chain(advertised.keys()).collect::<BTreeSet<_>>();
+    let mismatches = refnames
+        .into_iter()
+        .filter_map(|refname| match (signed.get(refname), advertised.get(refname)) {
+            (Some(signed), Some(advertised)) if signed != advertised => Some(Mismatch::Tip {
+                refname: refname.clone(),
+                signed: *signed,
+                advertised: *advertised,
+            }),
+            (Some(signed), None) => Some(Mismatch::Missing {
+                refname: refname.clone(),
+                signed: *signed,
+            }),
+            (None, Some(advertised)) => Some(Mismatch::Unsigned {
+                refname: refname.clone(),
+                advertised: *advertised,
+            }),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(error::Verify { remote, mismatches })
+    }
+}
+
 /// The sigrefs found for each remote.
 ///
 /// Construct using [`RemoteRefs::load`].
@@ -128,6 +207,12 @@ impl Deref for RemoteRefs {
     }
 }
 
+impl FromIterator<(PublicKey, SignedRefsAt)> for RemoteRefs {
+    fn from_iter<T: IntoIterator<Item = (PublicKey, SignedRefsAt)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 impl<'a> IntoIterator for &'a RemoteRefs {
     type Item = <&'a BTreeMap<PublicKey, SignedRefsAt> as IntoIterator>::Item;
     type IntoIter = <&'a BTreeMap<PublicKey, SignedRefsAt> as IntoIterator>::IntoIter;
@@ -136,3 +221,77 @@ impl<'a> IntoIterator for &'a RemoteRefs {
         self.0.iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refname(s: &str) -> RefString {
+        RefString::try_from(s).unwrap()
+    }
+
+    fn oid(s: &str) -> Oid {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_verify_mismatch() {
+        let remote: PublicKey = "z6MknSLrJoTcukLrE435hVNQT4JUhbvWLX4kUzqkEStBU8Vi"
+            .parse()
+            .unwrap();
+        let master = oid("f2de534b5e81d7c6e2dcaf58c3dd91573c0a0354");
+        let dev = oid("7a2ac4e14e8fdc1e6cc4d6ebc68ea7ae0fd3bd34");
+        let forged = oid("0f2e6d4a32b4fb1e86cd2f1e6e5c8d3e0b1a2c3d");
+        let issue = oid("3e674d1a1df90807e934f9ae5da2591dd6848a33");
+        let signed = [
+            (refname("refs/heads/master"), master),
+            (refname("refs/heads/dev"), dev),
+            (refname("refs/cobs/xyz.radicle.issue/1"), issue),
+            (refname("refs/rad/id"), issue),
+        ]
+        .into_iter()
+        .collect::<BTreeMap<_, _>>();
+
+        // Advertising exactly the signed refs is fine, `rad` refs aside.
+        verify(
+            remote,
+            &signed,
+            signed
+                .iter()
+                .filter(|(r, _)| !r.as_str().starts_with("refs/rad/"))
+                .map(|(r, o)| (r.clone(), *o)),
+        )
+        .unwrap();
+
+        let advertised = [
+            (refname("refs/heads/master"), master),
+            (refname("refs/heads/dev"), forged),
+            (refname("refs/heads/evil"), forged),
+        ];
+        let err = verify(remote, &signed, advertised).unwrap_err();
+
+        assert_eq!(err.remote, remote);
+        assert_eq!(
+            err.mismatches,
+            vec![
+                Mismatch::Missing {
+                    refname: refname("refs/cobs/xyz.radicle.issue/1"),
+                    signed: issue,
+                },
+                Mismatch::Tip {
+                    refname: refname("refs/heads/dev"),
+                    signed: dev,
+                    advertised: forged,
+                },
+                Mismatch::Unsigned {
+                    refname: refname("refs/heads/evil"),
+                    advertised: forged,
+                },
+            ]
+        );
+        assert!(err.to_string().contains(
+            "`refs/heads/dev` is advertised at 0f2e6d4a32b4fb1e86cd2f1e6e5c8d3e0b1a2c3d, \
+             but signed at 7a2ac4e14e8fdc1e6cc4d6ebc68ea7ae0fd3bd34"
+        ));
+    }
+}
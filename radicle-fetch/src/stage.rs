@@ -17,8 +17,10 @@
 //!      scope is all.
 //!   3. [`DataRefs`]: fetches the `Oid`s for each reference listed in
 //!      the `rad/sigrefs` for each fetched peer in the
-//!      [`SpecialRefs`] stage. Additionally, any references that have
-//!      been removed from `rad/sigrefs` are marked for deletion.
+//!      [`SpecialRefs`] stage. The references advertised for each of
+//!      these peers must match their `rad/sigrefs`, otherwise the fetch
+//!      is rejected. Additionally, any references that have been removed
+//!      from `rad/sigrefs` are marked for deletion.
 //!
 //! ### Pull
 //!
@@ -34,9 +36,9 @@ use either::Either;
 use gix_protocol::handshake::Ref;
 use nonempty::NonEmpty;
 use radicle::crypto::PublicKey;
-use radicle::git::{refname, Component, Namespaced, Qualified};
+use radicle::git::{refname, Component, Namespaced, Oid, Qualified};
 use radicle::storage::git::Repository;
-use radicle::storage::refs::{RefsAt, Special};
+use radicle::storage::refs::{Refs, RefsAt, Special};
 use radicle::storage::ReadRepository;
 
 use crate::git::refs::{Policy, Update, Updates};
@@ -47,6 +49,13 @@ use crate::state::FetchState;
 use crate::transport::WantsHaves;
 use crate::{policy, refs};
 
+/// Maximum number of threads used to negotiate the wants and haves of
+/// the remotes in the [`DataRefs`] stage.
+pub const NEGOTIATION_CONCURRENCY: usize = 4;
+/// Minimum number of remotes negotiated by each thread. Below this,
+/// spawning a thread and opening the repository isn't worth it.
+const NEGOTIATION_BATCH: usize = 8;
+
 pub mod error {
     use radicle::crypto::PublicKey;
     use radicle::git::RefString;
@@ -56,6 +65,8 @@ pub mod error {
 
     #[derive(Debug, Error)]
     pub enum Layout {
+        #[error(transparent)]
+        Sigrefs(#[from] crate::sigrefs::error::Verify),
         #[error("missing required refs: {0:?}")]
         MissingRequiredRefs(Vec<String>),
         #[error("expected threshold of {threshold} of references, missing: {missing:?}")]
@@ -81,6 +92,8 @@ pub mod error {
     pub enum WantsHaves {
         #[error(transparent)]
         WantsHavesAdd(#[from] WantsHavesError),
+        #[error("failed to open repository for negotiation: {0}")]
+        Open(#[from] radicle::storage::RepositoryError),
         #[error("expected namespaced ref {0}")]
         NotNamespaced(RefString),
     }
@@ -395,6 +408,10 @@ impl ProtocolStage for SigrefsAt {
 /// `wants` will then be fetched from the server side to receive those
 /// particular objects.
 ///
+/// Before fetching, the refs advertised by the server for each remote
+/// are strictly verified against its sigrefs, and any mismatch is
+/// reported as an [`error::Layout::Sigrefs`] error.
+///
 /// Those refs and objects are then prepared for updating, removing
 /// any that were found to exist before the latest fetch.
 #[derive(Debug)]
@@ -406,22 +423,74 @@ pub struct DataRefs {
     pub remotes: sigrefs::RemoteRefs,
     /// The data limit for this stage of fetching.
     pub limit: u64,
+    /// The categories of references that are replicated.
+    pub filter: policy::RefFilter,
 }
 
 impl ProtocolStage for DataRefs {
-    // We don't need to ask for refs since we have all reference names
-    // and `Oid`s in `rad/sigrefs`.
+    // We have all reference names and `Oid`s in `rad/sigrefs`, but we
+    // ask for the refs of each remote to verify them against it.
     fn ls_refs(&self) -> Option<NonEmpty<BString>> {
-        None
+        NonEmpty::collect(
+            self.remotes
+                .keys()
+                .map(|remote| BString::from(format!("refs/namespaces/{remote}/refs/"))),
+        )
     }
 
-    // Since we don't ask for refs, we don't need to filter them.
-    fn ref_filter(&self, _: Ref) -> Option<ReceivedRef> {
-        None
+    fn ref_filter(&self, r: Ref) -> Option<ReceivedRef> {
+        let (refname, tip) = refs::unpack_ref(r).ok()?;
+        match refname {
+            ReceivedRefname::Namespaced { ref remote, .. } if self.remotes.contains_key(remote) => {
+                Some(ReceivedRef::new(tip, refname))
+            }
+            ReceivedRefname::Namespaced { .. } | ReceivedRefname::RadId => None,
+        }
     }
 
-    // Since we don't ask for refs, we don't need to validate them.
-    fn pre_validate(&self, _refs: &[ReceivedRef]) -> Result<(), error::Layout> {
+    // Ensure that the refs advertised for each remote are exactly the
+    // ones in its `rad/sigrefs`.
+    //
+    // N.b. if the advertised `rad/sigrefs` isn't the one we fetched, the
+    // remote was updated in the meantime, and we can't expect its refs to
+    // match.
+    fn pre_validate(&self, refs: &[ReceivedRef]) -> Result<(), error::Layout> {
+        let mut advertised = BTreeMap::<PublicKey, (Option<Oid>, Vec<_>)>::new();
+        for r in refs {
+            let ReceivedRefname::Namespaced { remote, suffix } = &r.name else {
+                continue;
+            };
+            let (at, refs) = advertised.entry(*remote).or_default();
+            match suffix {
+                Either::Left(Special::SignedRefs) => *at = Some(r.tip),
+                Either::Left(Special::Id) => {}
+                Either::Right(name) if self.filter.is_replicated(name) => {
+                    refs.push((name.to_ref_string(), r.tip));
+                }
+                Either::Right(_) => {}
+            }
+        }
+
+        for (remote, signed) in &self.remotes {
+            let Some((Some(at), refs)) = advertised.remove(remote) else {
+                continue;
+            };
+            if at != signed.at {
+                log::debug!(
+                    target: "fetch",
+                    "Skipping verification of {remote} refs, 'rad/sigrefs' moved to {at}"
+                );
+                continue;
+            }
+            sigrefs::verify(
+                *remote,
+                signed
+                    .refs
+                    .iter()
+                    .filter(|(name, _)| self.filter.is_replicated(name)),
+                refs,
+            )?;
+        }
         Ok(())
     }
 
@@ -432,20 +501,13 @@ impl ProtocolStage for DataRefs {
         refdb: &Repository,
         _refs: &[ReceivedRef],
     ) -> Result<WantsHaves, error::WantsHaves> {
-        let mut wants_haves = WantsHaves::default();
-
-        for (remote, loaded) in &self.remotes {
-            wants_haves.add(
-                refdb,
-                loaded.refs.iter().filter_map(|(refname, tip)| {
-                    let refname = Qualified::from_refstr(refname)
-                        .map(|refname| refname.with_namespace(Component::from(remote)))?;
-                    Some((refname, *tip))
-                }),
-            )?;
-        }
+        let remotes = self
+            .remotes
+            .iter()
+            .map(|(remote, loaded)| (*remote, &loaded.refs))
+            .collect::<Vec<_>>();
 
-        Ok(wants_haves)
+        negotiate(refdb, &remotes, &self.filter, NEGOTIATION_CONCURRENCY)
     }
 
     fn prepare_updates<'a>(
@@ -459,6 +521,9 @@ impl ProtocolStage for DataRefs {
         for (remote, refs) in &self.remotes {
             let mut signed = HashSet::with_capacity(refs.refs.len());
             for (name, tip) in refs.iter() {
+                if !self.filter.is_replicated(name) {
+                    continue;
+                }
                 let tracking: Namespaced<'_> = Qualified::from_refstr(name)
                     .and_then(|q| refs::ReceivedRefname::remote(*remote, q).to_namespaced())
                     .expect("we checked sigrefs well-formedness in wants_refs already");
@@ -501,6 +566,69 @@ impl ProtocolStage for DataRefs {
     }
 }
 
+/// Compute the wants and haves for the signed `refs` of each remote.
+///
+/// Since this only reads from the repository, the remotes are split
+/// between up to `concurrency` threads, each with its own handle on
+/// the repository. The result is the same as negotiating each remote
+/// serially. If negotiation fails for any remote, the first error, in
+/// order of remotes, is returned and nothing is negotiated.
+fn negotiate(
+    repo: &Repository,
+    remotes: &[(PublicKey, &Refs)],
+    filter: &policy::RefFilter,
+    concurrency: usize,
+) -> Result<WantsHaves, error::WantsHaves> {
+    let batch = |repo: &Repository, remotes: &[(PublicKey, &Refs)]| {
+        let mut wants_haves = WantsHaves::default();
+
+        for (remote, refs) in remotes {
+            wants_haves.add(
+                repo,
+                refs.iter().filter_map(|(refname, tip)| {
+                    if !filter.is_replicated(refname) {
+                        return None;
+                    }
+                    let refname = Qualified::from_refstr(refname)
+                        .map(|refname| refname.with_namespace(Component::from(remote)))?;
+                    Some((refname, *tip))
+                }),
+            )?;
+        }
+        Ok::<_, error::WantsHaves>(wants_haves)
+    };
+    let workers = concurrency.min(remotes.len().div_ceil(NEGOTIATION_BATCH));
+    if workers <= 1 {
+        return batch(repo, remotes);
+    }
+    let (path, rid) = (repo.backend.path(), repo.id);
+    let chunk = remotes.len().div_ceil(workers);
+    let batch = &batch;
+
+    let results = std::thread::scope(|scope| {
+        let handles = remotes
+            .chunks(chunk)
+            .map(|remotes| {
+                scope.spawn(move || {
+                    let repo = Repository::open(path, rid)?;
+                    batch(&repo, remotes)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect::<Vec<_>>()
+    });
+
+    let mut wants_haves = WantsHaves::default();
+    for result in results {
+        wants_haves.merge(result?);
+    }
+    Ok(wants_haves)
+}
+
 // N.b. the `delegates` are the delegates of the repository, with the
 // potential removal of the local peer in the case of a `pull`.
 fn special_refs_updates<'a>(
@@ -607,3 +735,83 @@ where
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use radicle::git::{raw, RefString};
+    use radicle::identity::RepoId;
+
+    use super::*;
+
+    fn commit(repo: &raw::Repository, msg: &str, parent: Option<&raw::Commit>) -> Oid {
+        let sig = raw::Signature::new("anonymous", "anonymous@radicle.xyz", &raw::Time::new(0, 0))
+            .unwrap();
+        let tree = repo.treebuilder(None).unwrap().write().unwrap();
+        let tree = repo.find_tree(tree).unwrap();
+        let parents = parent.into_iter().collect::<Vec<_>>();
+
+        repo.commit(None, &sig, &sig, msg, &tree, &parents)
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn test_negotiate_parallel() {
+        let tmp = tempfile::tempdir().unwrap();
+        let backend = raw::Repository::init_bare(tmp.path()).unwrap();
+        let rid = RepoId::from_urn("rad:z42hL2jL4XNk6K8oHQaSWfMgCL7ji").unwrap();
+        let master = RefString::try_from("refs/heads/master").unwrap();
+        let dev = RefString::try_from("refs/heads/dev").unwrap();
+
+        // Each remote has a `master` branch that's either up to date,
+        // behind or missing locally, and a `dev` branch whose tip we
+        // don't have.
+        let remotes = (0..25u8)
+            .map(|i| {
+                let remote = PublicKey::from([i; 32]);
+                let base = commit(&backend, &format!("base {i}"), None);
+                let head = commit(
+                    &backend,
+                    &format!("head {i}"),
+                    Some(&backend.find_commit(*base).unwrap()),
+                );
+                let local = match i % 3 {
+                    0 => Some(head),
+                    1 => Some(base),
+                    _ => None,
+                };
+                if let Some(local) = local {
+                    backend
+                        .reference(
+                            &format!("refs/namespaces/{remote}/{master}"),
+                            *local,
+                            false,
+                            "",
+                        )
+                        .unwrap();
+                }
+                let missing = Oid::from_str(&format!("{:0>40}", format!("{i:x}"))).unwrap();
+                let refs = Refs::from(BTreeMap::from_iter([
+                    (master.clone(), head),
+                    (dev.clone(), missing),
+                ]));
+                (remote, refs)
+            })
+            .collect::<Vec<_>>();
+        let remotes = remotes
+            .iter()
+            .map(|(remote, refs)| (*remote, refs))
+            .collect::<Vec<_>>();
+        let repo = Repository { id: rid, backend };
+        let filter = policy::RefFilter::all();
+
+        let serial = negotiate(&repo, &remotes, &filter, 1).unwrap();
+        let parallel = negotiate(&repo, &remotes, &filter, NEGOTIATION_CONCURRENCY).unwrap();
+
+        assert_eq!(serial, parallel);
+        assert_eq!(serial.wants.len(), 25);
+        assert_eq!(serial.haves.len(), 17);
+    }
+}
@@ -8,11 +8,13 @@ use radicle::identity::{Did, Doc, DocError};
 
 use radicle::prelude::Verified;
 use radicle::storage;
+use radicle::storage::quota::Quota;
 use radicle::storage::refs::{RefsAt, SignedRefs};
 use radicle::storage::{
     git::Validation, Remote, RemoteId, RemoteRepository, Remotes, ValidateRepository, Validations,
 };
 
+use crate::checkpoint::{Checkpoint, CHECKPOINT_INTERVAL};
 use crate::git;
 use crate::git::refs::{Applied, Update};
 use crate::git::repository;
@@ -55,6 +57,8 @@ pub mod error {
         Ancestry(#[from] repository::error::Ancestry),
         #[error(transparent)]
         Canonical(#[from] Canonical),
+        #[error("failed to write clone checkpoint")]
+        Checkpoint(#[source] io::Error),
         #[error("delegate '{remote}' has diverged 'rad/sigrefs': {current} -> {received}")]
         Diverged {
             remote: PublicKey,
@@ -70,6 +74,8 @@ pub mod error {
         #[error(transparent)]
         Resolve(#[from] repository::error::Resolve),
         #[error(transparent)]
+        Quota(#[from] radicle::storage::quota::Error),
+        #[error(transparent)]
         Refs(#[from] radicle::storage::refs::Error),
         #[error(transparent)]
         RemoteRefs(#[from] sigrefs::error::RemoteRefs),
@@ -99,6 +105,8 @@ type SigrefTips = BTreeMap<PublicKey, Oid>;
 pub struct FetchLimit {
     pub special: u64,
     pub refs: u64,
+    /// Storage quota of each fetched namespace.
+    pub quota: Quota,
 }
 
 impl Default for FetchLimit {
@@ -106,6 +114,7 @@ impl Default for FetchLimit {
         Self {
             special: DEFAULT_FETCH_SPECIAL_REFS_LIMIT,
             refs: DEFAULT_FETCH_DATA_REFS_LIMIT,
+            quota: Quota::default(),
         }
     }
 }
@@ -146,6 +155,17 @@ impl FetchResult {
     }
 }
 
+/// Whether the [`stage::DataRefs`] stage writes [`Checkpoint`]s.
+#[derive(Default)]
+enum Checkpointing {
+    #[default]
+    Disabled,
+    Enabled {
+        /// The checkpoint of a previous, interrupted, clone.
+        resume: Option<Checkpoint>,
+    },
+}
+
 #[derive(Default)]
 pub struct FetchState {
     /// In-memory refdb used to keep track of new updates without
@@ -160,9 +180,20 @@ pub struct FetchState {
     sigrefs: SigrefTips,
     /// Seen reference tips, per remote.
     tips: BTreeMap<PublicKey, Vec<Update<'static>>>,
+    /// Checkpointing of the data refs, only used when cloning.
+    checkpointing: Checkpointing,
 }
 
 impl FetchState {
+    /// Construct a `FetchState` which checkpoints its progress while
+    /// fetching data refs, resuming from `resume` if given.
+    pub fn checkpointed(resume: Option<Checkpoint>) -> Self {
+        Self {
+            checkpointing: Checkpointing::Enabled { resume },
+            ..Self::default()
+        }
+    }
+
     /// Remove all tips associated with this `remote` in the
     /// `FetchState`.
     pub fn prune(&mut self, remote: &PublicKey) {
@@ -332,6 +363,93 @@ impl FetchState {
         }
     }
 
+    /// Fetch the data refs in batches of [`CHECKPOINT_INTERVAL`]
+    /// namespaces, writing a [`Checkpoint`] after each batch.
+    ///
+    /// Namespaces found in the `resume` checkpoint with the same
+    /// `rad/sigrefs` are not fetched again, since their objects are
+    /// already in the Odb, and only their updates are prepared.
+    fn run_data_refs_checkpointed<S>(
+        &mut self,
+        handle: &mut Handle<S>,
+        handshake: &handshake::Outcome,
+        data_refs: &stage::DataRefs,
+        resume: Option<Checkpoint>,
+    ) -> Result<(), error::Protocol>
+    where
+        S: transport::ConnectionStream,
+    {
+        let rad_id = self.canonical_rad_id.ok_or(error::Protocol::MissingRadId)?;
+        let git_dir = handle.repo.backend.path().to_path_buf();
+        let advertised = data_refs
+            .remotes
+            .iter()
+            .map(|(remote, sigrefs)| RefsAt {
+                remote: *remote,
+                at: sigrefs.at,
+            })
+            .collect::<Vec<_>>();
+
+        let mut checkpoint = match resume {
+            Some(mut checkpoint) if checkpoint.is_compatible(&data_refs.remote, &rad_id) => {
+                checkpoint.retain(advertised.iter().copied());
+                checkpoint
+            }
+            Some(_) => {
+                log::debug!(target: "fetch", "Discarding incompatible clone checkpoint");
+                Checkpoint::new(data_refs.remote, rad_id)
+            }
+            None => Checkpoint::new(data_refs.remote, rad_id),
+        };
+        let (fetched, pending): (Vec<_>, Vec<_>) = advertised
+            .into_iter()
+            .partition(|refs_at| checkpoint.is_fetched(refs_at));
+        let remotes = |refs: Vec<RefsAt>| {
+            refs.into_iter()
+                .filter_map(|r| {
+                    data_refs
+                        .remotes
+                        .get(&r.remote)
+                        .map(|sigrefs| (r.remote, sigrefs.clone()))
+                })
+                .collect::<sigrefs::RemoteRefs>()
+        };
+        log::debug!(
+            target: "fetch",
+            "Clone checkpoint has {} of {} namespace(s) fetched",
+            fetched.len(),
+            data_refs.remotes.len()
+        );
+
+        let done = stage::DataRefs {
+            remote: data_refs.remote,
+            remotes: remotes(fetched),
+            limit: data_refs.limit,
+            filter: data_refs.filter.clone(),
+        };
+        let up = done.prepare_updates(self, &handle.repo, &[]).map_err(error::Step::from)?;
+        self.update_all(up.tips);
+
+        for batch in pending.chunks(CHECKPOINT_INTERVAL) {
+            let batch = stage::DataRefs {
+                remote: data_refs.remote,
+                remotes: remotes(batch.to_vec()),
+                limit: data_refs.limit,
+                filter: data_refs.filter.clone(),
+            };
+            self.run_stage(handle, handshake, &batch)?;
+
+            for (remote, sigrefs) in &batch.remotes {
+                checkpoint.record(RefsAt {
+                    remote: *remote,
+                    at: sigrefs.at,
+                });
+            }
+            checkpoint.save(&git_dir).map_err(error::Protocol::Checkpoint)?;
+        }
+        Ok(())
+    }
+
     /// The finalization of the protocol exchange is as follows:
     ///
     ///   1. Load the canonical `rad/id` to use as the anchor for
@@ -421,8 +539,16 @@ impl FetchState {
             remote,
             remotes: signed_refs,
             limit: limit.refs,
+            filter: handle.filter.clone(),
         };
-        self.run_stage(handle, handshake, &data_refs)?;
+        match std::mem::take(&mut self.checkpointing) {
+            Checkpointing::Disabled => {
+                self.run_stage(handle, handshake, &data_refs)?;
+            }
+            Checkpointing::Enabled { resume } => {
+                self.run_data_refs_checkpointed(handle, handshake, &data_refs, resume)?;
+            }
+        }
         log::debug!(
             target: "fetch",
             "Fetched data refs for {} remotes ({}ms)",
@@ -507,6 +633,8 @@ impl FetchState {
                         }
                     }
 
+                    limit.quota.check(&handle.repo, &remote, &sigrefs.sigrefs)?;
+
                     let cache = self.as_cached(handle);
                     if let Some(warns) = sigrefs::validate(&cache, sigrefs)?.as_mut() {
                         log::debug!(
@@ -539,6 +667,8 @@ impl FetchState {
                         }
                     }
 
+                    limit.quota.check(&handle.repo, &remote, &sigrefs.sigrefs)?;
+
                     let cache = self.as_cached(handle);
                     let mut fails = Validations::default();
                     // N.b. we only validate the existence of the
@@ -681,8 +811,10 @@ impl<'a, S> ValidateRepository for Cached<'a, S> {
     // not have a reference to the COB if they have not interacted
     // with it.
     fn validate_remote(&self, remote: &Remote) -> Result<Validations, storage::Error> {
-        // Contains a copy of the signed refs of this remote.
+        // Contains a copy of the signed refs of this remote, without
+        // the refs that are excluded from replication.
         let mut signed = BTreeMap::from((*remote.refs).clone());
+        signed.retain(|refname, _| self.handle.is_replicated(refname));
         let mut validations = Validations::default();
         let mut has_sigrefs = false;
 
@@ -281,7 +281,7 @@ pub enum WantsHavesError {
     Resolve(#[from] repository::error::Resolve),
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub(crate) struct WantsHaves {
     pub wants: BTreeSet<Oid>,
     pub haves: BTreeSet<Oid>,
@@ -301,6 +301,19 @@ impl WantsHaves {
         self.haves.insert(oid);
     }
 
+    /// Merge the `wants` and `haves` of `other` into these.
+    ///
+    /// N.b. the result doesn't depend on the order in which sets are
+    /// merged, since anything we have is never wanted.
+    pub fn merge(&mut self, other: WantsHaves) {
+        for oid in other.haves {
+            self.have(oid);
+        }
+        for oid in other.wants {
+            self.want(oid);
+        }
+    }
+
     /// Add a set of references to the `wants` and `haves`.
     ///
     /// For each reference we want to build the range between its
@@ -17,17 +17,21 @@ use serde_json::json;
 use tokio::sync::RwLock;
 use tower_http::cors::{self, CorsLayer};
 
+use radicle::cob::common::{Label, Timestamp};
 use radicle::cob::issue;
 use radicle::cob::patch;
+use radicle::cob::query::Query;
 use radicle::identity::{DocAt, RepoId};
 use radicle::node::policy::Scope;
 use radicle::node::routing::Store;
 use radicle::node::{Handle, NodeId};
+use radicle::prelude::Did;
 use radicle::storage::{ReadRepository, ReadStorage};
 use radicle::{Node, Profile};
 
 mod error;
 mod json;
+mod openapi;
 mod v1;
 
 use crate::api::error::Error;
@@ -108,6 +112,7 @@ impl Context {
 pub fn router(ctx: Context) -> Router {
     Router::new()
         .route("/", get(root_handler))
+        .route("/openapi.json", get(openapi::openapi_handler))
         .merge(v1::router(ctx))
         .layer(
             CorsLayer::new()
@@ -132,6 +137,11 @@ async fn root_handler() -> impl IntoResponse {
                 "href": "/v1",
                 "rel": "v1",
                 "type": "GET"
+            },
+            {
+                "href": "/openapi.json",
+                "rel": "openapi",
+                "type": "GET"
             }
         ]
     });
@@ -168,6 +178,39 @@ pub struct CobsQuery<T> {
     pub page: Option<usize>,
     pub per_page: Option<usize>,
     pub state: Option<T>,
+    pub author: Option<Did>,
+    pub label: Option<Label>,
+    pub assignee: Option<Did>,
+    /// Unix timestamp, in seconds.
+    pub since: Option<u64>,
+    /// Unix timestamp, in seconds.
+    pub until: Option<u64>,
+    pub text: Option<String>,
+}
+
+impl<T> CobsQuery<T> {
+    /// Add the filters given in the query string, other than the state, to a COB query.
+    pub fn filters<S: PartialEq>(&self, mut query: Query<S>) -> Query<S> {
+        if let Some(author) = self.author {
+            query = query.author(author);
+        }
+        if let Some(label) = &self.label {
+            query = query.label(label.clone());
+        }
+        if let Some(assignee) = self.assignee {
+            query = query.assignee(assignee);
+        }
+        if let Some(since) = self.since {
+            query = query.since(Timestamp::from_secs(since));
+        }
+        if let Some(until) = self.until {
+            query = query.until(Timestamp::from_secs(until));
+        }
+        if let Some(text) = &self.text {
+            query = query.text(text);
+        }
+        query
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -187,10 +230,23 @@ pub enum IssueState {
 }
 
 impl IssueState {
-    pub fn matches(&self, issue: &issue::State) -> bool {
+    pub fn query(&self) -> Query<issue::State> {
         match self {
-            Self::Open => matches!(issue, issue::State::Open),
-            Self::Closed => matches!(issue, issue::State::Closed { .. }),
+            Self::Open => Query::new().state(issue::State::Open),
+            Self::Closed => Query::new().states([
+                issue::State::Closed {
+                    reason: issue::CloseReason::Other,
+                },
+                issue::State::Closed {
+                    reason: issue::CloseReason::Solved,
+                },
+                issue::State::Closed {
+                    reason: issue::CloseReason::WontFix,
+                },
+                issue::State::Closed {
+                    reason: issue::CloseReason::Duplicate,
+                },
+            ]),
         }
     }
 }
@@ -206,13 +262,14 @@ pub enum PatchState {
 }
 
 impl PatchState {
-    pub fn matches(&self, patch: &patch::State) -> bool {
-        match self {
-            Self::Open => matches!(patch, patch::State::Open { .. }),
-            Self::Draft => matches!(patch, patch::State::Draft),
-            Self::Archived => matches!(patch, patch::State::Archived),
-            Self::Merged => matches!(patch, patch::State::Merged { .. }),
-        }
+    pub fn query(&self) -> Query<patch::Status> {
+        let status = match self {
+            Self::Open => patch::Status::Open,
+            Self::Draft => patch::Status::Draft,
+            Self::Archived => patch::Status::Archived,
+            Self::Merged => patch::Status::Merged,
+        };
+        Query::new().state(status)
     }
 }
 
@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+
 use serde::{Deserialize, Serialize};
 use time::serde::timestamp;
 use time::{Duration, OffsetDateTime};
@@ -18,6 +20,35 @@ pub enum AuthState {
     Unauthorized,
 }
 
+/// An action that a session token may be granted.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Scope {
+    /// Read access. Reading is always allowed, with or without a token.
+    #[serde(rename = "read")]
+    Read,
+    /// Create and update issues.
+    #[serde(rename = "issues:write")]
+    IssuesWrite,
+    /// Create and update patches.
+    #[serde(rename = "patches:write")]
+    PatchesWrite,
+    /// Change the seeding policies of the node.
+    #[serde(rename = "node:write")]
+    NodeWrite,
+}
+
+impl Scope {
+    /// All the scopes. Sessions that don't request specific scopes are granted these.
+    pub fn all() -> BTreeSet<Scope> {
+        BTreeSet::from([
+            Scope::Read,
+            Scope::IssuesWrite,
+            Scope::PatchesWrite,
+            Scope::NodeWrite,
+        ])
+    }
+}
+
 #[derive(Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Session {
@@ -28,9 +59,12 @@ pub struct Session {
     pub issued_at: OffsetDateTime,
     #[serde(with = "timestamp")]
     pub expires_at: OffsetDateTime,
+    #[serde(default = "Scope::all")]
+    pub scopes: BTreeSet<Scope>,
 }
 
-pub async fn validate(ctx: &Context, token: &str) -> Result<(), Error> {
+/// Check that a session token is authorized, and was granted the given scope.
+pub async fn validate(ctx: &Context, token: &str, scope: Scope) -> Result<(), Error> {
     let sessions_store = ctx.sessions.read().await;
     let session = sessions_store
         .get(token)
@@ -39,6 +73,9 @@ pub async fn validate(ctx: &Context, token: &str) -> Result<(), Error> {
     if session.status != AuthState::Authorized || session.expires_at <= OffsetDateTime::now_utc() {
         return Err(Error::Auth("Unauthorized"));
     }
+    if !session.scopes.contains(&scope) {
+        return Err(Error::Forbidden("Insufficient scope"));
+    }
 
     Ok(())
 }
@@ -14,6 +14,10 @@ pub enum Error {
     #[error("could not authenticate: {0}")]
     Auth(&'static str),
 
+    /// The session is not allowed to perform an action.
+    #[error("forbidden: {0}")]
+    Forbidden(&'static str),
+
     /// An error occurred with env variables.
     #[error(transparent)]
     Env(#[from] std::env::VarError),
@@ -54,6 +58,10 @@ pub enum Error {
     #[error(transparent)]
     CobStore(#[from] radicle::cob::store::Error),
 
+    /// Cob operation decoding error.
+    #[error(transparent)]
+    CobOp(#[from] radicle::cob::op::OpEncodingError),
+
     /// Repository error.
     #[error(transparent)]
     Repository(#[from] radicle::storage::RepositoryError),
@@ -112,6 +120,7 @@ impl IntoResponse for Error {
                 (StatusCode::NOT_FOUND, Some(e.to_string()))
             }
             Error::Auth(msg) => (StatusCode::UNAUTHORIZED, Some(msg.to_string())),
+            Error::Forbidden(msg) => (StatusCode::FORBIDDEN, Some(msg.to_string())),
             Error::Crypto(msg) => (StatusCode::BAD_REQUEST, Some(msg.to_string())),
             Error::Surf(radicle_surf::Error::Git(e)) if radicle::git::is_not_found_err(&e) => {
                 (StatusCode::NOT_FOUND, Some(e.message().to_owned()))
@@ -1,20 +1,21 @@
 //! Utilities for building JSON responses of our API.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 use std::str;
 
 use base64::prelude::{Engine, BASE64_STANDARD};
 use radicle::cob::{CodeLocation, Reaction};
 use radicle::patch::ReviewId;
+use serde::Serialize;
 use serde_json::{json, Value};
 
 use radicle::cob::issue::{Issue, IssueId};
 use radicle::cob::patch::{Merge, Patch, PatchId, Review};
 use radicle::cob::thread::{Comment, CommentId, Edit};
-use radicle::cob::{ActorId, Author};
+use radicle::cob::{ActorId, Author, EntryId, Op};
 use radicle::git::RefString;
-use radicle::node::{Alias, AliasStore};
+use radicle::node::AliasStore;
 use radicle::prelude::NodeId;
 use radicle::storage::{git, refs, RemoteRepository};
 use radicle_surf::blob::Blob;
@@ -50,7 +51,8 @@ pub(crate) fn session(session_id: String, session: &Session) -> Value {
       "publicKey": session.public_key,
       "alias": session.alias,
       "issuedAt": session.issued_at.unix_timestamp(),
-      "expiresAt": session.expires_at.unix_timestamp()
+      "expiresAt": session.expires_at.unix_timestamp(),
+      "scopes": session.scopes,
     })
 }
 
@@ -106,14 +108,16 @@ pub(crate) fn tree(tree: &Tree, path: &str, stats: &Stats) -> Value {
 pub(crate) fn issue(id: IssueId, issue: Issue, aliases: &impl AliasStore) -> Value {
     json!({
         "id": id.to_string(),
-        "author": author(&issue.author(), aliases.alias(issue.author().id())),
+        "author": author(&issue.author(), aliases.unique_alias(issue.author().id())),
         "title": issue.title(),
         "state": issue.state(),
         "assignees": issue.assignees().map(|assignee|
-            author(&Author::from(*assignee.as_key()), aliases.alias(assignee))
+            author(&Author::from(*assignee.as_key()), aliases.unique_alias(assignee))
         ).collect::<Vec<_>>(),
         "discussion": issue.comments().map(|(id, c)| issue_comment(id, c, aliases)).collect::<Vec<_>>(),
         "labels": issue.labels().collect::<Vec<_>>(),
+        "relations": issue.relations().collect::<Vec<_>>(),
+        "links": issue.links().collect::<Vec<_>>(),
     })
 }
 
@@ -126,19 +130,20 @@ pub(crate) fn patch(
 ) -> Value {
     json!({
         "id": id.to_string(),
-        "author": author(patch.author(), aliases.alias(patch.author().id())),
+        "author": author(patch.author(), aliases.unique_alias(patch.author().id())),
         "title": patch.title(),
         "state": patch.state(),
         "target": patch.target(),
         "labels": patch.labels().collect::<Vec<_>>(),
+        "links": patch.links().collect::<Vec<_>>(),
         "merges": patch.merges().map(|(nid, m)| merge(nid, m, aliases)).collect::<Vec<_>>(),
         "assignees": patch.assignees().map(|assignee|
-            author(&Author::from(*assignee), aliases.alias(&assignee))
+            author(&Author::from(*assignee), aliases.unique_alias(&assignee))
         ).collect::<Vec<_>>(),
         "revisions": patch.revisions().map(|(id, rev)| {
             json!({
                 "id": id,
-                "author": author(rev.author(), aliases.alias(rev.author().id())),
+                "author": author(rev.author(), aliases.unique_alias(rev.author().id())),
                 "description": rev.description(),
                 "edits": rev.edits().map(|e| edit(e, aliases)).collect::<Vec<_>>(),
                 "reactions": rev.reactions().iter().flat_map(|(location, reaction)| {
@@ -162,6 +167,38 @@ pub(crate) fn patch(
     })
 }
 
+/// Returns JSON for the timeline of a collaborative object, ie. its actions in chronological
+/// order. Actions that belong to a `redacted` entry are kept, but marked as such.
+pub(crate) fn timeline<A: Serialize>(
+    mut ops: Vec<Op<A>>,
+    redacted: &BTreeSet<EntryId>,
+    aliases: &impl AliasStore,
+) -> Vec<Value> {
+    // Nb. The sort is stable, so operations with the same timestamp keep their causal order.
+    ops.sort_by_key(|op| op.timestamp);
+    ops.into_iter()
+        .flat_map(|op| {
+            let redacted = redacted.contains(&op.id);
+            let author = author(&Author::from(op.author), aliases.unique_alias(&op.author));
+
+            op.actions.into_iter().map(move |action| {
+                let mut event = json!({
+                    "id": op.id,
+                    "author": author,
+                    "timestamp": op.timestamp.as_secs(),
+                    "redacted": redacted,
+                });
+                if let (Some(event), Ok(Value::Object(action))) =
+                    (event.as_object_mut(), serde_json::to_value(action))
+                {
+                    event.extend(action);
+                }
+                event
+            })
+        })
+        .collect()
+}
+
 /// Returns JSON for a `reaction`.
 fn reactions(
     reactions: BTreeMap<&Reaction, Vec<&ActorId>>,
@@ -173,11 +210,11 @@ fn reactions(
         .map(|(emoji, authors)| {
             if let Some(l) = location {
                 json!({ "location": l, "emoji": emoji, "authors": authors.into_iter().map(|a|
-                    author(&Author::from(*a), aliases.alias(a))
+                    author(&Author::from(*a), aliases.unique_alias(a))
                 ).collect::<Vec<_>>()})
             } else {
                 json!({ "emoji": emoji, "authors": authors.into_iter().map(|a|
-                    author(&Author::from(*a), aliases.alias(a))
+                    author(&Author::from(*a), aliases.unique_alias(a))
                 ).collect::<Vec<_>>()})
             }
         })
@@ -185,7 +222,7 @@ fn reactions(
 }
 
 /// Returns JSON for an `author` and fills in `alias` when present.
-fn author(author: &Author, alias: Option<Alias>) -> Value {
+fn author(author: &Author, alias: Option<String>) -> Value {
     match alias {
         Some(alias) => json!({
             "id": author.id,
@@ -198,7 +235,7 @@ fn author(author: &Author, alias: Option<Alias>) -> Value {
 /// Returns JSON for a patch `Merge` and fills in `alias` when present.
 fn merge(nid: &NodeId, merge: &Merge, aliases: &impl AliasStore) -> Value {
     json!({
-        "author": author(&Author::from(*nid), aliases.alias(nid)),
+        "author": author(&Author::from(*nid), aliases.unique_alias(nid)),
         "commit": merge.commit,
         "timestamp": merge.timestamp.as_secs(),
         "revision": merge.revision,
@@ -210,7 +247,7 @@ fn review(id: &ReviewId, review: &Review, aliases: &impl AliasStore) -> Value {
     let a = review.author();
     json!({
         "id": id,
-        "author": author(a, aliases.alias(a.id())),
+        "author": author(a, aliases.unique_alias(a.id())),
         "verdict": review.verdict(),
         "summary": review.summary(),
         "comments": review.comments().map(|(id, c)| review_comment(id, c, aliases)).collect::<Vec<_>>(),
@@ -221,7 +258,7 @@ fn review(id: &ReviewId, review: &Review, aliases: &impl AliasStore) -> Value {
 /// Returns JSON for an `Edit`.
 fn edit(edit: &Edit, aliases: &impl AliasStore) -> Value {
     json!({
-      "author": author(&Author::from(edit.author), aliases.alias(&edit.author)),
+      "author": author(&Author::from(edit.author), aliases.unique_alias(&edit.author)),
       "body": edit.body,
       "timestamp": edit.timestamp.as_secs(),
       "embeds": edit.embeds,
@@ -232,9 +269,9 @@ fn edit(edit: &Edit, aliases: &impl AliasStore) -> Value {
 fn issue_comment(id: &CommentId, comment: &Comment, aliases: &impl AliasStore) -> Value {
     json!({
         "id": *id,
-        "author": author(&Author::from(comment.author()), aliases.alias(&comment.author())),
+        "author": author(&Author::from(comment.author()), aliases.unique_alias(&comment.author())),
         "body": comment.body(),
-        "edits": comment.edits().map(|e| edit(e, aliases)).collect::<Vec<_>>(),
+        "edits": comment.edits().iter().map(|e| edit(e, aliases)).collect::<Vec<_>>(),
         "embeds": comment.embeds().to_vec(),
         "reactions": reactions(comment.reactions(), None, aliases),
         "timestamp": comment.timestamp().as_secs(),
@@ -251,9 +288,9 @@ fn patch_comment(
 ) -> Value {
     json!({
         "id": *id,
-        "author": author(&Author::from(comment.author()), aliases.alias(&comment.author())),
+        "author": author(&Author::from(comment.author()), aliases.unique_alias(&comment.author())),
         "body": comment.body(),
-        "edits": comment.edits().map(|e| edit(e, aliases)).collect::<Vec<_>>(),
+        "edits": comment.edits().iter().map(|e| edit(e, aliases)).collect::<Vec<_>>(),
         "embeds": comment.embeds().to_vec(),
         "reactions": reactions(comment.reactions(), None, aliases),
         "timestamp": comment.timestamp().as_secs(),
@@ -271,9 +308,9 @@ fn review_comment(
 ) -> Value {
     json!({
         "id": *id,
-        "author": author(&Author::from(comment.author()), aliases.alias(&comment.author())),
+        "author": author(&Author::from(comment.author()), aliases.unique_alias(&comment.author())),
         "body": comment.body(),
-        "edits": comment.edits().map(|e| edit(e, aliases)).collect::<Vec<_>>(),
+        "edits": comment.edits().iter().map(|e| edit(e, aliases)).collect::<Vec<_>>(),
         "embeds": comment.embeds().to_vec(),
         "reactions": reactions(comment.reactions(), None, aliases),
         "timestamp": comment.timestamp().as_secs(),
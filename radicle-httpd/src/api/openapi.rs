@@ -0,0 +1,494 @@
+//! OpenAPI 3 description of the HTTP API, served at `/api/openapi.json`.
+//!
+//! The document is generated from [`OPERATIONS`], which mirrors the route table of
+//! [`super::v1::router`]. When adding or changing a route, update the table as well.
+use axum::response::{IntoResponse, Json};
+use serde_json::{json, Map, Value};
+
+use crate::api::VERSION;
+
+/// Path under which the documented operations are served.
+pub const BASE_PATH: &str = "/api/v1";
+
+/// A query string parameter.
+#[derive(Clone, Copy)]
+struct Param {
+    name: &'static str,
+    /// JSON type of the parameter.
+    kind: &'static str,
+    description: &'static str,
+}
+
+/// A documented API operation.
+struct Operation {
+    method: &'static str,
+    /// Route path, in the syntax used by the router, eg. `/projects/:project`.
+    path: &'static str,
+    summary: &'static str,
+    query: &'static [Param],
+    /// Name of the request body schema, if any.
+    body: Option<&'static str>,
+    /// Whether a session token is required.
+    auth: bool,
+}
+
+impl Operation {
+    const fn new(method: &'static str, path: &'static str, summary: &'static str) -> Self {
+        Self {
+            method,
+            path,
+            summary,
+            query: &[],
+            body: None,
+            auth: false,
+        }
+    }
+
+    const fn query(mut self, query: &'static [Param]) -> Self {
+        self.query = query;
+        self
+    }
+
+    const fn body(mut self, schema: &'static str) -> Self {
+        self.body = Some(schema);
+        self
+    }
+
+    const fn authorized(mut self) -> Self {
+        self.auth = true;
+        self
+    }
+}
+
+const PAGE: Param = Param {
+    name: "page",
+    kind: "integer",
+    description: "Page number, starting from zero.",
+};
+
+const PER_PAGE: Param = Param {
+    name: "perPage",
+    kind: "integer",
+    description: "Number of items per page.",
+};
+
+const AUTHOR: Param = Param {
+    name: "author",
+    kind: "string",
+    description: "Only show items created by this DID.",
+};
+
+const LABEL: Param = Param {
+    name: "label",
+    kind: "string",
+    description: "Only show items with this label.",
+};
+
+const ASSIGNEE: Param = Param {
+    name: "assignee",
+    kind: "string",
+    description: "Only show items assigned to this DID.",
+};
+
+const SINCE: Param = Param {
+    name: "since",
+    kind: "integer",
+    description: "Only show items created at or after this UNIX timestamp.",
+};
+
+const UNTIL: Param = Param {
+    name: "until",
+    kind: "integer",
+    description: "Only show items created at or before this UNIX timestamp.",
+};
+
+const TEXT: Param = Param {
+    name: "text",
+    kind: "string",
+    description: "Only show items whose title or description contains this text, ignoring case.",
+};
+
+const PROJECTS: &[Param] = &[
+    Param {
+        name: "show",
+        kind: "string",
+        description: "Which projects to show: `pinned` (default) or `all`.",
+    },
+    PAGE,
+    PER_PAGE,
+];
+
+const COMMITS: &[Param] = &[
+    Param {
+        name: "parent",
+        kind: "string",
+        description: "Commit to start the history from. Defaults to the project head.",
+    },
+    Param {
+        name: "since",
+        kind: "integer",
+        description: "Only show commits after this UNIX timestamp.",
+    },
+    Param {
+        name: "until",
+        kind: "integer",
+        description: "Only show commits before this UNIX timestamp.",
+    },
+    PAGE,
+    PER_PAGE,
+];
+
+const ISSUES: &[Param] = &[
+    PAGE,
+    PER_PAGE,
+    Param {
+        name: "state",
+        kind: "string",
+        description: "Issue state: `open` (default) or `closed`.",
+    },
+    AUTHOR,
+    LABEL,
+    ASSIGNEE,
+    SINCE,
+    UNTIL,
+    TEXT,
+];
+
+const PATCHES: &[Param] = &[
+    PAGE,
+    PER_PAGE,
+    Param {
+        name: "state",
+        kind: "string",
+        description: "Patch state: `open` (default), `draft`, `archived` or `merged`.",
+    },
+    AUTHOR,
+    LABEL,
+    ASSIGNEE,
+    SINCE,
+    UNTIL,
+    TEXT,
+];
+
+const POLICIES: &[Param] = &[
+    Param {
+        name: "from",
+        kind: "string",
+        description: "Node to fetch the repository from after seeding it.",
+    },
+    Param {
+        name: "scope",
+        kind: "string",
+        description: "Seeding scope: `followed` or `all`.",
+    },
+];
+
+/// Every operation of the API, in route table order.
+const OPERATIONS: &[Operation] = &[
+    Operation::new("get", "/", "API root, with service information and links"),
+    Operation::new("get", "/node", "Local node information"),
+    Operation::new("get", "/node/policies/repos", "Repository seeding policies"),
+    Operation::new("put", "/node/policies/repos/:rid", "Seed a repository")
+        .query(POLICIES)
+        .authorized(),
+    Operation::new("delete", "/node/policies/repos/:rid", "Stop seeding a repository")
+        .authorized(),
+    Operation::new("get", "/nodes/:nid", "Node information"),
+    Operation::new("get", "/nodes/:nid/inventory", "Repositories seeded by a node"),
+    Operation::new("get", "/profile", "Local profile"),
+    Operation::new("post", "/sessions", "Create a session"),
+    Operation::new("get", "/sessions/:id", "Get a session"),
+    Operation::new("put", "/sessions/:id", "Sign in to a session").body("AuthChallenge"),
+    Operation::new("delete", "/sessions/:id", "Delete a session").authorized(),
+    Operation::new("get", "/delegates/:delegate/projects", "Projects of a delegate")
+        .query(PROJECTS),
+    Operation::new("get", "/projects", "Projects hosted on this node").query(PROJECTS),
+    Operation::new("get", "/projects/:project", "Project information"),
+    Operation::new("get", "/projects/:project/commits", "Project commit history").query(COMMITS),
+    Operation::new("get", "/projects/:project/commits/:sha", "Commit, with its diff"),
+    Operation::new("get", "/projects/:project/diff/:base/:oid", "Diff between two commits"),
+    Operation::new("get", "/projects/:project/activity", "Weekly commit activity"),
+    Operation::new("get", "/projects/:project/tree/:sha/", "Root tree at a commit"),
+    Operation::new("get", "/projects/:project/tree/:sha/*path", "Tree at a path and commit"),
+    Operation::new("get", "/projects/:project/remotes", "Project remotes"),
+    Operation::new("get", "/projects/:project/remotes/:peer", "Project remote"),
+    Operation::new("get", "/projects/:project/blob/:sha/*path", "Blob at a path and commit"),
+    Operation::new("get", "/projects/:project/readme/:sha", "Project README at a commit"),
+    Operation::new("get", "/projects/:project/issues", "Project issues").query(ISSUES),
+    Operation::new("post", "/projects/:project/issues", "Create an issue")
+        .body("IssueCreate")
+        .authorized(),
+    Operation::new("get", "/projects/:project/issues/:id", "Project issue"),
+    Operation::new("patch", "/projects/:project/issues/:id", "Update an issue")
+        .body("IssueAction")
+        .authorized(),
+    Operation::new("get", "/projects/:project/issues/:id/timeline", "Project issue timeline"),
+    Operation::new("get", "/projects/:project/patches", "Project patches").query(PATCHES),
+    Operation::new("post", "/projects/:project/patches", "Create a patch")
+        .body("PatchCreate")
+        .authorized(),
+    Operation::new("get", "/projects/:project/patches/:id", "Project patch"),
+    Operation::new("patch", "/projects/:project/patches/:id", "Update a patch")
+        .body("PatchAction")
+        .authorized(),
+    Operation::new("get", "/projects/:project/patches/:id/timeline", "Project patch timeline"),
+    Operation::new("get", "/projects/:project/labels", "Labels in use, with counts"),
+    Operation::new("patch", "/projects/:project/labels/:label", "Rename or merge a label")
+        .body("LabelUpdate")
+        .authorized(),
+    Operation::new("get", "/stats", "Node statistics"),
+];
+
+/// Get the OpenAPI document.
+/// `GET /openapi.json`
+pub async fn openapi_handler() -> impl IntoResponse {
+    Json(document())
+}
+
+/// Generate the OpenAPI document.
+pub fn document() -> Value {
+    let mut paths = Map::new();
+
+    for op in OPERATIONS {
+        let (path, params) = template(op.path);
+        let mut parameters = params
+            .into_iter()
+            .map(|name| {
+                json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "string" },
+                })
+            })
+            .collect::<Vec<_>>();
+        parameters.extend(op.query.iter().map(|p| {
+            json!({
+                "name": p.name,
+                "in": "query",
+                "required": false,
+                "description": p.description,
+                "schema": { "type": p.kind },
+            })
+        }));
+
+        let mut operation = json!({
+            "summary": op.summary,
+            "parameters": parameters,
+            "responses": {
+                "200": {
+                    "description": "Success",
+                    "content": { "application/json": { "schema": { "type": "object" } } },
+                },
+                "default": { "$ref": "#/components/responses/Error" },
+            },
+        });
+        if let Some(body) = op.body {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": {
+                    "application/json": {
+                        "schema": { "$ref": format!("#/components/schemas/{body}") },
+                    },
+                },
+            });
+        }
+        if op.auth {
+            operation["security"] = json!([{ "bearer": [] }]);
+        }
+
+        let item = paths
+            .entry(path)
+            .or_insert_with(|| Value::Object(Map::new()));
+        item[op.method] = operation;
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "radicle-httpd",
+            "description": "Radicle HTTP daemon API",
+            "version": VERSION,
+        },
+        "servers": [{ "url": BASE_PATH }],
+        "paths": paths,
+        "components": {
+            "securitySchemes": {
+                "bearer": { "type": "http", "scheme": "bearer" },
+            },
+            "responses": {
+                "Error": {
+                    "description": "Error",
+                    "content": {
+                        "application/json": {
+                            "schema": {
+                                "type": "object",
+                                "properties": {
+                                    "error": { "type": "string" },
+                                    "code": { "type": "integer" },
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+            "schemas": schemas(),
+        },
+    })
+}
+
+/// Request body schemas.
+fn schemas() -> Value {
+    json!({
+        "AuthChallenge": {
+            "type": "object",
+            "required": ["sig", "pk"],
+            "properties": {
+                "sig": { "type": "string", "description": "Signature of the session challenge." },
+                "pk": { "type": "string", "description": "Public key of the signer." },
+            },
+        },
+        "IssueCreate": {
+            "type": "object",
+            "required": ["title", "description", "labels", "assignees", "embeds"],
+            "properties": {
+                "title": { "type": "string" },
+                "description": { "type": "string" },
+                "labels": { "type": "array", "items": { "type": "string" } },
+                "assignees": { "type": "array", "items": { "type": "string" } },
+                "embeds": { "type": "array", "items": { "$ref": "#/components/schemas/Embed" } },
+            },
+        },
+        "PatchCreate": {
+            "type": "object",
+            "required": ["title", "description", "target", "oid", "labels"],
+            "properties": {
+                "title": { "type": "string" },
+                "description": { "type": "string" },
+                "target": { "type": "string", "description": "Base commit of the patch." },
+                "oid": { "type": "string", "description": "Head commit of the patch." },
+                "labels": { "type": "array", "items": { "type": "string" } },
+            },
+        },
+        "LabelUpdate": {
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "New name of the label. If it's already in use, the labels are merged.",
+                },
+            },
+        },
+        "Embed": {
+            "type": "object",
+            "required": ["name", "content"],
+            "properties": {
+                "name": { "type": "string" },
+                "content": { "type": "string" },
+            },
+        },
+        "IssueAction": action(&[
+            "assign",
+            "edit",
+            "lifecycle",
+            "label",
+            "comment",
+            "comment.edit",
+            "comment.redact",
+            "comment.react",
+            "relate",
+            "unrelate",
+            "link",
+            "unlink",
+        ]),
+        "PatchAction": action(&[
+            "edit",
+            "label",
+            "assign",
+            "merge",
+            "lifecycle",
+            "link",
+            "unlink",
+            "revision",
+            "revision.edit",
+            "revision.redact",
+            "revision.react",
+            "revision.comment",
+            "revision.comment.edit",
+            "revision.comment.redact",
+            "revision.comment.react",
+            "review",
+            "review.edit",
+            "review.redact",
+            "review.comment",
+            "review.comment.edit",
+            "review.comment.redact",
+            "review.comment.react",
+            "review.comment.resolve",
+            "review.comment.unresolve",
+        ]),
+    })
+}
+
+/// Schema of a COB action, tagged by its `type` field.
+fn action(types: &[&str]) -> Value {
+    json!({
+        "type": "object",
+        "required": ["type"],
+        "properties": {
+            "type": { "type": "string", "enum": types },
+        },
+        "additionalProperties": true,
+    })
+}
+
+/// Convert a router path to an OpenAPI path template, returning the path parameters.
+///
+/// Eg. `/projects/:project/tree/:sha/*path` becomes `/projects/{project}/tree/{sha}/{path}`.
+fn template(path: &str) -> (String, Vec<&str>) {
+    let mut params = Vec::new();
+    let path = path
+        .split('/')
+        .map(|segment| match segment.strip_prefix([':', '*']) {
+            Some(param) => {
+                params.push(param);
+                format!("{{{param}}}")
+            }
+            None => segment.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    (path, params)
+}
+
+#[cfg(test)]
+mod routes {
+    use axum::http::StatusCode;
+
+    use crate::test::{self, get};
+
+    #[tokio::test]
+    async fn test_openapi() {
+        let tmp = tempfile::tempdir().unwrap();
+        let app = crate::api::router(test::seed(tmp.path()));
+        let response = get(&app, "/openapi.json").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let doc = response.json().await;
+        let issues = &doc["paths"]["/projects/{project}/issues"];
+
+        assert_eq!(doc["openapi"], "3.0.3");
+        assert_eq!(doc["servers"][0]["url"], super::BASE_PATH);
+        assert!(issues["get"].is_object());
+        assert!(issues["post"]["requestBody"].is_object());
+        assert_eq!(issues["get"]["parameters"][0]["name"], "project");
+        assert!(doc["paths"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .all(|path| !path.contains([':', '*'])));
+    }
+}
@@ -11,6 +11,7 @@ use radicle::node::routing::Store;
 use radicle::node::{policy, AliasStore, Handle, NodeId, DEFAULT_TIMEOUT};
 use radicle::Node;
 
+use crate::api::auth::Scope;
 use crate::api::error::Error;
 use crate::api::{self, Context, PoliciesQuery, VERSION};
 use crate::axum_extra::{Path, Query};
@@ -30,7 +31,13 @@ pub fn router(ctx: Context) -> Router {
 
 /// Return local node information.
 /// `GET /node`
-async fn node_handler(State(ctx): State<Context>) -> impl IntoResponse {
+///
+/// Authorized requests also get the listen addresses, peer sessions and
+/// inventory size of the running node.
+async fn node_handler(
+    State(ctx): State<Context>,
+    auth: Option<AuthBearer>,
+) -> impl IntoResponse {
     let node = Node::new(ctx.profile.socket());
     let node_id = ctx.profile.public_key;
     let node_state = if node.is_running() {
@@ -45,13 +52,21 @@ async fn node_handler(State(ctx): State<Context>) -> impl IntoResponse {
             None
         }
     };
-    let response = json!({
+    let mut response = json!({
         "id": node_id.to_string(),
         "version": format!("{}-{}", VERSION, env!("GIT_HEAD")),
         "config": config,
         "state": node_state,
     });
 
+    if let Some(AuthBearer(token)) = auth {
+        api::auth::validate(&ctx, &token, Scope::Read).await?;
+
+        response["listen"] = json!(node.listen_addrs().unwrap_or_default());
+        response["sessions"] = json!(node.sessions().unwrap_or_default());
+        response["inventory"] = json!(node.status().ok().map(|s| s.inventory));
+    }
+
     Ok::<_, Error>(Json(response))
 }
 
@@ -59,8 +74,10 @@ async fn node_handler(State(ctx): State<Context>) -> impl IntoResponse {
 /// `GET /nodes/:nid`
 async fn nodes_handler(State(ctx): State<Context>, Path(nid): Path<NodeId>) -> impl IntoResponse {
     let aliases = ctx.profile.aliases();
+    let db = &ctx.profile.database()?;
     let response = json!({
-        "alias": aliases.alias(&nid),
+        "alias": aliases.unique_alias(&nid),
+        "seeding": db.count_resources(&nid)?,
     });
 
     Ok::<_, Error>(Json(response))
@@ -108,7 +125,7 @@ async fn node_policies_seed_handler(
     Path(project): Path<RepoId>,
     Query(qs): Query<PoliciesQuery>,
 ) -> impl IntoResponse {
-    api::auth::validate(&ctx, &token).await?;
+    api::auth::validate(&ctx, &token, Scope::NodeWrite).await?;
     let mut node = Node::new(ctx.profile.socket());
     node.seed(project, qs.scope.unwrap_or_default())?;
 
@@ -129,9 +146,47 @@ async fn node_policies_unseed_handler(
     AuthBearer(token): AuthBearer,
     Path(project): Path<RepoId>,
 ) -> impl IntoResponse {
-    api::auth::validate(&ctx, &token).await?;
+    api::auth::validate(&ctx, &token, Scope::NodeWrite).await?;
     let mut node = Node::new(ctx.profile.socket());
     node.unseed(project)?;
 
     Ok::<_, Error>((StatusCode::OK, Json(json!({ "success": true }))))
 }
+
+#[cfg(test)]
+mod routes {
+    use axum::http::StatusCode;
+    use serde_json::json;
+
+    use crate::test::{self, create_session, get, get_auth, SESSION_ID};
+
+    #[tokio::test]
+    async fn test_node() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ctx = test::seed(tmp.path());
+        let app = super::router(ctx.to_owned());
+        let nid = ctx.profile().public_key.to_string();
+
+        let response = get(&app, "/node").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.json().await;
+        assert_eq!(body["id"], json!(nid));
+        assert_eq!(body["state"], json!("stopped"));
+        assert!(body.get("sessions").is_none());
+
+        let response = get_auth(&app, "/node", Some(SESSION_ID.to_string())).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        create_session(ctx).await;
+
+        let response = get_auth(&app, "/node", Some(SESSION_ID.to_string())).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.json().await;
+        assert_eq!(body["id"], json!(nid));
+        assert_eq!(body["listen"], json!([]));
+        assert_eq!(body["sessions"], json!([]));
+        assert_eq!(body["inventory"], json!(null));
+    }
+}
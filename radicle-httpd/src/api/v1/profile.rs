@@ -95,6 +95,7 @@ mod routes {
                     "routingMaxSize": 1000,
                     "routingMaxAge": 604800,
                     "gossipMaxAge": 1209600,
+                    "gossipMaxHops": 8,
                     "fetchConcurrency": 1,
                     "maxOpenFiles": 4096,
                     "rate": {
@@ -105,16 +106,30 @@ mod routes {
                       "outbound": {
                         "fillRate": 1.0,
                         "capacity": 64
+                      },
+                      "announce": {
+                        "fillRate": 32.0,
+                        "capacity": 512
                       }
                     },
                     "connection": {
                       "inbound": 128,
-                      "outbound": 16
+                      "outbound": 16,
+                      "total": 144,
+                      "minOutbound": 4
+                    },
+                    "quota": {
+                      "refs": 16384,
+                      "bytes": 1073741824
                     }
                   },
                   "workers": 8,
                   "policy": "block",
-                  "scope": "all"
+                  "scope": "all",
+                  "announceRefsWindow": 1,
+                  "announceInterval": 3600,
+                  "announceJitter": 300,
+                  "excludeRefs": []
                 }
               },
               "home": seed.profile.path()
@@ -1,21 +1,24 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use axum::extract::{DefaultBodyLimit, State};
 use axum::handler::Handler;
-use axum::http::{header, HeaderValue};
+use axum::http::{header, HeaderMap, HeaderValue};
 use axum::response::{IntoResponse, Response};
 use axum::routing::{get, patch, post};
 use axum::{Json, Router};
 use axum_auth::AuthBearer;
 use hyper::StatusCode;
+use nonempty::NonEmpty;
 use radicle_surf::blob::BlobRef;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use time::OffsetDateTime;
 use tower_http::set_header::SetResponseHeaderLayer;
 
 use radicle::cob::{
-    issue, issue::cache::Issues as _, patch, patch::cache::Patches as _, resolve_embed, Embed,
-    Label, Uri,
+    self, issue, issue::cache::Issues as _, patch, patch::cache::Patches as _, resolve_embed,
+    Embed, Label, ObjectId, Op, TypeName, Uri,
 };
 use radicle::identity::{Did, RepoId};
 use radicle::node::routing::Store;
@@ -23,10 +26,11 @@ use radicle::node::{AliasStore, Node, NodeId};
 use radicle::storage::{ReadRepository, ReadStorage, RemoteRepository, WriteRepository};
 use radicle_surf::{diff, Glob, Oid, Repository};
 
+use crate::api::auth::Scope;
 use crate::api::error::Error;
 use crate::api::project::Info;
 use crate::api::{self, announce_refs, CobsQuery, Context, PaginationQuery, ProjectQuery};
-use crate::axum_extra::{immutable_response, Path, Query};
+use crate::axum_extra::{http_date, immutable_response, modified_since, Path, Query};
 
 const CACHE_1_HOUR: &str = "public, max-age=3600, must-revalidate";
 const MAX_BODY_LIMIT: usize = 4_194_304;
@@ -66,23 +70,45 @@ pub fn router(ctx: Context) -> Router {
             "/projects/:project/issues/:id",
             patch(issue_update_handler).get(issue_handler),
         )
+        .route(
+            "/projects/:project/issues/:id/timeline",
+            get(issue_timeline_handler),
+        )
         .route(
             "/projects/:project/patches",
             post(patch_create_handler).get(patches_handler),
         )
+        .route("/projects/:project/patches/stack", get(patch_stack_handler))
         .route(
             "/projects/:project/patches/:id",
             patch(patch_update_handler).get(patch_handler),
         )
+        .route(
+            "/projects/:project/patches/:id/revisions",
+            get(patch_revisions_handler),
+        )
+        .route(
+            "/projects/:project/patches/:id/timeline",
+            get(patch_timeline_handler),
+        )
+        .route("/projects/:project/labels", get(labels_handler))
+        .route(
+            "/projects/:project/labels/:label",
+            patch(label_update_handler),
+        )
         .with_state(ctx)
         .layer(DefaultBodyLimit::max(MAX_BODY_LIMIT))
 }
 
 /// List all projects.
 /// `GET /projects`
+///
+/// Responds with `304 Not Modified` if none of the listed repository heads changed since
+/// the time given in the `If-Modified-Since` header.
 async fn project_root_handler(
     State(ctx): State<Context>,
     Query(qs): Query<PaginationQuery>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
     let PaginationQuery {
         show,
@@ -110,7 +136,7 @@ async fn project_root_handler(
     };
     projects.sort_by_key(|p| p.rid);
 
-    let infos = projects
+    let repos = projects
         .into_iter()
         .filter_map(|info| {
             if !policies.is_seeding(&info.rid).unwrap_or_default() {
@@ -122,6 +148,25 @@ async fn project_root_handler(
             let Ok((_, head)) = repo.head() else {
                 return None;
             };
+            Some((info, repo, head))
+        })
+        .collect::<Vec<_>>();
+    let last_modified = repos
+        .iter()
+        .filter_map(|(_, repo, head)| repo.commit(*head).ok())
+        .map(|commit| commit.time().seconds())
+        .max()
+        .and_then(|t| OffsetDateTime::from_unix_timestamp(t).ok());
+
+    if let Some(last_modified) = last_modified {
+        if !modified_since(&headers, last_modified) {
+            return Ok::<_, Error>(StatusCode::NOT_MODIFIED.into_response());
+        }
+    }
+
+    let infos = repos
+        .into_iter()
+        .filter_map(|(info, repo, head)| {
             let Ok(payload) = info.doc.project() else {
                 return None;
             };
@@ -155,7 +200,11 @@ async fn project_root_handler(
         .take(per_page)
         .collect::<Vec<_>>();
 
-    Ok::<_, Error>(Json(infos))
+    let mut response = Json(infos).into_response();
+    if let Some(value) = last_modified.and_then(|t| HeaderValue::from_str(&http_date(t)).ok()) {
+        response.headers_mut().insert(header::LAST_MODIFIED, value);
+    }
+    Ok::<_, Error>(response)
 }
 
 /// Get project metadata.
@@ -464,7 +513,7 @@ async fn remotes_handler(
                 })
                 .collect::<BTreeMap<String, &Oid>>();
 
-            match aliases.alias(&remote.id) {
+            match aliases.unique_alias(&remote.id) {
                 Some(alias) => json!({
                     "id": remote.id,
                     "alias": alias,
@@ -563,20 +612,16 @@ async fn issues_handler(
     Query(qs): Query<CobsQuery<api::IssueState>>,
 ) -> impl IntoResponse {
     let (repo, _) = ctx.repo(project)?;
-    let CobsQuery {
-        page,
-        per_page,
-        state,
-    } = qs;
+    let query = qs.filters(qs.state.clone().unwrap_or_default().query());
+    let CobsQuery { page, per_page, .. } = qs;
     let page = page.unwrap_or(0);
     let per_page = per_page.unwrap_or(10);
-    let state = state.unwrap_or_default();
     let issues = ctx.profile.issues(&repo)?;
     let mut issues: Vec<_> = issues
         .list()?
         .filter_map(|r| {
             let (id, issue) = r.ok()?;
-            (state.matches(issue.state())).then_some((id, issue))
+            query.matches(&issue).then_some((id, issue))
         })
         .collect::<Vec<_>>();
 
@@ -609,7 +654,7 @@ async fn issue_create_handler(
     Path(project): Path<RepoId>,
     Json(issue): Json<IssueCreate>,
 ) -> impl IntoResponse {
-    api::auth::validate(&ctx, &token).await?;
+    api::auth::validate(&ctx, &token, Scope::IssuesWrite).await?;
 
     let (repo, _) = ctx.repo(project)?;
     let node = Node::new(ctx.profile.socket());
@@ -651,7 +696,7 @@ async fn issue_update_handler(
     Path((project, issue_id)): Path<(RepoId, Oid)>,
     Json(action): Json<issue::Action>,
 ) -> impl IntoResponse {
-    api::auth::validate(&ctx, &token).await?;
+    api::auth::validate(&ctx, &token, Scope::IssuesWrite).await?;
 
     let (repo, _) = ctx.repo(project)?;
     let node = Node::new(ctx.profile.socket());
@@ -692,6 +737,10 @@ async fn issue_update_handler(
             issue.edit_comment(id, body, embeds, &signer)?
         }
         issue::Action::CommentRedact { id } => issue.redact_comment(id, &signer)?,
+        issue::Action::Relate { target, kind } => issue.relate(target, kind, &signer)?,
+        issue::Action::Unrelate { target, kind } => issue.unrelate(target, kind, &signer)?,
+        issue::Action::Link { link } => issue.link(link, &signer)?,
+        issue::Action::Unlink { link } => issue.unlink(link, &signer)?,
     };
 
     announce_refs(node, repo.id())?;
@@ -716,6 +765,27 @@ async fn issue_handler(
     Ok::<_, Error>(Json(api::json::issue(issue_id.into(), issue, &aliases)))
 }
 
+/// Get project issue timeline.
+/// `GET /projects/:project/issues/:id/timeline`
+async fn issue_timeline_handler(
+    State(ctx): State<Context>,
+    Path((project, issue_id)): Path<(RepoId, Oid)>,
+) -> impl IntoResponse {
+    let (repo, _) = ctx.repo(project)?;
+    let ops = ops::<issue::Action>(&issue_id.into(), &issue::TYPENAME, &repo)?;
+    let redacted = ops
+        .iter()
+        .flat_map(|op| op.actions.iter())
+        .filter_map(|action| match action {
+            issue::Action::CommentRedact { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+    let aliases = ctx.profile.aliases();
+
+    Ok::<_, Error>(Json(api::json::timeline(ops, &redacted, &aliases)))
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct PatchCreate {
     pub title: String,
@@ -733,7 +803,7 @@ async fn patch_create_handler(
     Path(project): Path<RepoId>,
     Json(patch): Json<PatchCreate>,
 ) -> impl IntoResponse {
-    api::auth::validate(&ctx, &token).await?;
+    api::auth::validate(&ctx, &token, Scope::PatchesWrite).await?;
 
     let node = Node::new(ctx.profile.socket());
     let signer = ctx
@@ -772,7 +842,7 @@ async fn patch_update_handler(
     Path((project, patch_id)): Path<(RepoId, Oid)>,
     Json(action): Json<patch::Action>,
 ) -> impl IntoResponse {
-    api::auth::validate(&ctx, &token).await?;
+    api::auth::validate(&ctx, &token, Scope::PatchesWrite).await?;
 
     let node = Node::new(ctx.profile.socket());
     let signer = ctx
@@ -791,6 +861,8 @@ async fn patch_update_handler(
             // TODO: We should cleanup the stored copy at least.
             patch.merge(revision, commit, &signer)?.entry
         }
+        patch::Action::Link { link } => patch.link(link, &signer)?,
+        patch::Action::Unlink { link } => patch.unlink(link, &signer)?,
         patch::Action::Review {
             revision,
             summary,
@@ -916,20 +988,16 @@ async fn patches_handler(
     Query(qs): Query<CobsQuery<api::PatchState>>,
 ) -> impl IntoResponse {
     let (repo, _) = ctx.repo(rid)?;
-    let CobsQuery {
-        page,
-        per_page,
-        state,
-    } = qs;
+    let query = qs.filters(qs.state.clone().unwrap_or_default().query());
+    let CobsQuery { page, per_page, .. } = qs;
     let page = page.unwrap_or(0);
     let per_page = per_page.unwrap_or(10);
-    let state = state.unwrap_or_default();
     let patches = ctx.profile.patches(&repo)?;
     let mut patches = patches
         .list()?
         .filter_map(|r| {
             let (id, patch) = r.ok()?;
-            (state.matches(patch.state())).then_some((id, patch))
+            query.matches(&patch).then_some((id, patch))
         })
         .collect::<Vec<_>>();
     patches.sort_by(|(_, a), (_, b)| b.timestamp().cmp(&a.timestamp()));
@@ -944,6 +1012,37 @@ async fn patches_handler(
     Ok::<_, Error>(Json(patches))
 }
 
+/// Get the open patches of a project, in the order they are stacked on each other.
+/// `GET /projects/:project/patches/stack`
+async fn patch_stack_handler(
+    State(ctx): State<Context>,
+    Path(rid): Path<RepoId>,
+) -> impl IntoResponse {
+    let (repo, _) = ctx.repo(rid)?;
+    let patches = ctx.profile.patches(&repo)?;
+    let opened = patches
+        .opened()?
+        .filter_map(|r| r.ok())
+        .collect::<BTreeMap<_, _>>();
+    let stack = patch::PatchStack::detect(&repo, opened.iter().map(|(id, p)| (*id, p)))
+        .map_err(patch::Error::from)?;
+    let stack = stack
+        .iter()
+        .filter_map(|id| {
+            let patch = opened.get(id)?;
+
+            Some(json!({
+                "id": id,
+                "title": patch.title(),
+                "stackedOn": stack.parent(id),
+                "depth": stack.depth(id),
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    Ok::<_, Error>(Json(stack))
+}
+
 /// Get project patch.
 /// `GET /projects/:project/patches/:id`
 async fn patch_handler(
@@ -963,13 +1062,241 @@ async fn patch_handler(
     )))
 }
 
+/// Get a summary of a project patch's revisions, without their diffs.
+/// `GET /projects/:project/patches/:id/revisions`
+async fn patch_revisions_handler(
+    State(ctx): State<Context>,
+    Path((rid, patch_id)): Path<(RepoId, Oid)>,
+) -> impl IntoResponse {
+    let (repo, _) = ctx.repo(rid)?;
+    let patches = ctx.profile.patches(&repo)?;
+    let patch = patches.get(&patch_id.into())?.ok_or(Error::NotFound)?;
+    let surf = Repository::open(repo.path())?;
+    let revisions = patch
+        .revisions()
+        .map(|(id, rev)| {
+            let diff = surf.diff(*rev.base(), rev.head())?;
+
+            Ok::<_, Error>(json!({
+                "id": id,
+                "base": rev.base(),
+                "oid": rev.head(),
+                "timestamp": rev.timestamp().as_secs(),
+                "stats": diff.stats(),
+            }))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok::<_, Error>(Json(revisions))
+}
+
+/// Get project patch timeline.
+/// `GET /projects/:project/patches/:id/timeline`
+async fn patch_timeline_handler(
+    State(ctx): State<Context>,
+    Path((project, patch_id)): Path<(RepoId, Oid)>,
+) -> impl IntoResponse {
+    let (repo, _) = ctx.repo(project)?;
+    let ops = ops::<patch::Action>(&patch_id.into(), &patch::TYPENAME, &repo)?;
+    let redacted = ops
+        .iter()
+        .flat_map(|op| op.actions.iter())
+        .filter_map(|action| match action {
+            patch::Action::RevisionRedact { revision } => Some(**revision),
+            patch::Action::ReviewRedact { review } => Some(**review),
+            patch::Action::RevisionCommentRedact { comment, .. }
+            | patch::Action::ReviewCommentRedact { comment, .. } => Some(*comment),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+    let aliases = ctx.profile.aliases();
+
+    Ok::<_, Error>(Json(api::json::timeline(ops, &redacted, &aliases)))
+}
+
+/// Get the labels in use by the issues and patches of a project.
+/// `GET /projects/:project/labels`
+async fn labels_handler(
+    State(ctx): State<Context>,
+    Path(project): Path<RepoId>,
+) -> impl IntoResponse {
+    let (repo, _) = ctx.repo(project)?;
+    let mut counts = BTreeMap::<Label, (usize, usize)>::new();
+
+    for (_, issue) in ctx.profile.issues(&repo)?.list()?.filter_map(|r| r.ok()) {
+        for label in issue.labels() {
+            counts.entry(label.clone()).or_default().0 += 1;
+        }
+    }
+    for (_, patch) in ctx.profile.patches(&repo)?.list()?.filter_map(|r| r.ok()) {
+        for label in patch.labels() {
+            counts.entry(label.clone()).or_default().1 += 1;
+        }
+    }
+    let labels = counts
+        .into_iter()
+        .map(|(name, (issues, patches))| {
+            json!({
+                "name": name,
+                "issues": issues,
+                "patches": patches,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok::<_, Error>(Json(labels))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LabelUpdate {
+    pub name: Label,
+}
+
+/// A label change to a single COB.
+struct Relabel<Id> {
+    id: Id,
+    before: Vec<Label>,
+    after: Vec<Label>,
+}
+
+impl<Id> Relabel<Id> {
+    /// Compute the labels of a COB after renaming `from` to `to`, if it has the label `from`.
+    fn new<'a>(
+        id: Id,
+        labels: impl Iterator<Item = &'a Label>,
+        from: &Label,
+        to: &Label,
+    ) -> Option<Self> {
+        let before = labels.cloned().collect::<Vec<_>>();
+        if !before.contains(from) {
+            return None;
+        }
+        let after = before
+            .iter()
+            .map(|l| if l == from { to } else { l })
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        Some(Self { id, before, after })
+    }
+}
+
+/// Rename a label across all the issues and patches of a project. If the new name is
+/// already in use, the two labels are merged.
+/// `PATCH /projects/:project/labels/:label`
+///
+/// Either all objects are updated, or none are: if an update fails, the objects that were
+/// already updated have their labels restored.
+async fn label_update_handler(
+    State(ctx): State<Context>,
+    AuthBearer(token): AuthBearer,
+    Path((project, label)): Path<(RepoId, Label)>,
+    Json(LabelUpdate { name }): Json<LabelUpdate>,
+) -> impl IntoResponse {
+    api::auth::validate(&ctx, &token, Scope::IssuesWrite).await?;
+    api::auth::validate(&ctx, &token, Scope::PatchesWrite).await?;
+
+    let (repo, _) = ctx.repo(project)?;
+    let node = Node::new(ctx.profile.socket());
+    let signer = ctx.profile.signer()?;
+    let mut issues = ctx.profile.issues_mut(&repo)?;
+    let mut patches = ctx.profile.patches_mut(&repo)?;
+
+    // Compute all the changes up-front, so that we don't start updating objects if
+    // any of them fails to load.
+    let mut relabel_issues = Vec::new();
+    for result in issues.list()? {
+        let (id, issue) = result?;
+        relabel_issues.extend(Relabel::new(id, issue.labels(), &label, &name));
+    }
+    let mut relabel_patches = Vec::new();
+    for result in patches.list()? {
+        let (id, patch) = result?;
+        relabel_patches.extend(Relabel::new(id, patch.labels(), &label, &name));
+    }
+
+    let mut updated_issues = 0;
+    let mut updated_patches = 0;
+    let mut result = Ok(());
+
+    for r in &relabel_issues {
+        if let Err(e) = issues
+            .get_mut(&r.id)
+            .map_err(Error::from)
+            .and_then(|mut issue| Ok(issue.label(r.after.clone(), &signer)?))
+        {
+            result = Err(e);
+            break;
+        }
+        updated_issues += 1;
+    }
+    if result.is_ok() {
+        for r in &relabel_patches {
+            if let Err(e) = patches
+                .get_mut(&r.id)
+                .map_err(Error::from)
+                .and_then(|mut patch| Ok(patch.label(r.after.clone(), &signer)?))
+            {
+                result = Err(e);
+                break;
+            }
+            updated_patches += 1;
+        }
+    }
+    if let Err(e) = result {
+        // Restore the labels of the objects that were already updated. Failures are
+        // ignored, since we're already returning an error.
+        for r in &relabel_issues[..updated_issues] {
+            if let Ok(mut issue) = issues.get_mut(&r.id) {
+                issue.label(r.before.clone(), &signer).ok();
+            }
+        }
+        for r in &relabel_patches[..updated_patches] {
+            if let Ok(mut patch) = patches.get_mut(&r.id) {
+                patch.label(r.before.clone(), &signer).ok();
+            }
+        }
+        return Err(e);
+    }
+
+    if updated_issues > 0 || updated_patches > 0 {
+        announce_refs(node, repo.id())?;
+    }
+
+    Ok::<_, Error>(Json(json!({
+        "success": true,
+        "issues": relabel_issues.iter().map(|r| r.id).collect::<Vec<_>>(),
+        "patches": relabel_patches.iter().map(|r| r.id).collect::<Vec<_>>(),
+    })))
+}
+
+/// Get the decoded operations of a collaborative object.
+fn ops<A: DeserializeOwned>(
+    id: &ObjectId,
+    type_name: &TypeName,
+    repo: &radicle::storage::git::Repository,
+) -> Result<Vec<Op<A>>, Error> {
+    let cob = cob::get::<NonEmpty<cob::Entry>, _>(repo, type_name, id)
+        .map_err(cob::store::Error::from)?
+        .ok_or(Error::NotFound)?;
+    let ops = cob
+        .object
+        .iter()
+        .map(Op::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ops)
+}
+
 #[cfg(test)]
 mod routes {
     use std::net::SocketAddr;
 
     use axum::body::Body;
     use axum::extract::connect_info::MockConnectInfo;
-    use axum::http::StatusCode;
+    use axum::http::{header, StatusCode};
     use pretty_assertions::assert_eq;
     use radicle::storage::ReadStorage;
     use serde_json::json;
@@ -1049,6 +1376,40 @@ mod routes {
         );
     }
 
+    #[tokio::test]
+    async fn test_projects_root_not_modified() {
+        let tmp = tempfile::tempdir().unwrap();
+        let seed = seed(tmp.path());
+        let app = super::router(seed.clone())
+            .layer(MockConnectInfo(SocketAddr::from(([127, 0, 0, 1], 8080))));
+        let response = get(&app, "/projects?show=all").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let last_modified = response
+            .header(header::LAST_MODIFIED)
+            .expect("`Last-Modified` header is set")
+            .to_owned();
+
+        let response = get_with_headers(
+            &app,
+            "/projects?show=all",
+            [(header::IF_MODIFIED_SINCE, last_modified)],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let response = get_with_headers(
+            &app,
+            "/projects?show=all",
+            [(
+                header::IF_MODIFIED_SINCE,
+                String::from("Thu, 01 Jan 1970 00:00:00 GMT"),
+            )],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn test_projects() {
         let tmp = tempfile::tempdir().unwrap();
@@ -1740,12 +2101,78 @@ mod routes {
                     "resolved": false,
                   }
                 ],
-                "labels": []
+                "labels": [],
+                "relations": [],
+                "links": []
               }
             ])
         );
     }
 
+    #[tokio::test]
+    async fn test_projects_issues_filters() {
+        let tmp = tempfile::tempdir().unwrap();
+        let app = super::router(seed(tmp.path()));
+        let ids = |json: serde_json::Value| {
+            json.as_array()
+                .unwrap()
+                .iter()
+                .map(|i| i["id"].as_str().unwrap().to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        for (query, expected) in [
+            (format!("text=HELLO%20WORLD&author={DID}"), vec![ISSUE_ID]),
+            (
+                format!("since={TIMESTAMP}&until={}", TIMESTAMP + 1),
+                vec![ISSUE_ID],
+            ),
+            (format!("until={}", TIMESTAMP - 1), vec![]),
+            (String::from("text=goodbye"), vec![]),
+            (String::from("label=bug"), vec![]),
+            (format!("assignee={DID}"), vec![]),
+            (String::from("state=closed&text=hello"), vec![]),
+        ] {
+            let response = get(&app, format!("/projects/{RID}/issues?{query}")).await;
+
+            assert_eq!(response.status(), StatusCode::OK, "{query}");
+            assert_eq!(ids(response.json().await), expected, "{query}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_projects_issues_create_read_scope() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ctx = contributor(tmp.path());
+        let app = super::router(ctx.to_owned());
+
+        create_session_with(ctx, [crate::api::auth::Scope::Read].into()).await;
+
+        let body = serde_json::to_vec(&json!({
+            "title": "Issue #2",
+            "description": "Change 'hello world' to 'hello everyone'",
+            "labels": [],
+            "embeds": [],
+            "assignees": [],
+        }))
+        .unwrap();
+
+        let response = post(
+            &app,
+            format!("/projects/{CONTRIBUTOR_RID}/issues"),
+            Some(Body::from(body)),
+            Some(SESSION_ID.to_string()),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = get(&app, format!("/projects/{CONTRIBUTOR_RID}/issues")).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.json().await.as_array().unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_projects_issues_create() {
         const CREATED_ISSUE_ID: &str = "fcd0d5940b55df596cf8079fd1845903f1104bcd";
@@ -1840,6 +2267,8 @@ mod routes {
               "labels": [
                   "bug",
               ],
+              "relations": [],
+              "links": [],
             })
         );
     }
@@ -2009,6 +2438,8 @@ mod routes {
                 },
               ],
               "labels": [],
+              "relations": [],
+              "links": [],
             })
         );
     }
@@ -2087,6 +2518,8 @@ mod routes {
                 },
               ],
               "labels": [],
+              "relations": [],
+              "links": [],
             })
         );
     }
@@ -2204,10 +2637,93 @@ mod routes {
                 },
               ],
               "labels": [],
+              "relations": [],
+              "links": [],
             })
         );
     }
 
+    #[tokio::test]
+    async fn test_projects_issues_timeline() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ctx = contributor(tmp.path());
+        let app = super::router(ctx.to_owned());
+
+        create_session(ctx).await;
+
+        let body = serde_json::to_vec(&json!({
+          "type": "comment",
+          "body": "This comment will be redacted",
+          "replyTo": ISSUE_DISCUSSION_ID,
+        }))
+        .unwrap();
+        let response = patch(
+            &app,
+            format!("/projects/{CONTRIBUTOR_RID}/issues/{ISSUE_DISCUSSION_ID}"),
+            Some(Body::from(body)),
+            Some(SESSION_ID.to_string()),
+        )
+        .await;
+        let comment = response.json().await["id"].clone();
+
+        let body = serde_json::to_vec(&json!({
+          "type": "label",
+          "labels": ["bug"],
+        }))
+        .unwrap();
+        let response = patch(
+            &app,
+            format!("/projects/{CONTRIBUTOR_RID}/issues/{ISSUE_DISCUSSION_ID}"),
+            Some(Body::from(body)),
+            Some(SESSION_ID.to_string()),
+        )
+        .await;
+        assert_eq!(response.success().await, true);
+
+        let body = serde_json::to_vec(&json!({
+          "type": "comment.redact",
+          "id": comment,
+        }))
+        .unwrap();
+        let response = patch(
+            &app,
+            format!("/projects/{CONTRIBUTOR_RID}/issues/{ISSUE_DISCUSSION_ID}"),
+            Some(Body::from(body)),
+            Some(SESSION_ID.to_string()),
+        )
+        .await;
+        assert_eq!(response.success().await, true);
+
+        let response = get(
+            &app,
+            format!("/projects/{CONTRIBUTOR_RID}/issues/{ISSUE_DISCUSSION_ID}/timeline"),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let timeline = response.json().await;
+        let events = timeline
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| (e["type"].as_str().unwrap(), e["redacted"].as_bool().unwrap()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            events,
+            vec![
+                ("comment", false),
+                ("edit", false),
+                ("comment", true),
+                ("label", false),
+                ("comment.redact", false),
+            ]
+        );
+        assert_eq!(timeline[0]["id"], ISSUE_DISCUSSION_ID);
+        assert_eq!(timeline[2]["id"], comment);
+        assert_eq!(timeline[3]["labels"], json!(["bug"]));
+    }
+
     #[tokio::test]
     async fn test_projects_patches() {
         let tmp = tempfile::tempdir().unwrap();
@@ -2229,6 +2745,7 @@ mod routes {
                 "state": { "status": "open" },
                 "target": "delegates",
                 "labels": [],
+                "links": [],
                 "merges": [],
                 "assignees": [],
                 "revisions": [
@@ -2285,6 +2802,7 @@ mod routes {
                 "state": { "status": "open" },
                 "target": "delegates",
                 "labels": [],
+                "links": [],
                 "merges": [],
                 "assignees": [],
                 "revisions": [
@@ -2380,6 +2898,7 @@ mod routes {
                 "state": { "status": "open" },
                 "target": "delegates",
                 "labels": [],
+                "links": [],
                 "merges": [],
                 "assignees": [],
                 "revisions": [
@@ -2456,6 +2975,7 @@ mod routes {
               "state": { "status": "open" },
               "target": "delegates",
               "labels": [],
+              "links": [],
               "merges": [],
               "assignees": [
                 {
@@ -2497,6 +3017,91 @@ mod routes {
         );
     }
 
+    #[tokio::test]
+    async fn test_projects_labels() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ctx = contributor(tmp.path());
+        let app = super::router(ctx.to_owned());
+        create_session(ctx).await;
+
+        for (path, labels) in [
+            (
+                format!("issues/{ISSUE_DISCUSSION_ID}"),
+                json!(["bug", "ui"]),
+            ),
+            (format!("patches/{CONTRIBUTOR_PATCH_ID}"), json!(["bug"])),
+        ] {
+            let body = serde_json::to_vec(&json!({ "type": "label", "labels": labels })).unwrap();
+            let response = patch(
+                &app,
+                format!("/projects/{CONTRIBUTOR_RID}/{path}"),
+                Some(Body::from(body)),
+                Some(SESSION_ID.to_string()),
+            )
+            .await;
+            assert_eq!(response.success().await, true);
+        }
+
+        let response = get(&app, format!("/projects/{CONTRIBUTOR_RID}/labels")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.json().await,
+            json!([
+              { "name": "bug", "issues": 1, "patches": 1 },
+              { "name": "ui", "issues": 1, "patches": 0 },
+            ])
+        );
+
+        // Renaming requires a valid session.
+        let body = serde_json::to_vec(&json!({ "name": "ui" })).unwrap();
+        let response = patch(
+            &app,
+            format!("/projects/{CONTRIBUTOR_RID}/labels/bug"),
+            Some(Body::from(body.clone())),
+            Some(String::from("invalid")),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // Renaming `bug` to `ui` merges the two labels.
+        let response = patch(
+            &app,
+            format!("/projects/{CONTRIBUTOR_RID}/labels/bug"),
+            Some(Body::from(body)),
+            Some(SESSION_ID.to_string()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.json().await,
+            json!({
+              "success": true,
+              "issues": [ISSUE_DISCUSSION_ID],
+              "patches": [CONTRIBUTOR_PATCH_ID],
+            })
+        );
+
+        let response = get(&app, format!("/projects/{CONTRIBUTOR_RID}/labels")).await;
+        assert_eq!(
+            response.json().await,
+            json!([{ "name": "ui", "issues": 1, "patches": 1 }])
+        );
+
+        let response = get(
+            &app,
+            format!("/projects/{CONTRIBUTOR_RID}/issues/{ISSUE_DISCUSSION_ID}"),
+        )
+        .await;
+        assert_eq!(response.json().await["labels"], json!(["ui"]));
+
+        let response = get(
+            &app,
+            format!("/projects/{CONTRIBUTOR_RID}/patches/{CONTRIBUTOR_PATCH_ID}"),
+        )
+        .await;
+        assert_eq!(response.json().await["labels"], json!(["ui"]));
+    }
+
     #[tokio::test]
     async fn test_projects_patches_label() {
         let tmp = tempfile::tempdir().unwrap();
@@ -2539,6 +3144,7 @@ mod routes {
                 "bug",
                 "design"
               ],
+              "links": [],
               "merges": [],
               "assignees": [],
               "revisions": [
@@ -2616,6 +3222,7 @@ mod routes {
               "state": { "status": "open" },
               "target": "delegates",
               "labels": [],
+              "links": [],
               "merges": [],
               "assignees": [],
               "revisions": [
@@ -2680,6 +3287,88 @@ mod routes {
         );
     }
 
+    #[tokio::test]
+    async fn test_projects_patches_stack() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ctx = contributor(tmp.path());
+        let app = super::router(ctx.to_owned());
+        let response = get(&app, format!("/projects/{CONTRIBUTOR_RID}/patches/stack")).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.json().await,
+            json!([
+              {
+                "id": CONTRIBUTOR_PATCH_ID,
+                "title": "A new `hello world`",
+                "stackedOn": null,
+                "depth": 0,
+              }
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_projects_patches_revisions_summary() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ctx = contributor(tmp.path());
+        let app = super::router(ctx.to_owned());
+        create_session(ctx).await;
+        let body = serde_json::to_vec(&json!({
+          "type": "revision",
+          "description": "This is a new revision",
+          "base": INITIAL_COMMIT,
+          "oid": HEAD,
+        }))
+        .unwrap();
+        let response = patch(
+            &app,
+            format!("/projects/{CONTRIBUTOR_RID}/patches/{CONTRIBUTOR_PATCH_ID}"),
+            Some(Body::from(body)),
+            Some(SESSION_ID.to_string()),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = get(
+            &app,
+            format!("/projects/{CONTRIBUTOR_RID}/patches/{CONTRIBUTOR_PATCH_ID}/revisions"),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let revisions = response.json().await;
+        let revisions = revisions.as_array().unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(
+            revisions[0],
+            json!({
+              "id": CONTRIBUTOR_PATCH_ID,
+              "base": PARENT,
+              "oid": HEAD,
+              "timestamp": TIMESTAMP,
+              "stats": {
+                "filesChanged": 3,
+                "insertions": 2,
+                "deletions": 1,
+              },
+            })
+        );
+        assert_eq!(revisions[1]["base"], json!(INITIAL_COMMIT));
+        assert_eq!(revisions[1]["oid"], json!(HEAD));
+        assert_eq!(revisions[1]["timestamp"], json!(TIMESTAMP));
+        assert_eq!(
+            revisions[1]["stats"],
+            json!({
+              "filesChanged": 1,
+              "insertions": 1,
+              "deletions": 0,
+            })
+        );
+    }
+
     #[tokio::test]
     async fn test_projects_patches_edit() {
         let tmp = tempfile::tempdir().unwrap();
@@ -2721,6 +3410,7 @@ mod routes {
               "state": { "status": "open" },
               "target": "delegates",
               "labels": [],
+              "links": [],
               "merges": [],
               "assignees": [],
               "revisions": [
@@ -2843,6 +3533,7 @@ mod routes {
               "state": { "status": "open" },
               "target": "delegates",
               "labels": [],
+              "links": [],
               "merges": [],
               "assignees": [],
               "revisions": [
@@ -3023,6 +3714,7 @@ mod routes {
               "state": { "status": "open" },
               "target": "delegates",
               "labels": [],
+              "links": [],
               "merges": [],
               "assignees": [],
               "revisions": [
@@ -3269,6 +3961,7 @@ mod routes {
               "state": { "status": "open" },
               "target": "delegates",
               "labels": [],
+              "links": [],
               "merges": [],
               "assignees": [],
               "revisions": [
@@ -3432,6 +4125,7 @@ mod routes {
               },
               "target": "delegates",
               "labels": [],
+              "links": [],
               "merges": [{
                   "author": {
                     "id": CONTRIBUTOR_DID,
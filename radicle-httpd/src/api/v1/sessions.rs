@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::iter::repeat_with;
 
 use axum::extract::State;
@@ -10,7 +11,7 @@ use radicle::crypto::{PublicKey, Signature};
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-use crate::api::auth::{self, AuthState, Session};
+use crate::api::auth::{self, AuthState, Scope, Session};
 use crate::api::error::Error;
 use crate::api::json;
 use crate::api::Context;
@@ -34,9 +35,25 @@ struct AuthChallenge {
     pk: PublicKey,
 }
 
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct SessionRequest {
+    /// Scopes requested for the session. All scopes are granted if not specified.
+    scopes: Option<BTreeSet<Scope>>,
+}
+
 /// Create session.
 /// `POST /sessions`
-async fn session_create_handler(State(ctx): State<Context>) -> impl IntoResponse {
+async fn session_create_handler(
+    State(ctx): State<Context>,
+    request: Option<Json<SessionRequest>>,
+) -> impl IntoResponse {
+    let scopes = match request.and_then(|Json(r)| r.scopes) {
+        Some(mut scopes) => {
+            scopes.insert(Scope::Read);
+            scopes
+        }
+        None => Scope::all(),
+    };
     let mut rng = fastrand::Rng::new();
     let session_id = repeat_with(|| rng.alphanumeric())
         .take(32)
@@ -50,6 +67,7 @@ async fn session_create_handler(State(ctx): State<Context>) -> impl IntoResponse
         expires_at: OffsetDateTime::now_utc()
             .checked_add(auth::UNAUTHORIZED_SESSIONS_EXPIRATION)
             .unwrap(),
+        scopes,
     };
     let mut sessions = ctx.sessions.write().await;
     sessions.insert(session_id.clone(), session.clone());
@@ -2,12 +2,14 @@ use axum::extract::path::ErrorKind;
 use axum::extract::rejection::{PathRejection, QueryRejection};
 use axum::extract::FromRequestParts;
 use axum::http::request::Parts;
-use axum::http::{header, StatusCode};
+use axum::http::{header, HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::{async_trait, Json};
 
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use time::format_description::well_known::Rfc2822;
+use time::OffsetDateTime;
 
 pub struct Path<T>(pub T);
 
@@ -97,3 +99,54 @@ pub fn immutable_response(data: impl serde::Serialize) -> impl IntoResponse {
         Json(data),
     )
 }
+
+/// Format a time as an HTTP date, eg. `Sun, 06 Nov 1994 08:49:37 GMT`.
+pub fn http_date(time: OffsetDateTime) -> String {
+    let time = time.to_offset(time::UtcOffset::UTC);
+
+    format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+        &time.weekday().to_string()[..3],
+        time.day(),
+        &time.month().to_string()[..3],
+        time.year(),
+        time.hour(),
+        time.minute(),
+        time.second(),
+    )
+}
+
+/// Check whether a resource last modified at the given time was modified since the
+/// time given in the request's `If-Modified-Since` header.
+///
+/// Returns `true` if the header is missing or invalid.
+pub fn modified_since(headers: &HeaderMap, last_modified: OffsetDateTime) -> bool {
+    let Some(since) = headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| OffsetDateTime::parse(v, &Rfc2822).ok())
+    else {
+        return true;
+    };
+    // HTTP dates have a one second resolution.
+    last_modified.unix_timestamp() > since.unix_timestamp()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_http_date_roundtrip() {
+        let time = OffsetDateTime::from_unix_timestamp(784111777).unwrap();
+        let date = http_date(time);
+        assert_eq!(date, "Sun, 06 Nov 1994 08:49:37 GMT");
+
+        let mut headers = HeaderMap::new();
+        assert!(modified_since(&headers, time));
+
+        headers.insert(header::IF_MODIFIED_SINCE, date.parse().unwrap());
+        assert!(!modified_since(&headers, time));
+        assert!(modified_since(&headers, time + time::Duration::seconds(1)));
+    }
+}
@@ -5,7 +5,7 @@ use std::sync::Arc;
 use std::{env, fs};
 
 use axum::body::{Body, Bytes};
-use axum::http::{Method, Request};
+use axum::http::{header, Method, Request};
 use axum::Router;
 use serde_json::Value;
 use time::OffsetDateTime;
@@ -276,6 +276,11 @@ fn seed_with_signer<G: Signer>(dir: &Path, profile: radicle::Profile, signer: &G
 
 /// Adds an authorized session to the Context::sessions HashMap.
 pub async fn create_session(ctx: Context) {
+    create_session_with(ctx, auth::Scope::all()).await
+}
+
+/// Adds an authorized session with the given scopes to the Context::sessions HashMap.
+pub async fn create_session_with(ctx: Context, scopes: BTreeSet<auth::Scope>) {
     let issued_at = OffsetDateTime::now_utc();
     let mut sessions = ctx.sessions().write().await;
     sessions.insert(
@@ -288,6 +293,7 @@ pub async fn create_session(ctx: Context) {
             expires_at: issued_at
                 .checked_add(auth::AUTHORIZED_SESSIONS_EXPIRATION)
                 .unwrap(),
+            scopes,
         },
     );
 }
@@ -301,6 +307,29 @@ pub async fn get(app: &Router, path: impl ToString) -> Response {
     )
 }
 
+pub async fn get_with_headers(
+    app: &Router,
+    path: impl ToString,
+    headers: impl IntoIterator<Item = (header::HeaderName, String)>,
+) -> Response {
+    let mut request = request(path, Method::GET, None, None);
+    for (name, value) in headers {
+        request
+            .headers_mut()
+            .insert(name, header::HeaderValue::from_str(&value).unwrap());
+    }
+    Response(app.clone().oneshot(request).await.unwrap())
+}
+
+pub async fn get_auth(app: &Router, path: impl ToString, auth: Option<String>) -> Response {
+    Response(
+        app.clone()
+            .oneshot(request(path, Method::GET, None, auth))
+            .await
+            .unwrap(),
+    )
+}
+
 pub async fn post(
     app: &Router,
     path: impl ToString,
@@ -387,6 +416,10 @@ impl Response {
         self.0.status()
     }
 
+    pub fn header(&self, name: header::HeaderName) -> Option<&str> {
+        self.0.headers().get(name).and_then(|v| v.to_str().ok())
+    }
+
     pub async fn body(self) -> Bytes {
         axum::body::to_bytes(self.0.into_body(), usize::MAX)
             .await
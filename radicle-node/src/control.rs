@@ -168,6 +168,11 @@ where
 
             CommandResult::Okay(refs).to_writer(writer)?;
         }
+        Command::AnnounceCobRefs { rid, type_name, id } => {
+            let refs = handle.announce_cob_refs(rid, type_name, id)?;
+
+            CommandResult::Okay(refs).to_writer(writer)?;
+        }
         Command::AnnounceInventory => {
             if let Err(e) = handle.announce_inventory() {
                 return Err(CommandError::Runtime(e));
@@ -191,9 +196,18 @@ where
             }
             Err(e) => return Err(CommandError::Runtime(e)),
         },
-        Command::Status => {
-            CommandResult::ok().to_writer(writer).ok();
-        }
+        Command::Status => match handle.status() {
+            Ok(status) => {
+                CommandResult::Okay(status).to_writer(writer)?;
+            }
+            Err(e) => return Err(CommandError::Runtime(e)),
+        },
+        Command::Metrics => match handle.metrics() {
+            Ok(metrics) => {
+                CommandResult::Okay(metrics).to_writer(writer)?;
+            }
+            Err(e) => return Err(CommandError::Runtime(e)),
+        },
         Command::NodeId => match handle.nid() {
             Ok(nid) => {
                 CommandResult::Okay(nid).to_writer(writer)?;
@@ -110,9 +110,19 @@ fn execute() -> anyhow::Result<()> {
     log::info!(target: "node", "Node ID is {}", signer.public_key());
 
     let config = options.config.unwrap_or_else(|| home.config());
-    let mut config = profile::Config::load(&config)?;
+    let mut config = match profile::Config::load(&config) {
+        Err(profile::ConfigError::Invalid(path, errors)) => {
+            for e in errors {
+                log::error!(target: "node", "Invalid configuration: {e}");
+            }
+            anyhow::bail!("invalid configuration in {}", path.display());
+        }
+        result => result?,
+    };
 
-    // Add the preferred seeds as persistent peers so that we reconnect to them automatically.
+    // Add the preferred seeds as persistent peers so that we reconnect to them automatically,
+    // and keep their order for fetch failover.
+    config.node.preferred_seeds = config.preferred_seeds.iter().map(|s| s.id).collect();
     config.node.connect.extend(config.preferred_seeds);
 
     let proxy = net::SocketAddr::new(net::Ipv4Addr::LOCALHOST.into(), 9050);
@@ -4,7 +4,7 @@ pub mod thread;
 use std::os::unix::net::UnixListener;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use std::{fs, io, net};
+use std::{fs, io, net, time};
 
 use crossbeam_channel as chan;
 use cyphernet::Ecdh;
@@ -32,11 +32,17 @@ use crate::service::{gossip, policy, Event};
 use crate::wire;
 use crate::wire::{Decode, Wire};
 use crate::worker;
-use crate::{service, LocalTime};
+use crate::service;
+use crate::service::clock::{Clock as _, SystemClock};
 
 pub use handle::Error as HandleError;
 pub use handle::Handle;
 
+/// Maximum time to wait for ongoing fetches to finish when shutting down.
+pub const DRAIN_TIMEOUT: time::Duration = time::Duration::from_secs(30);
+/// How often to check whether ongoing fetches have finished when shutting down.
+pub const DRAIN_INTERVAL: time::Duration = time::Duration::from_millis(100);
+
 /// A client error.
 #[derive(Error, Debug)]
 pub enum Error {
@@ -151,7 +157,7 @@ impl Runtime {
         let node_dir = home.node();
         let network = config.network;
         let rng = fastrand::Rng::new();
-        let clock = LocalTime::now();
+        let clock = SystemClock.now();
         let storage = Storage::open(home.storage(), git::UserInfo { alias, key: id })?;
         let scope = config.scope;
         let policy = config.policy;
@@ -253,9 +259,13 @@ impl Runtime {
 
         let nid = *signer.public_key();
         let fetch = worker::FetchConfig {
-            limit: FetchLimit::default(),
+            limit: FetchLimit {
+                quota: config.limits.quota,
+                ..FetchLimit::default()
+            },
             local: nid,
             expiry: worker::garbage::Expiry::default(),
+            filter: config.exclude_refs.iter().cloned().collect(),
         };
         let pool = worker::Pool::with(
             worker_recv,
@@ -308,7 +318,19 @@ impl Runtime {
         });
         let _signals = thread::spawn(&self.id, "signals", move || {
             if let Ok(()) = self.signals.recv() {
-                log::info!(target: "node", "Termination signal received; shutting down..");
+                log::info!(target: "node", "Termination signal received; draining fetches..");
+
+                match drain(&self.handle, DRAIN_TIMEOUT) {
+                    Ok(true) => {}
+                    Ok(false) => log::warn!(
+                        target: "node",
+                        "Ongoing fetches did not finish after {}s; aborting them..",
+                        DRAIN_TIMEOUT.as_secs()
+                    ),
+                    Err(e) => log::error!(target: "node", "Error draining fetches: {e}"),
+                }
+                log::info!(target: "node", "Shutting down..");
+
                 self.handle.shutdown().ok();
             }
         });
@@ -327,3 +349,24 @@ impl Runtime {
         Ok(())
     }
 }
+
+/// Stop initiating fetches and wait for the ongoing ones to finish, up to the given timeout.
+/// Returns `false` if the timeout was reached before all fetches finished.
+///
+/// Fetches that are aborted by the subsequent shutdown don't leave partial state behind,
+/// since refs are only updated once a fetch has completed.
+fn drain(handle: &Handle, timeout: time::Duration) -> Result<bool, HandleError> {
+    let started = time::Instant::now();
+
+    handle.drain()?;
+
+    loop {
+        if handle.status()?.pending_fetches == 0 {
+            return Ok(true);
+        }
+        if started.elapsed() >= timeout {
+            return Ok(false);
+        }
+        std::thread::sleep(DRAIN_INTERVAL);
+    }
+}
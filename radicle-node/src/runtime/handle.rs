@@ -5,7 +5,8 @@ use std::sync::Arc;
 use std::{fmt, io, time};
 
 use crossbeam_channel as chan;
-use radicle::node::{ConnectOptions, ConnectResult, Link, Seeds};
+use radicle::cob;
+use radicle::node::{ConnectOptions, ConnectResult, Link, Seeds, Status};
 use radicle::storage::refs::RefsAt;
 use reactor::poller::popol::PopolWaker;
 use thiserror::Error;
@@ -117,6 +118,13 @@ impl Handle {
         self.controller.cmd(wire::Control::Flush { remote, stream })
     }
 
+    /// Stop initiating fetches, letting ongoing fetches finish. See [`service::Service::drain`].
+    pub fn drain(&self) -> Result<(), Error> {
+        self.command(service::Command::Drain)?;
+
+        Ok(())
+    }
+
     pub(crate) fn command(&self, cmd: service::Command) -> Result<(), io::Error> {
         self.controller.cmd(wire::Control::User(cmd))
     }
@@ -211,6 +219,18 @@ impl radicle::node::Handle for Handle {
         receiver.recv().map_err(Error::from)
     }
 
+    fn status(&self) -> Result<Status, Self::Error> {
+        let (sender, receiver) = chan::bounded(1);
+        self.command(service::Command::Status(sender))?;
+        receiver.recv().map_err(Error::from)
+    }
+
+    fn metrics(&self) -> Result<String, Self::Error> {
+        let (sender, receiver) = chan::bounded(1);
+        self.command(service::Command::Metrics(sender))?;
+        receiver.recv().map_err(Error::from)
+    }
+
     fn fetch(
         &mut self,
         id: RepoId,
@@ -252,6 +272,17 @@ impl radicle::node::Handle for Handle {
         receiver.recv().map_err(Error::from)
     }
 
+    fn announce_cob_refs(
+        &mut self,
+        rid: RepoId,
+        type_name: cob::TypeName,
+        id: cob::ObjectId,
+    ) -> Result<RefsAt, Error> {
+        let (sender, receiver) = chan::bounded(1);
+        self.command(service::Command::AnnounceCobRefs(rid, type_name, id, sender))?;
+        receiver.recv().map_err(Error::from)
+    }
+
     fn announce_inventory(&mut self) -> Result<(), Error> {
         self.command(service::Command::AnnounceInventory)
             .map_err(Error::from)
@@ -285,6 +316,7 @@ impl radicle::node::Handle for Handle {
                     },
                     addr: s.addr.clone(),
                     state: s.state.clone(),
+                    rtt: s.state.rtt().map(|rtt| rtt.as_millis() as u64),
                 })
                 .collect();
             sender.send(sessions).ok();
@@ -2,15 +2,17 @@
 #![allow(clippy::collapsible_match)]
 #![allow(clippy::collapsible_if)]
 #![warn(clippy::unwrap_used)]
+pub mod clock;
 pub mod filter;
 pub mod gossip;
 pub mod io;
 pub mod limitter;
 pub mod message;
+pub mod metrics;
 pub mod session;
 
 use std::collections::hash_map::Entry;
-use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 use std::{fmt, net, time};
@@ -21,6 +23,8 @@ use localtime::{LocalDuration, LocalTime};
 use log::*;
 use nonempty::NonEmpty;
 
+use radicle::cob;
+use radicle::git;
 use radicle::node;
 use radicle::node::address;
 use radicle::node::address::Store as _;
@@ -32,7 +36,7 @@ use radicle::node::seed;
 use radicle::node::seed::Store as _;
 use radicle::node::{ConnectOptions, Penalty, Severity};
 use radicle::storage::refs::SIGREFS_BRANCH;
-use radicle::storage::{Inventory, RepositoryError};
+use radicle::storage::{Inventory, ReadRepository, RepositoryError};
 
 use crate::crypto;
 use crate::crypto::{Signer, Verified};
@@ -69,8 +73,6 @@ use self::policy::NamespacesError;
 
 /// How often to run the "idle" task.
 pub const IDLE_INTERVAL: LocalDuration = LocalDuration::from_secs(30);
-/// How often to run the "announce" task.
-pub const ANNOUNCE_INTERVAL: LocalDuration = LocalDuration::from_mins(60);
 /// How often to run the "sync" task.
 pub const SYNC_INTERVAL: LocalDuration = LocalDuration::from_secs(60);
 /// How often to run the "prune" task.
@@ -96,6 +98,12 @@ pub const MAX_RECONNECTION_DELTA: LocalDuration = LocalDuration::from_mins(60);
 pub const CONNECTION_RETRY_DELTA: LocalDuration = LocalDuration::from_mins(10);
 /// How long to wait for a fetch to stall before aborting.
 pub const FETCH_TIMEOUT: time::Duration = time::Duration::from_secs(9);
+/// Maximum number of fetches kept in the fetch retry queue.
+pub const MAX_FETCH_RETRIES: usize = 256;
+/// How long fetches are kept in the fetch retry queue before they are dropped.
+pub const FETCH_RETRY_EXPIRY: LocalDuration = LocalDuration::from_mins(60 * 24);
+/// How long satisfied fetches are remembered, so that fetching the same refs again is skipped.
+pub const FETCH_CACHE_EXPIRY: LocalDuration = LocalDuration::from_mins(1);
 
 /// Maximum external address limit imposed by message size limits.
 pub use message::ADDRESS_LIMIT;
@@ -160,7 +168,12 @@ pub enum Error {
 
 /// A store for all node data.
 pub trait Store:
-    address::Store + gossip::Store + routing::Store + seed::Store + node::refs::Store
+    address::Store
+    + gossip::Store
+    + routing::Store
+    + seed::Store
+    + node::refs::Store
+    + node::retry::Store
 {
 }
 
@@ -173,6 +186,8 @@ pub type QueryState = dyn Fn(&dyn ServiceState) -> Result<(), CommandError> + Se
 pub enum Command {
     /// Announce repository references for given repository to peers.
     AnnounceRefs(RepoId, chan::Sender<RefsAt>),
+    /// Announce repository references after the given collaborative object was updated.
+    AnnounceCobRefs(RepoId, cob::TypeName, cob::ObjectId, chan::Sender<RefsAt>),
     /// Announce local repositories to peers.
     AnnounceInventory,
     /// Update local inventory.
@@ -185,6 +200,10 @@ pub enum Command {
     Config(chan::Sender<Config>),
     /// Get the node's listen addresses.
     ListenAddrs(chan::Sender<Vec<std::net::SocketAddr>>),
+    /// Get the node's status.
+    Status(chan::Sender<node::Status>),
+    /// Get the node's metrics, in the Prometheus text format.
+    Metrics(chan::Sender<String>),
     /// Lookup seeds for the given repository in the routing table.
     Seeds(RepoId, chan::Sender<Seeds>),
     /// Fetch the given repository from the network.
@@ -199,18 +218,25 @@ pub enum Command {
     Unfollow(NodeId, chan::Sender<bool>),
     /// Query the internal service state.
     QueryState(Arc<QueryState>, chan::Sender<Result<(), CommandError>>),
+    /// Stop initiating fetches, in preparation for a shutdown.
+    Drain,
 }
 
 impl fmt::Debug for Command {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::AnnounceRefs(id, _) => write!(f, "AnnounceRefs({id})"),
+            Self::AnnounceCobRefs(id, type_name, object, _) => {
+                write!(f, "AnnounceCobRefs({id}, {type_name}, {object})")
+            }
             Self::AnnounceInventory => write!(f, "AnnounceInventory"),
             Self::UpdateInventory(rid, _) => write!(f, "UpdateInventory({rid})"),
             Self::Connect(id, addr, opts) => write!(f, "Connect({id}, {addr}, {opts:?})"),
             Self::Disconnect(id) => write!(f, "Disconnect({id})"),
             Self::Config(_) => write!(f, "Config"),
             Self::ListenAddrs(_) => write!(f, "ListenAddrs"),
+            Self::Status(_) => write!(f, "Status"),
+            Self::Metrics(_) => write!(f, "Metrics"),
             Self::Seeds(id, _) => write!(f, "Seeds({id})"),
             Self::Fetch(id, node, _, _) => write!(f, "Fetch({id}, {node})"),
             Self::Seed(id, scope, _) => write!(f, "Seed({id}, {scope})"),
@@ -218,6 +244,7 @@ impl fmt::Debug for Command {
             Self::Follow(id, _, _) => write!(f, "Follow({id})"),
             Self::Unfollow(id, _) => write!(f, "Unfollow({id})"),
             Self::QueryState { .. } => write!(f, "QueryState(..)"),
+            Self::Drain => write!(f, "Drain"),
         }
     }
 }
@@ -244,6 +271,8 @@ enum TryFetchError<'a> {
     SessionNotConnected,
     #[error("session fetch capacity reached; cannot initiate fetch")]
     SessionCapacityReached,
+    #[error("node is shutting down; cannot initiate fetch")]
+    Draining,
     #[error(transparent)]
     Namespaces(#[from] NamespacesError),
 }
@@ -291,6 +320,64 @@ impl PartialEq for QueuedFetch {
     }
 }
 
+/// Queue of fetches waiting to be processed.
+///
+/// Fetches are grouped by repository, and repositories take turns being served, so that
+/// a repository with many queued fetches can't starve the others.
+#[derive(Debug, Default)]
+struct FetchQueue {
+    /// Repositories with queued fetches, in the order in which they will be served.
+    turns: VecDeque<RepoId>,
+    /// Queued fetches, per repository, in the order in which they were queued.
+    fetches: HashMap<RepoId, VecDeque<QueuedFetch>>,
+}
+
+impl FetchQueue {
+    /// Number of queued fetches.
+    fn len(&self) -> usize {
+        self.fetches.values().map(VecDeque::len).sum()
+    }
+
+    /// Check whether an equivalent fetch is already queued.
+    fn contains(&self, fetch: &QueuedFetch) -> bool {
+        self.fetches
+            .get(&fetch.rid)
+            .is_some_and(|queue| queue.contains(fetch))
+    }
+
+    /// Queue a fetch. If the repository has no other queued fetches, it is served last.
+    fn push(&mut self, fetch: QueuedFetch) {
+        let queue = self.fetches.entry(fetch.rid).or_default();
+        if queue.is_empty() {
+            self.turns.push_back(fetch.rid);
+        }
+        queue.push_back(fetch);
+    }
+
+    /// Dequeue the oldest fetch of the next repository to be served, skipping repositories
+    /// that aren't `ready`. If the repository has other queued fetches, it is then served
+    /// after all other repositories.
+    fn pop(&mut self, mut ready: impl FnMut(&RepoId) -> bool) -> Option<QueuedFetch> {
+        let ix = self.turns.iter().position(|rid| ready(rid))?;
+        let rid = self.turns.remove(ix)?;
+        let queue = self.fetches.get_mut(&rid)?;
+        let fetch = queue.pop_front();
+
+        if queue.is_empty() {
+            self.fetches.remove(&rid);
+        } else {
+            self.turns.push_back(rid);
+        }
+        fetch
+    }
+
+    /// Remove all queued fetches.
+    fn drain(&mut self) -> impl Iterator<Item = QueuedFetch> + '_ {
+        self.turns.clear();
+        self.fetches.drain().flat_map(|(_, queue)| queue)
+    }
+}
+
 /// Holds all node stores.
 #[derive(Debug)]
 pub struct Stores<D>(D);
@@ -348,6 +435,16 @@ where
     pub fn refs_mut(&mut self) -> &mut impl node::refs::Store {
         &mut self.0
     }
+
+    /// Get the database as a fetch retry store.
+    pub fn retries(&self) -> &impl node::retry::Store {
+        &self.0
+    }
+
+    /// Get the database as a fetch retry store, mutably.
+    pub fn retries_mut(&mut self) -> &mut impl node::retry::Store {
+        &mut self.0
+    }
 }
 
 impl<D> From<D> for Stores<D> {
@@ -382,7 +479,21 @@ pub struct Service<D, S, G> {
     /// Ongoing fetches.
     fetching: HashMap<RepoId, FetchState>,
     /// Fetch queue.
-    queue: VecDeque<QueuedFetch>,
+    queue: FetchQueue,
+    /// Recently satisfied fetches, and when they completed. See [`FETCH_CACHE_EXPIRY`].
+    fetched: HashMap<(RepoId, RefsAt), LocalTime>,
+    /// Whether the service is draining, ie. letting ongoing fetches finish before a shutdown.
+    /// No new fetches are initiated while draining.
+    draining: bool,
+    /// Preferred seeds that a fetch failed from, and which are skipped when looking
+    /// for the active seed.
+    failed_seeds: HashSet<NodeId>,
+    /// Repositories with changes to our own refs that are yet to be announced, and when
+    /// they should be announced.
+    pending_refs: HashMap<RepoId, LocalTime>,
+    /// Fetches left over from a previous run, to be retried once we're connected to the
+    /// node they were from. See [`MAX_FETCH_RETRIES`].
+    retries: HashMap<NodeId, BTreeSet<RepoId>>,
     /// Request/connection rate limitter.
     limiter: RateLimiter,
     /// Current seeded repositories bloom filter.
@@ -395,12 +506,16 @@ pub struct Service<D, S, G> {
     last_prune: LocalTime,
     /// Last time the inventory was announced.
     last_announce: LocalTime,
+    /// Next time the inventory is scheduled to be announced.
+    next_announce: LocalTime,
     /// Last timestamp used for announcements.
     last_timestamp: Timestamp,
     /// Time when the service was initialized, or `None` if it wasn't initialized.
     started_at: Option<LocalTime>,
     /// Publishes events to subscribers.
     emitter: Emitter<Event>,
+    /// Node metrics.
+    metrics: metrics::Metrics,
     /// Local listening addresses.
     listening: Vec<net::SocketAddr>,
 }
@@ -438,6 +553,7 @@ where
         emitter: Emitter<Event>,
     ) -> Self {
         let sessions = Sessions::new(rng.clone());
+        let outbox = Outbox::new(config.limits.rate.announce.clone());
 
         Self {
             config,
@@ -448,28 +564,77 @@ where
             node,
             clock,
             db,
-            outbox: Outbox::default(),
+            outbox,
             limiter: RateLimiter::default(),
             sessions,
             fetching: HashMap::new(),
-            queue: VecDeque::new(),
+            queue: FetchQueue::default(),
+            fetched: HashMap::new(),
+            draining: false,
+            failed_seeds: HashSet::new(),
+            pending_refs: HashMap::new(),
+            retries: HashMap::new(),
             filter: Filter::empty(),
             last_idle: LocalTime::default(),
             last_sync: LocalTime::default(),
             last_prune: LocalTime::default(),
             last_timestamp: Timestamp::MIN,
             last_announce: LocalTime::default(),
+            next_announce: LocalTime::default(),
             started_at: None,
             emitter,
+            metrics: metrics::Metrics::default(),
             listening: vec![],
         }
     }
 
+    /// Get the active preferred seed, ie. the first of the configured preferred seeds
+    /// that we're connected to and haven't failed to fetch from. Missing repositories
+    /// are fetched from this seed.
+    pub fn active_seed(&self) -> Option<NodeId> {
+        self.config
+            .preferred_seeds
+            .iter()
+            .find(|nid| self.sessions.is_connected(nid) && !self.failed_seeds.contains(nid))
+            .copied()
+    }
+
     /// Whether the service was started (initialized) and if so, at what time.
     pub fn started(&self) -> Option<LocalTime> {
         self.started_at
     }
 
+    /// Get the node's health status.
+    pub fn status(&self) -> Result<node::Status, storage::Error> {
+        let started_at = self.started_at.unwrap_or(self.clock);
+        let inventory = self.storage.inventory()?;
+        // N.b. the last announcement time is only ever set when announcing.
+        let last_announcement =
+            (self.last_announce != LocalTime::default()).then(|| self.last_announce.into());
+
+        Ok(node::Status {
+            started_at: started_at.into(),
+            uptime: (self.clock - started_at).as_secs(),
+            peers: self.sessions.connected().count(),
+            inventory: inventory.len(),
+            pending_fetches: self.fetching.len() + self.queue.len(),
+            last_announcement,
+        })
+    }
+
+    /// Get the node's metrics, rendered in the Prometheus text format.
+    pub fn metrics(&self) -> Result<String, storage::Error> {
+        let status = self.status()?;
+
+        Ok(self.metrics.render(&status))
+    }
+
+    /// Get a mutable reference to the node's metrics.
+    /// Used by the transport layer to count the bytes exchanged with peers.
+    pub fn metrics_mut(&mut self) -> &mut metrics::Metrics {
+        &mut self.metrics
+    }
+
     /// Return the next i/o action to execute.
     #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Option<io::Io> {
@@ -646,6 +811,20 @@ where
             }
         }
 
+        // Load the fetches that didn't complete before we were stopped, dropping stale ones.
+        // They are retried once we connect to the nodes they were from.
+        if let Err(e) = self.db.retries_mut().prune(time - FETCH_RETRY_EXPIRY) {
+            error!(target: "service", "Error pruning fetch retry queue: {e}");
+        }
+        match self.db.retries().entries() {
+            Ok(entries) => {
+                for (rid, node, _) in entries {
+                    self.retries.entry(node).or_default().insert(rid);
+                }
+            }
+            Err(e) => error!(target: "service", "Error loading fetch retry queue: {e}"),
+        }
+
         // Setup subscription filter for seeded repos.
         self.filter = Filter::new(
             self.policies
@@ -701,7 +880,7 @@ where
             self.outbox.wakeup(SYNC_INTERVAL);
             self.last_sync = now;
         }
-        if now - self.last_announce >= ANNOUNCE_INTERVAL {
+        if now >= self.next_announce {
             trace!(target: "service", "Running 'announce' task...");
 
             if let Err(err) = self
@@ -710,8 +889,9 @@ where
                 .and_then(|i| self.announce_inventory(i))
             {
                 error!(target: "service", "Error announcing inventory: {err}");
+                self.schedule_announce(now);
             }
-            self.outbox.wakeup(ANNOUNCE_INTERVAL);
+            self.outbox.wakeup(self.next_announce - now);
         }
         if now - self.last_prune >= PRUNE_INTERVAL {
             trace!(target: "service", "Running 'prune' task...");
@@ -731,6 +911,12 @@ where
             self.last_prune = now;
         }
 
+        // Announce the refs that changed during their debounce window.
+        self.announce_pending_refs(&now);
+
+        // Write the announcements that were held back by the rate limit.
+        self.outbox.flush_announcements(now);
+
         // Always check whether there are persistent peers that need reconnecting.
         self.maintain_persistent();
     }
@@ -748,6 +934,8 @@ where
                 }
             }
             Command::Disconnect(nid) => {
+                // Stop maintaining a connection to this peer, otherwise we'd reconnect.
+                self.config.connect.retain(|ca| ca.id != nid);
                 self.outbox.disconnect(nid, DisconnectReason::Command);
             }
             Command::Config(resp) => {
@@ -756,6 +944,22 @@ where
             Command::ListenAddrs(resp) => {
                 resp.send(self.listening.clone()).ok();
             }
+            Command::Status(resp) => match self.status() {
+                Ok(status) => {
+                    resp.send(status).ok();
+                }
+                Err(e) => {
+                    error!(target: "service", "Error getting node status: {e}");
+                }
+            },
+            Command::Metrics(resp) => match self.metrics() {
+                Ok(metrics) => {
+                    resp.send(metrics).ok();
+                }
+                Err(e) => {
+                    error!(target: "service", "Error getting node metrics: {e}");
+                }
+            },
             Command::Seeds(rid, resp) => match self.seeds(&rid) {
                 Ok(seeds) => {
                     let (connected, disconnected) = seeds.partition();
@@ -819,6 +1023,18 @@ where
                     }
                 };
 
+                if self.config.announce_refs_window > LocalDuration::from_secs(0) {
+                    match self.debounce_own_refs(id) {
+                        Ok(refs) => {
+                            resp.send(refs).ok();
+                        }
+                        Err(err) => {
+                            error!(target: "service", "Error announcing refs: {err}");
+                        }
+                    }
+                    return;
+                }
+
                 match self.announce_own_refs(id, doc) {
                     Ok(refs) => match refs.as_slice() {
                         &[refs] => {
@@ -832,6 +1048,29 @@ where
                     }
                 }
             }
+            Command::AnnounceCobRefs(rid, type_name, id, resp) => {
+                let nid = self.node_id();
+                let name = git::refs::storage::cob(&nid, &type_name, &id);
+                let exists = self
+                    .storage
+                    .repository(rid)
+                    .map_err(Error::from)
+                    .and_then(|repo| {
+                        repo.reference_oid(&nid, &name.strip_namespace())
+                            .map_err(Error::from)
+                    });
+
+                // Our signed refs cover the whole namespace, so the announcement itself is the
+                // same as for `AnnounceRefs`; we just make sure the object was actually written.
+                if let Err(e) = exists {
+                    error!(
+                        target: "service",
+                        "Error announcing refs: {type_name} {id} not found in {rid}: {e}"
+                    );
+                    return;
+                }
+                self.command(Command::AnnounceRefs(rid, resp));
+            }
             Command::AnnounceInventory => {
                 if let Err(err) = self
                     .storage
@@ -853,9 +1092,36 @@ where
             Command::QueryState(query, sender) => {
                 sender.send(query(self)).ok();
             }
+            Command::Drain => self.drain(),
         }
     }
 
+    /// Stop initiating fetches, so that the node can be shut down once the ongoing fetches
+    /// are done. Queued fetches are dropped.
+    pub fn drain(&mut self) {
+        info!(
+            target: "service",
+            "Draining {} ongoing fetch(es) and dropping {} queued fetch(es)..",
+            self.fetching.len(),
+            self.queue.len()
+        );
+        self.draining = true;
+
+        for fetch in self.queue.drain() {
+            if let Some(c) = fetch.channel {
+                c.send(FetchResult::Failed {
+                    reason: TryFetchError::Draining.to_string(),
+                })
+                .ok();
+            }
+        }
+    }
+
+    /// Whether the service is draining. See [`Service::drain`].
+    pub fn is_draining(&self) -> bool {
+        self.draining
+    }
+
     /// Initiate an outgoing fetch for some repository, based on another node's announcement.
     /// Returns `true` if the fetch was initiated and `false` if it was skipped.
     fn fetch_refs_at(
@@ -867,6 +1133,20 @@ where
         timeout: time::Duration,
         channel: Option<chan::Sender<FetchResult>>,
     ) -> bool {
+        // Skip the refs we've just fetched. This happens when the same refs are announced
+        // by multiple peers in a short period of time.
+        let now = self.clock;
+        self.fetched.retain(|_, t| now - *t < FETCH_CACHE_EXPIRY);
+
+        let refs = refs
+            .into_iter()
+            .filter(|r| !self.fetched.contains_key(&(rid, *r)))
+            .collect::<Vec<_>>();
+        let Some(refs) = NonEmpty::from_vec(refs) else {
+            debug!(target: "service", "Skipping fetch for {rid}, all refs were recently fetched");
+            return false;
+        };
+
         match self.refs_status_of(rid, refs, &scope) {
             Ok(status) => {
                 if status.want.is_empty() {
@@ -903,6 +1183,16 @@ where
         timeout: time::Duration,
         channel: Option<chan::Sender<FetchResult>>,
     ) {
+        // Keep track of the fetch until it succeeds, so that it can be retried if we're
+        // stopped in the meantime.
+        if let Err(e) = self
+            .db
+            .retries_mut()
+            .add(&rid, &from, self.clock, MAX_FETCH_RETRIES)
+        {
+            error!(target: "service", "Error adding {rid} to fetch retry queue: {e}");
+        }
+
         match self.try_fetch(rid, &from, refs_at.clone(), timeout) {
             Ok(fetching) => {
                 if let Some(c) = channel {
@@ -930,13 +1220,13 @@ where
                         debug!(target: "service", "Fetch for {rid} with {from} is already queued..");
                     } else {
                         debug!(target: "service", "Queueing fetch for {rid} with {from}..");
-                        self.queue.push_back(fetch);
+                        self.queue.push(fetch);
                     }
                 }
             }
             Err(TryFetchError::SessionCapacityReached) => {
                 debug!(target: "service", "Fetch capacity reached for {from}, queueing {rid}..");
-                self.queue.push_back(QueuedFetch {
+                self.queue.push(QueuedFetch {
                     rid,
                     refs_at,
                     from,
@@ -963,6 +1253,9 @@ where
         timeout: time::Duration,
     ) -> Result<&mut FetchState, TryFetchError> {
         let from = *from;
+        if self.draining {
+            return Err(TryFetchError::Draining);
+        }
         let Some(session) = self.sessions.get_mut(&from) else {
             return Err(TryFetchError::SessionNotFound);
         };
@@ -994,6 +1287,7 @@ where
             subscribers: vec![],
         });
         self.outbox.fetch(session, rid, refs_at, timeout);
+        self.metrics.fetches_started += 1;
 
         Ok(fetching)
     }
@@ -1010,6 +1304,12 @@ where
         };
         debug_assert_eq!(fetching.from, remote);
 
+        if result.is_ok() {
+            self.metrics.fetches_succeeded += 1;
+        } else {
+            self.metrics.fetches_failed += 1;
+        }
+
         if let Some(s) = self.sessions.get_mut(&remote) {
             // Mark this RID as fetched for this session.
             s.fetched(rid);
@@ -1045,6 +1345,13 @@ where
                 doc,
             }) => {
                 info!(target: "service", "Fetched {rid} from {remote} successfully");
+
+                if let Err(e) = self.db.retries_mut().remove(&rid, &remote) {
+                    error!(target: "service", "Error removing {rid} from fetch retry queue: {e}");
+                }
+                for refs in &fetching.refs_at {
+                    self.fetched.insert((rid, *refs), self.clock);
+                }
                 // Update our routing table in case this fetch was user-initiated and doesn't
                 // come from an announcement.
                 self.seed_discovered(rid, remote, self.clock.into());
@@ -1085,11 +1392,21 @@ where
             Err(err) => {
                 error!(target: "service", "Fetch failed for {rid} from {remote}: {err}");
 
-                // For now, we only disconnect the remote in case of timeout. In the future,
-                // there may be other reasons to disconnect.
-                if err.is_timeout() {
+                // We disconnect the remote in case of timeout, if it tried to make us
+                // store more than its quota allows, or if it advertised refs that don't
+                // match their signed refs.
+                if err.is_timeout() || err.is_quota_exceeded() || err.is_sigrefs_mismatch() {
                     self.outbox.disconnect(remote, DisconnectReason::Fetch(err));
                 }
+                // If the fetch was from a preferred seed, fail over to the next one.
+                if self.config.preferred_seeds.contains(&remote) {
+                    self.failed_seeds.insert(remote);
+
+                    if let Some(next) = self.active_seed() {
+                        info!(target: "service", "Failing over to preferred seed {next} for {rid}..");
+                        self.fetch(rid, next, FETCH_TIMEOUT, None);
+                    }
+                }
             }
         }
         // We can now try to dequeue another fetch.
@@ -1099,13 +1416,16 @@ where
     /// Fetches are queued for two reasons:
     /// 1. The RID was already being fetched.
     /// 2. The session was already at fetch capacity.
+    ///
+    /// Repositories take turns, and repositories that are already being fetched are skipped,
+    /// so that a single repository with many queued fetches doesn't hold up the others.
     pub fn dequeue_fetch(&mut self) {
         while let Some(QueuedFetch {
             rid,
             from,
             refs_at,
             channel,
-        }) = self.queue.pop_front()
+        }) = self.queue.pop(|rid| !self.fetching.contains_key(rid))
         {
             debug!(target: "service", "Dequeued fetch for {rid} from session {from}..");
 
@@ -1134,10 +1454,8 @@ where
         if addr.is_trusted() {
             return true;
         }
-        // Check for inbound connection limit.
-        if self.sessions.inbound().count() >= self.config.limits.connection.inbound {
-            return false;
-        }
+        // Nb. Inbound connection limits are enforced once the peer is connected, by evicting
+        // the least useful inbound peer. See [`Service::enforce_connection_limits`].
         let host: HostName = addr.into();
 
         if self
@@ -1170,6 +1488,8 @@ where
     pub fn connected(&mut self, remote: NodeId, addr: Address, link: Link) {
         info!(target: "service", "Connected to {} ({:?})", remote, link);
         self.emitter.emit(Event::PeerConnected { nid: remote });
+        // Give reconnected preferred seeds another chance.
+        self.failed_seeds.remove(&remote);
 
         let msgs = self.initial(link);
 
@@ -1207,6 +1527,29 @@ where
                 }
             }
         }
+        self.enforce_connection_limits();
+        self.retry_fetches(remote);
+    }
+
+    /// Retry the fetches from the given node that were left over from a previous run.
+    fn retry_fetches(&mut self, remote: NodeId) {
+        let Some(rids) = self.retries.remove(&remote) else {
+            return;
+        };
+        for rid in rids {
+            match self.policies.is_seeding(&rid) {
+                Ok(true) => {
+                    debug!(target: "service", "Retrying fetch of {rid} from {remote}..");
+                    self.fetch(rid, remote, FETCH_TIMEOUT, None);
+                }
+                Ok(false) => {
+                    if let Err(e) = self.db.retries_mut().remove(&rid, &remote) {
+                        error!(target: "service", "Error removing {rid} from fetch retry queue: {e}");
+                    }
+                }
+                Err(e) => error!(target: "service", "Error checking seeding policy of {rid}: {e}"),
+            }
+        }
     }
 
     pub fn disconnected(&mut self, remote: NodeId, link: Link, reason: &DisconnectReason) {
@@ -1275,8 +1618,11 @@ where
                     }
                 }
                 DisconnectReason::Session(e) => e.severity(),
+                // The peer is unlikely to upgrade or downgrade soon.
+                DisconnectReason::IncompatibleVersion { .. } => Severity::High,
                 DisconnectReason::Command
                 | DisconnectReason::Conflict
+                | DisconnectReason::Evicted
                 | DisconnectReason::SelfConnection => Severity::Low,
             };
 
@@ -1330,12 +1676,21 @@ where
         if announcer == self.nid() {
             return Ok(false);
         }
+        self.metrics.announcements_received.record(message);
         let now = self.clock;
         let timestamp = message.timestamp();
         // To avoid spamming peers on startup with historical gossip messages,
         // don't relay messages that are too old.
         let relay = if now - timestamp.to_local_time() > MAX_TIME_DELTA {
             false
+        } else if announcement.hops >= self.config.limits.gossip_max_hops {
+            // Messages that have reached the hop limit are processed, but not relayed.
+            debug!(
+                target: "service",
+                "Not relaying announcement from {announcer}: hop limit reached ({})",
+                announcement.hops
+            );
+            false
         } else {
             self.config.relay
         };
@@ -1631,6 +1986,19 @@ where
                 let relayer_addr = peer.addr.clone();
                 let announcer = ann.node;
 
+                // Peers send us their own node announcement when the session is established,
+                // which we use to negotiate the capabilities of the session.
+                if let AnnouncementMessage::Node(node) = &ann.message {
+                    if announcer == relayer && ann.verify() {
+                        // Only the features supported by both sides are enabled.
+                        peer.capabilities = self.node.features.common(node.features);
+                        debug!(
+                            target: "service",
+                            "Negotiated capabilities {} with {relayer}", peer.capabilities
+                        );
+                    }
+                }
+
                 // Returning true here means that the message should be relayed.
                 if self.handle_announcement(&relayer_addr, &ann)? {
                     // Choose peers we should relay this message to.
@@ -1642,7 +2010,7 @@ where
                         .filter(|(id, _)| *id != &relayer && *id != &announcer)
                         .map(|(_, p)| p);
 
-                    self.outbox.relay(ann, relay_to);
+                    self.outbox.relay(ann.relayed(), relay_to, self.clock);
 
                     return Ok(());
                 }
@@ -1932,6 +2300,52 @@ where
         Ok(refs)
     }
 
+    /// Schedule an announcement of our own refs for the given repo, once the announcement
+    /// window has elapsed. Changes to the repo's refs within the window are coalesced into
+    /// that one announcement. Returns our current refs.
+    fn debounce_own_refs(&mut self, rid: RepoId) -> Result<RefsAt, Error> {
+        let repo = self.storage.repository(rid)?;
+        let refs = RefsAt::new(&repo, self.node_id())?;
+        let window = self.config.announce_refs_window;
+
+        if let Entry::Vacant(e) = self.pending_refs.entry(rid) {
+            debug!(target: "service", "Announcing own refs for {rid} in {}s..", window.as_secs());
+
+            e.insert(self.clock + window);
+            self.outbox.wakeup(window);
+        }
+        Ok(refs)
+    }
+
+    /// Announce our own refs for the repos whose announcement window has elapsed.
+    fn announce_pending_refs(&mut self, now: &LocalTime) {
+        let due = self
+            .pending_refs
+            .iter()
+            .filter(|(_, at)| *at <= now)
+            .map(|(rid, _)| *rid)
+            .collect::<Vec<_>>();
+
+        for rid in due {
+            self.pending_refs.remove(&rid);
+
+            let doc = match self.storage.get(rid) {
+                Ok(Some(doc)) => doc,
+                Ok(None) => {
+                    error!(target: "service", "Error announcing refs: repository {rid} not found");
+                    continue;
+                }
+                Err(e) => {
+                    error!(target: "service", "Error announcing refs: doc error: {e}");
+                    continue;
+                }
+            };
+            if let Err(e) = self.announce_own_refs(rid, doc) {
+                error!(target: "service", "Error announcing refs for {rid}: {e}");
+            }
+        }
+    }
+
     /// Announce local refs for given repo.
     fn announce_refs(
         &mut self,
@@ -1962,6 +2376,7 @@ where
             }
         }
 
+        self.metrics.announcements_sent.record(&ann.message);
         self.outbox.announce(
             ann,
             peers.filter(|p| {
@@ -1969,6 +2384,7 @@ where
                 doc.is_visible_to(&p.id)
             }),
             self.db.gossip_mut(),
+            self.clock,
         );
         Ok((refs, timestamp))
     }
@@ -2115,16 +2531,28 @@ where
         let time = self.timestamp();
         let msg = AnnouncementMessage::from(gossip::inventory(time, inventory));
 
+        self.metrics.announcements_sent.record(&msg);
         self.outbox.announce(
             msg.signed(&self.signer),
             self.sessions.connected().map(|(_, p)| p),
             self.db.gossip_mut(),
+            self.clock,
         );
         self.last_announce = time.to_local_time();
+        self.schedule_announce(self.clock);
 
         Ok(())
     }
 
+    /// Schedule the next inventory announcement after the configured interval, plus a
+    /// random jitter, so that nodes don't all announce at the same time.
+    fn schedule_announce(&mut self, now: LocalTime) {
+        let jitter = self.config.announce_jitter.as_millis() as u64;
+        let jitter = LocalDuration::from_millis(self.rng.u64(0..=jitter) as u128);
+
+        self.next_announce = now + self.config.announce_interval + jitter;
+    }
+
     fn prune_routing_entries(&mut self, now: &LocalTime) -> Result<(), routing::Error> {
         let count = self.db.routing().len()?;
         if count <= self.config.limits.routing_max_size {
@@ -2213,7 +2641,16 @@ where
             .filter_map(|t| (t.policy == Policy::Allow).then_some(t.rid))
             .filter(|rid| !inventory.contains(rid));
 
+        // If every connected preferred seed has failed, start over from the first one.
+        if self.active_seed().is_none() {
+            self.failed_seeds.clear();
+        }
         for rid in missing {
+            // Preferred seeds are tried one at a time, in order. See [`Service::fetched`].
+            if let Some(seed) = self.active_seed() {
+                self.fetch(rid, seed, FETCH_TIMEOUT, None);
+                continue;
+            }
             match self.seeds(&rid) {
                 Ok(seeds) => {
                     if let Some(connected) = NonEmpty::from_vec(seeds.connected().collect()) {
@@ -2247,6 +2684,7 @@ where
         };
         trace!(target: "service", "Maintaining connections..");
 
+        let target = target.max(self.config.limits.connection.min_outbound);
         let now = self.clock;
         let outbound = self
             .sessions
@@ -2295,6 +2733,36 @@ where
         }
     }
 
+    /// Evict inbound peers until we're within the inbound and total connection limits.
+    ///
+    /// The least useful peers are evicted first: these are the ones that have been idle the
+    /// longest. Persistent peers and peers we're fetching from are never evicted.
+    fn enforce_connection_limits(&mut self) {
+        let limits = &self.config.limits.connection;
+        let inbound = self.sessions.inbound().count();
+        let outbound = self.sessions.outbound().count();
+        let excess = inbound
+            .saturating_sub(limits.inbound)
+            .max((inbound + outbound).saturating_sub(limits.total));
+
+        if excess == 0 {
+            return;
+        }
+        let mut candidates = self
+            .sessions
+            .inbound()
+            .filter(|(_, s)| !s.persistent)
+            .filter(|(nid, _)| !self.fetching.values().any(|f| &f.from == *nid))
+            .map(|(nid, s)| (*nid, s.last_active))
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|(_, last_active)| *last_active);
+
+        for (nid, _) in candidates.into_iter().take(excess) {
+            debug!(target: "service", "Evicting {nid} to stay within connection limits..");
+            self.outbox.disconnect(nid, DisconnectReason::Evicted);
+        }
+    }
+
     /// Maintain persistent peer connections.
     fn maintain_persistent(&mut self) {
         trace!(target: "service", "Maintaining persistent peers..");
@@ -2389,6 +2857,15 @@ pub enum DisconnectReason {
     SelfConnection,
     /// User requested disconnect
     Command,
+    /// Peer was evicted to stay within connection limits.
+    Evicted,
+    /// The peer speaks a protocol version we don't support.
+    IncompatibleVersion {
+        /// Our protocol version.
+        local: u8,
+        /// The peer's protocol version.
+        remote: u8,
+    },
 }
 
 impl DisconnectReason {
@@ -2415,8 +2892,13 @@ impl fmt::Display for DisconnectReason {
             Self::Command => write!(f, "command"),
             Self::SelfConnection => write!(f, "self-connection"),
             Self::Conflict => write!(f, "conflict"),
+            Self::Evicted => write!(f, "evicted"),
             Self::Session(err) => write!(f, "{err}"),
             Self::Fetch(err) => write!(f, "fetch: {err}"),
+            Self::IncompatibleVersion { local, remote } => write!(
+                f,
+                "incompatible protocol version {remote} (local version is {local})"
+            ),
         }
     }
 }
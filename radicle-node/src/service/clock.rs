@@ -0,0 +1,85 @@
+//! Sources of time for the service.
+//!
+//! The service never reads the time by itself: it is told what time it is via
+//! [`super::Service::tick`]. A [`Clock`] is what the time is read from before being handed
+//! over to the service, which allows tests to control time explicitly with a [`ManualClock`].
+use std::sync::{Arc, Mutex};
+
+use localtime::{LocalDuration, LocalTime};
+
+/// A source of time.
+pub trait Clock {
+    /// Get the current local time.
+    fn now(&self) -> LocalTime;
+}
+
+/// The system clock, ie. wall-clock time. Used in production.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> LocalTime {
+        LocalTime::now()
+    }
+}
+
+/// A clock that only moves when explicitly advanced. Used in tests.
+///
+/// Clones share the same time, so that a test can keep a handle on a clock given to a peer.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    time: Arc<Mutex<LocalTime>>,
+}
+
+impl ManualClock {
+    /// Create a new clock, starting at the given time.
+    pub fn new(time: LocalTime) -> Self {
+        Self {
+            time: Arc::new(Mutex::new(time)),
+        }
+    }
+
+    /// Advance the clock by the given duration.
+    pub fn advance(&self, duration: LocalDuration) {
+        self.time.lock().unwrap().elapse(duration);
+    }
+
+    /// Move the clock forward to the given time. Has no effect if the time is in the past,
+    /// since time only moves forward.
+    pub fn set(&self, time: LocalTime) {
+        let mut current = self.time.lock().unwrap();
+        *current = (*current).max(time);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new(SystemClock.now())
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> LocalTime {
+        *self.time.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock() {
+        let clock = ManualClock::new(LocalTime::from_secs(10));
+        let other = clock.clone();
+
+        clock.advance(LocalDuration::from_secs(5));
+        assert_eq!(other.now(), LocalTime::from_secs(15));
+
+        other.set(LocalTime::from_secs(12));
+        assert_eq!(clock.now(), LocalTime::from_secs(15));
+
+        other.set(LocalTime::from_secs(20));
+        assert_eq!(clock.now(), LocalTime::from_secs(20));
+    }
+}
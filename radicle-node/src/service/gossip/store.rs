@@ -154,10 +154,13 @@ impl Store for Database {
 
                     debug_assert_eq!(timestamp, message.timestamp());
 
+                    // Nb. Hop counts aren't stored. Stored announcements are sent to
+                    // subscribers as if they were our own.
                     Ok(Announcement {
                         node,
                         message,
                         signature,
+                        hops: 0,
                     })
                 })
                 .filter(|ann| match ann {
@@ -2,6 +2,7 @@ use std::collections::VecDeque;
 use std::time;
 
 use log::*;
+use radicle::node::config::RateLimit;
 use radicle::storage::refs::RefsAt;
 
 use crate::prelude::*;
@@ -9,8 +10,13 @@ use crate::service::session::Session;
 use crate::service::Link;
 
 use super::gossip;
+use super::limitter::{AsTokens, TokenBucket};
 use super::message::{Announcement, AnnouncementMessage};
 
+/// Maximum number of announcement messages waiting to be written. When reached, relayed
+/// announcements are dropped in favor of our own.
+pub const MAX_QUEUED_ANNOUNCEMENTS: usize = 4096;
+
 /// I/O operation to execute at the network/wire level.
 #[derive(Debug)]
 pub enum Io {
@@ -35,14 +41,85 @@ pub enum Io {
     Wakeup(LocalDuration),
 }
 
-/// Interface to the network.
+/// Priority of an announcement message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    /// Our own announcements, eg. of locally-authored ref changes.
+    Local,
+    /// Announcements relayed on behalf of other nodes.
+    Relayed,
+}
+
+/// Announcement messages waiting to be written, by priority.
 #[derive(Debug, Default)]
+struct AnnouncementQueue {
+    local: VecDeque<(NodeId, Message)>,
+    relayed: VecDeque<(NodeId, Message)>,
+}
+
+impl AnnouncementQueue {
+    fn len(&self) -> usize {
+        self.local.len() + self.relayed.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.local.is_empty() && self.relayed.is_empty()
+    }
+
+    /// Queue a message. If the queue is full, relayed messages are dropped first.
+    fn push(&mut self, priority: Priority, remote: NodeId, msg: Message) {
+        if self.len() >= MAX_QUEUED_ANNOUNCEMENTS {
+            match priority {
+                Priority::Local => {
+                    if self.relayed.pop_front().is_none() {
+                        self.local.pop_front();
+                    }
+                }
+                Priority::Relayed => {
+                    debug!(target: "service", "Dropping relayed announcement to {remote}: queue is full");
+                    return;
+                }
+            }
+        }
+        match priority {
+            Priority::Local => self.local.push_back((remote, msg)),
+            Priority::Relayed => self.relayed.push_back((remote, msg)),
+        }
+    }
+
+    /// Dequeue the next message, local messages first.
+    fn pop(&mut self) -> Option<(NodeId, Message)> {
+        self.local.pop_front().or_else(|| self.relayed.pop_front())
+    }
+}
+
+/// Interface to the network.
+#[derive(Debug)]
 pub struct Outbox {
     /// Outgoing I/O queue.
     io: VecDeque<Io>,
+    /// Announcements waiting to be written, because of the rate limit.
+    announcements: AnnouncementQueue,
+    /// Announcement rate limit.
+    limit: RateLimit,
+    /// Announcement token bucket, created on first use.
+    bucket: Option<TokenBucket>,
+    /// Time at which we asked to be woken up to write queued announcements.
+    retry_at: Option<LocalTime>,
 }
 
 impl Outbox {
+    /// Create a new outbox, with the given rate limit for announcements.
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            io: VecDeque::new(),
+            announcements: AnnouncementQueue::default(),
+            limit,
+            bucket: None,
+            retry_at: None,
+        }
+    }
+
     /// Connect to a peer.
     pub fn connect(&mut self, id: NodeId, addr: Address) {
         self.io.push_back(Io::Connect(id, addr));
@@ -60,12 +137,14 @@ impl Outbox {
         self.io.push_back(Io::Write(remote.id, vec![msg]));
     }
 
-    /// Announce something to a peer. This is meant for our own announcement messages.
+    /// Announce something to a peer. This is meant for our own announcement messages, which
+    /// take priority over relayed ones.
     pub fn announce<'a>(
         &mut self,
         ann: Announcement,
         peers: impl Iterator<Item = &'a Session>,
         gossip: &mut impl gossip::Store,
+        now: LocalTime,
     ) {
         // Store our announcement so that it can be retrieved from us later, just like
         // announcements we receive from peers.
@@ -77,7 +156,8 @@ impl Outbox {
             if let AnnouncementMessage::Refs(refs) = &ann.message {
                 if let Some(subscribe) = &peer.subscribe {
                     if subscribe.filter.contains(&refs.rid) {
-                        self.write(peer, ann.clone().into());
+                        self.announcements
+                            .push(Priority::Local, peer.id, ann.clone().into());
                     } else {
                         debug!(
                             target: "service",
@@ -92,9 +172,11 @@ impl Outbox {
                     );
                 }
             } else {
-                self.write(peer, ann.clone().into());
+                self.announcements
+                    .push(Priority::Local, peer.id, ann.clone().into());
             }
         }
+        self.flush_announcements(now);
     }
 
     pub fn write_all(&mut self, remote: &Session, msgs: impl IntoIterator<Item = Message>) {
@@ -159,21 +241,65 @@ impl Outbox {
     }
 
     /// Relay a message to interested peers.
-    pub fn relay<'a>(&mut self, ann: Announcement, peers: impl IntoIterator<Item = &'a Session>) {
-        if let AnnouncementMessage::Refs(msg) = &ann.message {
-            let id = msg.rid;
-            let peers = peers.into_iter().filter(|p| {
-                if let Some(subscribe) = &p.subscribe {
-                    subscribe.filter.contains(&id)
-                } else {
-                    // If the peer did not send us a `subscribe` message, we don't
-                    // relay any messages to them.
-                    false
+    pub fn relay<'a>(
+        &mut self,
+        ann: Announcement,
+        peers: impl IntoIterator<Item = &'a Session>,
+        now: LocalTime,
+    ) {
+        let rid = match &ann.message {
+            AnnouncementMessage::Refs(msg) => Some(msg.rid),
+            _ => None,
+        };
+        let msg = Message::from(ann);
+
+        for peer in peers {
+            if let Some(rid) = rid {
+                // If the peer did not send us a `subscribe` message, we don't
+                // relay any messages to them.
+                if !peer
+                    .subscribe
+                    .as_ref()
+                    .is_some_and(|s| s.filter.contains(&rid))
+                {
+                    continue;
                 }
-            });
-            self.broadcast(ann, peers);
-        } else {
-            self.broadcast(ann, peers);
+            }
+            self.announcements
+                .push(Priority::Relayed, peer.id, msg.clone());
+        }
+        self.flush_announcements(now);
+    }
+
+    /// Write as many queued announcements as the rate limit allows, our own announcements
+    /// first. If some are left, ask to be woken up when more can be written.
+    pub fn flush_announcements(&mut self, now: LocalTime) {
+        let bucket = self
+            .bucket
+            .get_or_insert_with(|| TokenBucket::new(self.limit.capacity(), self.limit.rate(), now));
+
+        while !self.announcements.is_empty() && bucket.take(now) {
+            let Some((remote, msg)) = self.announcements.pop() else {
+                break;
+            };
+            msg.log(log::Level::Debug, &remote, Link::Outbound);
+            self.io.push_back(Io::Write(remote, vec![msg]));
+        }
+
+        if self.announcements.is_empty() {
+            self.retry_at = None;
+        } else if !self.retry_at.is_some_and(|t| t > now) {
+            // Wait until at least one token is available.
+            let delay =
+                LocalDuration::from_millis((1000. / self.limit.rate().max(0.001)).ceil() as u128);
+
+            debug!(
+                target: "service",
+                "Announcement rate limit reached, {} message(s) queued..",
+                self.announcements.len()
+            );
+            self.retry_at = Some(now + delay);
+            self.wakeup(delay);
         }
     }
 
@@ -190,3 +316,88 @@ impl Iterator for Outbox {
         self.io.pop_front()
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::crypto::test::signer::MockSigner;
+    use crate::node::config::Limits;
+    use crate::service::message::Ping;
+    use crate::test::arbitrary;
+
+    /// Get the announcing node of each message written to the network.
+    fn written(outbox: &mut Outbox) -> Vec<NodeId> {
+        outbox
+            .queue()
+            .drain(..)
+            .filter_map(|io| match io {
+                Io::Write(_, mut msgs) => match msgs.pop() {
+                    Some(Message::Announcement(ann)) => Some(ann.node),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_announcement_priority() {
+        let mut rng = fastrand::Rng::with_seed(1);
+        let mut outbox = Outbox::new(RateLimit {
+            fill_rate: 1.,
+            capacity: 2,
+        });
+        let mut db = radicle::node::Database::memory().unwrap();
+        let local = MockSigner::new(&mut rng);
+        let remote = MockSigner::new(&mut rng);
+        let peer = Session::outbound(
+            arbitrary::gen::<NodeId>(1),
+            arbitrary::gen::<Address>(1),
+            false,
+            rng,
+            Limits::default(),
+        );
+        let ann = |signer: &MockSigner, t: u64| {
+            AnnouncementMessage::from(gossip::inventory(Timestamp::from(t), [])).signed(signer)
+        };
+        let now = LocalTime::from_secs(1);
+
+        // Use up all tokens, and queue up more relayed announcements.
+        for t in 0..4 {
+            outbox.relay(ann(&remote, t), [&peer], now);
+        }
+        assert_eq!(written(&mut outbox), vec![*remote.public_key(); 2]);
+        assert_eq!(outbox.announcements.len(), 2);
+
+        // Our own announcement jumps the queue.
+        outbox.announce(ann(&local, 0), [&peer].into_iter(), &mut db, now);
+        assert_eq!(written(&mut outbox), vec![]);
+        assert_eq!(outbox.announcements.len(), 3);
+        assert_eq!(outbox.retry_at, Some(now + LocalDuration::from_secs(1)));
+
+        outbox.flush_announcements(now + LocalDuration::from_secs(2));
+        assert_eq!(
+            written(&mut outbox),
+            vec![*local.public_key(), *remote.public_key()]
+        );
+        assert_eq!(outbox.announcements.len(), 1);
+    }
+
+    #[test]
+    fn test_announcement_queue_full() {
+        let mut queue = AnnouncementQueue::default();
+        let remote = arbitrary::gen::<NodeId>(1);
+        let msg = Message::Ping(Ping::new(&mut fastrand::Rng::with_seed(1)));
+
+        for _ in 0..MAX_QUEUED_ANNOUNCEMENTS {
+            queue.push(Priority::Relayed, remote, msg.clone());
+        }
+        queue.push(Priority::Relayed, remote, msg.clone());
+        assert_eq!(queue.relayed.len(), MAX_QUEUED_ANNOUNCEMENTS);
+
+        queue.push(Priority::Local, remote, msg);
+        assert_eq!(queue.local.len(), 1);
+        assert_eq!(queue.relayed.len(), MAX_QUEUED_ANNOUNCEMENTS - 1);
+    }
+}
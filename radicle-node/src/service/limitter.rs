@@ -67,7 +67,7 @@ pub struct TokenBucket {
 }
 
 impl TokenBucket {
-    fn new(tokens: usize, rate: f64, now: LocalTime) -> Self {
+    pub(super) fn new(tokens: usize, rate: f64, now: LocalTime) -> Self {
         Self {
             rate,
             capacity: tokens as f64,
@@ -78,13 +78,13 @@ impl TokenBucket {
 
     fn refill(&mut self, now: LocalTime) {
         let elapsed = now.duration_since(self.refilled_at);
-        let tokens = elapsed.as_secs() as f64 * self.rate;
+        let tokens = elapsed.as_millis() as f64 / 1000. * self.rate;
 
         self.tokens = (self.tokens + tokens).min(self.capacity);
         self.refilled_at = now;
     }
 
-    fn take(&mut self, now: LocalTime) -> bool {
+    pub(super) fn take(&mut self, now: LocalTime) -> bool {
         self.refill(now);
 
         if self.tokens >= 1.0 {
@@ -264,6 +264,7 @@ impl AnnouncementMessage {
             node: *signer.public_key(),
             message: self,
             signature,
+            hops: 0,
         }
     }
 
@@ -330,6 +331,11 @@ pub struct Announcement {
     pub message: AnnouncementMessage,
     /// Signature over the announcement.
     pub signature: crypto::Signature,
+    /// Number of times the announcement was relayed before reaching us.
+    ///
+    /// This isn't signed, nor part of the message encoding: it is carried in the gossip frame,
+    /// after the message, where peers that don't know about it ignore it.
+    pub hops: u8,
 }
 
 impl Announcement {
@@ -374,6 +380,14 @@ impl Announcement {
     pub fn timestamp(&self) -> Timestamp {
         self.message.timestamp()
     }
+
+    /// The announcement as relayed by us, ie. with one more hop.
+    pub fn relayed(self) -> Self {
+        Self {
+            hops: self.hops.saturating_add(1),
+            ..self
+        }
+    }
 }
 
 /// Message payload.
@@ -430,6 +444,7 @@ impl Message {
             node,
             signature,
             message: message.into(),
+            hops: 0,
         }
         .into()
     }
@@ -0,0 +1,198 @@
+//! Node metrics, rendered in the Prometheus text exposition format.
+//!
+//! See <https://prometheus.io/docs/instrumenting/exposition_formats/>.
+use std::fmt::Write as _;
+
+use radicle::node;
+
+use crate::service::message::AnnouncementMessage;
+
+/// Prefix of all metric names.
+const PREFIX: &str = "radicle";
+
+/// Announcement counters, by announcement type.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Announcements {
+    /// Inventory announcements.
+    pub inventory: u64,
+    /// Node announcements.
+    pub node: u64,
+    /// Refs announcements.
+    pub refs: u64,
+}
+
+impl Announcements {
+    /// Count an announcement.
+    pub fn record(&mut self, msg: &AnnouncementMessage) {
+        match msg {
+            AnnouncementMessage::Inventory(_) => self.inventory += 1,
+            AnnouncementMessage::Node(_) => self.node += 1,
+            AnnouncementMessage::Refs(_) => self.refs += 1,
+        }
+    }
+}
+
+/// Metric registry of the service.
+///
+/// Only holds counters. Gauges are computed from the node status when rendering.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Metrics {
+    /// Fetches initiated by us.
+    pub fetches_started: u64,
+    /// Fetches that completed successfully.
+    pub fetches_succeeded: u64,
+    /// Fetches that failed.
+    pub fetches_failed: u64,
+    /// Announcements received from peers.
+    pub announcements_received: Announcements,
+    /// Announcements of our own, broadcast to peers.
+    /// Doesn't include the announcements sent to each peer on connection.
+    pub announcements_sent: Announcements,
+    /// Bytes received from peers, including git data.
+    pub bytes_received: u64,
+    /// Bytes sent to peers, including git data.
+    pub bytes_sent: u64,
+}
+
+impl Metrics {
+    /// Render the metrics in the Prometheus text format, along with gauges
+    /// taken from the given node status.
+    pub fn render(&self, status: &node::Status) -> String {
+        let mut out = String::new();
+
+        metric(
+            &mut out,
+            "fetches_started_total",
+            "counter",
+            "Number of fetches initiated.",
+            &[("", self.fetches_started)],
+        );
+        metric(
+            &mut out,
+            "fetches_total",
+            "counter",
+            "Number of completed fetches, by result.",
+            &[
+                ("result=\"success\"", self.fetches_succeeded),
+                ("result=\"failure\"", self.fetches_failed),
+            ],
+        );
+        for (name, help, counts) in [
+            (
+                "announcements_received_total",
+                "Number of announcements received, by type.",
+                &self.announcements_received,
+            ),
+            (
+                "announcements_sent_total",
+                "Number of announcements sent, by type.",
+                &self.announcements_sent,
+            ),
+        ] {
+            metric(
+                &mut out,
+                name,
+                "counter",
+                help,
+                &[
+                    ("type=\"inventory\"", counts.inventory),
+                    ("type=\"node\"", counts.node),
+                    ("type=\"refs\"", counts.refs),
+                ],
+            );
+        }
+        metric(
+            &mut out,
+            "bytes_received_total",
+            "counter",
+            "Number of bytes received from peers.",
+            &[("", self.bytes_received)],
+        );
+        metric(
+            &mut out,
+            "bytes_sent_total",
+            "counter",
+            "Number of bytes sent to peers.",
+            &[("", self.bytes_sent)],
+        );
+        metric(
+            &mut out,
+            "peers",
+            "gauge",
+            "Number of connected peers.",
+            &[("", status.peers as u64)],
+        );
+        metric(
+            &mut out,
+            "inventory",
+            "gauge",
+            "Number of repositories in the local inventory.",
+            &[("", status.inventory as u64)],
+        );
+        metric(
+            &mut out,
+            "fetches_pending",
+            "gauge",
+            "Number of ongoing and queued fetches.",
+            &[("", status.pending_fetches as u64)],
+        );
+        metric(
+            &mut out,
+            "uptime_seconds",
+            "gauge",
+            "Number of seconds since the node started.",
+            &[("", status.uptime)],
+        );
+        out
+    }
+}
+
+/// Write a metric, with one sample per set of labels.
+fn metric(out: &mut String, name: &str, kind: &str, help: &str, samples: &[(&str, u64)]) {
+    writeln!(out, "# HELP {PREFIX}_{name} {help}").ok();
+    writeln!(out, "# TYPE {PREFIX}_{name} {kind}").ok();
+
+    for (labels, value) in samples {
+        if labels.is_empty() {
+            writeln!(out, "{PREFIX}_{name} {value}").ok();
+        } else {
+            writeln!(out, "{PREFIX}_{name}{{{labels}}} {value}").ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use radicle::node::Timestamp;
+
+    #[test]
+    fn test_render() {
+        let metrics = Metrics {
+            fetches_started: 3,
+            fetches_succeeded: 2,
+            fetches_failed: 1,
+            bytes_received: 1024,
+            ..Metrics::default()
+        };
+        let status = node::Status {
+            started_at: Timestamp::EPOCH,
+            uptime: 60,
+            peers: 4,
+            inventory: 7,
+            pending_fetches: 0,
+            last_announcement: None,
+        };
+        let output = metrics.render(&status);
+
+        assert!(output.contains("# TYPE radicle_fetches_total counter\n"));
+        assert!(output.contains("radicle_fetches_started_total 3\n"));
+        assert!(output.contains("radicle_fetches_total{result=\"success\"} 2\n"));
+        assert!(output.contains("radicle_fetches_total{result=\"failure\"} 1\n"));
+        assert!(output.contains("radicle_announcements_received_total{type=\"refs\"} 0\n"));
+        assert!(output.contains("radicle_bytes_received_total 1024\n"));
+        assert!(output.contains("# TYPE radicle_peers gauge\n"));
+        assert!(output.contains("radicle_peers 4\n"));
+        assert!(output.contains("radicle_inventory 7\n"));
+    }
+}
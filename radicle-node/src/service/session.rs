@@ -2,7 +2,7 @@ use std::collections::{HashSet, VecDeque};
 use std::fmt;
 
 use crate::node::config::Limits;
-use crate::node::Severity;
+use crate::node::{Features, Severity};
 use crate::service::message;
 use crate::service::message::Message;
 use crate::service::{Address, LocalTime, NodeId, Outbox, RepoId, Rng};
@@ -58,6 +58,9 @@ pub struct Session {
     pub subscribe: Option<message::Subscribe>,
     /// Last time a message was received from the peer.
     pub last_active: LocalTime,
+    /// Capabilities supported by both us and the peer in this session.
+    /// These are negotiated when the peer sends us its node announcement.
+    pub capabilities: Features,
 
     /// Connection attempts. For persistent peers, Tracks
     /// how many times we've attempted to connect. We reset this to zero
@@ -96,6 +99,7 @@ impl Session {
             state: State::Initial,
             link: Link::Outbound,
             subscribe: None,
+            capabilities: Features::NONE,
             persistent,
             last_active: LocalTime::default(),
             attempts: 1,
@@ -123,6 +127,7 @@ impl Session {
             },
             link: Link::Inbound,
             subscribe: None,
+            capabilities: Features::NONE,
             persistent,
             last_active: time,
             attempts: 0,
@@ -222,6 +227,13 @@ impl Session {
     /// that was requested.
     pub fn to_disconnected(&mut self, since: LocalTime, retry_at: LocalTime) {
         self.state = State::Disconnected { since, retry_at };
+        self.capabilities = Features::NONE;
+    }
+
+    /// Check whether the given capabilities are enabled for this session.
+    /// This should be checked before using a feature with the peer.
+    pub fn supports(&self, capabilities: Features) -> bool {
+        self.capabilities.has(capabilities)
     }
 
     /// Return to initial state from disconnected state. This state transition
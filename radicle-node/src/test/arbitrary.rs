@@ -94,6 +94,7 @@ impl Arbitrary for Message {
                     node: NodeId::arbitrary(g),
                     signature,
                     message,
+                    hops: 0,
                 }
                 .into()
             }
@@ -10,7 +10,7 @@ use std::{
 use crossbeam_channel as chan;
 
 use radicle::cob::cache::COBS_DB_FILE;
-use radicle::cob::issue;
+use radicle::cob::{issue, patch};
 use radicle::crypto::ssh::{keystore::MemorySigner, Keystore};
 use radicle::crypto::test::signer::MockSigner;
 use radicle::crypto::{KeyPair, Seed, Signer};
@@ -26,12 +26,14 @@ use radicle::node::{ConnectOptions, Handle as _};
 use radicle::profile;
 use radicle::profile::{Home, Profile};
 use radicle::rad;
-use radicle::storage::{ReadStorage as _, RemoteRepository as _, SignRepository as _};
+use radicle::storage::{
+    ReadRepository as _, ReadStorage as _, RemoteRepository as _, SignRepository as _,
+};
 use radicle::test::fixtures;
 use radicle::Storage;
 use radicle::{cli, node};
 use radicle::{cob, explorer};
-use radicle::{git, web};
+use radicle::{git, review, web};
 
 use crate::node::NodeId;
 use crate::service::Event;
@@ -91,10 +93,15 @@ impl Environment {
     pub fn config(alias: Alias) -> profile::Config {
         profile::Config {
             node: node::Config::test(alias),
-            cli: cli::Config { hints: false },
+            cli: cli::Config {
+                hints: false,
+                patch_template: None,
+                theme: cli::Theme::default(),
+            },
             public_explorer: explorer::Explorer::default(),
             preferred_seeds: vec![],
             web: web::Config::default(),
+            review: review::Config::default(),
         }
     }
 
@@ -186,6 +193,8 @@ pub struct NodeHandle<G: Signer + cyphernet::Ecdh + 'static> {
     pub addr: net::SocketAddr,
     pub thread: ManuallyDrop<thread::JoinHandle<Result<(), runtime::Error>>>,
     pub handle: ManuallyDrop<Handle>,
+    /// Delivers termination signals to the node.
+    pub signals: chan::Sender<()>,
 }
 
 impl<G: Signer + cyphernet::Ecdh + 'static> Drop for NodeHandle<G> {
@@ -451,6 +460,26 @@ impl<G: Signer + cyphernet::Ecdh> NodeHandle<G> {
             .unwrap()
             .id()
     }
+
+    /// Create a [`patch::Patch`] in the `NodeHandle`'s storage, proposing the
+    /// repository's current head.
+    pub fn patch(&self, rid: RepoId, title: &str, desc: &str) -> cob::ObjectId {
+        let repo = self.storage.repository(rid).unwrap();
+        let (_, head) = repo.head().unwrap();
+        let mut patches = patch::Cache::no_cache(&repo).unwrap();
+        patches
+            .create(
+                title,
+                desc,
+                patch::MergeTarget::Delegates,
+                head,
+                head,
+                &[],
+                &self.signer,
+            )
+            .unwrap()
+            .id
+    }
 }
 
 impl Node<MockSigner> {
@@ -493,7 +522,7 @@ impl<G: cyphernet::Ecdh<Pk = NodeId> + Signer + Clone> Node<G> {
     pub fn spawn(self) -> NodeHandle<G> {
         let listen = vec![([0, 0, 0, 0], 0).into()];
         let proxy = net::SocketAddr::new(net::Ipv4Addr::LOCALHOST.into(), 9050);
-        let (_, signals) = chan::bounded(1);
+        let (terminate, signals) = chan::bounded(1);
         let rt = Runtime::init(
             self.home.clone(),
             self.config,
@@ -516,6 +545,7 @@ impl<G: cyphernet::Ecdh<Pk = NodeId> + Signer + Clone> Node<G> {
             addr,
             handle,
             thread,
+            signals: terminate,
         }
     }
 
@@ -3,11 +3,14 @@ use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 use std::time;
 
+use radicle::cob;
 use radicle::git;
 use radicle::storage::refs::RefsAt;
 
 use crate::identity::RepoId;
-use crate::node::{Alias, Config, ConnectOptions, ConnectResult, Event, FetchResult, Seeds};
+use crate::node::{
+    Alias, Config, ConnectOptions, ConnectResult, Event, FetchResult, Seeds, Status, Timestamp,
+};
 use crate::runtime::HandleError;
 use crate::service::policy;
 use crate::service::NodeId;
@@ -39,6 +42,21 @@ impl radicle::node::Handle for Handle {
         Ok(Config::new(Alias::new("acme")))
     }
 
+    fn status(&self) -> Result<Status, Self::Error> {
+        Ok(Status {
+            started_at: Timestamp::EPOCH,
+            uptime: 0,
+            peers: 0,
+            inventory: self.seeding.lock().unwrap().len(),
+            pending_fetches: 0,
+            last_announcement: None,
+        })
+    }
+
+    fn metrics(&self) -> Result<String, Self::Error> {
+        Ok(String::new())
+    }
+
     fn connect(
         &mut self,
         _node: NodeId,
@@ -101,6 +119,15 @@ impl radicle::node::Handle for Handle {
         })
     }
 
+    fn announce_cob_refs(
+        &mut self,
+        rid: RepoId,
+        _type_name: cob::TypeName,
+        _id: cob::ObjectId,
+    ) -> Result<RefsAt, Self::Error> {
+        self.announce_refs(rid)
+    }
+
     fn announce_inventory(&mut self) -> Result<(), Self::Error> {
         Ok(())
     }
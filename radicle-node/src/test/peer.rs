@@ -22,6 +22,7 @@ use crate::node;
 use crate::prelude::*;
 use crate::runtime::Emitter;
 use crate::service;
+use crate::service::clock::{Clock as _, ManualClock};
 use crate::service::io::Io;
 use crate::service::message::*;
 use crate::service::policy::{Policy, Scope};
@@ -33,7 +34,7 @@ use crate::test::storage::MockStorage;
 use crate::test::{arbitrary, fixtures, simulator};
 use crate::wire::MessageType;
 use crate::Link;
-use crate::{LocalDuration, LocalTime};
+use crate::LocalDuration;
 
 /// Service instantiation used for testing.
 pub type Service<S, G> = service::Service<Database, S, G>;
@@ -47,6 +48,8 @@ pub struct Peer<S, G> {
     pub rng: fastrand::Rng,
     pub local_addr: net::SocketAddr,
     pub tempdir: tempfile::TempDir,
+    /// Clock driving this peer's notion of time.
+    pub clock: ManualClock,
 
     initialized: bool,
 }
@@ -101,7 +104,7 @@ where
 pub struct Config<G: Signer + 'static> {
     pub config: service::Config,
     pub db: Stores<node::Database>,
-    pub local_time: LocalTime,
+    pub clock: ManualClock,
     pub policy: Policy,
     pub scope: Scope,
     pub signer: G,
@@ -121,7 +124,7 @@ impl Default for Config<MockSigner> {
         Config {
             config: service::Config::test(Alias::from_str("mocky").unwrap()),
             db,
-            local_time: LocalTime::now(),
+            clock: ManualClock::default(),
             policy: Policy::default(),
             scope: Scope::default(),
             signer,
@@ -174,11 +177,12 @@ where
         for rid in storage.inventory().unwrap() {
             policies.seed(&rid, Scope::Followed).unwrap();
         }
-        let announcement = service::gossip::node(&config.config, config.local_time.into());
+        let local_time = config.clock.now();
+        let announcement = service::gossip::node(&config.config, local_time.into());
         let emitter: Emitter<Event> = Default::default();
         let service = Service::new(
             config.config,
-            config.local_time,
+            local_time,
             config.db,
             storage,
             policies,
@@ -197,6 +201,7 @@ where
             rng: config.rng,
             initialized: false,
             tempdir: config.tmp,
+            clock: config.clock,
         }
     }
 
@@ -209,7 +214,7 @@ where
             );
 
             self.initialized = true;
-            self.service.initialize(LocalTime::now()).unwrap();
+            self.service.initialize(self.clock.now()).unwrap();
             return true;
         }
         false
@@ -222,7 +227,7 @@ where
             "{}: Restarting: id = {}, address = {}",
             self.name, self.id, self.ip
         );
-        self.service.initialize(LocalTime::now()).unwrap();
+        self.service.initialize(self.clock.now()).unwrap();
     }
 
     pub fn address(&self) -> Address {
@@ -397,10 +402,20 @@ where
     }
 
     pub fn elapse(&mut self, duration: LocalDuration) {
-        self.clock_mut().elapse(duration);
+        self.clock.advance(duration);
+        self.sync_clock();
         self.service.wake();
     }
 
+    /// Update the service time from the peer's clock.
+    ///
+    /// Since the simulator may also move the service time forward, the clock is first
+    /// brought up to date with the service.
+    pub fn sync_clock(&mut self) {
+        self.clock.set(*self.service.clock());
+        *self.service.clock_mut() = self.clock.now();
+    }
+
     /// Drain outgoing messages sent from this peer to the remote peer.
     pub fn messages(&mut self, remote: NodeId) -> impl Iterator<Item = Message> {
         let mut msgs = Vec::new();
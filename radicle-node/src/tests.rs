@@ -23,6 +23,7 @@ use crate::node;
 use crate::node::config::*;
 use crate::prelude::*;
 use crate::prelude::{LocalDuration, Timestamp};
+use crate::service::clock::{Clock as _, ManualClock};
 use crate::service::filter::Filter;
 use crate::service::io::Io;
 use crate::service::message::*;
@@ -106,6 +107,54 @@ fn test_ping_response() {
     );
 }
 
+#[test]
+fn test_capability_negotiation() {
+    let mut alice = Peer::new("alice", [8, 8, 8, 8]);
+    let bob = Peer::new("bob", [9, 9, 9, 9]);
+    let eve = Peer::new("eve", [7, 7, 7, 7]);
+    let node = |features: node::Features, peer: &Peer<MockStorage, MockSigner>| {
+        peer.announcement(
+            NodeAnnouncement {
+                features,
+                timestamp: peer.timestamp(),
+                alias: node::Alias::new(peer.name),
+                addresses: BoundedVec::new(),
+                nonce: 0,
+            }
+            .solve(0)
+            .unwrap(),
+        )
+    };
+
+    alice.connect_to(&bob);
+    let session = alice.sessions().get(&bob.id()).unwrap();
+    assert!(session.supports(node::Features::SEED));
+    assert!(!session.supports(node::Features::COMPRESSION));
+
+    // Bob supports compression, but Alice doesn't, so it's disabled for the session.
+    alice.receive(
+        bob.id(),
+        node(node::Features::SEED | node::Features::COMPRESSION, &bob),
+    );
+    let session = alice.sessions().get(&bob.id()).unwrap();
+    assert!(session.supports(node::Features::SEED));
+    assert!(!session.supports(node::Features::COMPRESSION));
+
+    // Eve doesn't support anything, which doesn't affect the session with Bob.
+    alice.connect_to(&eve);
+    alice.receive(eve.id(), node(node::Features::NONE, &eve));
+    assert!(!alice
+        .sessions()
+        .get(&eve.id())
+        .unwrap()
+        .supports(node::Features::SEED));
+    assert!(alice
+        .sessions()
+        .get(&bob.id())
+        .unwrap()
+        .supports(node::Features::SEED));
+}
+
 #[test]
 fn test_disconnecting_unresponsive_peer() {
     let mut alice = Peer::new("alice", [8, 8, 8, 8]);
@@ -281,6 +330,78 @@ fn test_inventory_sync() {
     }
 }
 
+#[test]
+fn test_inventory_announce_jitter() {
+    let interval = LocalDuration::from_mins(60);
+    let jitter = LocalDuration::from_mins(5);
+    let config = |alias: &str| Config {
+        announce_interval: interval,
+        announce_jitter: jitter,
+        ..Config::test(node::Alias::new(alias))
+    };
+    let bob = Peer::new("bob", [8, 8, 8, 8]);
+    let mut alice = Peer::config(
+        "alice",
+        [7, 7, 7, 7],
+        MockStorage::empty(),
+        peer::Config {
+            config: config("alice"),
+            rng: fastrand::Rng::with_seed(1),
+            ..peer::Config::default()
+        },
+    );
+    let mut eve = Peer::config(
+        "eve",
+        [9, 9, 9, 9],
+        MockStorage::empty(),
+        peer::Config {
+            config: config("eve"),
+            clock: ManualClock::new(alice.local_time()),
+            rng: fastrand::Rng::with_seed(2),
+            ..peer::Config::default()
+        },
+    );
+    let mut announcements = Vec::new();
+
+    for peer in [&mut alice, &mut eve] {
+        let mut times = Vec::new();
+
+        peer.init();
+        peer.wake();
+        peer.connect_to(&bob);
+
+        let start = peer.local_time();
+        for _ in 0..(interval + jitter).as_secs() * 3 {
+            peer.elapse(LocalDuration::from_secs(1));
+
+            if peer.inventory_announcements(bob.id()).next().is_some() {
+                times.push(peer.local_time());
+            }
+        }
+        assert_eq!(times.len(), 3);
+
+        let mut last = start;
+        for time in &times {
+            let elapsed = *time - last;
+
+            assert!(
+                elapsed >= interval,
+                "{elapsed:?} is shorter than the interval"
+            );
+            assert!(
+                elapsed <= interval + jitter + LocalDuration::from_secs(1),
+                "{elapsed:?} is longer than the jittered interval"
+            );
+            last = *time;
+        }
+        announcements.push(times);
+    }
+    assert_ne!(
+        announcements[0], announcements[1],
+        "Announcements are not synchronized"
+    );
+}
+
 #[test]
 fn test_inventory_pruning() {
     struct Test {
@@ -356,7 +477,7 @@ fn test_inventory_pruning() {
             [8, 8, 8, 8],
             MockStorage::empty(),
             peer::Config {
-                local_time: alice.local_time(),
+                clock: ManualClock::new(alice.local_time()),
                 ..peer::Config::default()
             },
         );
@@ -644,6 +765,34 @@ fn test_announcement_relay() {
     );
 }
 
+#[test]
+fn test_announcement_relay_max_hops() {
+    let mut alice = Peer::new("alice", [7, 7, 7, 7]);
+    let mut bob = Peer::new("bob", [8, 8, 8, 8]);
+    let eve = Peer::new("eve", [9, 9, 9, 9]);
+    let max = Limits::default().gossip_max_hops;
+    let with_hops = |msg: Message, hops: u8| match msg {
+        Message::Announcement(ann) => Message::Announcement(Announcement { hops, ..ann }),
+        _ => unreachable!(),
+    };
+
+    alice.connect_to(&bob);
+    alice.connect_to(&eve);
+    alice.receive(bob.id(), with_hops(bob.inventory_announcement(), max));
+    assert!(
+        alice.messages(eve.id()).next().is_none(),
+        "An announcement that reached the hop limit is not relayed"
+    );
+
+    bob.elapse(LocalDuration::from_mins(1));
+    alice.receive(bob.id(), with_hops(bob.inventory_announcement(), max - 1));
+    assert_matches!(
+        alice.messages(eve.id()).next(),
+        Some(Message::Announcement(Announcement { hops, .. })) if hops == max,
+        "An announcement below the hop limit is relayed with one more hop"
+    );
+}
+
 #[test]
 fn test_refs_announcement_relay() {
     let tmp = tempfile::tempdir().unwrap();
@@ -939,6 +1088,246 @@ fn test_refs_announcement_offline() {
     assert_eq!(anns.first().unwrap().refs.first().unwrap().at, new_refs.at);
 }
 
+#[test]
+fn test_refs_announcement_debounce() {
+    let tmp = tempfile::tempdir().unwrap();
+    let window = LocalDuration::from_secs(5);
+    let mut alice = {
+        let signer = MockSigner::default();
+        let storage = fixtures::storage(tmp.path().join("alice"), &signer).unwrap();
+
+        Peer::config(
+            "alice",
+            [7, 7, 7, 7],
+            storage,
+            peer::Config {
+                config: Config {
+                    announce_refs_window: window,
+                    ..Config::test(node::Alias::new("alice"))
+                },
+                signer,
+                ..peer::Config::default()
+            },
+        )
+    };
+    let rid = *alice.inventory().first().unwrap();
+    let mut bob = Peer::new("bob", [8, 8, 8, 8]);
+    bob.seed(&rid, policy::Scope::All).unwrap();
+
+    alice.connect_to(&bob);
+    alice.receive(bob.id, Message::Subscribe(Subscribe::all()));
+    alice.messages(bob.id).for_each(drop);
+
+    // Open a few issues in quick succession, announcing refs after each one.
+    let repo = alice.storage().repository(rid).unwrap();
+    let mut issues = radicle::issue::Cache::no_cache(&repo).unwrap();
+    let mut refs = Vec::new();
+
+    for i in 0..3 {
+        issues
+            .create(format!("Issue #{i}"), "", &[], &[], [], alice.signer())
+            .unwrap();
+
+        let (sender, receiver) = chan::bounded(1);
+        alice.command(Command::AnnounceRefs(rid, sender));
+        refs.push(receiver.try_recv().unwrap());
+        alice.elapse(LocalDuration::from_secs(1));
+    }
+    assert_eq!(refs.last(), Some(&RefsAt::new(&repo, alice.id).unwrap()));
+    assert!(
+        alice.messages(bob.id).next().is_none(),
+        "Nothing is announced while the window is open"
+    );
+
+    // Once the window elapses, a single announcement with the latest refs is sent.
+    alice.elapse(window);
+
+    let mut messages = alice.messages(bob.id).filter(|m| {
+        matches!(
+            m,
+            Message::Announcement(Announcement {
+                message: AnnouncementMessage::Refs(_),
+                ..
+            })
+        )
+    });
+    assert_matches!(
+        messages.next(),
+        Some(Message::Announcement(Announcement {
+            node,
+            message: AnnouncementMessage::Refs(RefsAnnouncement { refs: announced, .. }),
+            ..
+        }))
+        if node == alice.id && announced.as_slice() == &refs[2..]
+    );
+    assert!(messages.next().is_none());
+}
+
+#[test]
+fn test_announce_cob_refs() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut alice = {
+        let signer = MockSigner::default();
+        let storage = fixtures::storage(tmp.path().join("alice"), &signer).unwrap();
+
+        Peer::config(
+            "alice",
+            [7, 7, 7, 7],
+            storage,
+            peer::Config {
+                config: Config {
+                    announce_refs_window: LocalDuration::from_secs(0),
+                    ..Config::test(node::Alias::new("alice"))
+                },
+                signer,
+                ..peer::Config::default()
+            },
+        )
+    };
+    let rid = *alice.inventory().first().unwrap();
+    let mut bob = Peer::new("bob", [8, 8, 8, 8]);
+    bob.seed(&rid, policy::Scope::All).unwrap();
+
+    alice.connect_to(&bob);
+    alice.receive(bob.id, Message::Subscribe(Subscribe::all()));
+    alice.messages(bob.id).for_each(drop);
+
+    let repo = alice.storage().repository(rid).unwrap();
+    let mut issues = radicle::issue::Cache::no_cache(&repo).unwrap();
+    let issue = issues.create("Issue", "", &[], &[], [], alice.signer()).unwrap();
+    let id = *issue.id();
+
+    // An object we don't have isn't announced.
+    let (sender, receiver) = chan::bounded(1);
+    alice.command(Command::AnnounceCobRefs(
+        rid,
+        radicle::issue::TYPENAME.clone(),
+        git::raw::Oid::zero().into(),
+        sender,
+    ));
+    assert!(receiver.try_recv().is_err());
+    assert!(alice.messages(bob.id).next().is_none());
+
+    // The object we just created is, along with only our own refs.
+    let (sender, receiver) = chan::bounded(1);
+    alice.command(Command::AnnounceCobRefs(
+        rid,
+        radicle::issue::TYPENAME.clone(),
+        id,
+        sender,
+    ));
+    let refs = receiver.try_recv().unwrap();
+    assert_eq!(refs, RefsAt::new(&repo, alice.id).unwrap());
+
+    let mut messages = alice.messages(bob.id).filter(|m| {
+        matches!(
+            m,
+            Message::Announcement(Announcement {
+                message: AnnouncementMessage::Refs(_),
+                ..
+            })
+        )
+    });
+    assert_matches!(
+        messages.next(),
+        Some(Message::Announcement(Announcement {
+            node,
+            message: AnnouncementMessage::Refs(RefsAnnouncement { rid: r, refs: announced, .. }),
+            ..
+        }))
+        if node == alice.id && r == rid && announced.as_slice() == [refs]
+    );
+    assert!(messages.next().is_none());
+}
+
+#[test]
+fn test_fetch_retry_queue_restart() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db = node::Database::open(tmp.path().join(node::NODE_DB_FILE)).unwrap();
+    let signer = MockSigner::default();
+    let rid = arbitrary::gen::<RepoId>(1);
+    let bob = Peer::new("bob", [8, 8, 8, 8]);
+    let restart = |clock: ManualClock| {
+        let mut alice = Peer::config(
+            "alice",
+            [7, 7, 7, 7],
+            MockStorage::empty(),
+            peer::Config {
+                db: db.clone().into(),
+                signer: signer.clone(),
+                clock,
+                ..peer::Config::default()
+            },
+        );
+        alice.seed(&rid, policy::Scope::All).unwrap();
+        alice.initialize();
+        alice
+    };
+
+    // Alice starts fetching from Bob, but is stopped before the fetch completes.
+    let mut alice = restart(ManualClock::default());
+    let started = alice.local_time();
+    alice.connected(bob.id, bob.address(), Link::Inbound);
+    alice.command(Command::Fetch(rid, bob.id, DEFAULT_TIMEOUT, chan::bounded(1).0));
+    assert_matches!(alice.fetches().next(), Some((r, n)) if r == rid && n == bob.id);
+    drop(alice);
+
+    // Once restarted, the fetch is retried when Bob connects.
+    let mut alice = restart(ManualClock::new(started + LocalDuration::from_mins(1)));
+    assert!(alice.fetches().next().is_none());
+    alice.connected(bob.id, bob.address(), Link::Inbound);
+    assert_matches!(alice.fetches().next(), Some((r, n)) if r == rid && n == bob.id);
+    drop(alice);
+
+    // Stale fetches are dropped instead of retried.
+    let expired = started + FETCH_RETRY_EXPIRY + LocalDuration::from_mins(2);
+    let mut alice = restart(ManualClock::new(expired));
+    alice.connected(bob.id, bob.address(), Link::Inbound);
+    assert!(alice.fetches().next().is_none());
+    assert_eq!(node::retry::Store::count(&db).unwrap(), 0);
+}
+
+#[test]
+fn test_metrics() {
+    let mut alice = Peer::new("alice", [7, 7, 7, 7]);
+    let bob = Peer::new("bob", [8, 8, 8, 8]);
+    let rid = arbitrary::gen::<RepoId>(1);
+
+    alice.init();
+    alice.connect_to(&bob);
+    alice.receive(
+        bob.id(),
+        Message::inventory(
+            InventoryAnnouncement {
+                inventory: BoundedVec::try_from(vec![rid]).unwrap(),
+                timestamp: LocalTime::now().into(),
+            },
+            bob.signer(),
+        ),
+    );
+    alice.command(Command::Fetch(rid, bob.id, DEFAULT_TIMEOUT, chan::bounded(1).0));
+
+    let (resp, metrics) = chan::bounded(1);
+    alice.command(Command::Metrics(resp));
+    let metrics = metrics.try_recv().unwrap();
+
+    for name in [
+        "radicle_fetches_started_total",
+        "radicle_fetches_total",
+        "radicle_announcements_received_total",
+        "radicle_announcements_sent_total",
+        "radicle_bytes_received_total",
+        "radicle_bytes_sent_total",
+        "radicle_peers",
+        "radicle_inventory",
+    ] {
+        assert!(metrics.contains(&format!("# TYPE {name} ")), "{name} missing");
+    }
+    assert!(metrics.contains("radicle_fetches_started_total 1\n"));
+    assert!(metrics.contains("radicle_announcements_received_total{type=\"inventory\"} 1\n"));
+    assert!(metrics.contains("radicle_peers 1\n"));
+}
+
 #[test]
 fn test_inventory_relay() {
     // Topology is eve <-> alice <-> bob
@@ -1096,6 +1485,62 @@ fn test_persistent_peer_reconnect_attempt() {
     }
 }
 
+#[test]
+fn test_persistent_peer_reconnect_manual_clock() {
+    use std::collections::HashSet;
+
+    let clock = ManualClock::default();
+    let bob = Peer::config(
+        "bob",
+        [9, 9, 9, 9],
+        MockStorage::empty(),
+        peer::Config {
+            clock: clock.clone(),
+            ..peer::Config::default()
+        },
+    );
+    let mut alice = Peer::config(
+        "alice",
+        [7, 7, 7, 7],
+        MockStorage::empty(),
+        peer::Config {
+            config: Config {
+                connect: HashSet::from_iter([(bob.id, bob.addr()).into()]),
+                ..Config::new(node::Alias::new("alice"))
+            },
+            clock: clock.clone(),
+            ..peer::Config::default()
+        },
+    );
+    alice.connect_to(&bob);
+    alice.outbox().for_each(drop);
+
+    let error = Arc::new(io::Error::from(io::ErrorKind::ConnectionReset));
+    alice.disconnected(
+        bob.id(),
+        Link::Outbound,
+        &DisconnectReason::Connection(error),
+    );
+
+    // Time hasn't moved enough for a reconnection attempt.
+    clock.advance(service::MIN_RECONNECTION_DELTA - LocalDuration::from_secs(1));
+    alice.sync_clock();
+    alice.wake();
+    assert_eq!(*alice.clock(), clock.now());
+    assert!(!alice
+        .outbox()
+        .any(|io| matches!(io, Io::Connect(id, _) if id == bob.id())));
+
+    // Once the reconnection delta has elapsed, Alice tries to reconnect.
+    clock.advance(LocalDuration::from_secs(1));
+    alice.sync_clock();
+    alice.wake();
+    alice
+        .outbox()
+        .find(|io| matches!(io, Io::Connect(id, _) if id == &bob.id()))
+        .expect("Alice attempts to reconnect to Bob");
+}
+
 #[test]
 fn test_persistent_peer_reconnect_success() {
     use std::collections::HashSet;
@@ -1191,6 +1636,84 @@ fn test_maintain_connections() {
     );
 }
 
+#[test]
+fn test_connection_limits_evict_inbound() {
+    let bob = Peer::new("bob", [8, 8, 8, 8]);
+    let eve = Peer::new("eve", [9, 9, 9, 9]);
+    let mut alice = Peer::config(
+        "alice",
+        [7, 7, 7, 7],
+        MockStorage::empty(),
+        peer::Config {
+            config: Config {
+                limits: Limits {
+                    connection: ConnectionLimits {
+                        inbound: 1,
+                        ..ConnectionLimits::default()
+                    },
+                    ..Limits::default()
+                },
+                ..Config::new(node::Alias::new("alice"))
+            },
+            ..peer::Config::default()
+        },
+    );
+
+    alice.connect_from(&bob);
+    alice.elapse(LocalDuration::from_secs(1));
+    alice.connect_from(&eve);
+
+    // Bob has been idle the longest, and is evicted to make room for Eve.
+    let evicted = alice
+        .outbox()
+        .filter_map(|o| match o {
+            Io::Disconnect(id, DisconnectReason::Evicted) => Some(id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(evicted, vec![bob.id()]);
+}
+
+#[test]
+fn test_connection_limits_min_outbound() {
+    let bob = Peer::new("bob", [8, 8, 8, 8]);
+    let eve = Peer::new("eve", [9, 9, 9, 9]);
+    let mut alice = Peer::config(
+        "alice",
+        [7, 7, 7, 7],
+        MockStorage::empty(),
+        peer::Config {
+            config: Config {
+                peers: PeerConfig::Dynamic { target: 0 },
+                limits: Limits {
+                    connection: ConnectionLimits {
+                        min_outbound: 1,
+                        ..ConnectionLimits::default()
+                    },
+                    ..Limits::default()
+                },
+                ..Config::new(node::Alias::new("alice"))
+            },
+            ..peer::Config::default()
+        },
+    );
+    alice.connect_to(&bob);
+    alice.import_addresses([&eve]);
+    alice.outbox().for_each(drop);
+
+    // Even though Alice doesn't target any outbound peers, she dials a new one to stay
+    // above the minimum.
+    alice.disconnected(
+        bob.id(),
+        Link::Outbound,
+        &DisconnectReason::Session(session::Error::Misbehavior),
+    );
+    alice
+        .outbox()
+        .find(|o| matches!(o, Io::Connect(id, _) if id == &eve.id()))
+        .expect("Alice dials Eve");
+}
+
 #[test]
 fn test_maintain_connections_transient() {
     // Peers alice starts out connected to.
@@ -1337,6 +1860,78 @@ fn test_fetch_missing_inventory_on_schedule() {
         .unwrap();
 }
 
+#[test]
+fn test_drain_stops_fetches() {
+    let rid = arbitrary::gen::<RepoId>(1);
+    let mut alice = Peer::new("alice", [7, 7, 7, 7]);
+    let bob = Peer::new("bob", [8, 8, 8, 8]);
+    let now = LocalTime::now();
+
+    alice.seed(&rid, node::policy::Scope::All).unwrap();
+    alice.connect_to(&bob);
+    alice.drain();
+    assert!(alice.is_draining());
+
+    alice.receive(
+        bob.id(),
+        Message::inventory(
+            InventoryAnnouncement {
+                inventory: vec![rid].try_into().unwrap(),
+                timestamp: now.into(),
+            },
+            bob.signer(),
+        ),
+    );
+    assert!(!alice
+        .outbox()
+        .any(|m| matches!(m, Io::Fetch { rid: other, .. } if other == rid)));
+}
+
+#[test]
+fn test_preferred_seed_failover() {
+    let rid = arbitrary::gen::<RepoId>(1);
+    let bob = Peer::new("bob", [8, 8, 8, 8]);
+    let eve = Peer::new("eve", [9, 9, 9, 9]);
+    let mut alice = Peer::config(
+        "alice",
+        [7, 7, 7, 7],
+        MockStorage::empty(),
+        peer::Config {
+            config: Config {
+                preferred_seeds: vec![bob.id(), eve.id()],
+                ..Config::new(node::Alias::new("alice"))
+            },
+            ..peer::Config::default()
+        },
+    );
+
+    alice.seed(&rid, node::policy::Scope::All).unwrap();
+    alice.connect_to(&bob);
+    alice.connect_to(&eve);
+    assert_eq!(alice.active_seed(), Some(bob.id()));
+
+    // The missing repository is only fetched from the first preferred seed.
+    alice.elapse(service::SYNC_INTERVAL);
+    assert_matches!(
+        alice.fetches().collect::<Vec<_>>().as_slice(),
+        [(rid_, nid)] if *rid_ == rid && *nid == bob.id()
+    );
+
+    // The fetch fails, so we fail over to the second preferred seed.
+    alice.fetched(
+        rid,
+        bob.id,
+        Err(worker::FetchError::Io(
+            io::ErrorKind::ConnectionReset.into(),
+        )),
+    );
+    assert_eq!(alice.active_seed(), Some(eve.id()));
+    assert_matches!(
+        alice.fetches().next(),
+        Some((rid_, nid)) if rid_ == rid && nid == eve.id()
+    );
+}
+
 #[test]
 fn test_queued_fetch_max_capacity() {
     let storage = arbitrary::nonempty_storage(3);
@@ -1385,6 +1980,66 @@ fn test_queued_fetch_max_capacity() {
     assert_matches!(alice.fetches().next(), Some((rid, _)) if rid == rid3);
 }
 
+#[test]
+fn test_queued_fetch_fairness() {
+    let storage = arbitrary::nonempty_storage(3);
+    let mut repo_keys = storage.repos.keys();
+    let big = *repo_keys.next().unwrap();
+    let rid2 = *repo_keys.next().unwrap();
+    let rid3 = *repo_keys.next().unwrap();
+    let doc = storage.repos.get(&big).unwrap().doc.clone();
+    let mut alice = Peer::with_storage("alice", [7, 7, 7, 7], storage);
+    let bob = Peer::new("bob", [8, 8, 8, 8]);
+    let eve = Peer::new("eve", [9, 9, 9, 9]);
+    let carol = Peer::new("carol", [10, 10, 10, 10]);
+
+    alice.connect_to(&bob);
+    alice.connect_to(&eve);
+    alice.connect_to(&carol);
+
+    // The large repository is fetched from Bob, and more fetches of it are queued
+    // before the fetches of the other repositories.
+    let mut receivers = Vec::new();
+    for (rid, from) in [
+        (big, bob.id),
+        (big, eve.id),
+        (big, carol.id),
+        (rid2, bob.id),
+        (rid3, bob.id),
+    ] {
+        let (send, recv) = chan::bounded::<node::FetchResult>(1);
+        alice.command(Command::Fetch(rid, from, DEFAULT_TIMEOUT, send));
+        receivers.push(recv);
+    }
+    assert_matches!(alice.fetches().next(), Some((rid, nid)) if rid == big && nid == bob.id);
+    assert_matches!(alice.fetches().next(), None);
+
+    alice.elapse(KEEP_ALIVE_DELTA);
+
+    // Each repository gets its turn, instead of the large repository being fetched
+    // until its queue is empty.
+    let mut current = (big, bob.id);
+
+    for (rid, from) in [
+        (big, eve.id),
+        (rid2, bob.id),
+        (rid3, bob.id),
+        (big, carol.id),
+    ] {
+        let (rid_, from_) = current;
+
+        alice.fetched(rid_, from_, Ok(fetch::FetchResult::new(doc.clone())));
+        assert_matches!(
+            alice.fetches().next(),
+            Some((rid_, nid)) if rid_ == rid && nid == from,
+            "Expected a fetch of {rid} from {from}"
+        );
+        current = (rid, from);
+    }
+    alice.fetched(current.0, current.1, Ok(fetch::FetchResult::new(doc)));
+    assert_matches!(alice.fetches().next(), None);
+}
+
 #[test]
 fn test_queued_fetch_from_ann_same_rid() {
     let storage = arbitrary::nonempty_storage(3);
@@ -1461,6 +2116,69 @@ fn test_queued_fetch_from_ann_same_rid() {
     assert_matches!(alice.fetches().next(), None);
 }
 
+#[test]
+fn test_fetch_cache_skips_redundant_fetches() {
+    let storage = arbitrary::nonempty_storage(1);
+    let rid = *storage.repos.keys().next().unwrap();
+    let mut alice = Peer::with_storage("alice", [7, 7, 7, 7], storage);
+    let bob = Peer::new("bob", [8, 8, 8, 8]);
+    let mut eve = Peer::new("eve", [9, 9, 9, 9]);
+    let carol = Peer::new("carol", [10, 10, 10, 10]);
+    let ann = |oid, timestamp| RefsAnnouncement {
+        rid,
+        refs: vec![RefsAt {
+            remote: carol.id(),
+            at: oid,
+        }]
+        .try_into()
+        .unwrap(),
+        timestamp,
+    };
+    let result = |oid| {
+        Ok(fetch::FetchResult {
+            updated: vec![RefUpdate::Created {
+                name: carol
+                    .id()
+                    .to_namespace()
+                    .join(git::refname!("refs/sigrefs")),
+                oid,
+            }],
+            namespaces: [carol.id()].into_iter().collect(),
+            clone: false,
+            doc: arbitrary::gen(1),
+        })
+    };
+    let oid = arbitrary::oid();
+
+    alice.seed(&rid, policy::Scope::All).unwrap();
+    alice.connect_to(&bob);
+    alice.connect_to(&eve);
+
+    // Bob announces Carol's refs, and we fetch them.
+    alice.receive(bob.id, bob.announcement(ann(oid, bob.timestamp())));
+    assert_matches!(alice.fetches().next(), Some((rid_, nid_)) if rid_ == rid && nid_ == bob.id);
+    alice.fetched(rid, bob.id, result(oid));
+
+    // Eve announces the same refs shortly after. Even though our storage wasn't updated,
+    // the refs were just fetched, so there's nothing to do.
+    eve.elapse(LocalDuration::from_secs(1));
+    alice.receive(eve.id, eve.announcement(ann(oid, eve.timestamp())));
+    assert_matches!(alice.fetches().next(), None);
+
+    // New refs are fetched.
+    let new = arbitrary::oid();
+    eve.elapse(LocalDuration::from_secs(1));
+    alice.receive(eve.id, eve.announcement(ann(new, eve.timestamp())));
+    assert_matches!(alice.fetches().next(), Some((rid_, nid_)) if rid_ == rid && nid_ == eve.id);
+    alice.fetched(rid, eve.id, result(new));
+
+    // Once the fetch is no longer cached, the same refs are fetched again.
+    alice.elapse(FETCH_CACHE_EXPIRY);
+    eve.elapse(FETCH_CACHE_EXPIRY);
+    alice.receive(eve.id, eve.announcement(ann(oid, eve.timestamp())));
+    assert_matches!(alice.fetches().next(), Some((rid_, nid_)) if rid_ == rid && nid_ == eve.id);
+}
+
 #[test]
 fn test_queued_fetch_from_command_same_rid() {
     let storage = arbitrary::nonempty_storage(3);
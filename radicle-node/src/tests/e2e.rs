@@ -2,15 +2,16 @@ use std::{collections::HashSet, thread, time};
 
 use radicle::crypto::{test::signer::MockSigner, Signer};
 use radicle::node::{Alias, ConnectResult, FetchResult, Handle as _, DEFAULT_TIMEOUT};
+use radicle::storage::quota::Quota;
 use radicle::storage::{
     ReadRepository, ReadStorage, RefUpdate, RemoteRepository, SignRepository, ValidateRepository,
     WriteRepository, WriteStorage,
 };
 use radicle::test::fixtures;
 use radicle::{assert_matches, rad};
-use radicle::{git, issue};
+use radicle::{git, issue, patch};
 
-use crate::node::config::Limits;
+use crate::node::config::{Limits, RefCategory};
 use crate::node::{Config, ConnectOptions};
 use crate::service;
 use crate::service::policy::Scope;
@@ -42,6 +43,33 @@ fn test_inventory_sync_basic() {
     assert_eq!(routes.len(), 2);
 }
 
+#[test]
+fn test_node_status() {
+    logger::init(log::Level::Debug);
+
+    let tmp = tempfile::tempdir().unwrap();
+
+    let mut alice = Node::init(tmp.path(), Config::test(Alias::new("alice")));
+    let mut bob = Node::init(tmp.path(), Config::test(Alias::new("bob")));
+
+    alice.project("alice", "");
+    bob.project("bob", "");
+
+    let mut alice = alice.spawn();
+    let bob = bob.spawn();
+
+    alice.connect(&bob);
+    converge([&alice, &bob]);
+    alice.handle.announce_inventory().unwrap();
+
+    let status = alice.handle.status().unwrap();
+    assert_eq!(status.peers, 1);
+    assert_eq!(status.inventory, 1);
+    assert_eq!(status.pending_fetches, 0);
+    assert!(*status.started_at > 0);
+    assert!(status.last_announcement.is_some());
+}
+
 #[test]
 //
 //     alice -- bob -- eve
@@ -509,6 +537,49 @@ fn test_fetch_preserve_owned_refs() {
     assert_eq!(before, after);
 }
 
+#[test]
+fn test_shutdown_drains_fetches() {
+    logger::init(log::Level::Debug);
+
+    let tmp = tempfile::tempdir().unwrap();
+    let alice = Node::init(tmp.path(), Config::test(Alias::new("alice")));
+    let mut bob = Node::init(tmp.path(), Config::test(Alias::new("bob")));
+    let acme = bob.project("acme", "");
+
+    let mut alice = alice.spawn();
+    let bob = bob.spawn();
+
+    alice.connect(&bob);
+    converge([&alice, &bob]);
+    alice.handle.seed(acme, Scope::All).unwrap();
+
+    let fetch = thread::spawn({
+        let mut handle = (*alice.handle).clone();
+        let from = bob.id;
+
+        move || handle.fetch(acme, from, DEFAULT_TIMEOUT)
+    });
+    // Terminate the node while the fetch is ongoing.
+    while alice.handle.status().unwrap().pending_fetches == 0 && !fetch.is_finished() {
+        thread::sleep(time::Duration::from_millis(1));
+    }
+    alice.signals.send(()).unwrap();
+
+    let result = fetch.join().unwrap().unwrap();
+    assert!(result.is_success(), "{result:?}");
+
+    // The node shuts down once the fetch is done.
+    let started = time::Instant::now();
+    while !alice.thread.is_finished() {
+        assert!(started.elapsed() < time::Duration::from_secs(10), "Node didn't shut down");
+        thread::sleep(time::Duration::from_millis(100));
+    }
+    assert_matches!(
+        alice.storage.repository(acme).unwrap().validate(),
+        Ok(validations) if validations.is_empty()
+    );
+}
+
 #[test]
 fn test_clone() {
     logger::init(log::Level::Debug);
@@ -1305,3 +1376,97 @@ fn test_catchup_on_refs_announcements() {
     alice.connect(&seed);
     alice.has_remote_of(&acme, &bob_id);
 }
+
+#[test]
+fn test_fetch_quota_exceeded() {
+    logger::init(log::Level::Debug);
+
+    let tmp = tempfile::tempdir().unwrap();
+    let alice = Node::init(
+        tmp.path(),
+        Config {
+            limits: Limits {
+                // Bob's initial namespace has two references: `rad/id` and the default branch.
+                quota: Quota {
+                    refs: 3,
+                    ..Quota::default()
+                },
+                ..Limits::default()
+            },
+            ..Config::test(Alias::new("alice"))
+        },
+    );
+    let mut bob = Node::init(tmp.path(), Config::test(Alias::new("bob")));
+    let rid = bob.project("acme", "");
+
+    let mut alice = alice.spawn();
+    let bob = bob.spawn();
+
+    alice.handle.seed(rid, Scope::All).unwrap();
+    alice.connect(&bob);
+    converge([&alice, &bob]);
+
+    let result = alice.handle.fetch(rid, bob.id, DEFAULT_TIMEOUT).unwrap();
+    assert!(result.is_success());
+
+    let sigrefs = alice
+        .storage
+        .repository(rid)
+        .unwrap()
+        .reference_oid(&bob.id, &radicle::storage::refs::SIGREFS_BRANCH)
+        .unwrap();
+
+    // Each issue adds a reference to Bob's namespace, taking him over quota.
+    bob.issue(rid, "First", "Within quota");
+    bob.issue(rid, "Second", "Over quota");
+
+    let result = alice.handle.fetch(rid, bob.id, DEFAULT_TIMEOUT).unwrap();
+    assert_matches!(result, FetchResult::Failed { reason } if reason.contains("quota"));
+
+    // Bob's references in Alice's storage were left untouched.
+    let repo = alice.storage.repository(rid).unwrap();
+    assert_eq!(
+        repo.reference_oid(&bob.id, &radicle::storage::refs::SIGREFS_BRANCH)
+            .unwrap(),
+        sigrefs
+    );
+}
+
+#[test]
+fn test_replication_excluded_refs() {
+    logger::init(log::Level::Debug);
+
+    let tmp = tempfile::tempdir().unwrap();
+    let alice = Node::init(
+        tmp.path(),
+        Config {
+            exclude_refs: [RefCategory::Cob(issue::TYPENAME.clone())].into(),
+            ..Config::test(Alias::new("alice"))
+        },
+    );
+    let mut bob = Node::init(tmp.path(), Config::test(Alias::new("bob")));
+    let rid = bob.project("acme", "");
+
+    let mut alice = alice.spawn();
+    let bob = bob.spawn();
+
+    let issue_id = bob.issue(rid, "Crash on startup", "Blah.");
+    let patch_id = bob.patch(rid, "Fix crash on startup", "Blah blah.");
+
+    alice.handle.seed(rid, Scope::All).unwrap();
+    alice.connect(&bob);
+    converge([&alice, &bob]);
+
+    let result = alice.handle.fetch(rid, bob.id, DEFAULT_TIMEOUT).unwrap();
+    assert!(result.is_success());
+
+    // Bob's issue was not replicated, while his patch and identity were.
+    let repo = alice.storage.repository(rid).unwrap();
+    let refs = repo.references_of(&bob.id).unwrap();
+    let issue = git::refs::storage::cob(&bob.id, &issue::TYPENAME, &issue_id);
+    let patch = git::refs::storage::cob(&bob.id, &patch::TYPENAME, &patch_id);
+
+    assert!(!refs.contains_key(&issue.strip_namespace().to_ref_string()));
+    assert!(refs.contains_key(&patch.strip_namespace().to_ref_string()));
+    assert!(refs.contains_key(&radicle::storage::refs::IDENTITY_BRANCH.to_ref_string()));
+}
@@ -6,7 +6,11 @@ use crate::{wire, wire::varint, wire::varint::VarInt, wire::Message, Link};
 
 /// Protocol version strings all start with the magic sequence `rad`, followed
 /// by a version number.
-pub const PROTOCOL_VERSION: Version = Version([b'r', b'a', b'd', 0x1]);
+pub const PROTOCOL_VERSION: Version = Version::new(1);
+/// Oldest protocol version we are able to speak.
+pub const MIN_PROTOCOL_VERSION: Version = Version::new(1);
+/// Magic sequence every protocol version string starts with.
+const PROTOCOL_MAGIC: [u8; 3] = [b'r', b'a', b'd'];
 
 /// Control open byte.
 const CONTROL_OPEN: u8 = 0;
@@ -16,14 +20,52 @@ const CONTROL_CLOSE: u8 = 1;
 const CONTROL_EOF: u8 = 2;
 
 /// Protocol version.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Version([u8; 4]);
 
+impl Version {
+    /// Create a protocol version from its number.
+    pub const fn new(number: u8) -> Self {
+        let [r, a, d] = PROTOCOL_MAGIC;
+
+        Self([r, a, d, number])
+    }
+
+    /// The version number.
+    pub fn number(&self) -> u8 {
+        self.0[3]
+    }
+
+    /// Negotiate the version to speak with a remote, given the range of versions we support
+    /// and the highest version the remote supports.
+    ///
+    /// This is the highest version both sides support, or `None` if the remote's version is
+    /// older than the oldest we support. If the remote's version is newer than ours, we
+    /// expect the remote to downgrade to ours.
+    pub fn negotiate(min: Version, max: Version, remote: Version) -> Option<Version> {
+        let version = remote.min(max);
+
+        (version >= min).then_some(version)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Negotiate the version to speak with a remote that supports versions up to `remote`.
+/// See [`Version::negotiate`].
+pub fn negotiate(remote: Version) -> Option<Version> {
+    Version::negotiate(MIN_PROTOCOL_VERSION, PROTOCOL_VERSION, remote)
+}
+
 impl wire::Encode for Version {
     fn encode<W: io::Write + ?Sized>(&self, writer: &mut W) -> Result<usize, io::Error> {
-        writer.write_all(&PROTOCOL_VERSION.0)?;
+        writer.write_all(&self.0)?;
 
-        Ok(PROTOCOL_VERSION.0.len())
+        Ok(self.0.len())
     }
 }
 
@@ -32,7 +74,9 @@ impl wire::Decode for Version {
         let mut version = [0u8; 4];
         reader.read_exact(&mut version[..])?;
 
-        if version != PROTOCOL_VERSION.0 {
+        // Nb. Unknown version numbers are accepted here, so that the version can be
+        // negotiated by the protocol. Only the magic sequence is checked.
+        if version[..3] != PROTOCOL_MAGIC {
             return Err(wire::Error::InvalidProtocolVersion(version));
         }
         Ok(Self(version))
@@ -221,6 +265,12 @@ impl Frame {
         }
     }
 
+    /// Set the protocol version of the frame.
+    pub fn with_version(mut self, version: Version) -> Self {
+        self.version = version;
+        self
+    }
+
     /// Serialize frame to bytes.
     pub fn to_bytes(&self) -> Vec<u8> {
         wire::serialize(self)
@@ -321,7 +371,15 @@ impl wire::Decode for Frame {
             Ok(StreamKind::Gossip) => {
                 let data = varint::payload::decode(reader)?;
                 let mut cursor = io::Cursor::new(data);
-                let msg = Message::decode(&mut cursor)?;
+                let mut msg = Message::decode(&mut cursor)?;
+
+                // Announcements are followed by their hop count, unless the peer that sent
+                // them predates hop counts.
+                if let Message::Announcement(ann) = &mut msg {
+                    if cursor.position() < cursor.get_ref().len() as u64 {
+                        ann.hops = u8::decode(&mut cursor)?;
+                    }
+                }
                 let frame = Frame {
                     version,
                     stream,
@@ -335,7 +393,7 @@ impl wire::Decode for Frame {
             }
             Ok(StreamKind::Git { .. }) => {
                 let data = varint::payload::decode(reader)?;
-                Ok(Frame::git(stream, data))
+                Ok(Frame::git(stream, data).with_version(version))
             }
             Err(n) => Err(wire::Error::InvalidStreamKind(n)),
         }
@@ -351,7 +409,16 @@ impl wire::Encode for Frame {
         n += match &self.data {
             FrameData::Control(ctrl) => ctrl.encode(writer)?,
             FrameData::Git(data) => varint::payload::encode(data, writer)?,
-            FrameData::Gossip(msg) => varint::payload::encode(&wire::serialize(msg), writer)?,
+            FrameData::Gossip(msg) => {
+                let mut data = wire::serialize(msg);
+
+                // Nb. The hop count comes after the message, where peers that don't know
+                // about it ignore it.
+                if let Message::Announcement(ann) = msg {
+                    ann.hops.encode(&mut data)?;
+                }
+                varint::payload::encode(&data, writer)?
+            }
         };
 
         Ok(n)
@@ -360,7 +427,12 @@ impl wire::Encode for Frame {
 
 #[cfg(test)]
 mod test {
+    use radicle_crypto::test::signer::MockSigner;
+
     use super::*;
+    use crate::prelude::{BoundedVec, Timestamp};
+    use crate::service::message::{Announcement, AnnouncementMessage, InventoryAnnouncement};
+    use crate::wire::Encode;
 
     #[test]
     fn test_stream_id() {
@@ -380,4 +452,76 @@ mod test {
         assert_eq!(StreamId::control(Link::Inbound), StreamId(VarInt(0b001)));
         assert_eq!(StreamId::gossip(Link::Inbound), StreamId(VarInt(0b011)));
     }
+
+    #[test]
+    fn test_version_negotiation_matching() {
+        let v1 = Version::new(1);
+
+        assert_eq!(Version::negotiate(v1, v1, v1), Some(v1));
+        assert_eq!(negotiate(PROTOCOL_VERSION), Some(PROTOCOL_VERSION));
+    }
+
+    #[test]
+    fn test_version_negotiation_downgrade() {
+        let (v1, v2, v3) = (Version::new(1), Version::new(2), Version::new(3));
+
+        // The remote is older, we downgrade to its version.
+        assert_eq!(Version::negotiate(v1, v3, v2), Some(v2));
+        assert_eq!(Version::negotiate(v1, v2, v1), Some(v1));
+        // The remote is newer, it's expected to downgrade to ours.
+        assert_eq!(Version::negotiate(v1, v2, v3), Some(v2));
+    }
+
+    #[test]
+    fn test_version_negotiation_incompatible() {
+        let (v1, v2, v3) = (Version::new(1), Version::new(2), Version::new(3));
+
+        assert_eq!(Version::negotiate(v2, v3, v1), None);
+        assert_eq!(negotiate(Version::new(0)), None);
+    }
+
+    #[test]
+    fn test_version_decode() {
+        let frame =
+            Frame::git(StreamId::git(Link::Outbound), vec![1, 2, 3]).with_version(Version::new(9));
+        let decoded = wire::deserialize::<Frame>(&frame.to_bytes()).unwrap();
+
+        // Unknown version numbers are decoded, so that they can be negotiated.
+        assert_eq!(decoded.version, Version::new(9));
+        assert!(matches!(
+            wire::deserialize::<Version>(b"git\x01"),
+            Err(wire::Error::InvalidProtocolVersion(_))
+        ));
+    }
+
+    #[test]
+    fn test_gossip_hops() {
+        let signer = MockSigner::default();
+        let mut ann = AnnouncementMessage::from(InventoryAnnouncement {
+            inventory: BoundedVec::new(),
+            timestamp: Timestamp::EPOCH,
+        })
+        .signed(&signer);
+        ann.hops = 3;
+
+        let frame = Frame::gossip(Link::Outbound, Message::Announcement(ann.clone()));
+        let decoded = wire::deserialize::<Frame>(&frame.to_bytes()).unwrap();
+        assert_eq!(decoded.data, FrameData::Gossip(Message::Announcement(ann.clone())));
+
+        // Frames from peers that predate hop counts don't have one.
+        let mut bytes = Vec::new();
+        PROTOCOL_VERSION.encode(&mut bytes).unwrap();
+        StreamId::gossip(Link::Outbound).encode(&mut bytes).unwrap();
+        varint::payload::encode(
+            &wire::serialize(&Message::Announcement(ann.clone())),
+            &mut bytes,
+        )
+        .unwrap();
+
+        let decoded = wire::deserialize::<Frame>(&bytes).unwrap();
+        assert_eq!(
+            decoded.data,
+            FrameData::Gossip(Message::Announcement(Announcement { hops: 0, ..ann }))
+        );
+    }
 }
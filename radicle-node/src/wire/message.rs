@@ -272,6 +272,7 @@ impl wire::Encode for Message {
                 node,
                 message,
                 signature,
+                ..
             }) => {
                 n += node.encode(writer)?;
                 n += message.encode(writer)?;
@@ -324,6 +325,7 @@ impl wire::Decode for Message {
                     node,
                     message,
                     signature,
+                    hops: 0,
                 }
                 .into())
             }
@@ -336,6 +338,7 @@ impl wire::Decode for Message {
                     node,
                     message,
                     signature,
+                    hops: 0,
                 }
                 .into())
             }
@@ -348,6 +351,7 @@ impl wire::Decode for Message {
                     node,
                     message,
                     signature,
+                    hops: 0,
                 }
                 .into())
             }
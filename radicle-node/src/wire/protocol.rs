@@ -10,7 +10,7 @@ use std::{io, net, time};
 
 use amplify::Wrapper as _;
 use crossbeam_channel as chan;
-use cyphernet::addr::{HostName, InetHost, NetAddr};
+use cyphernet::addr::{HostName, NetAddr};
 use cyphernet::encrypt::noise::{HandshakePattern, Keyset, NoiseState};
 use cyphernet::proxy::socks5;
 use cyphernet::{Digest, EcSk, Ecdh, Sha256};
@@ -21,7 +21,8 @@ use netservices::{NetConnection, NetProtocol, NetReader, NetWriter};
 use reactor::{ResourceId, ResourceType, Timestamp};
 
 use radicle::collections::RandomMap;
-use radicle::node::NodeId;
+use radicle::node::address::{AddressResolver, Resolver};
+use radicle::node::{Address, NodeId, PeerKey};
 use radicle::storage::WriteStorage;
 
 use crate::crypto::Signer;
@@ -168,24 +169,111 @@ impl Streams {
     }
 }
 
-/// The initial state of an outbound peer before handshake is completed.
+/// The initial state of a peer before the handshake is completed.
 #[derive(Debug)]
-struct Outbound {
+struct Pending {
     /// Resource ID, if registered.
     id: Option<ResourceId>,
     /// Remote address.
     addr: NetAddr<HostName>,
-    /// Remote Node ID.
-    nid: NodeId,
+    /// Peer key. Only outbound peers are known by their Node ID at this stage.
+    key: PeerKey,
+    /// Connection direction.
+    link: Link,
 }
 
-/// The initial state of an inbound peer before handshake is completed.
-#[derive(Debug)]
-struct Inbound {
-    /// Resource ID, if registered.
-    id: Option<ResourceId>,
-    /// Remote address.
-    addr: NetAddr<HostName>,
+/// Holds peers that have not yet completed the handshake.
+///
+/// Entries are keyed by file descriptor and not by [`PeerKey`], since multiple inbound peers
+/// may share the same socket address, eg. when connecting via a NAT or SOCKS5 proxy.
+#[derive(Default)]
+struct Connecting(RandomMap<RawFd, Pending>);
+
+impl Connecting {
+    /// Add an inbound peer, known only by its socket address.
+    fn inbound(&mut self, fd: RawFd, addr: net::SocketAddr) {
+        self.insert(
+            fd,
+            Pending {
+                id: None,
+                addr: Address::from(addr).into_inner(),
+                key: PeerKey::Pending(addr),
+                link: Link::Inbound,
+            },
+        );
+    }
+
+    /// Add an outbound peer, known by its Node ID.
+    fn outbound(&mut self, fd: RawFd, nid: NodeId, addr: NetAddr<HostName>) {
+        self.insert(
+            fd,
+            Pending {
+                id: None,
+                addr,
+                key: PeerKey::Known(nid),
+                link: Link::Outbound,
+            },
+        );
+    }
+
+    fn insert(&mut self, fd: RawFd, peer: Pending) {
+        if let Some(existing) = self.0.insert(fd, peer) {
+            log::warn!(target: "wire", "Replacing existing pending peer {} (fd={fd})", existing.key);
+        }
+    }
+
+    /// Associate a pending peer with its resource id, once registered.
+    fn register(&mut self, fd: RawFd, id: ResourceId) -> Option<&Pending> {
+        let peer = self.0.get_mut(&fd)?;
+        peer.id = Some(id);
+
+        Some(peer)
+    }
+
+    /// Remove a pending peer, eg. when the connection is dropped before the handshake completes.
+    fn remove(&mut self, fd: &RawFd) -> Option<Pending> {
+        self.0.remove(fd)
+    }
+
+    /// Find a pending peer by resource id.
+    fn find(&self, id: &ResourceId) -> Option<&Pending> {
+        self.0.values().find(|p| p.id.as_ref() == Some(id))
+    }
+
+    /// Pending peers with the given Node ID, but a different file descriptor.
+    fn conflicting<'a>(
+        &'a self,
+        nid: &'a NodeId,
+        fd: RawFd,
+    ) -> impl Iterator<Item = (ResourceId, Link)> + 'a {
+        self.0.iter().filter_map(move |(c_fd, other)| {
+            if other.key.nid() == Some(nid) && *c_fd != fd {
+                other.id.map(|c_id| (c_id, other.link))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Complete the handshake of a pending peer, now known to be `nid`.
+    ///
+    /// This is the only place where a peer transitions from [`PeerKey::Pending`] to
+    /// [`PeerKey::Known`]: the entry is removed from the pending set and returned with its
+    /// key set to the remote's Node ID, ready to be registered as an established session.
+    ///
+    /// # Panics
+    ///
+    /// If the peer was dialed as a different node than the one that completed the handshake.
+    fn establish(&mut self, fd: RawFd, nid: NodeId) -> Option<Pending> {
+        let mut peer = self.0.remove(&fd)?;
+
+        if let PeerKey::Known(expected) = peer.key {
+            assert_eq!(expected, nid, "Connecting::establish: remote key mismatch");
+        }
+        peer.key = PeerKey::Known(nid);
+
+        Some(peer)
+    }
 }
 
 /// Peer connection state machine.
@@ -199,6 +287,9 @@ enum Peer {
         nid: NodeId,
         inbox: Deserializer<Frame>,
         streams: Streams,
+        /// Protocol version negotiated with the peer. Until we receive a frame from the peer,
+        /// this is our own version.
+        version: frame::Version,
     },
     /// The peer was scheduled for disconnection. Once the transport is handed over
     /// by the reactor, we can consider it disconnected.
@@ -242,6 +333,7 @@ impl Peer {
             nid,
             inbox: Deserializer::default(),
             streams: Streams::new(link),
+            version: frame::PROTOCOL_VERSION,
         }
     }
 }
@@ -310,16 +402,14 @@ pub struct Wire<D, S, G: Signer + Ecdh> {
     signer: G,
     /// Internal queue of actions to send to the reactor.
     actions: VecDeque<Action<G>>,
-    /// Outbound attempted peers without a session.
-    outbound: RandomMap<RawFd, Outbound>,
-    /// Inbound peers without a session.
-    inbound: RandomMap<RawFd, Inbound>,
+    /// Peers without a session, ie. that haven't completed the handshake.
+    connecting: Connecting,
     /// Listening addresses that are not yet registered.
     listening: RandomMap<RawFd, net::SocketAddr>,
     /// Peer (established) sessions.
     peers: Peers,
-    /// SOCKS5 proxy address.
-    proxy: net::SocketAddr,
+    /// Resolves peer addresses into connection targets.
+    resolver: Resolver,
 }
 
 impl<D, S, G> Wire<D, S, G>
@@ -340,10 +430,9 @@ where
             service,
             worker,
             signer,
-            proxy,
+            resolver: Resolver::new(proxy),
             actions: VecDeque::new(),
-            inbound: RandomMap::default(),
-            outbound: RandomMap::default(),
+            connecting: Connecting::default(),
             listening: RandomMap::default(),
             peers: Peers(RandomMap::default()),
         }
@@ -364,10 +453,9 @@ where
 
                 // Check for attempted outbound connections. Unestablished inbound connections don't
                 // have an NID yet.
-                self.outbound
-                    .values()
-                    .find(|o| o.id == Some(id))
-                    .map(|o| (o.nid, Link::Outbound))
+                self.connecting
+                    .find(&id)
+                    .and_then(|p| p.key.nid().map(|nid| (*nid, p.link)))
             }
             Entry::Occupied(mut e) => match e.get_mut() {
                 Peer::Disconnecting { nid, link, .. } => {
@@ -381,6 +469,7 @@ where
                     log::debug!(target: "wire", "Disconnecting peer with id={id}: {reason}");
                     let nid = *nid;
                     let link = *link;
+                    let version = *version;
 
                     streams.shutdown();
                     e.insert(Peer::Disconnecting {
@@ -409,7 +498,13 @@ where
             return;
         };
 
-        if let Peer::Connected { link, streams, .. } = peer {
+        if let Peer::Connected {
+            link,
+            streams,
+            version,
+            ..
+        } = peer
+        {
             // Nb. It's possible that the stream would already be unregistered if we received an
             // early "close" from the remote. Otherwise, we unregister it here and send the "close"
             // ourselves.
@@ -423,7 +518,8 @@ where
                     frame::Control::Close {
                         stream: task.stream,
                     },
-                );
+                )
+                .with_version(*version);
                 self.actions.push_back(Action::Send(fd, frame.to_bytes()));
             }
         } else {
@@ -455,7 +551,13 @@ where
             log::warn!(target: "wire", "Peer {remote} is not known; ignoring flush");
             return;
         };
-        let Peer::Connected { streams, link, .. } = peer else {
+        let Peer::Connected {
+            streams,
+            link,
+            version,
+            ..
+        } = peer
+        else {
             log::warn!(target: "wire", "Peer {remote} is not connected; ignoring flush");
             return;
         };
@@ -472,22 +574,27 @@ where
                 }
                 ChannelEvent::Close => Frame::control(*link, frame::Control::Close { stream }),
                 ChannelEvent::Eof => Frame::control(*link, frame::Control::Eof { stream }),
-            };
-            self.actions
-                .push_back(reactor::Action::Send(fd, frame.to_bytes()));
+            }
+            .with_version(*version)
+            .to_bytes();
+
+            self.service.metrics_mut().bytes_sent += frame.len() as u64;
+            self.actions.push_back(reactor::Action::Send(fd, frame));
         }
     }
 
     fn cleanup(&mut self, id: ResourceId, fd: RawFd) {
-        if self.inbound.remove(&fd).is_some() {
-            log::debug!(target: "wire", "Cleaning up inbound peer state with id={id} (fd={fd})");
-        } else if let Some(outbound) = self.outbound.remove(&fd) {
-            log::debug!(target: "wire", "Cleaning up outbound peer state with id={id} (fd={fd})");
-            self.service.disconnected(
-                outbound.nid,
-                Link::Outbound,
-                &DisconnectReason::connection(),
-            );
+        if let Some(peer) = self.connecting.remove(&fd) {
+            match peer.key {
+                PeerKey::Pending(addr) => {
+                    log::debug!(target: "wire", "Cleaning up pending peer state for {addr} with id={id} (fd={fd})");
+                }
+                PeerKey::Known(nid) => {
+                    log::debug!(target: "wire", "Cleaning up pending peer state for {nid} with id={id} (fd={fd})");
+                    self.service
+                        .disconnected(nid, peer.link, &DisconnectReason::connection());
+                }
+            }
         } else {
             log::warn!(target: "wire", "Tried to cleanup unknown peer with id={id} (fd={fd})");
         }
@@ -521,26 +628,26 @@ where
     ) {
         match event {
             ListenerEvent::Accepted(connection) => {
-                let Ok(addr) = connection.remote_addr() else {
+                let Ok(socket_addr) = connection.peer_addr() else {
                     log::warn!(target: "wire", "Accepted connection doesn't have remote address; dropping..");
                     drop(connection);
 
                     return;
                 };
-                let addr: NetAddr<HostName> = addr.into();
+                let addr = Address::from(socket_addr);
                 let fd = connection.as_raw_fd();
                 log::debug!(target: "wire", "Accepting inbound connection from {addr} (fd={fd})..");
 
                 // If the service doesn't want to accept this connection,
                 // we drop the connection here, which disconnects the socket.
-                if !self.service.accepted(addr.clone().into()) {
+                if !self.service.accepted(addr.clone()) {
                     log::debug!(target: "wire", "Rejecting inbound connection from {addr} (fd={fd})..");
                     drop(connection);
 
                     return;
                 }
 
-                let session = accept::<G>(addr.clone(), connection, self.signer.clone());
+                let session = accept::<G>(addr.into_inner(), connection, self.signer.clone());
                 let transport = match NetTransport::with_session(session, Link::Inbound) {
                     Ok(transport) => transport,
                     Err(err) => {
@@ -549,7 +656,7 @@ where
                     }
                 };
 
-                self.inbound.insert(fd, Inbound { id: None, addr });
+                self.connecting.inbound(fd, socket_addr);
                 self.actions
                     .push_back(reactor::Action::RegisterTransport(transport))
             }
@@ -567,12 +674,8 @@ where
                 }
             }
             ResourceType::Transport => {
-                if let Some(outbound) = self.outbound.get_mut(&fd) {
-                    log::debug!(target: "wire", "Outbound peer resource registered for {} with id={id} (fd={fd})", outbound.nid);
-                    outbound.id = Some(id);
-                } else if let Some(inbound) = self.inbound.get_mut(&fd) {
-                    log::debug!(target: "wire", "Inbound peer resource registered with id={id} (fd={fd})");
-                    inbound.id = Some(id);
+                if let Some(peer) = self.connecting.register(fd, id) {
+                    log::debug!(target: "wire", "Peer resource registered for {} with id={id} (fd={fd})", peer.key);
                 } else {
                     log::warn!(target: "wire", "Unknown peer registered with fd={fd} and id={id}");
                 }
@@ -597,12 +700,7 @@ where
 
                     return;
                 }
-                let (addr, link) = if let Some(peer) = self.inbound.remove(&fd) {
-                    (peer.addr, Link::Inbound)
-                } else if let Some(peer) = self.outbound.remove(&fd) {
-                    assert_eq!(nid, peer.nid);
-                    (peer.addr, Link::Outbound)
-                } else {
+                let Some(Pending { addr, link, .. }) = self.connecting.establish(fd, nid) else {
                     log::error!(target: "wire", "Session for {nid} (id={id}) not found");
                     return;
                 };
@@ -641,13 +739,7 @@ where
 
                     // Outbound connection attempts with the same remote key but a different file
                     // descriptor are conflicting.
-                    conflicting.extend(self.outbound.iter().filter_map(|(c_fd, other)| {
-                        if other.nid == nid && *c_fd != fd {
-                            other.id.map(|c_id| (c_id, Link::Outbound))
-                        } else {
-                            None
-                        }
-                    }));
+                    conflicting.extend(self.connecting.conflicting(&nid, fd));
 
                     for (c_id, c_link) in conflicting {
                         // If we have precedence, the inbound connection is closed.
@@ -699,17 +791,45 @@ where
                 }
             }
             SessionEvent::Data(data) => {
+                self.service.metrics_mut().bytes_received += data.len() as u64;
+
                 if let Some(Peer::Connected {
                     nid,
                     inbox,
                     streams,
+                    version,
                     ..
                 }) = self.peers.get_mut(&id)
                 {
                     inbox.input(&data);
 
                     loop {
-                        match inbox.deserialize_next() {
+                        let result = inbox.deserialize_next();
+
+                        if let Ok(Some(Frame { version: remote, .. })) = &result {
+                            let Some(negotiated) = frame::negotiate(*remote) else {
+                                log::error!(
+                                    target: "wire",
+                                    "Peer {nid} speaks incompatible protocol version {remote}, \
+                                    while we support versions {} to {}",
+                                    frame::MIN_PROTOCOL_VERSION, frame::PROTOCOL_VERSION
+                                );
+                                self.disconnect(
+                                    id,
+                                    DisconnectReason::IncompatibleVersion {
+                                        local: frame::PROTOCOL_VERSION.number(),
+                                        remote: remote.number(),
+                                    },
+                                );
+                                break;
+                            };
+                            if negotiated != *version {
+                                log::debug!(target: "wire", "Negotiated protocol version {negotiated} with {nid}");
+                                *version = negotiated;
+                            }
+                        }
+
+                        match result {
                             Ok(Some(Frame {
                                 data: FrameData::Control(frame::Control::Open { stream }),
                                 ..
@@ -913,8 +1033,13 @@ where
         while let Some(ev) = self.service.next() {
             match ev {
                 Io::Write(node_id, msgs) => {
-                    let (fd, link) = match self.peers.lookup(&node_id) {
-                        Some((fd, Peer::Connected { link, .. })) => (fd, *link),
+                    let (fd, link, version) = match self.peers.lookup(&node_id) {
+                        Some((
+                            fd,
+                            Peer::Connected {
+                                link, version, ..
+                            },
+                        )) => (fd, *link, *version),
                         Some((_, peer)) => {
                             // If the peer is disconnected by the wire protocol, the service may
                             // not be aware of this yet, and may continue to write messages to it.
@@ -933,9 +1058,11 @@ where
                     let mut data = Vec::new();
                     for msg in msgs {
                         Frame::gossip(link, msg)
+                            .with_version(version)
                             .encode(&mut data)
                             .expect("in-memory writes never fail");
                     }
+                    self.service.metrics_mut().bytes_sent += data.len() as u64;
                     self.actions.push_back(reactor::Action::Send(fd, data));
                 }
                 Io::Connect(node_id, addr) => {
@@ -950,24 +1077,15 @@ where
                     }
                     self.service.attempted(node_id, addr.clone());
 
-                    match dial::<G>(
-                        addr.to_inner(),
-                        node_id,
-                        self.signer.clone(),
-                        self.proxy.into(),
-                        false,
-                    )
+                    match dial::<G>(addr.to_inner(), node_id, self.signer.clone(), &self.resolver)
                     .and_then(|session| {
                         NetTransport::<WireSession<G>>::with_session(session, Link::Outbound)
                     }) {
                         Ok(transport) => {
-                            self.outbound.insert(
+                            self.connecting.outbound(
                                 transport.as_raw_fd(),
-                                Outbound {
-                                    id: None,
-                                    nid: node_id,
-                                    addr: addr.to_inner(),
-                                },
+                                node_id,
+                                addr.to_inner(),
                             );
                             log::debug!(
                                 target: "wire",
@@ -1006,8 +1124,15 @@ where
                 } => {
                     log::trace!(target: "wire", "Processing fetch for {rid} from {remote}..");
 
-                    let Some((fd, Peer::Connected { link, streams, .. })) =
-                        self.peers.lookup_mut(&remote)
+                    let Some((
+                        fd,
+                        Peer::Connected {
+                            link,
+                            streams,
+                            version,
+                            ..
+                        },
+                    )) = self.peers.lookup_mut(&remote)
                     else {
                         // Nb. It's possible that a peer is disconnected while an `Io::Fetch`
                         // is in the service's i/o buffer. Since the service may not purge the
@@ -1043,7 +1168,9 @@ where
                     }
                     self.actions.push_back(Action::Send(
                         fd,
-                        Frame::control(link, frame::Control::Open { stream }).to_bytes(),
+                        Frame::control(link, frame::Control::Open { stream })
+                            .with_version(version)
+                            .to_bytes(),
                     ));
                 }
             }
@@ -1053,23 +1180,20 @@ where
 }
 
 /// Establish a new outgoing connection.
+///
+/// The remote address is resolved with the given resolver, which decides whether we connect
+/// to the remote directly or via a SOCKS5 proxy.
 pub fn dial<G: Signer + Ecdh<Pk = NodeId>>(
     remote_addr: NetAddr<HostName>,
     remote_id: <G as EcSk>::Pk,
     signer: G,
-    proxy_addr: NetAddr<InetHost>,
-    force_proxy: bool,
+    resolver: &impl AddressResolver,
 ) -> io::Result<WireSession<G>> {
-    let connection = if force_proxy {
-        // Nb. This timeout is currently not used by the underlying library due to the
-        // `socket2` library not supporting non-blocking connect with timeout.
-        net::TcpStream::connect_nonblocking(proxy_addr, DEFAULT_DIAL_TIMEOUT)?
-    } else {
-        net::TcpStream::connect_nonblocking(
-            remote_addr.connection_addr(proxy_addr),
-            DEFAULT_DIAL_TIMEOUT,
-        )?
-    };
+    let target = resolver.resolve(&Address::from(remote_addr.clone()))?;
+    // Nb. This timeout is currently not used by the underlying library due to the
+    // `socket2` library not supporting non-blocking connect with timeout.
+    let connection =
+        net::TcpStream::connect_nonblocking(target.socket_addr().into(), DEFAULT_DIAL_TIMEOUT)?;
     connection.set_read_timeout(Some(DEFAULT_CONNECTION_TIMEOUT))?;
     connection.set_write_timeout(Some(DEFAULT_CONNECTION_TIMEOUT))?;
 
@@ -1078,7 +1202,7 @@ pub fn dial<G: Signer + Ecdh<Pk = NodeId>>(
         Some(remote_id),
         connection,
         signer,
-        force_proxy,
+        target.is_proxied(),
     ))
 }
 
@@ -1122,9 +1246,77 @@ fn session<G: Signer + Ecdh<Pk = NodeId>>(
 mod test {
     use super::*;
     use crate::service::{Message, ZeroBytes};
+    use crate::test::arbitrary;
     use crate::wire;
     use crate::wire::varint;
 
+    #[test]
+    fn test_connecting_establish() {
+        let alice = arbitrary::gen::<NodeId>(1);
+        let bob = arbitrary::gen::<NodeId>(1);
+        let addr = net::SocketAddr::from(([192, 168, 1, 7], 8776));
+        let mut connecting = Connecting::default();
+
+        connecting.inbound(3, addr);
+        connecting.outbound(4, bob, Address::from(addr).into_inner());
+
+        assert_eq!(connecting.0[&3].key, PeerKey::Pending(addr));
+        assert_eq!(connecting.0[&4].key, PeerKey::Known(bob));
+
+        let peer = connecting.establish(3, alice).unwrap();
+        assert_eq!(peer.key, PeerKey::Known(alice));
+        assert_eq!(peer.link, Link::Inbound);
+
+        let peer = connecting.establish(4, bob).unwrap();
+        assert_eq!(peer.key, PeerKey::Known(bob));
+        assert_eq!(peer.link, Link::Outbound);
+
+        assert!(connecting.0.is_empty());
+        assert!(connecting.establish(3, alice).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_connecting_establish_mismatch() {
+        let alice = arbitrary::gen::<NodeId>(1);
+        let bob = arbitrary::gen::<NodeId>(1);
+        let addr = net::SocketAddr::from(([192, 168, 1, 7], 8776));
+        let mut connecting = Connecting::default();
+
+        connecting.outbound(3, bob, Address::from(addr).into_inner());
+        connecting.establish(3, alice);
+    }
+
+    #[test]
+    fn test_connecting_shared_socket_addr() {
+        // Two peers connecting via the same SOCKS5 proxy, or from behind the same NAT,
+        // share a socket address.
+        let alice = arbitrary::gen::<NodeId>(1);
+        let bob = arbitrary::gen::<NodeId>(1);
+        let proxy = net::SocketAddr::from(([127, 0, 0, 1], 9050));
+        let mut connecting = Connecting::default();
+
+        connecting.inbound(3, proxy);
+        connecting.inbound(4, proxy);
+
+        assert_eq!(connecting.0.len(), 2);
+        assert_eq!(connecting.0[&3].key, connecting.0[&4].key);
+
+        // Each peer transitions independently, based on the key learned in its own handshake.
+        let b = connecting.establish(4, bob).unwrap();
+        assert_eq!(b.key, PeerKey::Known(bob));
+        assert_eq!(connecting.0[&3].key, PeerKey::Pending(proxy));
+
+        let a = connecting.establish(3, alice).unwrap();
+        assert_eq!(a.key, PeerKey::Known(alice));
+        assert_eq!(a.addr, b.addr);
+
+        // Pending inbound peers never conflict with each other, since their identity is unknown.
+        connecting.inbound(5, proxy);
+        connecting.inbound(6, proxy);
+        assert_eq!(connecting.conflicting(&alice, 5).count(), 0);
+    }
+
     #[test]
     fn test_message_with_extension() {
         use crate::deserializer;
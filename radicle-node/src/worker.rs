@@ -67,6 +67,16 @@ impl FetchError {
     pub fn is_timeout(&self) -> bool {
         matches!(self, FetchError::Io(e) if e.kind() == io::ErrorKind::TimedOut)
     }
+
+    /// Check if the remote exceeded its storage quota.
+    pub fn is_quota_exceeded(&self) -> bool {
+        matches!(self, FetchError::Fetch(fetch::error::Fetch::Run(e)) if e.is_quota_exceeded())
+    }
+
+    /// Check if the remote advertised refs that don't match their signed refs.
+    pub fn is_sigrefs_mismatch(&self) -> bool {
+        matches!(self, FetchError::Fetch(fetch::error::Fetch::Run(e)) if e.is_sigrefs_mismatch())
+    }
 }
 
 /// Error returned by fetch responder.
@@ -168,6 +178,8 @@ pub struct FetchConfig {
     /// Configuration for `git gc` garbage collection. Defaults to `1
     /// hour ago`.
     pub expiry: garbage::Expiry,
+    /// Categories of references that are replicated.
+    pub filter: radicle_fetch::RefFilter,
 }
 
 /// A worker that replicates git objects.
@@ -296,6 +308,7 @@ impl Worker {
             limit,
             local,
             expiry,
+            filter,
         } = &self.fetch_config;
         // N.b. if the `rid` is blocked this will return an error, so
         // we won't continue with any further set up of the fetch.
@@ -309,6 +322,7 @@ impl Worker {
             &self.storage,
             allowed,
             blocked,
+            filter.clone(),
             channels,
             notifs,
         )?;
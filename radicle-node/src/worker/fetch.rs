@@ -13,7 +13,7 @@ use radicle::storage::{
     ReadRepository, ReadStorage as _, RefUpdate, RemoteRepository, WriteRepository as _,
 };
 use radicle::{cob, git, node, Storage};
-use radicle_fetch::{Allowed, BlockList, FetchLimit};
+use radicle_fetch::{Allowed, BlockList, FetchLimit, RefFilter};
 
 use super::channels::ChannelsFlush;
 
@@ -58,20 +58,23 @@ impl Handle {
         storage: &Storage,
         follow: Allowed,
         blocked: BlockList,
+        filter: RefFilter,
         channels: ChannelsFlush,
         notifications: node::notifications::StoreWriter,
     ) -> Result<Self, error::Handle> {
         let exists = storage.contains(&rid)?;
         if exists {
             let repo = storage.repository(rid)?;
-            let handle = radicle_fetch::Handle::new(local, repo, follow, blocked, channels)?;
+            let handle =
+                radicle_fetch::Handle::new(local, repo, follow, blocked, filter, channels)?;
             Ok(Handle::Pull {
                 handle,
                 notifications,
             })
         } else {
             let (repo, tmp) = storage.lock_repository(rid)?;
-            let handle = radicle_fetch::Handle::new(local, repo, follow, blocked, channels)?;
+            let handle =
+                radicle_fetch::Handle::new(local, repo, follow, blocked, filter, channels)?;
             Ok(Handle::Clone { handle, tmp })
         }
     }
@@ -394,8 +394,14 @@ fn patch_open<G: Signer>(
     if base == head {
         return Err(Error::EmptyPatch);
     }
-    let (title, description) =
-        term::patch::get_create_message(opts.message, &stored.backend, &base, &head)?;
+    let template = term::patch::template(working, &profile.config_for(&stored.id)?)?;
+    let (title, description) = term::patch::get_create_message(
+        opts.message,
+        &stored.backend,
+        &base,
+        &head,
+        template.as_deref(),
+    )?;
 
     let patch = if opts.draft {
         patches.draft(
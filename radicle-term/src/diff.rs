@@ -0,0 +1,311 @@
+//! Rendering of unified diffs.
+use crate::{Color, Constraint, Element, Interactive, Label, Line, Paint, Size};
+
+/// Placeholder rendered in place of the hunks of binary files.
+pub const BINARY_PLACEHOLDER: &str = "Binary file changed";
+
+/// Kind of diff line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    /// Line added.
+    Addition,
+    /// Line removed.
+    Deletion,
+    /// Unchanged line, shown for context.
+    Context,
+}
+
+impl LineKind {
+    /// The marker that prefixes lines of this kind.
+    pub fn marker(&self) -> char {
+        match self {
+            Self::Addition => '+',
+            Self::Deletion => '-',
+            Self::Context => ' ',
+        }
+    }
+
+    /// The color of lines of this kind.
+    pub fn color(&self) -> Color {
+        match self {
+            Self::Addition => Color::Green,
+            Self::Deletion => Color::Red,
+            Self::Context => Color::Unset,
+        }
+    }
+}
+
+/// A line of a diff hunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    /// Kind of line.
+    pub kind: LineKind,
+    /// Line number in the old file, if any.
+    pub old: Option<u32>,
+    /// Line number in the new file, if any.
+    pub new: Option<u32>,
+    /// Line content, without the trailing newline.
+    pub content: String,
+}
+
+/// A diff hunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// Hunk header, eg. `@@ -1,3 +1,4 @@`.
+    pub header: String,
+    /// Hunk lines.
+    pub lines: Vec<DiffLine>,
+}
+
+/// The changes made to a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileContent {
+    /// Textual changes, as hunks.
+    Plain(Vec<Hunk>),
+    /// Binary file changes, which are not rendered.
+    Binary,
+}
+
+/// The diff of a single file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    /// Path of the file.
+    pub path: String,
+    /// Changes made to the file.
+    pub content: FileContent,
+}
+
+/// A unified diff that implements [`Element`].
+///
+/// Added, removed and context lines are colored and prefixed with their old and new line
+/// numbers. When output is not [`Interactive`], lines are rendered without styling.
+#[derive(Debug, Clone, Default)]
+pub struct Diff {
+    files: Vec<FileDiff>,
+    interactive: Interactive,
+}
+
+impl Diff {
+    /// Create a new diff from file diffs.
+    pub fn new(files: impl IntoIterator<Item = FileDiff>) -> Self {
+        Self {
+            files: files.into_iter().collect(),
+            interactive: Interactive::default(),
+        }
+    }
+
+    /// Set whether the diff is rendered to an interactive terminal.
+    pub fn interactive(mut self, interactive: Interactive) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Get the file diffs.
+    pub fn files(&self) -> &[FileDiff] {
+        &self.files
+    }
+
+    /// Style a label, if output is interactive.
+    fn paint(&self, s: &str, f: impl FnOnce(Paint<String>) -> Paint<String>) -> Label {
+        if self.interactive.yes() {
+            Label::from(f(Paint::new(s.to_owned())))
+        } else {
+            Label::new(s)
+        }
+    }
+
+    /// Width of the line number gutter.
+    fn gutter(&self) -> usize {
+        self.files
+            .iter()
+            .filter_map(|f| match &f.content {
+                FileContent::Plain(hunks) => Some(hunks),
+                FileContent::Binary => None,
+            })
+            .flatten()
+            .flat_map(|h| &h.lines)
+            .flat_map(|l| [l.old, l.new])
+            .flatten()
+            .max()
+            .map_or(1, |n| n.to_string().len())
+    }
+
+    fn lines(&self) -> Vec<Line> {
+        let gutter = self.gutter();
+        let lineno = |n: Option<u32>| {
+            let n = n.map(|n| n.to_string()).unwrap_or_default();
+            self.paint(&format!("{n:>gutter$}"), |p| p.dim())
+        };
+        let mut lines = Vec::new();
+
+        for file in &self.files {
+            lines.push(Line::new(self.paint(&file.path, |p| p.bold())));
+
+            match &file.content {
+                FileContent::Binary => {
+                    lines.push(Line::new(self.paint(BINARY_PLACEHOLDER, |p| p.dim())));
+                }
+                FileContent::Plain(hunks) => {
+                    for hunk in hunks {
+                        lines.push(Line::new(self.paint(&hunk.header, |p| p.fg(Color::Cyan))));
+
+                        for line in &hunk.lines {
+                            let color = line.kind.color();
+                            let marker = line.kind.marker().to_string();
+                            let content = format!(" {}", line.content);
+
+                            lines.push(
+                                Line::spaced([
+                                    lineno(line.old),
+                                    lineno(line.new),
+                                    self.paint(&marker, |p| p.fg(color)),
+                                ])
+                                .item(self.paint(&content, |p| p.fg(color))),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        lines
+    }
+}
+
+impl Element for Diff {
+    fn size(&self, parent: Constraint) -> Size {
+        let lines = self.render(parent);
+        let cols = lines.iter().map(Line::width).max().unwrap_or(0);
+
+        Size::new(cols, lines.len()).constrain(parent)
+    }
+
+    fn render(&self, parent: Constraint) -> Vec<Line> {
+        self.lines()
+            .into_iter()
+            .map(|mut line| {
+                line.truncate(parent.max.cols, "…");
+                line
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "git2")]
+impl TryFrom<&git2::Diff<'_>> for Diff {
+    type Error = git2::Error;
+
+    fn try_from(diff: &git2::Diff<'_>) -> Result<Self, Self::Error> {
+        let mut files = Vec::new();
+
+        for (ix, delta) in diff.deltas().enumerate() {
+            let path = delta
+                .new_file()
+                .path()
+                .or(delta.old_file().path())
+                .map(|p| p.display().to_string())
+                .unwrap_or_default();
+            let patch = git2::Patch::from_diff(diff, ix)?;
+
+            let content = match patch {
+                Some(patch) if !delta.flags().is_binary() => {
+                    let mut hunks = Vec::with_capacity(patch.num_hunks());
+
+                    for h in 0..patch.num_hunks() {
+                        let (hunk, n) = patch.hunk(h)?;
+                        let mut lines = Vec::with_capacity(n);
+
+                        for l in 0..n {
+                            let line = patch.line_in_hunk(h, l)?;
+                            let kind = match line.origin() {
+                                '+' => LineKind::Addition,
+                                '-' => LineKind::Deletion,
+                                ' ' => LineKind::Context,
+                                // Eg. "No newline at end of file" markers.
+                                _ => continue,
+                            };
+                            lines.push(DiffLine {
+                                kind,
+                                old: line.old_lineno(),
+                                new: line.new_lineno(),
+                                content: String::from_utf8_lossy(line.content())
+                                    .trim_end_matches(['\n', '\r'])
+                                    .to_owned(),
+                            });
+                        }
+                        hunks.push(Hunk {
+                            header: String::from_utf8_lossy(hunk.header())
+                                .trim_end()
+                                .to_owned(),
+                            lines,
+                        });
+                    }
+                    FileContent::Plain(hunks)
+                }
+                _ => FileContent::Binary,
+            };
+            files.push(FileDiff { path, content });
+        }
+        Ok(Self::new(files))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PATCH: &str = "\
+diff --git a/README b/README
+index 557db03..f6b2a4e 100644
+--- a/README
++++ b/README
+@@ -1,2 +1,2 @@
+ Hello World
+-Goodbye
++Farewell
+";
+
+    #[test]
+    #[cfg(feature = "git2")]
+    fn test_diff_render() {
+        let diff = git2::Diff::from_buffer(PATCH.as_bytes()).unwrap();
+        let mut files = Diff::try_from(&diff).unwrap().files().to_vec();
+        files.push(FileDiff {
+            path: String::from("logo.png"),
+            content: FileContent::Binary,
+        });
+        let diff = Diff::new(files).interactive(Interactive::Yes);
+        let lines = diff.render(Constraint::UNBOUNDED);
+        let markers = lines[2..5]
+            .iter()
+            .map(|line| {
+                let marker = line.clone().into_iter().nth(4).unwrap();
+                (marker.content().to_owned(), marker.paint().style.foreground)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 7);
+        assert!(lines[1].to_string().contains("@@ -1,2 +1,2 @@"));
+        assert_eq!(
+            markers,
+            vec![
+                (String::from(" "), Color::Unset),
+                (String::from("-"), Color::Red),
+                (String::from("+"), Color::Green),
+            ]
+        );
+
+        let plain = Diff::new(diff.files().to_vec()).display(Constraint::UNBOUNDED);
+        assert_eq!(
+            plain.lines().collect::<Vec<_>>(),
+            vec![
+                "README",
+                "@@ -1,2 +1,2 @@",
+                "1 1   Hello World",
+                "2   - Goodbye",
+                "  2 + Farewell",
+                "logo.png",
+                BINARY_PLACEHOLDER,
+            ]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::theme::{self, Role};
 use crate::Paint;
 
 pub fn default<D: std::fmt::Display>(msg: D) -> Paint<D> {
@@ -9,11 +10,11 @@ pub fn wrap<D: std::fmt::Display>(msg: D) -> Paint<D> {
 }
 
 pub fn negative<D: std::fmt::Display>(msg: D) -> Paint<D> {
-    Paint::red(msg)
+    theme::paint(Role::Error, msg)
 }
 
 pub fn positive<D: std::fmt::Display>(msg: D) -> Paint<D> {
-    Paint::green(msg)
+    theme::paint(Role::Success, msg)
 }
 
 pub fn primary<D: std::fmt::Display>(msg: D) -> Paint<D> {
@@ -33,7 +34,7 @@ pub fn yellow<D: std::fmt::Display>(msg: D) -> Paint<D> {
 }
 
 pub fn hint<D: std::fmt::Display>(msg: D) -> Paint<D> {
-    Paint::yellow(msg)
+    theme::paint(Role::Hint, msg)
 }
 
 pub fn faint<D: std::fmt::Display>(msg: D) -> Paint<D> {
@@ -41,7 +42,7 @@ pub fn faint<D: std::fmt::Display>(msg: D) -> Paint<D> {
 }
 
 pub fn highlight<D: std::fmt::Debug + std::fmt::Display>(input: D) -> Paint<D> {
-    Paint::green(input).bold()
+    theme::paint(Role::Highlight, input)
 }
 
 pub fn badge_primary<D: std::fmt::Display>(input: D) -> Paint<String> {
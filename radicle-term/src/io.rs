@@ -1,17 +1,20 @@
 use std::ffi::OsStr;
 use std::fmt::Write;
+use std::io::IsTerminal;
+use std::path::PathBuf;
 use std::process::Stdio;
-use std::{env, fmt, io, process};
+use std::{env, fmt, fs, io, process};
 
 use inquire::ui::{ErrorMessageRenderConfig, StyleSheet, Styled};
 use inquire::validator;
 use inquire::InquireError;
-use inquire::{ui::Color, ui::RenderConfig, Confirm, CustomType, Password};
+use inquire::{ui::Color, ui::RenderConfig, Confirm, CustomType, MultiSelect, Password};
 use once_cell::sync::Lazy;
 use zeroize::Zeroizing;
 
 use crate::command;
 use crate::format;
+use crate::theme::{self, Role};
 use crate::{style, Paint, Size};
 
 pub use inquire;
@@ -87,7 +90,7 @@ pub use success;
 pub use tip;
 
 pub fn success_args<W: io::Write>(w: &mut W, args: fmt::Arguments) {
-    writeln!(w, "{} {args}", Paint::green("✓")).ok();
+    writeln!(w, "{} {args}", theme::paint(Role::Success, "✓")).ok();
 }
 
 pub fn tip_args(args: fmt::Arguments) {
@@ -184,17 +187,87 @@ pub fn subcommand(msg: impl fmt::Display) {
 pub fn warning(warning: impl fmt::Display) {
     println!(
         "{} {} {warning}",
-        WARNING_PREFIX,
-        Paint::yellow("Warning:").bold(),
+        theme::paint(Role::Warning, "!"),
+        theme::paint(Role::Warning, "Warning:").bold(),
     );
 }
 
 pub fn error(error: impl fmt::Display) {
-    println!("{ERROR_PREFIX} {} {error}", Paint::red("Error:"));
+    println!(
+        "{} {} {error}",
+        theme::paint(Role::Error, "✗"),
+        theme::paint(Role::Error, "Error:")
+    );
+}
+
+/// Print an error along with its chain of causes, and an optional hint.
+///
+/// See [`error_report`].
+pub fn error_chain(err: &(dyn std::error::Error + 'static), hint: Option<&str>) {
+    print!("{}", error_report(err, hint));
+}
+
+/// Render an error along with its chain of causes, and an optional hint.
+///
+/// Each cause is listed on its own line, indented one level deeper than
+/// the error it caused. Since many errors already include their source in
+/// their own message, causes that add nothing to the previous message are
+/// skipped.
+pub fn error_report(err: &(dyn std::error::Error + 'static), hint: Option<&str>) -> String {
+    let mut out = String::new();
+    let msg = err.to_string();
+
+    for line in msg.trim_end().lines() {
+        writeln!(
+            out,
+            "{} {} {line}",
+            theme::paint(Role::Error, "✗"),
+            theme::paint(Role::Error, "Error:")
+        )
+        .ok();
+    }
+
+    let mut prev = msg;
+    let mut depth = 1;
+    let mut source = err.source();
+
+    while let Some(cause) = source {
+        let msg = cause.to_string();
+
+        if !prev.contains(msg.trim()) {
+            let indent = "  ".repeat(depth);
+
+            for (i, line) in msg.trim_end().lines().enumerate() {
+                if i == 0 {
+                    writeln!(out, "{indent}{} {line}", theme::paint(Role::Error, "↳")).ok();
+                } else {
+                    writeln!(out, "{indent}  {line}").ok();
+                }
+            }
+            depth += 1;
+        }
+        prev = msg;
+        source = cause.source();
+    }
+
+    if let Some(hint) = hint {
+        writeln!(
+            out,
+            "{} {}",
+            theme::paint(Role::Hint, "✗ Hint:"),
+            format::hint(hint)
+        )
+        .ok();
+    }
+    out
 }
 
 pub fn hint(hint: impl fmt::Display) {
-    println!("{ERROR_HINT_PREFIX} {}", format::hint(hint));
+    println!(
+        "{} {}",
+        theme::paint(Role::Hint, "✗ Hint:"),
+        format::hint(hint)
+    );
 }
 
 pub fn ask<D: fmt::Display>(prompt: D, default: bool) -> bool {
@@ -263,12 +336,53 @@ pub fn passphrase_confirm<K: AsRef<OsStr>>(
 }
 
 pub fn passphrase_stdin() -> Result<Passphrase, anyhow::Error> {
-    let mut input = String::new();
+    let mut input = Zeroizing::new(String::new());
     std::io::stdin().read_line(&mut input)?;
 
     Ok(Passphrase::from(input.trim_end().to_owned()))
 }
 
+/// A non-interactive source for a passphrase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PassphraseSource {
+    /// Read the passphrase from the first line of a file.
+    File(PathBuf),
+    /// Read the passphrase from the first line of the output of a shell command.
+    Command(String),
+}
+
+impl PassphraseSource {
+    /// Read the passphrase. Any intermediate buffer is zeroized when dropped.
+    pub fn read(&self) -> Result<Passphrase, anyhow::Error> {
+        let buf = match self {
+            Self::File(path) => Zeroizing::new(fs::read(path).map_err(|e| {
+                anyhow::anyhow!("failed to read passphrase file {}: {e}", path.display())
+            })?),
+            Self::Command(cmd) => {
+                let output = process::Command::new("sh")
+                    .arg("-c")
+                    .arg(cmd)
+                    .stdin(Stdio::inherit())
+                    .stderr(Stdio::inherit())
+                    .output()
+                    .map_err(|e| anyhow::anyhow!("failed to run passphrase command: {e}"))?;
+                let stdout = Zeroizing::new(output.stdout);
+
+                if !output.status.success() {
+                    anyhow::bail!("passphrase command failed with {}", output.status);
+                }
+                stdout
+            }
+        };
+        let line = buf.split(|b| *b == b'\n').next().unwrap_or_default();
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let line = std::str::from_utf8(line)
+            .map_err(|_| anyhow::anyhow!("passphrase is not valid UTF-8"))?;
+
+        Ok(Passphrase::from(line.to_owned()))
+    }
+}
+
 pub fn select<'a, T>(prompt: &str, options: &'a [T], help: &str) -> Result<&'a T, InquireError>
 where
     T: fmt::Display + Eq + PartialEq,
@@ -281,8 +395,148 @@ where
     selection.with_starting_cursor(0).prompt()
 }
 
+/// Prompt the user to select any number of the given items, and return the selected ones,
+/// in their original order.
+///
+/// Returns [`InquireError::NotTTY`] when not connected to a terminal.
+pub fn multi_select<T>(prompt: &str, items: Vec<T>) -> Result<Vec<T>, InquireError>
+where
+    T: fmt::Display,
+{
+    if !io::stdin().is_terminal() {
+        return Err(InquireError::NotTTY);
+    }
+    multi_select_with(items, |options| {
+        let selection = MultiSelect::new(prompt, options)
+            .with_vim_mode(true)
+            .with_render_config(*CONFIG)
+            .raw_prompt()?;
+
+        Ok(selection.into_iter().map(|o| o.index).collect())
+    })
+}
+
+/// Select items using the given function, which returns the indices of the selected items.
+fn multi_select_with<T, F>(items: Vec<T>, select: F) -> Result<Vec<T>, InquireError>
+where
+    T: fmt::Display,
+    F: FnOnce(Vec<String>) -> Result<Vec<usize>, InquireError>,
+{
+    if items.is_empty() {
+        return Ok(Vec::new());
+    }
+    let options = items.iter().map(|i| i.to_string()).collect();
+    let selected = select(options)?;
+
+    Ok(items
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| selected.contains(i))
+        .map(|(_, item)| item)
+        .collect())
+}
+
 pub fn markdown(content: &str) {
     if !content.is_empty() && command::bat(["-p", "-l", "md"], content).is_err() {
         blob(content);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_multi_select() {
+        let items = vec!["bug", "good-first-issue", "wontfix"];
+        let selected = multi_select_with(items, |options| {
+            assert_eq!(options, vec!["bug", "good-first-issue", "wontfix"]);
+            // Select in a different order than displayed.
+            Ok(vec![2, 0])
+        })
+        .unwrap();
+
+        assert_eq!(selected, vec!["bug", "wontfix"]);
+    }
+
+    #[test]
+    fn test_multi_select_cancelled() {
+        let items = vec![1, 2, 3];
+        let result = multi_select_with(items, |_| Err(InquireError::OperationCanceled));
+
+        assert!(matches!(result, Err(InquireError::OperationCanceled)));
+    }
+
+    #[derive(Debug)]
+    struct Nested {
+        msg: &'static str,
+        source: Option<Box<Nested>>,
+    }
+
+    impl fmt::Display for Nested {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.msg)
+        }
+    }
+
+    impl std::error::Error for Nested {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            self.source.as_deref().map(|e| e as _)
+        }
+    }
+
+    #[test]
+    fn test_error_report() {
+        let err = Nested {
+            msg: "failed to fetch repository",
+            source: Some(Box::new(Nested {
+                msg: "connection refused",
+                source: Some(Box::new(Nested {
+                    // Already part of the previous message, so it's skipped.
+                    msg: "refused",
+                    source: Some(Box::new(Nested {
+                        msg: "os error 111",
+                        source: None,
+                    })),
+                })),
+            })),
+        };
+        let report = error_report(&err, Some("is the node running?"));
+        let lines = report.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 4, "{report}");
+        assert!(lines[0].contains("Error:"));
+        assert!(lines[0].ends_with(" failed to fetch repository"));
+        assert!(lines[1].starts_with("  ") && lines[1].contains('↳'));
+        assert!(lines[1].ends_with(" connection refused"));
+        assert!(lines[2].starts_with("    ") && lines[2].contains('↳'));
+        assert!(lines[2].ends_with(" os error 111"));
+        assert!(lines[3].contains("Hint:"));
+        assert!(lines[3].contains("is the node running?"));
+    }
+
+    #[test]
+    fn test_passphrase_source() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("passphrase");
+
+        fs::write(&path, "hunter2\r\nignored\n").unwrap();
+        assert_eq!(
+            PassphraseSource::File(path).read().unwrap().as_str(),
+            "hunter2"
+        );
+        assert_eq!(
+            PassphraseSource::Command(String::from("echo hunter2"))
+                .read()
+                .unwrap()
+                .as_str(),
+            "hunter2"
+        );
+        assert!(PassphraseSource::Command(String::from("exit 1"))
+            .read()
+            .is_err());
+        assert!(PassphraseSource::File(tmp.path().join("missing"))
+            .read()
+            .is_err());
+    }
+}
@@ -2,15 +2,18 @@ pub mod ansi;
 pub mod cell;
 pub mod colors;
 pub mod command;
+pub mod diff;
 pub mod editor;
 pub mod element;
 pub mod format;
 pub mod hstack;
 pub mod io;
 pub mod label;
+pub mod pager;
 pub mod spinner;
 pub mod table;
 pub mod textarea;
+pub mod theme;
 pub mod vstack;
 
 use std::fmt;
@@ -27,6 +30,7 @@ pub use label::{label, Label};
 pub use spinner::{spinner, spinner_to, Spinner};
 pub use table::{Table, TableOptions};
 pub use textarea::{textarea, TextArea};
+pub use theme::Theme;
 pub use vstack::{VStack, VStackOptions};
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
@@ -0,0 +1,336 @@
+//! Built-in pager, used when no external pager is configured.
+use std::io;
+use std::io::Write;
+
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
+use termion::screen::IntoAlternateScreen;
+
+use crate::{paint, Constraint, Element, Label, Line};
+
+/// Pager state: the buffered lines and the current viewport.
+#[derive(Debug)]
+pub struct Pager {
+    /// Rendered lines.
+    lines: Vec<Line>,
+    /// Unstyled contents of each line, used for searching.
+    text: Vec<String>,
+    /// Index of the line at the top of the viewport.
+    top: usize,
+    /// Number of content lines visible in the viewport.
+    height: usize,
+    /// Whether to prefix lines with their line number.
+    line_numbers: bool,
+    /// The last search pattern.
+    pattern: Option<String>,
+}
+
+impl Pager {
+    /// Create a new pager over the rendered element.
+    pub fn new(elem: &impl Element, constraint: Constraint, height: usize) -> Self {
+        let lines = elem.render(constraint);
+        let text = lines
+            .iter()
+            .map(|l| {
+                l.clone()
+                    .into_iter()
+                    .map(|label| label.content().to_owned())
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            lines,
+            text,
+            top: 0,
+            height,
+            line_numbers: false,
+            pattern: None,
+        }
+    }
+
+    /// Show or hide line numbers.
+    pub fn line_numbers(mut self, show: bool) -> Self {
+        self.line_numbers = show;
+        self
+    }
+
+    /// Toggle line numbers.
+    pub fn toggle_line_numbers(&mut self) {
+        self.line_numbers = !self.line_numbers;
+    }
+
+    /// Index of the line at the top of the viewport.
+    pub fn top(&self) -> usize {
+        self.top
+    }
+
+    /// Set the viewport height.
+    pub fn resize(&mut self, height: usize) {
+        self.height = height;
+    }
+
+    /// Scroll down by the given number of lines.
+    pub fn down(&mut self, n: usize) {
+        self.top = self.top.saturating_add(n).min(self.bottom()).max(self.top);
+    }
+
+    /// Scroll up by the given number of lines.
+    pub fn up(&mut self, n: usize) {
+        self.top = self.top.saturating_sub(n);
+    }
+
+    /// Scroll to the first line.
+    pub fn home(&mut self) {
+        self.top = 0;
+    }
+
+    /// Scroll to the last page.
+    pub fn end(&mut self) {
+        self.top = self.bottom();
+    }
+
+    /// Search forward for the given pattern, starting at the top of the viewport.
+    /// If found, the matching line becomes the top of the viewport.
+    /// Returns `false` if there was no match.
+    pub fn search(&mut self, pattern: &str) -> bool {
+        self.pattern = Some(pattern.to_owned());
+        self.find(self.top, true)
+    }
+
+    /// Move to the next match of the last search.
+    pub fn next_match(&mut self) -> bool {
+        self.find(self.top + 1, true)
+    }
+
+    /// Move to the previous match of the last search.
+    pub fn prev_match(&mut self) -> bool {
+        match self.top.checked_sub(1) {
+            Some(from) => self.find(from, false),
+            None => false,
+        }
+    }
+
+    /// Render the lines visible in the viewport, truncated to the given width.
+    pub fn render(&self, width: usize) -> Vec<Line> {
+        let digits = self.lines.len().to_string().len();
+
+        self.lines
+            .iter()
+            .enumerate()
+            .skip(self.top)
+            .take(self.height)
+            .map(|(i, line)| {
+                let mut line = if self.line_numbers {
+                    let number = format!("{:>digits$} ", i + 1);
+                    Line::new(Label::from(paint(number).dim())).extend(line.clone())
+                } else {
+                    line.clone()
+                };
+                line.truncate(width, "…");
+                line
+            })
+            .collect()
+    }
+
+    /// Last possible top line when scrolling.
+    fn bottom(&self) -> usize {
+        self.lines.len().saturating_sub(self.height)
+    }
+
+    /// Find the last pattern, starting from the given line.
+    fn find(&mut self, from: usize, forward: bool) -> bool {
+        let Some(pattern) = self.pattern.as_deref() else {
+            return false;
+        };
+        if pattern.is_empty() {
+            return false;
+        }
+        let found = if forward {
+            self.text
+                .iter()
+                .enumerate()
+                .skip(from)
+                .find(|(_, t)| t.contains(pattern))
+                .map(|(i, _)| i)
+        } else {
+            self.text
+                .iter()
+                .enumerate()
+                .take(from + 1)
+                .rev()
+                .find(|(_, t)| t.contains(pattern))
+                .map(|(i, _)| i)
+        };
+
+        if let Some(i) = found {
+            self.top = i;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Page the given element in the terminal, using the built-in pager.
+///
+/// Keys: `j`/`k` or arrows to scroll, space/`b` to page, `g`/`G` to go to the start or end,
+/// `/` to search, `n`/`N` to go to the next or previous match, `#` to toggle line numbers and
+/// `q` to quit.
+pub fn page(elem: impl Element, line_numbers: bool) -> io::Result<()> {
+    let (cols, rows) = termion::terminal_size()?;
+    let (cols, rows) = (cols as usize, rows as usize);
+    let constraint = Constraint::max(crate::Size::new(cols, usize::MAX));
+    // The last row is reserved for the status line.
+    let mut pager =
+        Pager::new(&elem, constraint, rows.saturating_sub(1)).line_numbers(line_numbers);
+    // Keys are read from the terminal device, in case standard input isn't a terminal.
+    let tty = termion::get_tty()?;
+    let mut screen = io::stdout().into_raw_mode()?.into_alternate_screen()?;
+    let mut prompt: Option<String> = None;
+    let mut status = String::new();
+
+    write!(screen, "{}", termion::cursor::Hide)?;
+
+    let mut keys = tty.keys();
+    loop {
+        write!(screen, "{}", termion::clear::All)?;
+        for (i, line) in pager.render(cols).into_iter().enumerate() {
+            write!(screen, "{}{}", termion::cursor::Goto(1, i as u16 + 1), line)?;
+        }
+        write!(screen, "{}", termion::cursor::Goto(1, rows as u16))?;
+        if let Some(input) = &prompt {
+            write!(screen, "/{input}")?;
+        } else if status.is_empty() {
+            write!(screen, ":")?;
+        } else {
+            write!(screen, "{}", paint(status.as_str()).invert())?;
+        }
+        screen.flush()?;
+
+        let Some(key) = keys.next() else {
+            break;
+        };
+        let key = key?;
+
+        if let Some(input) = &mut prompt {
+            match key {
+                Key::Char('\n') => {
+                    if !input.is_empty() && !pager.search(input) {
+                        status = String::from("Pattern not found");
+                    }
+                    prompt = None;
+                }
+                Key::Char(c) => input.push(c),
+                Key::Backspace => {
+                    if input.pop().is_none() {
+                        prompt = None;
+                    }
+                }
+                Key::Esc | Key::Ctrl('c') => prompt = None,
+                _ => {}
+            }
+            continue;
+        }
+        status.clear();
+
+        match key {
+            Key::Char('q') | Key::Esc | Key::Ctrl('c') => break,
+            Key::Char('j') | Key::Char('\n') | Key::Down => pager.down(1),
+            Key::Char('k') | Key::Up => pager.up(1),
+            Key::Char(' ') | Key::Char('f') | Key::PageDown => pager.down(pager.height),
+            Key::Char('b') | Key::PageUp => pager.up(pager.height),
+            Key::Char('g') | Key::Home => pager.home(),
+            Key::Char('G') | Key::End => pager.end(),
+            Key::Char('#') => pager.toggle_line_numbers(),
+            Key::Char('/') => prompt = Some(String::new()),
+            Key::Char('n') => {
+                if !pager.next_match() {
+                    status = String::from("Pattern not found");
+                }
+            }
+            Key::Char('N') => {
+                if !pager.prev_match() {
+                    status = String::from("Pattern not found");
+                }
+            }
+            _ => {}
+        }
+    }
+    write!(screen, "{}", termion::cursor::Show)?;
+    screen.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn pager() -> Pager {
+        let lines = (1..=100)
+            .map(|i| {
+                if i % 25 == 0 {
+                    Line::new(format!("line {i}: needle"))
+                } else {
+                    Line::new(format!("line {i}"))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Pager::new(&lines, Constraint::UNBOUNDED, 10)
+    }
+
+    #[test]
+    fn test_search() {
+        let mut pager = pager();
+
+        assert!(pager.search("needle"));
+        assert_eq!(pager.top(), 24);
+        assert_eq!(
+            pager.render(80).first().map(|l| l.to_string()),
+            Some(String::from("line 25: needle"))
+        );
+
+        assert!(pager.next_match());
+        assert_eq!(pager.top(), 49);
+        assert!(pager.next_match());
+        assert_eq!(pager.top(), 74);
+        assert!(pager.next_match());
+        assert_eq!(pager.top(), 99);
+        assert!(!pager.next_match());
+        assert_eq!(pager.top(), 99);
+
+        assert!(pager.prev_match());
+        assert_eq!(pager.top(), 74);
+
+        assert!(!pager.search("haystack"));
+        assert_eq!(pager.top(), 74);
+    }
+
+    #[test]
+    fn test_line_numbers() {
+        let mut pager = pager().line_numbers(true);
+
+        assert!(pager.search("needle"));
+        assert_eq!(
+            pager.render(80).first().map(|l| l.to_string()),
+            Some(format!("{}line 25: needle", paint(" 25 ").dim()))
+        );
+    }
+
+    #[test]
+    fn test_scroll() {
+        let mut pager = pager();
+
+        pager.down(5);
+        assert_eq!(pager.top(), 5);
+        pager.end();
+        assert_eq!(pager.top(), 90);
+        pager.down(5);
+        assert_eq!(pager.top(), 90);
+        pager.up(100);
+        assert_eq!(pager.top(), 0);
+    }
+}
@@ -1,9 +1,11 @@
+use std::collections::VecDeque;
 use std::io::{IsTerminal, Write};
 use std::mem::ManuallyDrop;
 use std::sync::{Arc, Mutex};
 use std::{fmt, io, thread, time};
 
 use crate::io::{ERROR_PREFIX, WARNING_PREFIX};
+use crate::theme::{self, Role};
 use crate::Paint;
 
 /// How much time to wait between spinner animation updates.
@@ -15,10 +17,13 @@ pub const DEFAULT_STYLE: [Paint<&'static str>; 4] = [
     Paint::magenta("◤"),
     Paint::blue("◥"),
 ];
+/// Number of recent progress updates the rate is computed from.
+pub const RATE_WINDOW: usize = 8;
 
 struct Progress {
     state: State,
     message: Paint<String>,
+    rate: Option<Rate>,
 }
 
 impl Progress {
@@ -26,10 +31,91 @@ impl Progress {
         Self {
             state: State::Running { cursor: 0 },
             message,
+            rate: None,
         }
     }
 }
 
+/// Throughput of a task with a known total, computed from a sliding window of the
+/// most recent progress updates.
+#[derive(Debug, Clone)]
+pub struct Rate {
+    done: usize,
+    total: usize,
+    window: VecDeque<(time::Instant, usize)>,
+}
+
+impl Rate {
+    /// Create a new rate for a task with the given total.
+    pub fn new(total: usize) -> Self {
+        Self {
+            done: 0,
+            total,
+            window: VecDeque::with_capacity(RATE_WINDOW),
+        }
+    }
+
+    /// Record that `done` out of `total` units were completed at the given time.
+    pub fn update(&mut self, done: usize, total: usize, at: time::Instant) {
+        // If progress went backwards, the task was restarted.
+        if done < self.done {
+            self.window.clear();
+        }
+        if self.window.len() == RATE_WINDOW {
+            self.window.pop_front();
+        }
+        self.window.push_back((at, done));
+        self.done = done;
+        self.total = total;
+    }
+
+    /// Units completed per second, if enough updates were recorded.
+    pub fn per_second(&self) -> Option<f64> {
+        let (start, from) = self.window.front()?;
+        let (end, to) = self.window.back()?;
+        let elapsed = end.duration_since(*start).as_secs_f64();
+
+        if elapsed > 0. {
+            Some(to.saturating_sub(*from) as f64 / elapsed)
+        } else {
+            None
+        }
+    }
+
+    /// Estimated time until the task completes, based on the current rate.
+    pub fn eta(&self) -> Option<time::Duration> {
+        let rate = self.per_second().filter(|r| *r > 0.)?;
+        let remaining = self.total.saturating_sub(self.done) as f64;
+
+        Some(time::Duration::from_secs_f64(remaining / rate))
+    }
+}
+
+impl fmt::Display for Rate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.done, self.total)?;
+
+        if let (Some(rate), Some(eta)) = (self.per_second(), self.eta()) {
+            write!(f, " ({rate:.1}/s, {} left)", format_eta(eta))?;
+        }
+        Ok(())
+    }
+}
+
+/// Format an estimated time remaining, eg. `1m 05s`.
+fn format_eta(eta: time::Duration) -> String {
+    let secs = eta.as_secs_f64().ceil() as u64;
+    let (hours, mins, secs) = (secs / 3600, secs % 3600 / 60, secs % 60);
+
+    if hours > 0 {
+        format!("{hours}h {mins:02}m")
+    } else if mins > 0 {
+        format!("{mins}m {secs:02}s")
+    } else {
+        format!("{secs}s")
+    }
+}
+
 enum State {
     Running { cursor: usize },
     Canceled,
@@ -100,6 +186,22 @@ impl Spinner {
             progress.message = Paint::new(msg);
         }
     }
+
+    /// Report progress on the task. When the `total` is known, the spinner displays
+    /// the rate and estimated time remaining. Otherwise, it's a plain spinner.
+    pub fn progress(&mut self, done: usize, total: Option<usize>) {
+        let now = time::Instant::now();
+
+        if let Ok(mut progress) = self.progress.lock() {
+            match total {
+                Some(total) => progress
+                    .rate
+                    .get_or_insert_with(|| Rate::new(total))
+                    .update(done, total, now),
+                None => progress.rate = None,
+            }
+        }
+    }
 }
 
 /// Create a new spinner with the given message. Sends animation output to `stderr` and success or
@@ -138,6 +240,7 @@ pub fn spinner_to(
                         Progress {
                             state: State::Running { cursor },
                             message,
+                            rate,
                         } => {
                             let spinner = DEFAULT_STYLE[*cursor];
 
@@ -148,20 +251,27 @@ pub fn spinner_to(
                             )
                             .ok();
 
+                            if let Some(rate) = rate {
+                                write!(animation, " {}", Paint::new(rate).dim()).ok();
+                            }
+
                             *cursor += 1;
                             *cursor %= DEFAULT_STYLE.len();
                         }
                         Progress {
                             state: State::Done,
                             message,
+                            ..
                         } => {
                             write!(animation, "\r{}", termion::clear::UntilNewline).ok();
-                            writeln!(completion, "{} {message}", Paint::green("✓")).ok();
+                            writeln!(completion, "{} {message}", theme::paint(Role::Success, "✓"))
+                                .ok();
                             break;
                         }
                         Progress {
                             state: State::Canceled,
                             message,
+                            ..
                         } => {
                             write!(animation, "\r{}", termion::clear::UntilNewline).ok();
                             writeln!(
@@ -175,6 +285,7 @@ pub fn spinner_to(
                         Progress {
                             state: State::Warn,
                             message,
+                            ..
                         } => {
                             write!(animation, "\r{}", termion::clear::UntilNewline).ok();
                             writeln!(completion, "{WARNING_PREFIX} {message}").ok();
@@ -183,6 +294,7 @@ pub fn spinner_to(
                         Progress {
                             state: State::Error,
                             message,
+                            ..
                         } => {
                             write!(animation, "\r{}", termion::clear::UntilNewline).ok();
                             writeln!(completion, "{ERROR_PREFIX} {message}").ok();
@@ -202,3 +314,41 @@ pub fn spinner_to(
         handle: ManuallyDrop::new(handle),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rate_eta() {
+        let start = time::Instant::now();
+        let mut rate = Rate::new(200);
+
+        assert_eq!(rate.eta(), None);
+        assert_eq!(rate.to_string(), "0/200");
+
+        // Ten items every 100ms, ie. a hundred items per second.
+        for i in 0..=10 {
+            rate.update(
+                i * 10,
+                200,
+                start + time::Duration::from_millis(i as u64 * 100),
+            );
+        }
+        assert_eq!(rate.per_second().map(f64::round), Some(100.));
+        assert_eq!(rate.eta().map(|d| d.as_secs_f64().round()), Some(1.));
+        assert_eq!(rate.to_string(), "100/200 (100.0/s, 1s left)");
+
+        // The rate is computed over the most recent updates only.
+        for i in 11..=20 {
+            rate.update(
+                100 + (i - 10) * 2,
+                200,
+                start + time::Duration::from_millis(i as u64 * 100),
+            );
+        }
+        assert_eq!(rate.to_string(), "120/200 (20.0/s, 4s left)");
+        assert_eq!(format_eta(time::Duration::from_secs(65)), "1m 05s");
+        assert_eq!(format_eta(time::Duration::from_secs(3720)), "1h 02m");
+    }
+}
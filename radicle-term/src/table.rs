@@ -220,6 +220,84 @@ impl<const W: usize, T: Cell> Table<W, T> {
     }
 }
 
+impl<const W: usize, T: Cell> Table<W, T>
+where
+    T::Padded: Into<Line>,
+{
+    /// Export the table as CSV, without styling. Dividers are skipped.
+    ///
+    /// Cells containing separators, quotes or line breaks are quoted.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+
+        for cells in self.cells() {
+            let row = cells
+                .iter()
+                .map(|cell| {
+                    let text = plain(cell);
+                    if text.contains([',', '"', '\n', '\r']) {
+                        format!("\"{}\"", text.replace('"', "\"\""))
+                    } else {
+                        text
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Export the table as a Markdown table, without styling. Dividers are skipped.
+    ///
+    /// The first header row is used as the table header. If there is none, the header is
+    /// left blank, since Markdown tables require one.
+    pub fn to_markdown(&self) -> String {
+        let header = self.rows.iter().find_map(|row| match row {
+            Row::Header(cells) => Some(cells),
+            _ => None,
+        });
+        let row = |cells: &[String]| format!("| {} |\n", cells.join(" | "));
+        let escape = |cell: &T| plain(cell).replace('|', "\\|");
+        let mut md = String::new();
+
+        match header {
+            Some(cells) => md.push_str(&row(&cells.iter().map(escape).collect::<Vec<_>>())),
+            None => md.push_str(&row(&vec![String::new(); W])),
+        }
+        md.push_str(&row(&vec![String::from("---"); W]));
+
+        for cells in self.cells() {
+            if header.is_some_and(|h| std::ptr::eq(h, cells)) {
+                continue;
+            }
+            md.push_str(&row(&cells.iter().map(escape).collect::<Vec<_>>()));
+        }
+        md
+    }
+
+    /// Iterate over the header and data rows.
+    fn cells(&self) -> impl Iterator<Item = &[T; W]> {
+        self.rows.iter().filter_map(|row| match row {
+            Row::Header(cells) | Row::Data(cells) => Some(cells),
+            Row::Divider => None,
+        })
+    }
+}
+
+/// Get the unstyled text of a cell.
+fn plain<T: Cell>(cell: &T) -> String
+where
+    T::Padded: Into<Line>,
+{
+    let line: Line = cell.pad(0).into();
+
+    line.into_iter()
+        .map(|label| label.content().to_owned())
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use crate::Element;
@@ -441,4 +519,52 @@ mod test {
             ].join("")
         );
     }
+
+    #[test]
+    fn test_table_to_csv() {
+        let mut t = Table::new(TableOptions::default());
+
+        t.header(["Country", "Population"]);
+        t.divider();
+        t.push(["France", "60M"]);
+        t.push(["Bosnia, Herzegovina", "3M"]);
+        t.push([r#"Côte d'Ivoire "CI""#, "28M"]);
+
+        assert_eq!(
+            t.to_csv(),
+            [
+                "Country,Population\n",
+                "France,60M\n",
+                "\"Bosnia, Herzegovina\",3M\n",
+                "\"Côte d'Ivoire \"\"CI\"\"\",28M\n",
+            ]
+            .join("")
+        );
+    }
+
+    #[test]
+    fn test_table_to_markdown() {
+        let mut t = Table::new(TableOptions::bordered());
+
+        t.header(["Command", "Description"]);
+        t.divider();
+        t.push([Paint::new("a | b").bold(), Paint::new("Pipe a to b")]);
+        t.push([Paint::new("ls"), Paint::new("List files")]);
+
+        assert_eq!(
+            t.to_markdown(),
+            [
+                "| Command | Description |\n",
+                "| --- | --- |\n",
+                "| a \\| b | Pipe a to b |\n",
+                "| ls | List files |\n",
+            ]
+            .join("")
+        );
+
+        let mut t = Table::new(TableOptions::default());
+        t.push(["a", "b"]);
+
+        assert_eq!(t.to_markdown(), "|  |  |\n| --- | --- |\n| a | b |\n");
+    }
 }
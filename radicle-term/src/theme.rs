@@ -0,0 +1,222 @@
+//! Color themes.
+//!
+//! A [`Theme`] maps semantic roles, eg. "success" or "error", to styles. The
+//! format helpers in [`crate::format`] and the output functions in [`crate::io`]
+//! look up the current theme, which can be changed with [`set`].
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+
+use crate::{Color, Paint, Style};
+
+/// The theme in use by the current process.
+static THEME: Lazy<RwLock<Theme>> = Lazy::new(|| RwLock::new(Theme::default()));
+
+/// Semantic role of a piece of output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Something went well.
+    Success,
+    /// Something went wrong.
+    Error,
+    /// Something might go wrong.
+    Warning,
+    /// Advice for the user.
+    Hint,
+    /// Something the user should notice.
+    Highlight,
+}
+
+impl FromStr for Role {
+    type Err = ThemeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "success" => Ok(Self::Success),
+            "error" => Ok(Self::Error),
+            "warning" => Ok(Self::Warning),
+            "hint" => Ok(Self::Hint),
+            "highlight" => Ok(Self::Highlight),
+            _ => Err(ThemeError::Role(s.to_owned())),
+        }
+    }
+}
+
+/// Error parsing a theme.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeError {
+    /// Unknown role name.
+    Role(String),
+    /// Invalid color.
+    Color(String),
+}
+
+impl std::fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Role(r) => write!(f, "unknown theme role `{r}`"),
+            Self::Color(c) => write!(f, "invalid theme color `{c}`"),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+/// Maps semantic roles to styles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub success: Style,
+    pub error: Style,
+    pub warning: Style,
+    pub hint: Style,
+    pub highlight: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            success: Style::new(Color::Green),
+            error: Style::new(Color::Red),
+            warning: Style::new(Color::Yellow),
+            hint: Style::new(Color::Yellow),
+            highlight: Style::new(Color::Green).bold(),
+        }
+    }
+}
+
+impl Theme {
+    /// A theme that doesn't emit any styling.
+    pub fn no_color() -> Self {
+        Self {
+            success: Style::default(),
+            error: Style::default(),
+            warning: Style::default(),
+            hint: Style::default(),
+            highlight: Style::default(),
+        }
+    }
+
+    /// Get the style of a role.
+    pub fn style(&self, role: Role) -> Style {
+        match role {
+            Role::Success => self.success,
+            Role::Error => self.error,
+            Role::Warning => self.warning,
+            Role::Hint => self.hint,
+            Role::Highlight => self.highlight,
+        }
+    }
+
+    /// Set the foreground color of a role, keeping its other properties.
+    pub fn with(mut self, role: Role, color: Color) -> Self {
+        let style = match role {
+            Role::Success => &mut self.success,
+            Role::Error => &mut self.error,
+            Role::Warning => &mut self.warning,
+            Role::Hint => &mut self.hint,
+            Role::Highlight => &mut self.highlight,
+        };
+        *style = style.fg(color);
+        self
+    }
+
+    /// Paint an item with the style of the given role.
+    pub fn paint<T>(&self, role: Role, item: T) -> Paint<T> {
+        Paint::new(item).with_style(self.style(role))
+    }
+}
+
+/// Get the current theme.
+pub fn current() -> Theme {
+    THEME.read().map(|t| *t).unwrap_or_default()
+}
+
+/// Set the current theme.
+pub fn set(theme: Theme) {
+    if let Ok(mut t) = THEME.write() {
+        *t = theme;
+    }
+}
+
+/// Paint an item with the style of the given role, using the current theme.
+pub fn paint<T>(role: Role, item: T) -> Paint<T> {
+    current().paint(role, item)
+}
+
+/// Parse a color, either by name, eg. `red`, as a 256-color palette index,
+/// eg. `236`, or as a hex RGB value, eg. `#ff7b72`.
+pub fn color(s: &str) -> Result<Color, ThemeError> {
+    let err = || ThemeError::Color(s.to_owned());
+    let color = match s {
+        "none" => Color::Unset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "white" => Color::White,
+        _ => {
+            if let Some(hex) = s.strip_prefix('#') {
+                if hex.len() != 6 || !hex.is_ascii() {
+                    return Err(err());
+                }
+                let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| err());
+
+                Color::RGB(channel(0)?, channel(2)?, channel(4)?)
+            } else {
+                Color::Fixed(s.parse().map_err(|_| err())?)
+            }
+        }
+    };
+    Ok(color)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::format;
+
+    #[test]
+    fn test_color() {
+        assert_eq!(color("red"), Ok(Color::Red));
+        assert_eq!(color("236"), Ok(Color::Fixed(236)));
+        assert_eq!(color("#ff7b72"), Ok(Color::RGB(0xff, 0x7b, 0x72)));
+        assert!(color("#ff7b").is_err());
+        assert!(color("256").is_err());
+        assert!(color("reddish").is_err());
+    }
+
+    /// Render the ANSI codes emitted by a paint, regardless of whether painting is enabled.
+    fn ansi(paint: Paint<&str>) -> String {
+        let mut s = String::new();
+        paint.style().fmt_prefix(&mut s).unwrap();
+        s
+    }
+
+    #[test]
+    fn test_theme() {
+        // N.b. the theme is global, so we test all cases in one test.
+        assert_eq!(ansi(format::positive("ok")), "\x1B[32m");
+        assert_eq!(ansi(format::negative("no")), "\x1B[31m");
+
+        set(Theme::default()
+            .with(Role::Success, Color::Blue)
+            .with(Role::Highlight, Color::Fixed(208)));
+
+        assert_eq!(ansi(format::positive("ok")), "\x1B[34m");
+        assert_eq!(ansi(format::negative("no")), "\x1B[31m");
+        assert_eq!(ansi(format::highlight("hi")), "\x1B[1;38;5;208m");
+
+        set(Theme::no_color());
+
+        assert_eq!(ansi(format::positive("ok")), "");
+        assert_eq!(ansi(format::negative("no")), "");
+        assert_eq!(ansi(format::hint("hint")), "");
+        assert_eq!(ansi(format::highlight("hi")), "");
+
+        set(Theme::default());
+    }
+}
@@ -1,3 +1,8 @@
+use std::path::PathBuf;
+
+/// Default path of the patch template, relative to the repository root.
+pub const DEFAULT_PATCH_TEMPLATE: &str = ".radicle/PATCH_TEMPLATE.md";
+
 /// CLI configuration.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -5,10 +10,58 @@ pub struct Config {
     /// Whether to show hints or not in the CLI.
     #[serde(default)]
     pub hints: bool,
+    /// Path of the template used to pre-fill new patch messages, relative to the
+    /// repository root. Defaults to [`DEFAULT_PATCH_TEMPLATE`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patch_template: Option<PathBuf>,
+    /// Color theme.
+    #[serde(default, skip_serializing_if = "crate::serde_ext::is_default")]
+    pub theme: Theme,
+}
+
+impl Config {
+    /// Path of the patch template, relative to the repository root.
+    pub fn patch_template(&self) -> PathBuf {
+        self.patch_template
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_PATCH_TEMPLATE))
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
-        Self { hints: true }
+        Self {
+            hints: true,
+            patch_template: None,
+            theme: Theme::default(),
+        }
     }
 }
+
+/// CLI color theme.
+///
+/// Colors are given by name, eg. `"red"`, as a 256-color palette index, eg. `"236"`,
+/// or as a hex RGB value, eg. `"#ff7b72"`. Roles that aren't set use the default
+/// theme's colors.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Theme {
+    /// Disable all colors.
+    #[serde(default, skip_serializing_if = "crate::serde_ext::is_default")]
+    pub no_color: bool,
+    /// Color of successful outcomes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<String>,
+    /// Color of errors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Color of warnings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    /// Color of hints.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    /// Color of highlighted text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+}
@@ -1,10 +1,13 @@
 #![warn(clippy::unwrap_used)]
 pub mod cache;
 pub mod common;
+pub mod embed;
 pub mod identity;
 pub mod issue;
+pub mod link;
 pub mod op;
 pub mod patch;
+pub mod query;
 pub mod store;
 pub mod thread;
 
@@ -12,6 +15,8 @@ pub mod thread;
 pub mod test;
 
 pub use common::*;
+pub use embed::gc_embeds;
+pub use link::Link;
 pub use op::{ActorId, Op};
 pub use radicle_cob::{
     change, history::EntryId, object, object::collaboration::error, type_name::TypeNameParse,
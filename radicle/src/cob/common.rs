@@ -370,6 +370,9 @@ impl TryFrom<&Uri> for DataUri {
 }
 
 /// Resolve an embed with a URI to one with actual data.
+///
+/// Returns `None` if the embed blob isn't available, eg. because it was removed by
+/// [`crate::cob::gc_embeds`] after the comments referencing it were redacted.
 pub fn resolve_embed(repo: &impl ReadRepository, embed: Embed<Uri>) -> Option<Embed<Vec<u8>>> {
     DataUri::try_from(&embed.content)
         .ok()
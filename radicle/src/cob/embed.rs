@@ -0,0 +1,273 @@
+//! Embed reference counting and garbage collection.
+//!
+//! Embeds are content-addressed blobs, written to the tree of the change that introduces
+//! them. Identical content is therefore only stored once, however many comments embed it,
+//! across all the COBs of a repository. [`References`] counts how many live comments and
+//! descriptions refer to each embed, so that the blobs which are only referred to by
+//! redacted comments can be found, and removed with [`gc_embeds`].
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::{fs, io};
+
+use thiserror::Error;
+
+use crate::cob::git::change::EMBEDS_PATH;
+use crate::cob::{issue, patch, store, thread, Embed, Uri};
+use crate::git::Oid;
+use crate::storage::git::Repository;
+use crate::storage::RepositoryError;
+
+/// Glob matching the COB references of all namespaces.
+const COBS_GLOB: &str = "refs/namespaces/*/refs/cobs/*";
+
+/// Error counting or collecting embeds.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Git(#[from] git2::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+    #[error(transparent)]
+    Store(#[from] store::Error),
+}
+
+/// Number of live references to each embed of a repository.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct References(BTreeMap<Oid, usize>);
+
+impl References {
+    /// Count the references to embeds from the issues and patches of a repository.
+    ///
+    /// Every edit of a comment or description that isn't redacted is counted, so that
+    /// the edit history of live comments stays complete.
+    pub fn load(repo: &Repository) -> Result<Self, Error> {
+        let mut refs = Self::default();
+
+        for result in issue::Issues::open(repo)?.all()? {
+            let (_, issue) = result?;
+
+            for (_, comment) in issue.comments() {
+                refs.comment(comment);
+            }
+        }
+        for result in patch::Patches::open(repo)?.all()? {
+            let (_, patch) = result?;
+
+            for (_, revision) in patch.revisions() {
+                for edit in revision.edits() {
+                    refs.insert(&edit.embeds);
+                }
+                for (_, comment) in revision.discussion().comments() {
+                    refs.comment(comment);
+                }
+                for (_, review) in revision.reviews() {
+                    for (_, comment) in review.comments() {
+                        refs.comment(comment);
+                    }
+                }
+            }
+        }
+        Ok(refs)
+    }
+
+    /// Number of live references to an embed.
+    pub fn count(&self, oid: &Oid) -> usize {
+        self.0.get(oid).copied().unwrap_or_default()
+    }
+
+    /// Whether an embed is referenced by any live comment or description.
+    pub fn is_referenced(&self, oid: &Oid) -> bool {
+        self.count(oid) > 0
+    }
+
+    /// Iterate over the referenced embeds and their reference count.
+    pub fn iter(&self) -> impl Iterator<Item = (&Oid, &usize)> {
+        self.0.iter()
+    }
+
+    fn comment<L>(&mut self, comment: &thread::Comment<L>) {
+        for edit in comment.edits() {
+            self.insert(&edit.embeds);
+        }
+    }
+
+    fn insert(&mut self, embeds: &[Embed<Uri>]) {
+        for embed in embeds {
+            // Embeds with a `data:` URI aren't stored as blobs.
+            if let Ok(oid) = Oid::try_from(&embed.content) {
+                *self.0.entry(oid).or_default() += 1;
+            }
+        }
+    }
+}
+
+/// Remove the embed blobs of a repository that aren't referenced anymore, eg. because all
+/// the comments embedding them were redacted. Returns the removed blobs.
+///
+/// Blobs that are also part of the repository's own history, or of a COB change other
+/// than as an embed, are kept. Only loose objects can be removed; packed embeds are kept
+/// until the repository is repacked.
+///
+/// Note that the removed blobs are still part of the trees of the COB changes that
+/// introduced them, so they can no longer be served to peers fetching the full COB history.
+pub fn gc_embeds(repo: &Repository) -> Result<Vec<Oid>, Error> {
+    let references = References::load(repo)?;
+    let (embeds, mut protected) = stored(repo)?;
+
+    protected.extend(reachable(repo)?);
+
+    let mut removed = Vec::new();
+    for oid in embeds {
+        if references.is_referenced(&oid) || protected.contains(&oid) {
+            continue;
+        }
+        let hex = oid.to_string();
+        let path = repo.backend.path().join("objects").join(&hex[..2]).join(&hex[2..]);
+
+        match fs::remove_file(path) {
+            Ok(()) => removed.push(oid),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(removed)
+}
+
+/// Get the embed blobs stored in the COB changes of a repository, along with the other
+/// blobs of those changes.
+fn stored(repo: &Repository) -> Result<(BTreeSet<Oid>, HashSet<Oid>), Error> {
+    let mut walk = repo.backend.revwalk()?;
+    let mut embeds = BTreeSet::new();
+    let mut other = HashSet::new();
+
+    walk.push_glob(COBS_GLOB)?;
+    // Don't walk the repository history, which COB changes may point to.
+    for r in repo.backend.references()? {
+        let r = r?;
+        if let (Some(name), Some(oid)) = (r.name(), r.target()) {
+            if !is_cob(name) && repo.backend.find_commit(oid).is_ok() {
+                walk.hide(oid)?;
+            }
+        }
+    }
+
+    for oid in walk {
+        let tree = repo.backend.find_commit(oid?)?.tree()?;
+
+        for entry in tree.iter() {
+            if entry.kind() == Some(git2::ObjectType::Blob) {
+                other.insert(entry.id().into());
+            }
+        }
+        let Ok(entry) = tree.get_path(EMBEDS_PATH.as_path()) else {
+            continue;
+        };
+        let Ok(tree) = repo.backend.find_tree(entry.id()) else {
+            continue;
+        };
+        embeds.extend(tree.iter().map(|e| Oid::from(e.id())));
+    }
+    Ok((embeds, other))
+}
+
+/// Get the blobs reachable from the references of a repository that aren't COBs.
+fn reachable(repo: &Repository) -> Result<HashSet<Oid>, Error> {
+    let mut walk = repo.backend.revwalk()?;
+    let mut trees = HashSet::new();
+    let mut blobs = HashSet::new();
+
+    for r in repo.backend.references()? {
+        let r = r?;
+        if let (Some(name), Some(oid)) = (r.name(), r.target()) {
+            if !is_cob(name) && repo.backend.find_commit(oid).is_ok() {
+                walk.push(oid)?;
+            }
+        }
+    }
+    for oid in walk {
+        let tree = repo.backend.find_commit(oid?)?.tree()?;
+
+        if !trees.insert(tree.id()) {
+            continue;
+        }
+        tree.walk(git2::TreeWalkMode::PreOrder, |_, entry| {
+            match entry.kind() {
+                Some(git2::ObjectType::Blob) => {
+                    blobs.insert(entry.id().into());
+                }
+                Some(git2::ObjectType::Tree) if !trees.insert(entry.id()) => {
+                    // Already visited.
+                    return git2::TreeWalkResult::Skip;
+                }
+                _ => {}
+            }
+            git2::TreeWalkResult::Ok
+        })?;
+    }
+    Ok(blobs)
+}
+
+/// Whether a reference name is that of a COB.
+fn is_cob(name: &str) -> bool {
+    name.contains("/refs/cobs/")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::cob::issue::cache::Issues as _;
+    use crate::cob::issue::Cache;
+    use crate::storage::ReadRepository as _;
+    use crate::test;
+
+    #[test]
+    fn test_gc_embeds() {
+        let test::setup::NodeWithRepo { node, repo, .. } = test::setup::NodeWithRepo::default();
+        let mut issues = Cache::no_cache(&*repo).unwrap();
+        let kept = Embed {
+            name: String::from("kept.txt"),
+            content: b"Still referenced".to_vec(),
+        };
+        let orphan = Embed {
+            name: String::from("orphan.txt"),
+            content: b"Soon to be orphaned".to_vec(),
+        };
+        let mut issue = issues
+            .create(
+                "Embeds",
+                "Some embeds.",
+                &[],
+                &[],
+                [kept.clone()],
+                &node.signer,
+            )
+            .unwrap();
+        let id = *issue.id();
+        let comment = issue
+            .comment("An attachment", id, [orphan.clone()], &node.signer)
+            .unwrap();
+
+        let references = References::load(&repo).unwrap();
+        assert_eq!(references.count(&kept.oid()), 1);
+        assert_eq!(references.count(&orphan.oid()), 1);
+
+        // Nothing to collect while both embeds are referenced.
+        assert!(gc_embeds(&repo).unwrap().is_empty());
+
+        issue.redact_comment(comment, &node.signer).unwrap();
+
+        let references = References::load(&repo).unwrap();
+        assert!(references.is_referenced(&kept.oid()));
+        assert!(!references.is_referenced(&orphan.oid()));
+
+        assert_eq!(gc_embeds(&repo).unwrap(), vec![orphan.oid()]);
+        assert!(repo.blob(orphan.oid()).is_err());
+        assert_eq!(repo.blob(kept.oid()).unwrap().content(), kept.content);
+
+        // The issue can still be loaded.
+        let issue = issues.get(&id).unwrap().unwrap();
+        assert_eq!(issue.comments().count(), 1);
+    }
+}
@@ -895,8 +895,7 @@ where
         let mut tx = Transaction::default();
         operations(&mut tx)?;
 
-        let (doc, commit) = tx.commit(message, self.id, &mut self.store, signer)?;
-        self.identity = doc;
+        let commit = tx.apply(message, self.id, &mut self.store, signer, &mut self.identity)?;
 
         Ok(commit)
     }
@@ -10,10 +10,12 @@ use thiserror::Error;
 
 use crate::cob;
 use crate::cob::common::{Author, Authorization, Label, Reaction, Timestamp, Uri};
+use crate::cob::link;
+use crate::cob::link::Link;
 use crate::cob::store::Transaction;
 use crate::cob::store::{Cob, CobAction};
 use crate::cob::thread;
-use crate::cob::thread::{Comment, CommentId, Thread};
+use crate::cob::thread::{Comment, CommentId, Edit, Thread};
 use crate::cob::{op, store, ActorId, Embed, EntryId, ObjectId, TypeName};
 use crate::crypto::Signer;
 use crate::identity::doc::{Doc, DocError};
@@ -51,6 +53,12 @@ pub enum Error {
     /// Title is invalid.
     #[error("invalid title: {0:?}")]
     InvalidTitle(String),
+    /// Relation target is not another issue of the repository.
+    #[error("invalid relation target: {0}")]
+    InvalidRelation(IssueId),
+    /// Link target is invalid.
+    #[error("invalid link: {0}")]
+    Link(#[from] link::Error),
     /// The identity doc is missing.
     #[error("identity document missing")]
     MissingIdentity,
@@ -80,6 +88,10 @@ pub enum Error {
 pub enum CloseReason {
     Other,
     Solved,
+    /// The issue won't be fixed.
+    WontFix,
+    /// The issue duplicates another issue.
+    Duplicate,
 }
 
 impl std::fmt::Display for CloseReason {
@@ -87,6 +99,8 @@ impl std::fmt::Display for CloseReason {
         let reason = match self {
             Self::Other => "unspecified",
             Self::Solved => "solved",
+            Self::WontFix => "wontfix",
+            Self::Duplicate => "duplicate",
         };
         write!(f, "{reason}")
     }
@@ -121,6 +135,54 @@ impl State {
     }
 }
 
+/// Kind of relation between two issues.
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RelationKind {
+    /// This issue blocks the target issue.
+    Blocks,
+    /// This issue is blocked by the target issue.
+    BlockedBy,
+    /// This issue is a duplicate of the target issue.
+    Duplicates,
+    /// This issue is related to the target issue.
+    Related,
+}
+
+impl RelationKind {
+    /// The relation kind, as seen from the target issue.
+    pub fn inverse(self) -> Self {
+        match self {
+            Self::Blocks => Self::BlockedBy,
+            Self::BlockedBy => Self::Blocks,
+            // An issue can't duplicate its own duplicate, so the other end is only related.
+            Self::Duplicates => Self::Related,
+            Self::Related => Self::Related,
+        }
+    }
+}
+
+impl std::fmt::Display for RelationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Blocks => write!(f, "blocks"),
+            Self::BlockedBy => write!(f, "blocked by"),
+            Self::Duplicates => write!(f, "duplicates"),
+            Self::Related => write!(f, "related"),
+        }
+    }
+}
+
+/// A typed link from an issue to another issue.
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Relation {
+    /// The related issue.
+    pub target: IssueId,
+    /// How the issue relates to the target.
+    pub kind: RelationKind,
+}
+
 /// Issue state. Accumulates [`Action`].
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -132,9 +194,21 @@ pub struct Issue {
     /// Current state of the issue.
     pub(super) state: State,
     /// Associated labels.
+    ///
+    /// Labels are kept in their canonical order, ie. sorted by name, so that all replicas
+    /// list them in the same order.
     pub(super) labels: BTreeSet<Label>,
     /// Discussion around this issue.
     pub(super) thread: Thread,
+    /// Relations to other issues.
+    ///
+    /// Since actions are applied in causal order, with concurrent actions ordered
+    /// deterministically, this set converges on all replicas.
+    #[serde(default)]
+    pub(super) relations: BTreeSet<Relation>,
+    /// Links to other objects of the repository, eg. patches.
+    #[serde(default)]
+    pub(super) links: BTreeSet<Link>,
 }
 
 impl store::Cob for Issue {
@@ -242,6 +316,8 @@ impl Issue {
             state: State::default(),
             labels: BTreeSet::default(),
             thread,
+            relations: BTreeSet::default(),
+            links: BTreeSet::default(),
         }
     }
 
@@ -257,10 +333,29 @@ impl Issue {
         &self.state
     }
 
+    /// Associated labels, in canonical order.
     pub fn labels(&self) -> impl Iterator<Item = &Label> {
         self.labels.iter()
     }
 
+    /// Relations of this issue to other issues.
+    pub fn relations(&self) -> impl Iterator<Item = &Relation> {
+        self.relations.iter()
+    }
+
+    /// Issues related to this one with the given relation kind.
+    pub fn related(&self, kind: RelationKind) -> impl Iterator<Item = &IssueId> + '_ {
+        self.relations
+            .iter()
+            .filter(move |r| r.kind == kind)
+            .map(|r| &r.target)
+    }
+
+    /// Links of this issue to other objects.
+    pub fn links(&self) -> impl Iterator<Item = &Link> {
+        self.links.iter()
+    }
+
     pub fn timestamp(&self) -> Timestamp {
         self.thread
             .comments()
@@ -301,6 +396,14 @@ impl Issue {
         self.thread.comments()
     }
 
+    /// Get the edit history of a comment, starting with the original version.
+    ///
+    /// Returns `None` if the comment doesn't exist or was redacted, since the edits of a
+    /// redacted comment are discarded along with it.
+    pub fn comment_edits(&self, id: &CommentId) -> Option<&[Edit]> {
+        self.thread.comment(id).map(Comment::edits)
+    }
+
     /// Get replies to a specific comment.
     pub fn replies_to<'a>(
         &'a self,
@@ -369,6 +472,10 @@ impl Issue {
             }
             // All roles can react to a comment on an issue.
             Action::CommentReact { .. } => Authorization::Allow,
+            // All roles can relate issues to one another.
+            Action::Relate { .. } | Action::Unrelate { .. } => Authorization::Allow,
+            // All roles can link issues to other objects.
+            Action::Link { .. } | Action::Unlink { .. } => Authorization::Allow,
         };
         Ok(outcome)
     }
@@ -435,6 +542,26 @@ impl Issue {
             } => {
                 thread::react(&mut self.thread, entry, author, id, reaction, active)?;
             }
+            Action::Relate { target, kind } => {
+                let (root, _) = self.root();
+                if target == IssueId::from(*root) {
+                    return Err(Error::NotAllowed(entry));
+                }
+                self.relations.insert(Relation { target, kind });
+            }
+            Action::Unrelate { target, kind } => {
+                self.relations.remove(&Relation { target, kind });
+            }
+            Action::Link { link } => {
+                let (root, _) = self.root();
+                if link.target_type == *TYPENAME && link.target_id == IssueId::from(*root) {
+                    return Err(Error::NotAllowed(entry));
+                }
+                self.links.insert(link);
+            }
+            Action::Unlink { link } => {
+                self.links.remove(&link);
+            }
         }
         Ok(())
     }
@@ -534,6 +661,26 @@ impl<R: ReadRepository> store::Transaction<Issue, R> {
         })
     }
 
+    /// Relate the issue to another issue.
+    pub fn relate(&mut self, target: IssueId, kind: RelationKind) -> Result<(), store::Error> {
+        self.push(Action::Relate { target, kind })
+    }
+
+    /// Remove a relation to another issue.
+    pub fn unrelate(&mut self, target: IssueId, kind: RelationKind) -> Result<(), store::Error> {
+        self.push(Action::Unrelate { target, kind })
+    }
+
+    /// Link the issue to another object.
+    pub fn link(&mut self, link: Link) -> Result<(), store::Error> {
+        self.push(Action::Link { link })
+    }
+
+    /// Remove a link to another object.
+    pub fn unlink(&mut self, link: Link) -> Result<(), store::Error> {
+        self.push(Action::Unlink { link })
+    }
+
     ////////////////////////////////////////////////////////////////////////////////////////////////
 
     /// Create the issue thread.
@@ -678,6 +825,64 @@ where
         self.transaction("React", signer, |tx| tx.react(to, reaction, active))
     }
 
+    /// Relate this issue to another issue of the repository.
+    ///
+    /// The inverse relation is recorded on the target issue, so that the relation
+    /// shows up on both ends, eg. relating with [`RelationKind::Blocks`] marks the
+    /// target as [`RelationKind::BlockedBy`] this issue.
+    pub fn relate<G: Signer>(
+        &mut self,
+        target: IssueId,
+        kind: RelationKind,
+        signer: &G,
+    ) -> Result<EntryId, Error> {
+        let id = self.id;
+        if target == id || self.store.get(&target)?.is_none() {
+            return Err(Error::InvalidRelation(target));
+        }
+        let entry = self.transaction("Relate", signer, |tx| tx.relate(target, kind))?;
+
+        let mut other = self.store.get_mut(&target, self.cache)?;
+        other.transaction("Relate", signer, |tx| tx.relate(id, kind.inverse()))?;
+
+        Ok(entry)
+    }
+
+    /// Remove a relation to another issue, on both ends.
+    pub fn unrelate<G: Signer>(
+        &mut self,
+        target: IssueId,
+        kind: RelationKind,
+        signer: &G,
+    ) -> Result<EntryId, Error> {
+        let id = self.id;
+        let entry = self.transaction("Unrelate", signer, |tx| tx.unrelate(target, kind))?;
+
+        match self.store.get_mut(&target, self.cache) {
+            Ok(mut other) => {
+                other.transaction("Unrelate", signer, |tx| tx.unrelate(id, kind.inverse()))?;
+            }
+            // The target issue was removed, there's nothing left to unrelate.
+            Err(store::Error::NotFound(..)) => {}
+            Err(e) => return Err(e.into()),
+        }
+        Ok(entry)
+    }
+
+    /// Link this issue to another object of the repository, which must exist.
+    ///
+    /// See [`cob::link`] for how to have the link show up on the target as well.
+    pub fn link<G: Signer>(&mut self, link: Link, signer: &G) -> Result<EntryId, Error> {
+        link.validate(self.store.as_ref())?;
+
+        self.transaction("Link", signer, |tx| tx.link(link))
+    }
+
+    /// Remove a link to another object.
+    pub fn unlink<G: Signer>(&mut self, link: Link, signer: &G) -> Result<EntryId, Error> {
+        self.transaction("Unlink", signer, |tx| tx.unlink(link))
+    }
+
     pub fn transaction<G, F>(
         &mut self,
         message: &str,
@@ -691,14 +896,19 @@ where
         let mut tx = Transaction::default();
         operations(&mut tx)?;
 
-        let (issue, commit) = tx.commit(message, self.id, &mut self.store.raw, signer)?;
+        let commit = tx.apply(
+            message,
+            self.id,
+            &mut self.store.raw,
+            signer,
+            &mut self.issue,
+        )?;
         self.cache
-            .update(&self.store.as_ref().id(), &self.id, &issue)
+            .update(&self.store.as_ref().id(), &self.id, &self.issue)
             .map_err(|e| Error::CacheUpdate {
                 id: self.id,
                 err: e.into(),
             })?;
-        self.issue = issue;
 
         Ok(commit)
     }
@@ -916,6 +1126,22 @@ pub enum Action {
         reaction: Reaction,
         active: bool,
     },
+
+    /// Relate the issue to another issue.
+    #[serde(rename = "relate")]
+    Relate { target: IssueId, kind: RelationKind },
+
+    /// Remove a relation to another issue.
+    #[serde(rename = "unrelate")]
+    Unrelate { target: IssueId, kind: RelationKind },
+
+    /// Link the issue to another object.
+    #[serde(rename = "link")]
+    Link { link: Link },
+
+    /// Remove a link to another object.
+    #[serde(rename = "unlink")]
+    Unlink { link: Link },
 }
 
 impl CobAction for Action {}
@@ -1259,7 +1485,7 @@ mod test {
 
         let id = issue.id;
         let issue = issues.get(&id).unwrap().unwrap();
-        let reactions = issue.comment(&comment).unwrap().reactions();
+        let reactions = issue.thread().comment(&comment).unwrap().reactions();
         let authors = reactions.get(&reaction).unwrap();
 
         assert_eq!(authors.first().unwrap(), &node.signer.public_key());
@@ -1359,6 +1585,52 @@ mod test {
         assert!(labels.contains(&wontfix_label));
     }
 
+    #[test]
+    fn test_issue_relate() {
+        let test::setup::NodeWithRepo { node, repo, .. } = test::setup::NodeWithRepo::default();
+        let mut issues = Cache::no_cache(&*repo).unwrap();
+        let blocked = issues
+            .create("Blocked", "Blah blah blah.", &[], &[], [], &node.signer)
+            .unwrap()
+            .id;
+        let mut issue = issues
+            .create("Blocker", "Blah blah blah.", &[], &[], [], &node.signer)
+            .unwrap();
+        let missing = arbitrary::oid().into();
+
+        assert!(matches!(
+            issue.relate(missing, RelationKind::Related, &node.signer),
+            Err(Error::InvalidRelation(id)) if id == missing
+        ));
+        issue
+            .relate(blocked, RelationKind::Blocks, &node.signer)
+            .unwrap();
+
+        let id = issue.id;
+        let blocker = issues.get(&id).unwrap().unwrap();
+        let blocked_issue = issues.get(&blocked).unwrap().unwrap();
+
+        assert_eq!(
+            blocker.relations().collect::<Vec<_>>(),
+            vec![&Relation {
+                target: blocked,
+                kind: RelationKind::Blocks
+            }]
+        );
+        assert_eq!(
+            blocked_issue.related(RelationKind::BlockedBy).collect::<Vec<_>>(),
+            vec![&id]
+        );
+
+        let mut issue = issues.get_mut(&id).unwrap();
+        issue
+            .unrelate(blocked, RelationKind::Blocks, &node.signer)
+            .unwrap();
+
+        assert_eq!(issues.get(&id).unwrap().unwrap().relations().count(), 0);
+        assert_eq!(issues.get(&blocked).unwrap().unwrap().relations().count(), 0);
+    }
+
     #[test]
     fn test_issue_comment() {
         let test::setup::NodeWithRepo { node, repo, .. } = test::setup::NodeWithRepo::default();
@@ -1400,6 +1672,55 @@ mod test {
         assert_eq!(c2.author(), author);
     }
 
+    #[test]
+    fn test_issue_comment_edits() {
+        let test::setup::NodeWithRepo { node, repo, .. } = test::setup::NodeWithRepo::default();
+        let author = *node.signer.public_key();
+        let mut issues = Cache::no_cache(&*repo).unwrap();
+        let mut issue = issues
+            .create(
+                "My first issue",
+                "Blah blah blah.",
+                &[],
+                &[],
+                [],
+                &node.signer,
+            )
+            .unwrap();
+        let (c0, _) = issue.root();
+        let c0 = *c0;
+
+        let comment = issue
+            .comment("Ho ho ho.", c0, vec![], &node.signer)
+            .unwrap();
+        assert!(!issue.thread().comment(&comment).unwrap().is_edited());
+
+        issue
+            .edit_comment(comment, "Ha ha ha.", vec![], &node.signer)
+            .unwrap();
+        issue
+            .edit_comment(comment, "He he he.", vec![], &node.signer)
+            .unwrap();
+
+        let id = issue.id;
+        let issue = issues.get(&id).unwrap().unwrap();
+        let edits = issue.comment_edits(&comment).unwrap();
+
+        assert!(issue.thread().comment(&comment).unwrap().is_edited());
+        assert_eq!(
+            edits.iter().map(|e| e.body.as_str()).collect::<Vec<_>>(),
+            vec!["Ho ho ho.", "Ha ha ha.", "He he he."]
+        );
+        assert!(edits.iter().all(|e| e.author == author));
+        assert!(edits.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+        assert_eq!(issue.thread().comment(&comment).unwrap().body(), "He he he.");
+
+        // The history of a redacted comment is discarded with it.
+        let mut issue = issues.get_mut(&id).unwrap();
+        issue.redact_comment(comment, &node.signer).unwrap();
+        assert!(issue.comment_edits(&comment).is_none());
+    }
+
     #[test]
     fn test_issue_comment_redact() {
         let test::setup::NodeWithRepo { node, repo, .. } = test::setup::NodeWithRepo::default();
@@ -1665,6 +1986,51 @@ mod test {
         assert_eq!(issue.comments().count(), 1);
     }
 
+    #[test]
+    fn test_issue_comment_rollback() {
+        let test::setup::NodeWithRepo { node, repo, .. } = test::setup::NodeWithRepo::default();
+        let mut issues = Cache::no_cache(&*repo).unwrap();
+        let mut issue = issues
+            .create(
+                "My first issue",
+                "Blah blah blah.",
+                &[],
+                &[],
+                [],
+                &node.signer,
+            )
+            .unwrap();
+        let (c0, _) = issue.root();
+        let c0 = *c0;
+
+        // Make the commit fail after the object is written, by locking the signed refs.
+        let lock = repo
+            .path()
+            .join("refs")
+            .join("namespaces")
+            .join(node.signer.public_key().to_string())
+            .join("refs")
+            .join("rad")
+            .join("sigrefs.lock");
+        std::fs::create_dir_all(lock.parent().unwrap()).unwrap();
+        std::fs::write(&lock, []).unwrap();
+
+        issue
+            .comment("Ho ho ho.", c0, vec![], &node.signer)
+            .unwrap_err();
+        assert_eq!(issue.comments().count(), 1);
+
+        // Storage was rolled back as well.
+        issue.reload().unwrap();
+        assert_eq!(issue.comments().count(), 1);
+
+        std::fs::remove_file(&lock).unwrap();
+        issue
+            .comment("Ho ho ho.", c0, vec![], &node.signer)
+            .unwrap();
+        assert_eq!(issue.comments().count(), 2);
+    }
+
     #[test]
     fn test_invalid_cob() {
         use crate::crypto::test::signer::MockSigner;
@@ -29,6 +29,12 @@ pub trait Issues {
     /// was not found.
     fn get(&self, id: &IssueId) -> Result<Option<Issue>, Self::Error>;
 
+    /// Get many `Issue`s at once, identified by `ids`. The result is in the
+    /// same order as `ids`, with `None` for any issue that was not found.
+    fn get_many(&self, ids: &[IssueId]) -> Result<Vec<Option<Issue>>, Self::Error> {
+        ids.iter().map(|id| self.get(id)).collect()
+    }
+
     /// List all issues that are in the store.
     fn list(&self) -> Result<Self::Iter<'_>, Self::Error>;
 
@@ -408,6 +414,10 @@ where
         query::get(&self.cache.db, &self.rid(), id)
     }
 
+    fn get_many(&self, ids: &[IssueId]) -> Result<Vec<Option<Issue>>, Self::Error> {
+        query::get_many(&self.cache.db, &self.rid(), ids)
+    }
+
     fn list(&self) -> Result<Self::Iter<'_>, Self::Error> {
         query::list(&self.cache.db, &self.rid())
     }
@@ -428,6 +438,10 @@ where
         query::get(&self.cache.db, &self.rid(), id)
     }
 
+    fn get_many(&self, ids: &[IssueId]) -> Result<Vec<Option<Issue>>, Self::Error> {
+        query::get_many(&self.cache.db, &self.rid(), ids)
+    }
+
     fn list(&self) -> Result<Self::Iter<'_>, Self::Error> {
         query::list(&self.cache.db, &self.rid())
     }
@@ -468,6 +482,34 @@ mod query {
         }
     }
 
+    pub(super) fn get_many(
+        db: &sql::ConnectionThreadSafe,
+        rid: &RepoId,
+        ids: &[IssueId],
+    ) -> Result<Vec<Option<Issue>>, Error> {
+        let json = serde_json::to_string(
+            &ids.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+        )?;
+        let mut stmt = db.prepare(
+            "SELECT id, issue
+             FROM issues
+             WHERE repo = ?1 AND id IN (SELECT value FROM json_each(?2))",
+        )?;
+
+        stmt.bind((1, rid))?;
+        stmt.bind((2, sql::Value::String(json)))?;
+
+        let mut found = std::collections::HashMap::with_capacity(ids.len());
+        for row in stmt.into_iter() {
+            let row = row?;
+            let id = IssueId::from_str(row.read::<&str, _>("id"))?;
+            let issue = serde_json::from_str::<Issue>(row.read::<&str, _>("issue"))?;
+
+            found.insert(id, issue);
+        }
+        Ok(ids.iter().map(|id| found.get(id).cloned()).collect())
+    }
+
     pub(super) fn list<'a>(
         db: &'a sql::ConnectionThreadSafe,
         rid: &RepoId,
@@ -596,6 +638,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_many() {
+        let repo = arbitrary::gen::<MockRepository>(1);
+        let mut cache = memory(repo);
+        let ids = (0..arbitrary::gen::<u8>(1).max(3))
+            .map(|_| IssueId::from(arbitrary::oid()))
+            .collect::<BTreeSet<IssueId>>();
+
+        for id in ids.iter() {
+            let issue = Issue {
+                title: id.to_string(),
+                ..Issue::new(Thread::default())
+            };
+            cache.update(&cache.rid(), id, &issue).unwrap();
+        }
+
+        // Query in reverse order, with a missing object in the middle.
+        let mut query = ids.iter().rev().copied().collect::<Vec<_>>();
+        query.insert(query.len() / 2, IssueId::from(arbitrary::oid()));
+
+        let many = cache.get_many(&query).unwrap();
+        let individual = query
+            .iter()
+            .map(|id| cache.get(id).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(many.len(), query.len());
+        assert_eq!(many, individual);
+        assert_eq!(many.iter().filter(|issue| issue.is_none()).count(), 1);
+    }
+
     #[test]
     fn test_get() {
         let repo = arbitrary::gen::<MockRepository>(1);
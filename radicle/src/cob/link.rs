@@ -0,0 +1,191 @@
+//! Typed links between collaborative objects of a repository, eg. a patch fixing an issue.
+//!
+//! Links are stored on the object they are made from, as a set that is updated by the
+//! `link` and `unlink` actions of that object. Since actions are applied in causal order,
+//! with concurrent actions ordered deterministically, the set converges on all replicas.
+//!
+//! To have a link show up on both ends, the target object is linked back with
+//! [`Link::inverse`].
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cob::{object, ObjectId, TypeName};
+
+/// Error validating a link.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The link target doesn't exist in the repository.
+    #[error("link target {type_name}/{id} not found")]
+    NotFound { type_name: TypeName, id: ObjectId },
+    /// The link target couldn't be looked up.
+    #[error("failed to look up link target: {0}")]
+    Storage(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+}
+
+/// How an object relates to the target of a link.
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Relation {
+    /// The object fixes the target, eg. a patch fixing an issue.
+    Fixes,
+    /// The object is fixed by the target.
+    FixedBy,
+    /// The object references the target.
+    References,
+    /// The object is referenced by the target.
+    ReferencedBy,
+}
+
+impl Relation {
+    /// The relation, as seen from the target.
+    pub fn inverse(self) -> Self {
+        match self {
+            Self::Fixes => Self::FixedBy,
+            Self::FixedBy => Self::Fixes,
+            Self::References => Self::ReferencedBy,
+            Self::ReferencedBy => Self::References,
+        }
+    }
+}
+
+impl std::fmt::Display for Relation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fixes => write!(f, "fixes"),
+            Self::FixedBy => write!(f, "fixed by"),
+            Self::References => write!(f, "references"),
+            Self::ReferencedBy => write!(f, "referenced by"),
+        }
+    }
+}
+
+/// A typed link from an object to another object of the same repository.
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Link {
+    /// Type of the target object, eg. `xyz.radicle.issue`.
+    pub target_type: TypeName,
+    /// Identifier of the target object.
+    pub target_id: ObjectId,
+    /// How the object relates to the target.
+    pub relation: Relation,
+}
+
+impl Link {
+    /// Create a new link.
+    pub fn new(target_type: TypeName, target_id: ObjectId, relation: Relation) -> Self {
+        Self {
+            target_type,
+            target_id,
+            relation,
+        }
+    }
+
+    /// The link from the target back to the given source object.
+    pub fn inverse(&self, source_type: TypeName, source_id: ObjectId) -> Self {
+        Self::new(source_type, source_id, self.relation.inverse())
+    }
+
+    /// Check that the link target exists in the repository.
+    pub fn validate<S: object::Storage>(&self, repo: &S) -> Result<(), Error> {
+        let objects = repo
+            .objects(&self.target_type, &self.target_id)
+            .map_err(|e| Error::Storage(Box::new(e)))?;
+
+        if objects.iter().next().is_none() {
+            return Err(Error::NotFound {
+                type_name: self.target_type.clone(),
+                id: self.target_id,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::cob::issue::cache::Issues as _;
+    use crate::cob::patch::cache::Patches as _;
+    use crate::cob::{issue, patch};
+    use crate::test;
+
+    #[test]
+    fn test_link_patch_to_issue() {
+        let alice = test::setup::NodeWithRepo::default();
+        let checkout = alice.repo.checkout();
+        let branch = checkout.branch_with([("README", b"Hello World!")]);
+        let mut issues = issue::Cache::no_cache(&*alice.repo).unwrap();
+        let mut patches = patch::Cache::no_cache(&*alice.repo).unwrap();
+
+        let issue = issues
+            .create("Crash on startup", "Blah.", &[], &[], [], &alice.signer)
+            .unwrap();
+        let issue_id = *issue.id();
+        let mut patch = patches
+            .create(
+                "Fix crash on startup",
+                "Blah blah.",
+                patch::MergeTarget::Delegates,
+                branch.base,
+                branch.oid,
+                &[],
+                &alice.signer,
+            )
+            .unwrap();
+        let patch_id = patch.id;
+
+        let fixes = Link::new(issue::TYPENAME.clone(), issue_id, Relation::Fixes);
+        patch.link(fixes.clone(), &alice.signer).unwrap();
+        issues
+            .get_mut(&issue_id)
+            .unwrap()
+            .link(fixes.inverse(patch::TYPENAME.clone(), patch_id), &alice.signer)
+            .unwrap();
+
+        let patch = patches.get(&patch_id).unwrap().unwrap();
+        let issue = issues.get(&issue_id).unwrap().unwrap();
+
+        assert_eq!(patch.links().collect::<Vec<_>>(), vec![&fixes]);
+        assert_eq!(
+            issue.links().collect::<Vec<_>>(),
+            vec![&Link::new(
+                patch::TYPENAME.clone(),
+                patch_id,
+                Relation::FixedBy
+            )]
+        );
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap()["links"],
+            serde_json::json!([{
+                "targetType": "xyz.radicle.issue",
+                "targetId": issue_id,
+                "relation": "fixes",
+            }])
+        );
+        assert_eq!(
+            serde_json::to_value(&issue).unwrap()["links"][0]["relation"],
+            "fixedBy"
+        );
+    }
+
+    #[test]
+    fn test_link_missing_target() {
+        let alice = test::setup::NodeWithRepo::default();
+        let mut issues = issue::Cache::no_cache(&*alice.repo).unwrap();
+        let mut issue = issues
+            .create("Crash on startup", "Blah.", &[], &[], [], &alice.signer)
+            .unwrap();
+        let missing = test::arbitrary::oid();
+
+        assert!(matches!(
+            issue.link(
+                Link::new(patch::TYPENAME.clone(), missing.into(), Relation::FixedBy),
+                &alice.signer,
+            ),
+            Err(issue::Error::Link(Error::NotFound { .. }))
+        ));
+        assert_eq!(issue.links().count(), 0);
+    }
+}
@@ -3,6 +3,7 @@ pub mod cache;
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt;
 use std::ops::Deref;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use amplify::Wrapper;
@@ -13,7 +14,11 @@ use storage::{HasRepoId, RepositoryError};
 use thiserror::Error;
 
 use crate::cob;
-use crate::cob::common::{Author, Authorization, CodeLocation, Label, Reaction, Timestamp};
+use crate::cob::common::{
+    Author, Authorization, CodeLocation, CodeRange, Label, Reaction, Timestamp,
+};
+use crate::cob::link;
+use crate::cob::link::Link;
 use crate::cob::store::Transaction;
 use crate::cob::store::{Cob, CobAction};
 use crate::cob::thread;
@@ -25,6 +30,7 @@ use crate::git;
 use crate::identity::doc::DocError;
 use crate::identity::PayloadError;
 use crate::prelude::*;
+use crate::review;
 use crate::storage;
 
 pub use cache::Cache;
@@ -123,6 +129,15 @@ pub enum Error {
     /// Revision not found.
     #[error("revision not found: {0}")]
     RevisionNotFound(RevisionId),
+    /// No revisions were given to squash.
+    #[error("no revisions to squash")]
+    NothingToSquash,
+    /// Link target is invalid.
+    #[error("invalid link: {0}")]
+    Link(#[from] link::Error),
+    /// Repository error.
+    #[error("repository: {0}")]
+    Repository(#[from] RepositoryError),
     /// Initialization failed.
     #[error("initialization failed: {0}")]
     Init(&'static str),
@@ -160,6 +175,10 @@ pub enum Action {
         revision: RevisionId,
         commit: git::Oid,
     },
+    #[serde(rename = "link")]
+    Link { link: Link },
+    #[serde(rename = "unlink")]
+    Unlink { link: Link },
 
     //
     // Review actions
@@ -231,6 +250,9 @@ pub enum Action {
         /// Review comments resolved by this revision.
         #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
         resolves: BTreeSet<(EntryId, CommentId)>,
+        /// Revisions squashed into this one, whose reviews are reattached to it.
+        #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+        squashes: BTreeSet<RevisionId>,
     },
     #[serde(rename = "revision.edit")]
     RevisionEdit {
@@ -386,6 +408,9 @@ pub struct Patch {
     pub(super) target: MergeTarget,
     /// Associated labels.
     /// Labels can be added and removed at will.
+    ///
+    /// Labels are kept in their canonical order, ie. sorted by name, so that all replicas
+    /// list them in the same order, regardless of the order in which they were added.
     pub(super) labels: BTreeSet<Label>,
     /// Patch merges.
     ///
@@ -406,6 +431,9 @@ pub struct Patch {
     pub(super) timeline: Vec<EntryId>,
     /// Reviews index. Keeps track of reviews for better performance.
     pub(super) reviews: BTreeMap<ReviewId, Option<(RevisionId, ActorId)>>,
+    /// Links to other objects of the repository, eg. the issues this patch fixes.
+    #[serde(default)]
+    pub(super) links: BTreeSet<Link>,
 }
 
 impl Patch {
@@ -422,6 +450,7 @@ impl Patch {
             assignees: BTreeSet::default(),
             timeline: vec![id.into_inner()],
             reviews: BTreeMap::default(),
+            links: BTreeSet::default(),
         }
     }
 
@@ -449,11 +478,16 @@ impl Patch {
             .timestamp
     }
 
-    /// Associated labels.
+    /// Associated labels, in canonical order.
     pub fn labels(&self) -> impl Iterator<Item = &Label> {
         self.labels.iter()
     }
 
+    /// Links of this patch to other objects.
+    pub fn links(&self) -> impl Iterator<Item = &Link> {
+        self.links.iter()
+    }
+
     /// Patch description.
     pub fn description(&self) -> &str {
         let (_, r) = self.root();
@@ -553,6 +587,52 @@ impl Patch {
         repo.merge_base(self.base(), self.head())
     }
 
+    /// Preview the merge of the given revision into the patch target, without
+    /// writing any objects or updating any references.
+    pub fn merge_preview<R: WriteRepository>(
+        &self,
+        repo: &R,
+        revision: RevisionId,
+    ) -> Result<MergePreview, Error> {
+        let revision = self
+            .revision(&revision)
+            .ok_or(Error::RevisionNotFound(revision))?;
+        let target = self.target.head(repo)?;
+        let raw = repo.raw();
+        let ours = raw.find_commit(target.into()).map_err(git::ext::Error::from)?;
+        let theirs = raw
+            .find_commit(revision.head().into())
+            .map_err(git::ext::Error::from)?;
+        let index = raw
+            .merge_commits(&ours, &theirs, None)
+            .map_err(git::ext::Error::from)?;
+
+        if !index.has_conflicts() {
+            return Ok(MergePreview::Clean);
+        }
+        let files = conflicts(&index).map_err(git::ext::Error::from)?;
+
+        Ok(MergePreview::Conflicting { files })
+    }
+
+    /// Cherry-pick the commits of the given revision onto the `HEAD` of a working copy.
+    /// The working copy is expected to contain the revision's commits.
+    ///
+    /// Commits are applied in order, and the first commit that doesn't apply cleanly
+    /// stops the process, in which case the conflicting files are reported. Commits
+    /// that were applied are kept.
+    pub fn cherry_pick(
+        &self,
+        revision: RevisionId,
+        working: &git::raw::Repository,
+    ) -> Result<CherryPick, Error> {
+        let revision = self
+            .revision(&revision)
+            .ok_or(Error::RevisionNotFound(revision))?;
+
+        cherry_pick(revision, working).map_err(|e| Error::Git(e.into()))
+    }
+
     /// Get the commit range of this patch.
     pub fn range(&self) -> Result<(git::Oid, git::Oid), git::ext::Error> {
         return Ok((*self.base(), *self.head()));
@@ -644,6 +724,8 @@ impl Patch {
             Action::Merge { .. } => match self.target() {
                 MergeTarget::Delegates => Authorization::Deny,
             },
+            // The patch author can link the patch to other objects.
+            Action::Link { .. } | Action::Unlink { .. } => Authorization::from(actor == author),
             // Anyone can submit a review.
             Action::Review { .. } => Authorization::Allow,
             // The review author can edit a review.
@@ -695,8 +777,17 @@ impl Patch {
                 // Redacted.
                 Authorization::Unknown
             }
-            // Anyone can propose revisions.
-            Action::Revision { .. } => Authorization::Allow,
+            // Anyone can propose revisions, but only squash their own.
+            Action::Revision { squashes, .. } => {
+                for revision in squashes {
+                    if let Some(revision) = lookup::revision(self, revision)? {
+                        if actor != revision.author.public_key() {
+                            return Ok(Authorization::Deny);
+                        }
+                    }
+                }
+                Authorization::Allow
+            }
             // Only the revision author can edit or redact their revision.
             Action::RevisionEdit { revision, .. } | Action::RevisionRedact { revision, .. } => {
                 if let Some(revision) = lookup::revision(self, revision)? {
@@ -773,6 +864,12 @@ impl Patch {
             Action::Assign { assignees } => {
                 self.assignees = BTreeSet::from_iter(assignees.into_iter().map(ActorId::from));
             }
+            Action::Link { link } => {
+                self.links.insert(link);
+            }
+            Action::Unlink { link } => {
+                self.links.remove(&link);
+            }
             Action::RevisionEdit {
                 revision,
                 description,
@@ -820,20 +917,15 @@ impl Patch {
                 base,
                 oid,
                 resolves,
+                squashes,
             } => {
                 debug_assert!(!self.revisions.contains_key(&entry));
 
-                self.revisions.insert(
-                    RevisionId(entry),
-                    Some(Revision::new(
-                        author.into(),
-                        description,
-                        base,
-                        oid,
-                        timestamp,
-                        resolves,
-                    )),
-                );
+                let id = RevisionId(entry);
+                let mut revision =
+                    Revision::new(author.into(), description, base, oid, timestamp, resolves);
+                self.reattach(id, &mut revision, &squashes, repo)?;
+                self.revisions.insert(id, Some(revision));
             }
             Action::RevisionReact {
                 revision,
@@ -1118,6 +1210,79 @@ impl Patch {
         }
         Ok(())
     }
+
+    /// Reattach the reviews of the `squashed` revisions to `revision`.
+    ///
+    /// Reviews are taken in the order the squashed revisions were created in, so that
+    /// the latest review of each author wins. See [`PatchMut::squash`].
+    fn reattach<R: ReadRepository>(
+        &mut self,
+        id: RevisionId,
+        revision: &mut Revision,
+        squashed: &BTreeSet<RevisionId>,
+        repo: &R,
+    ) -> Result<(), Error> {
+        let mut squashed = squashed
+            .iter()
+            .filter_map(|id| match self.revisions.get(id) {
+                Some(Some(r)) => Some((*id, r)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        squashed.sort_by_key(|(_, r)| r.timestamp);
+
+        let mut reattached = BTreeMap::new();
+        for (squashed_id, squashed) in squashed {
+            for (author, review) in squashed.reviews.iter() {
+                let Some(review) = review else {
+                    continue;
+                };
+                let mut review = review.clone();
+
+                // Verdicts only carry over if the reviewed code is exactly the new code.
+                if squashed.oid != revision.oid {
+                    review.verdict = None;
+                }
+                for comment in review.comments.comments.values_mut().flatten() {
+                    let Some(location) = comment.location.as_mut() else {
+                        continue;
+                    };
+                    if is_unchanged(location, &revision.oid, repo) {
+                        location.commit = revision.oid;
+                    }
+                }
+                revision.reviews.insert(*author, Some(review));
+                reattached.insert(*author, squashed_id);
+            }
+        }
+        for locator in self.reviews.values_mut().flatten() {
+            let (revision_id, author) = locator;
+
+            if reattached.get(author) == Some(revision_id) {
+                *revision_id = id;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether the code a review comment was made on is unchanged at `head`.
+fn is_unchanged<R: ReadRepository>(location: &CodeLocation, head: &git::Oid, repo: &R) -> bool {
+    let lines = match &location.new {
+        Some(CodeRange::Lines { range }) => range.clone(),
+        Some(CodeRange::Chars { line, .. }) => *line..*line + 1,
+        None => return false,
+    };
+    let (Ok(old), Ok(new)) = (
+        repo.blob_at(location.commit, &location.path),
+        repo.blob_at(*head, &location.path),
+    ) else {
+        return false;
+    };
+    let old = old.content().split(|b| *b == b'\n').collect::<Vec<_>>();
+    let new = new.content().split(|b| *b == b'\n').collect::<Vec<_>>();
+
+    old.get(lines.clone()).is_some() && old.get(lines.clone()) == new.get(lines)
 }
 
 impl store::Cob for Patch {
@@ -1136,6 +1301,7 @@ impl store::Cob for Patch {
             base,
             oid,
             resolves,
+            ..
         }) = actions.next()
         else {
             return Err(Error::Init("the first action must be of type `revision`"));
@@ -1436,6 +1602,188 @@ impl Revision {
     pub fn review(&self, author: &ActorId) -> Option<&Review> {
         self.reviews.get(author).and_then(|o| o.as_ref())
     }
+
+    /// Review coverage of the files changed by this revision.
+    ///
+    /// A file is covered by a review if the review has a comment on it. Since the
+    /// changes are computed against the merge base of this revision, comments on
+    /// files that are no longer changed, eg. after an update, don't count.
+    pub fn review_coverage<R: ReadRepository>(
+        &self,
+        repo: &R,
+    ) -> Result<BTreeMap<PathBuf, Reviewed>, git::ext::Error> {
+        let base = repo.merge_base(&self.base, &self.oid)?;
+        let mut coverage = changed_files(repo, base, self.oid)?
+            .into_iter()
+            .map(|path| (path, Reviewed::default()))
+            .collect::<BTreeMap<_, _>>();
+
+        for (reviewer, review) in self.reviews() {
+            for (_, comment) in review.comments() {
+                let Some(location) = comment.location() else {
+                    continue;
+                };
+                if let Some(reviewed) = coverage.get_mut(&location.path) {
+                    reviewed.insert(*reviewer, review.verdict());
+                }
+            }
+        }
+        Ok(coverage)
+    }
+}
+
+/// Outcome of merging a revision into its patch target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum MergePreview {
+    /// The revision merges cleanly.
+    Clean,
+    /// The revision conflicts with the target.
+    Conflicting {
+        /// Files with conflicts.
+        files: BTreeSet<PathBuf>,
+    },
+}
+
+impl MergePreview {
+    /// Whether the revision merges cleanly.
+    pub fn is_clean(&self) -> bool {
+        matches!(self, Self::Clean)
+    }
+}
+
+/// Outcome of cherry-picking a revision onto a working copy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CherryPick {
+    /// Commits created on the working copy, oldest first.
+    pub commits: Vec<git::Oid>,
+    /// Whether all of the revision's commits applied cleanly.
+    pub preview: MergePreview,
+}
+
+/// Get the conflicting files of a merge index.
+fn conflicts(index: &git::raw::Index) -> Result<BTreeSet<PathBuf>, git::raw::Error> {
+    let mut files = BTreeSet::new();
+
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        if let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) {
+            files.insert(PathBuf::from(String::from_utf8_lossy(&entry.path).as_ref()));
+        }
+    }
+    Ok(files)
+}
+
+/// Cherry-pick the commits of a revision onto the `HEAD` of a working copy.
+fn cherry_pick(
+    revision: &Revision,
+    working: &git::raw::Repository,
+) -> Result<CherryPick, git::raw::Error> {
+    let mut walk = working.revwalk()?;
+    walk.push(revision.head().into())?;
+    walk.hide(revision.base().into())?;
+    walk.set_sorting(git::raw::Sort::TOPOLOGICAL | git::raw::Sort::REVERSE)?;
+
+    let mut head = working.head()?;
+    let mut onto = head.peel_to_commit()?;
+    let mut commits = Vec::new();
+    let mut preview = MergePreview::Clean;
+
+    for oid in walk {
+        let commit = working.find_commit(oid?)?;
+        let mut index = working.cherrypick_commit(&commit, &onto, 0, None)?;
+
+        if index.has_conflicts() {
+            preview = MergePreview::Conflicting {
+                files: conflicts(&index)?,
+            };
+            break;
+        }
+        let tree = working.find_tree(index.write_tree_to(working)?)?;
+        let committer = working
+            .signature()
+            .unwrap_or_else(|_| commit.committer().to_owned());
+        let oid = working.commit(
+            None,
+            &commit.author(),
+            &committer,
+            &String::from_utf8_lossy(commit.message_bytes()),
+            &tree,
+            &[&onto],
+        )?;
+        onto = working.find_commit(oid)?;
+        commits.push(oid.into());
+    }
+
+    if !commits.is_empty() {
+        working.checkout_tree(onto.as_object(), None)?;
+        head.set_target(onto.id(), "cherry-pick")?;
+    }
+    Ok(CherryPick { commits, preview })
+}
+
+/// Review coverage of a file changed in a revision.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum Reviewed {
+    /// No review has comments on the file.
+    #[default]
+    Unreviewed,
+    /// Reviews have comments on the file.
+    Commented {
+        /// Reviewers who commented on the file, along with their review verdict.
+        reviewers: BTreeMap<ActorId, Option<Verdict>>,
+    },
+}
+
+impl Reviewed {
+    /// Whether the file was reviewed.
+    pub fn is_reviewed(&self) -> bool {
+        matches!(self, Self::Commented { .. })
+    }
+
+    fn insert(&mut self, reviewer: ActorId, verdict: Option<Verdict>) {
+        match self {
+            Self::Unreviewed => {
+                *self = Self::Commented {
+                    reviewers: BTreeMap::from([(reviewer, verdict)]),
+                }
+            }
+            Self::Commented { reviewers } => {
+                reviewers.insert(reviewer, verdict);
+            }
+        }
+    }
+}
+
+/// Paths of the files that differ between the trees of two commits.
+fn changed_files<R: ReadRepository>(
+    repo: &R,
+    old: git::Oid,
+    new: git::Oid,
+) -> Result<BTreeSet<PathBuf>, git::ext::Error> {
+    let files = |oid: git::Oid| -> Result<BTreeMap<PathBuf, git::raw::Oid>, git::ext::Error> {
+        let tree = repo.commit(oid)?.tree()?;
+        let mut files = BTreeMap::new();
+
+        tree.walk(git::raw::TreeWalkMode::PreOrder, |root, entry| {
+            if let (Some(git::raw::ObjectType::Blob), Some(name)) = (entry.kind(), entry.name()) {
+                files.insert(Path::new(root).join(name), entry.id());
+            }
+            git::raw::TreeWalkResult::Ok
+        })?;
+
+        Ok(files)
+    };
+    let old = files(old)?;
+    let new = files(new)?;
+
+    Ok(old
+        .iter()
+        .chain(new.iter())
+        .filter(|(path, oid)| old.get(*path) != Some(oid) || new.get(*path) != Some(oid))
+        .map(|(path, _)| path.clone())
+        .collect())
 }
 
 /// Patch state.
@@ -1875,9 +2223,32 @@ impl<R: ReadRepository> store::Transaction<Patch, R> {
             base: base.into(),
             oid: oid.into(),
             resolves: BTreeSet::new(),
+            squashes: BTreeSet::new(),
         })
     }
 
+    /// Squash revisions into a new revision, and redact them.
+    pub fn squash(
+        &mut self,
+        description: impl ToString,
+        base: impl Into<git::Oid>,
+        oid: impl Into<git::Oid>,
+        revisions: BTreeSet<RevisionId>,
+    ) -> Result<(), store::Error> {
+        self.push(Action::Revision {
+            description: description.to_string(),
+            base: base.into(),
+            oid: oid.into(),
+            resolves: BTreeSet::new(),
+            squashes: revisions.clone(),
+        })?;
+
+        for revision in revisions {
+            self.redact(revision)?;
+        }
+        Ok(())
+    }
+
     /// Lifecycle a patch.
     pub fn lifecycle(&mut self, state: Lifecycle) -> Result<(), store::Error> {
         self.push(Action::Lifecycle { state })
@@ -1894,6 +2265,16 @@ impl<R: ReadRepository> store::Transaction<Patch, R> {
             labels: labels.into_iter().collect(),
         })
     }
+
+    /// Link a patch to another object.
+    pub fn link(&mut self, link: Link) -> Result<(), store::Error> {
+        self.push(Action::Link { link })
+    }
+
+    /// Remove a link to another object.
+    pub fn unlink(&mut self, link: Link) -> Result<(), store::Error> {
+        self.push(Action::Unlink { link })
+    }
 }
 
 pub struct PatchMut<'a, 'g, R, C> {
@@ -1945,14 +2326,19 @@ where
         let mut tx = Transaction::default();
         operations(&mut tx)?;
 
-        let (patch, commit) = tx.commit(message, self.id, &mut self.store.raw, signer)?;
+        let commit = tx.apply(
+            message,
+            self.id,
+            &mut self.store.raw,
+            signer,
+            &mut self.patch,
+        )?;
         self.cache
-            .update(&self.store.as_ref().id(), &self.id, &patch)
+            .update(&self.store.as_ref().id(), &self.id, &self.patch)
             .map_err(|e| Error::CacheUpdate {
                 id: self.id,
                 err: e.into(),
             })?;
-        self.patch = patch;
 
         Ok(commit)
     }
@@ -2227,6 +2613,46 @@ where
         .map(RevisionId)
     }
 
+    /// Squash revisions into a single new revision.
+    ///
+    /// The new revision goes from the base of the earliest squashed revision to the head of
+    /// the latest one, so its diff is the cumulative diff of the squashed revisions, which
+    /// are then redacted. The root revision and merged revisions can't be squashed.
+    ///
+    /// Reviews of the squashed revisions are reattached to the new revision. Review comments
+    /// on code that is unchanged in the new revision are moved to its head, while the others
+    /// stay anchored to the commit they were made on. Unresolved comments stay unresolved,
+    /// and only the verdicts on the latest squashed revision are kept, since the code of the
+    /// earlier ones was not reviewed as a whole.
+    pub fn squash<G: Signer>(
+        &mut self,
+        revisions: &[RevisionId],
+        signer: &G,
+    ) -> Result<RevisionId, Error> {
+        let (root, _) = self.root();
+        let mut squashed = Vec::with_capacity(revisions.len());
+
+        for id in revisions {
+            if *id == root || self.merges.values().any(|m| m.revision == *id) {
+                return Err(Error::NotAllowed(id.into_inner()));
+            }
+            squashed.push(self.revision(id).ok_or(Error::RevisionNotFound(*id))?);
+        }
+        squashed.sort_by_key(|r| r.timestamp);
+
+        let (Some(first), Some(last)) = (squashed.first(), squashed.last()) else {
+            return Err(Error::NothingToSquash);
+        };
+        let description = last.description().to_owned();
+        let (base, oid) = (*first.base(), last.head());
+        let revisions = revisions.iter().copied().collect::<BTreeSet<_>>();
+
+        self.transaction("Squash revisions", signer, |tx| {
+            tx.squash(description, base, oid, revisions)
+        })
+        .map(RevisionId)
+    }
+
     /// Lifecycle a patch.
     pub fn lifecycle<G: Signer>(&mut self, state: Lifecycle, signer: &G) -> Result<EntryId, Error> {
         self.transaction("Lifecycle", signer, |tx| tx.lifecycle(state))
@@ -2289,6 +2715,20 @@ where
     ) -> Result<EntryId, Error> {
         self.transaction("Label", signer, |tx| tx.label(labels))
     }
+
+    /// Link a patch to another object of the repository, which must exist.
+    ///
+    /// See [`cob::link`] for how to have the link show up on the target as well.
+    pub fn link<G: Signer>(&mut self, link: Link, signer: &G) -> Result<EntryId, Error> {
+        link.validate(self.store.as_ref())?;
+
+        self.transaction("Link", signer, |tx| tx.link(link))
+    }
+
+    /// Remove a link to another object.
+    pub fn unlink<G: Signer>(&mut self, link: Link, signer: &G) -> Result<EntryId, Error> {
+        self.transaction("Unlink", signer, |tx| tx.unlink(link))
+    }
 }
 
 impl<'a, 'g, R, C> Deref for PatchMut<'a, 'g, R, C> {
@@ -2327,6 +2767,96 @@ pub struct ByRevision {
     pub revision: Revision,
 }
 
+/// Open patches, ordered by how they are stacked on each other.
+///
+/// A patch is stacked on another patch if it was built on top of it, ie. if the other
+/// patch's head is an ancestor of its own head. Patches that aren't stacked on any other
+/// patch are at the bottom of their stack.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PatchStack {
+    /// Patches in stacking order: each patch comes after the patches it is stacked on.
+    order: Vec<PatchId>,
+    /// The patch each patch is directly stacked on.
+    parents: BTreeMap<PatchId, PatchId>,
+}
+
+impl PatchStack {
+    /// Detect how the given patches are stacked, using the ancestry of their heads.
+    pub fn detect<'p, R: ReadRepository>(
+        repo: &R,
+        patches: impl IntoIterator<Item = (PatchId, &'p Patch)>,
+    ) -> Result<Self, git::ext::Error> {
+        let patches = patches
+            .into_iter()
+            .map(|(id, p)| (id, *p.base(), *p.head()))
+            .collect::<Vec<_>>();
+        // All the patches each patch is stacked on, directly or not.
+        let mut below = BTreeMap::<PatchId, BTreeSet<PatchId>>::new();
+
+        for (id, base, head) in &patches {
+            let mut deps = BTreeSet::new();
+
+            for (other, _, other_head) in &patches {
+                if other == id || other_head == head {
+                    continue;
+                }
+                if other_head == base || repo.is_ancestor_of(*other_head, *head)? {
+                    deps.insert(*other);
+                }
+            }
+            below.insert(*id, deps);
+        }
+        // Since ancestry is transitive, a patch is always stacked on more patches than
+        // the ones it's stacked on, so ordering by that number yields a topological order.
+        let mut order = below.keys().copied().collect::<Vec<_>>();
+        order.sort_by_key(|id| (below[id].len(), *id));
+
+        // A patch is directly stacked on the highest patch below it.
+        let parents = below
+            .iter()
+            .filter_map(|(id, deps)| {
+                deps.iter()
+                    .max_by_key(|dep| (below[*dep].len(), **dep))
+                    .map(|parent| (*id, *parent))
+            })
+            .collect();
+
+        Ok(Self { order, parents })
+    }
+
+    /// Iterate over the patches in stacking order, bottom first.
+    pub fn iter(&self) -> impl Iterator<Item = &PatchId> {
+        self.order.iter()
+    }
+
+    /// Get the patch the given patch is directly stacked on, if any.
+    pub fn parent(&self, id: &PatchId) -> Option<&PatchId> {
+        self.parents.get(id)
+    }
+
+    /// Get the number of patches below the given patch in its stack.
+    pub fn depth(&self, id: &PatchId) -> usize {
+        let mut depth = 0;
+        let mut id = id;
+
+        while let Some(parent) = self.parents.get(id) {
+            depth += 1;
+            id = parent;
+        }
+        depth
+    }
+
+    /// Get the number of patches.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Check whether there are no patches.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+}
+
 pub struct Patches<'a, R> {
     raw: store::Store<'a, Patch, R>,
 }
@@ -2420,6 +2950,34 @@ where
             .filter(|(_, p)| p.is_open()))
     }
 
+    /// Find the open patches whose head was merged into a branch out-of-band, eg. because
+    /// the patch commits were pushed to the branch directly, by fast-forward.
+    ///
+    /// Takes the tip of the branch, typically the canonical default branch, and returns the
+    /// merged patches along with their head, so that they can be transitioned to the merged
+    /// state with [`PatchMut::merge`].
+    pub fn detect_merged(&self, tip: git::Oid) -> Result<Vec<(PatchId, git::Oid)>, Error> {
+        let repo = self.as_ref();
+        let mut merged = Vec::new();
+
+        for (id, patch) in self.proposed()? {
+            let head = *patch.head();
+
+            if head == tip || repo.is_ancestor_of(head, tip)? {
+                merged.push((id, head));
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Get the open patches, ordered by how they are stacked on each other.
+    pub fn stack(&self) -> Result<PatchStack, Error> {
+        let proposed = self.proposed()?.collect::<Vec<_>>();
+        let stack = PatchStack::detect(self.as_ref(), proposed.iter().map(|(id, p)| (*id, p)))?;
+
+        Ok(stack)
+    }
+
     /// Get patches proposed by the given key.
     pub fn proposed_by<'b>(
         &'b self,
@@ -2431,6 +2989,46 @@ where
     }
 }
 
+impl<'a, R> Patches<'a, R>
+where
+    R: WriteRepository + cob::Store,
+{
+    /// Suggest reviewers for a patch, by applying the review rules to the files changed
+    /// by the patch and to its labels. The patch author is never suggested.
+    pub fn suggest_reviewers(
+        &self,
+        patch: &Patch,
+        config: &review::Config,
+    ) -> Result<Vec<Did>, Error> {
+        let raw = self.as_ref().raw();
+        let tree = |oid: &git::Oid| {
+            raw.find_commit((*oid).into())
+                .and_then(|c| c.tree())
+                .map_err(git::ext::Error::from)
+        };
+        let base = tree(patch.base())?;
+        let head = tree(patch.head())?;
+        let diff = raw
+            .diff_tree_to_tree(Some(&base), Some(&head), None)
+            .map_err(git::ext::Error::from)?;
+        let paths = diff
+            .deltas()
+            .filter_map(|d| d.new_file().path().or_else(|| d.old_file().path()))
+            .map(Path::to_path_buf)
+            .collect::<Vec<_>>();
+
+        let labels = patch.labels().cloned().collect::<Vec<_>>();
+        let author = patch.author().id();
+        let reviewers = config
+            .reviewers(&paths, &labels)
+            .into_iter()
+            .filter(|did| did != author)
+            .collect();
+
+        Ok(reviewers)
+    }
+}
+
 impl<'a, R> Patches<'a, R>
 where
     R: ReadRepository + SignRepository + cob::Store,
@@ -2674,7 +3272,6 @@ mod ser {
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod test {
-    use std::path::PathBuf;
     use std::str::FromStr;
     use std::vec;
 
@@ -2819,6 +3416,98 @@ mod test {
         assert_eq!("patch comment", comment.body(), "comment body untouched");
     }
 
+    #[test]
+    fn test_patch_detect_merged() {
+        let alice = test::setup::NodeWithRepo::default();
+        let checkout = alice.repo.checkout();
+        let first = checkout.branch_with([("README", b"Hello World!")]);
+        let second = checkout.branch_with([("CONTRIBUTING", b"Be nice.")]);
+        let mut patches = Cache::no_cache(&*alice.repo).unwrap();
+        let a = patches
+            .create(
+                "First patch",
+                "",
+                MergeTarget::Delegates,
+                first.base,
+                first.oid,
+                &[],
+                &alice.signer,
+            )
+            .unwrap()
+            .id;
+        let b = patches
+            .create(
+                "Second patch",
+                "",
+                MergeTarget::Delegates,
+                second.base,
+                second.oid,
+                &[],
+                &alice.signer,
+            )
+            .unwrap()
+            .id;
+
+        // Nothing is merged into the branch yet.
+        assert!(patches.detect_merged(first.base).unwrap().is_empty());
+
+        // The commits of the first patch land on the branch, by fast-forward.
+        assert_eq!(
+            patches.detect_merged(first.oid).unwrap(),
+            vec![(a, first.oid)]
+        );
+
+        // Then those of the second patch, which builds on the first.
+        let mut merged = patches.detect_merged(second.oid).unwrap();
+        merged.sort();
+        let mut expected = vec![(a, first.oid), (b, second.oid)];
+        expected.sort();
+
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn test_patch_stack() {
+        let alice = test::setup::NodeWithRepo::default();
+        let checkout = alice.repo.checkout();
+        let first = checkout.branch_with([("README", b"Hello World!")]);
+        let second = checkout.branch_with([("CONTRIBUTING", b"Be nice.")]);
+        let mut patches = Cache::no_cache(&*alice.repo).unwrap();
+        // Create the patch on top first, so that creation order doesn't match the stack.
+        let b = patches
+            .create(
+                "Second patch",
+                "",
+                MergeTarget::Delegates,
+                second.base,
+                second.oid,
+                &[],
+                &alice.signer,
+            )
+            .unwrap()
+            .id;
+        let a = patches
+            .create(
+                "First patch",
+                "",
+                MergeTarget::Delegates,
+                first.base,
+                first.oid,
+                &[],
+                &alice.signer,
+            )
+            .unwrap()
+            .id;
+
+        let stack = patches.stack().unwrap();
+
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&a, &b]);
+        assert_eq!(stack.parent(&a), None);
+        assert_eq!(stack.parent(&b), Some(&a));
+        assert_eq!(stack.depth(&a), 0);
+        assert_eq!(stack.depth(&b), 1);
+    }
+
     #[test]
     fn test_patch_merge() {
         let alice = test::setup::NodeWithRepo::default();
@@ -2955,6 +3644,7 @@ mod test {
                 base,
                 oid,
                 resolves: Default::default(),
+                squashes: Default::default(),
             },
             Action::Edit {
                 title: String::from("My patch"),
@@ -2966,6 +3656,7 @@ mod test {
             base,
             oid,
             resolves: Default::default(),
+            squashes: Default::default(),
         }]);
         let a3 = alice.op::<Patch>([Action::RevisionRedact {
             revision: RevisionId(a2.id()),
@@ -3006,6 +3697,7 @@ mod test {
                     base,
                     oid,
                     resolves: Default::default(),
+                    squashes: Default::default(),
                 },
                 Action::Edit {
                     title: String::from("Some patch"),
@@ -3021,6 +3713,7 @@ mod test {
                 base,
                 oid,
                 resolves: Default::default(),
+                squashes: Default::default(),
             },
             &alice,
         );
@@ -3052,6 +3745,78 @@ mod test {
         assert_eq!(patch.revisions().count(), 1);
     }
 
+    #[test]
+    fn test_patch_label_concurrent() {
+        let alice = MockSigner::default();
+        let bob = MockSigner::default();
+        let doc = Doc::new(
+            gen::<Project>(1),
+            nonempty::NonEmpty::from((
+                Did::from(*alice.public_key()),
+                vec![Did::from(*bob.public_key())],
+            )),
+            1,
+            identity::Visibility::Public,
+        )
+        .verified()
+        .unwrap();
+        let repo = MockRepository::new(gen::<RepoId>(1), doc);
+        let labels = |names: &[&str]| {
+            names
+                .iter()
+                .map(|n| Label::new(*n).unwrap())
+                .collect::<BTreeSet<_>>()
+        };
+        let h0: cob::test::HistoryBuilder<Patch> = cob::test::history(
+            &[
+                Action::Revision {
+                    description: String::from("Original"),
+                    base: arbitrary::oid(),
+                    oid: arbitrary::oid(),
+                    resolves: Default::default(),
+                    squashes: Default::default(),
+                },
+                Action::Edit {
+                    title: String::from("Some patch"),
+                    target: MergeTarget::Delegates,
+                },
+            ],
+            Timestamp::now(),
+            &alice,
+        );
+
+        // Alice and Bob label the patch concurrently.
+        let mut h1 = h0.clone();
+        h1.commit(
+            &Action::Label {
+                labels: labels(&["ux", "bug"]),
+            },
+            &alice,
+        );
+        let mut h2 = h0.clone();
+        h2.commit(
+            &Action::Label {
+                labels: labels(&["wontfix", "docs", "bug"]),
+            },
+            &bob,
+        );
+
+        // Each replica receives the changes in a different order.
+        let mut alice_history = h1.clone();
+        alice_history.merge(h2.clone());
+        let mut bob_history = h2;
+        bob_history.merge(h1);
+
+        let alice_patch = Patch::from_history(&alice_history, &repo).unwrap();
+        let bob_patch = Patch::from_history(&bob_history, &repo).unwrap();
+        let alice_labels = alice_patch.labels().collect::<Vec<_>>();
+        let bob_labels = bob_patch.labels().collect::<Vec<_>>();
+
+        assert_eq!(alice_labels, bob_labels);
+        assert!(!alice_labels.is_empty());
+        assert!(alice_labels.windows(2).all(|w| w[0] < w[1]));
+    }
+
     #[test]
     fn test_revision_reaction() {
         let base = git::Oid::from_str("cb18e95ada2bb38aadd8e6cef0963ce37a87add3").unwrap();
@@ -3066,6 +3831,7 @@ mod test {
                 base,
                 oid,
                 resolves: Default::default(),
+                squashes: Default::default(),
             },
             Action::Edit {
                 title: String::from("My patch"),
@@ -3180,6 +3946,163 @@ mod test {
         assert_eq!(comment.location(), Some(&location));
     }
 
+    #[test]
+    fn test_revision_review_coverage() {
+        let alice = test::setup::NodeWithRepo::default();
+        let checkout = alice.repo.checkout();
+        let branch =
+            checkout.branch_with([("README", "Hello World!"), ("CONTRIBUTING", "Be nice.")]);
+        let mut patches = Cache::no_cache(&*alice.repo).unwrap();
+        let mut patch = patches
+            .create(
+                "My first patch",
+                "Blah blah blah.",
+                MergeTarget::Delegates,
+                branch.base,
+                branch.oid,
+                &[],
+                &alice.signer,
+            )
+            .unwrap();
+
+        let (rid, _) = patch.latest();
+        let review = patch
+            .review(rid, Some(Verdict::Reject), None, vec![], &alice.signer)
+            .unwrap();
+        patch
+            .review_comment(
+                review,
+                "Missing newline",
+                Some(CodeLocation {
+                    commit: branch.oid,
+                    path: PathBuf::from("README"),
+                    old: None,
+                    new: Some(CodeRange::Lines { range: 0..1 }),
+                }),
+                None,
+                [],
+                &alice.signer,
+            )
+            .unwrap();
+
+        let (_, revision) = patch.latest();
+        let coverage = revision.review_coverage(&*alice.repo).unwrap();
+
+        assert_eq!(coverage.len(), 2);
+        assert_eq!(
+            coverage[Path::new("README")],
+            Reviewed::Commented {
+                reviewers: BTreeMap::from([(*alice.signer.public_key(), Some(Verdict::Reject))]),
+            }
+        );
+        assert_eq!(coverage[Path::new("CONTRIBUTING")], Reviewed::Unreviewed);
+
+        // The new revision only changes the README, and isn't covered by the previous review.
+        let update = checkout.branch_with([("README", "Hello World!\nHello Radicle!\n")]);
+        patch
+            .update("Fix newline", branch.base, update.oid, &alice.signer)
+            .unwrap();
+
+        let (_, revision) = patch.latest();
+        let coverage = revision.review_coverage(&*alice.repo).unwrap();
+
+        assert_eq!(
+            coverage.into_iter().collect::<Vec<_>>(),
+            vec![(PathBuf::from("README"), Reviewed::Unreviewed)]
+        );
+    }
+
+    #[test]
+    fn test_patch_merge_preview() {
+        let alice = test::setup::NodeWithRepo::default();
+        let checkout = alice.repo.checkout();
+        let target = checkout.branch_with([("README", "Hello World!")]);
+        alice.repo.set_head().unwrap();
+
+        // A revision based on the previous target head, which changes the same file.
+        let raw = alice.repo.raw();
+        let base = raw.find_commit(target.base.into()).unwrap();
+        let conflict = test::setup::commit(
+            raw,
+            &git::Qualified::from(git::lit::refs_heads(git::refname!("conflict"))),
+            [("README", "Hello Radicle!")],
+            &[&base],
+        );
+        let mut patches = Cache::no_cache(&*alice.repo).unwrap();
+        let mut patch = patches
+            .create(
+                "My first patch",
+                "Blah blah blah.",
+                MergeTarget::Delegates,
+                target.base,
+                conflict,
+                &[],
+                &alice.signer,
+            )
+            .unwrap();
+
+        let (rid, _) = patch.latest();
+        let preview = patch.merge_preview(&*alice.repo, rid).unwrap();
+        assert_eq!(
+            preview,
+            MergePreview::Conflicting {
+                files: BTreeSet::from([PathBuf::from("README")]),
+            }
+        );
+
+        // Rebasing on top of the target resolves the conflict.
+        let rebased = checkout.branch_with([("README", "Hello Radicle!")]);
+        patch
+            .update("Rebase", target.oid, rebased.oid, &alice.signer)
+            .unwrap();
+
+        let (rid, _) = patch.latest();
+        let preview = patch.merge_preview(&*alice.repo, rid).unwrap();
+        assert!(preview.is_clean());
+
+        // The target was left untouched.
+        assert_eq!(MergeTarget::Delegates.head(&*alice.repo).unwrap(), target.oid);
+    }
+
+    #[test]
+    fn test_patch_suggest_reviewers() {
+        let alice = test::setup::NodeWithRepo::default();
+        let checkout = alice.repo.checkout();
+        let branch = checkout.branch_with([("CONTRIBUTING.md", "Be nice.")]);
+        let bob = gen::<Did>(1);
+        let eve = gen::<Did>(1);
+        let config = review::Config {
+            rules: vec![
+                review::Rule {
+                    paths: vec![String::from("*.md")],
+                    labels: vec![],
+                    reviewers: vec![bob, alice.signer.public_key().into()],
+                },
+                review::Rule {
+                    paths: vec![String::from("docs/**")],
+                    labels: vec![Label::new("security").unwrap()],
+                    reviewers: vec![eve],
+                },
+            ],
+        };
+        let mut patches = Cache::no_cache(&*alice.repo).unwrap();
+        let patch = patches
+            .create(
+                "My first patch",
+                "Blah blah blah.",
+                MergeTarget::Delegates,
+                branch.base,
+                branch.oid,
+                &[],
+                &alice.signer,
+            )
+            .unwrap();
+        let patches = Patches::open(&*alice.repo).unwrap();
+
+        // Only the path rule matches, and the author isn't suggested.
+        assert_eq!(patches.suggest_reviewers(&patch, &config).unwrap(), vec![bob]);
+    }
+
     #[test]
     fn test_patch_review_remove_summary() {
         let alice = test::setup::NodeWithRepo::default();
@@ -3261,6 +4184,79 @@ mod test {
         assert_eq!(revision.description(), "I've made changes.");
     }
 
+    #[test]
+    fn test_patch_squash() {
+        let alice = test::setup::NodeWithRepo::default();
+        let checkout = alice.repo.checkout();
+        let branch = checkout.branch_with([("README", b"Hello World!")]);
+        let mut patches = Cache::no_cache(&*alice.repo).unwrap();
+        let mut patch = patches
+            .create(
+                "My first patch",
+                "Blah blah blah.",
+                MergeTarget::Delegates,
+                branch.base,
+                branch.oid,
+                &[],
+                &alice.signer,
+            )
+            .unwrap();
+
+        let first = checkout.branch_with([("README", b"Hello Radicle!")]);
+        let r1 = patch
+            .update("First change.", branch.base, first.oid, &alice.signer)
+            .unwrap();
+        let second = checkout.branch_with([("README", b"Hello Radicle!\nBye!")]);
+        let r2 = patch
+            .update("Second change.", branch.base, second.oid, &alice.signer)
+            .unwrap();
+
+        let location = CodeLocation {
+            commit: first.oid,
+            path: PathBuf::from_str("README").unwrap(),
+            old: None,
+            new: Some(CodeRange::Lines { range: 0..1 }),
+        };
+        let review = patch
+            .review(r1, Some(Verdict::Accept), None, vec![], &alice.signer)
+            .unwrap();
+        patch
+            .review_comment(
+                review,
+                "Unresolved",
+                Some(location.clone()),
+                None,
+                [],
+                &alice.signer,
+            )
+            .unwrap();
+
+        assert!(matches!(
+            patch.squash(&[], &alice.signer),
+            Err(Error::NothingToSquash)
+        ));
+        let squashed = patch.squash(&[r1, r2], &alice.signer).unwrap();
+
+        let id = patch.id;
+        let patch = patches.get(&id).unwrap().unwrap();
+        assert_eq!(patch.revisions().count(), 2);
+        assert!(patch.revision(&r1).is_none());
+        assert!(patch.revision(&r2).is_none());
+
+        let (latest, revision) = patch.latest();
+        assert_eq!(latest, squashed);
+        assert_eq!(revision.head(), second.oid);
+        assert_eq!(revision.base(), &branch.base);
+        assert_eq!(revision.description(), "Second change.");
+
+        // The verdict was on the first revision only, while the commented line is unchanged.
+        let review = revision.review(alice.signer.public_key()).unwrap();
+        let (_, comment) = review.comments().next().unwrap();
+        assert_eq!(review.verdict(), None);
+        assert_eq!(comment.location().unwrap().commit, second.oid);
+        assert!(!comment.is_resolved());
+    }
+
     #[test]
     fn test_patch_redact() {
         let alice = test::setup::Node::default();
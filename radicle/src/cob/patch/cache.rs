@@ -33,6 +33,12 @@ pub trait Patches {
     /// was not found.
     fn get(&self, id: &PatchId) -> Result<Option<Patch>, Self::Error>;
 
+    /// Get many `Patch`s at once, identified by `ids`. The result is in the
+    /// same order as `ids`, with `None` for any patch that was not found.
+    fn get_many(&self, ids: &[PatchId]) -> Result<Vec<Option<Patch>>, Self::Error> {
+        ids.iter().map(|id| self.get(id)).collect()
+    }
+
     /// Get the `Patch` and its `Revision`, identified by the revision
     /// `id`, returning `None` if it was not found.
     fn find_by_revision(&self, id: &RevisionId) -> Result<Option<ByRevision>, Self::Error>;
@@ -440,6 +446,10 @@ where
         query::get(&self.cache.db, &self.rid(), id)
     }
 
+    fn get_many(&self, ids: &[PatchId]) -> Result<Vec<Option<Patch>>, Self::Error> {
+        query::get_many(&self.cache.db, &self.rid(), ids)
+    }
+
     fn find_by_revision(&self, id: &RevisionId) -> Result<Option<ByRevision>, Error> {
         query::find_by_revision(&self.cache.db, &self.rid(), id)
     }
@@ -528,6 +538,10 @@ where
         query::get(&self.cache.db, &self.rid(), id)
     }
 
+    fn get_many(&self, ids: &[PatchId]) -> Result<Vec<Option<Patch>>, Self::Error> {
+        query::get_many(&self.cache.db, &self.rid(), ids)
+    }
+
     fn find_by_revision(&self, id: &RevisionId) -> Result<Option<ByRevision>, Error> {
         query::find_by_revision(&self.cache.db, &self.rid(), id)
     }
@@ -578,6 +592,34 @@ mod query {
         }
     }
 
+    pub(super) fn get_many(
+        db: &sql::ConnectionThreadSafe,
+        rid: &RepoId,
+        ids: &[PatchId],
+    ) -> Result<Vec<Option<Patch>>, Error> {
+        let json = serde_json::to_string(
+            &ids.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+        )?;
+        let mut stmt = db.prepare(
+            "SELECT id, patch
+             FROM patches
+             WHERE repo = ?1 AND id IN (SELECT value FROM json_each(?2))",
+        )?;
+
+        stmt.bind((1, rid))?;
+        stmt.bind((2, sql::Value::String(json)))?;
+
+        let mut found = std::collections::HashMap::with_capacity(ids.len());
+        for row in stmt.into_iter() {
+            let row = row?;
+            let id = PatchId::from_str(row.read::<&str, _>("id"))?;
+            let patch = serde_json::from_str::<Patch>(row.read::<&str, _>("patch"))?;
+
+            found.insert(id, patch);
+        }
+        Ok(ids.iter().map(|id| found.get(id).cloned()).collect())
+    }
+
     pub(super) fn find_by_revision(
         db: &sql::ConnectionThreadSafe,
         rid: &RepoId,
@@ -825,6 +867,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_many() {
+        let repo = arbitrary::gen::<MockRepository>(1);
+        let mut cache = memory(repo);
+        let ids = (0..arbitrary::gen::<u8>(1).max(3))
+            .map(|_| PatchId::from(arbitrary::oid()))
+            .collect::<BTreeSet<PatchId>>();
+
+        for id in ids.iter() {
+            let patch = Patch::new(id.to_string(), MergeTarget::Delegates, revision());
+            cache.update(&cache.rid(), id, &patch).unwrap();
+        }
+
+        // Query in reverse order, with a missing object in the middle.
+        let mut query = ids.iter().rev().copied().collect::<Vec<_>>();
+        query.insert(query.len() / 2, PatchId::from(arbitrary::oid()));
+
+        let many = cache.get_many(&query).unwrap();
+        let individual = query
+            .iter()
+            .map(|id| cache.get(id).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(many.len(), query.len());
+        assert_eq!(many, individual);
+        assert_eq!(many.iter().filter(|patch| patch.is_none()).count(), 1);
+    }
+
     #[test]
     fn test_get() {
         let repo = arbitrary::gen::<MockRepository>(1);
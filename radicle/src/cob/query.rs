@@ -0,0 +1,350 @@
+//! Typed queries over collaborative objects.
+//!
+//! A [`Query`] is a conjunction of [`Filter`]s, which can be evaluated over any
+//! [`Queryable`] COB, eg. issues and patches loaded from the COB cache.
+//!
+//! ```
+//! # use radicle::cob::query::Query;
+//! # use radicle::cob::issue;
+//! let query = Query::<issue::State>::new()
+//!     .state(issue::State::Open)
+//!     .text("crash");
+//!
+//! assert_eq!(query.filters().len(), 2);
+//! ```
+use crate::cob::common::{Label, Timestamp};
+use crate::cob::{issue, patch};
+use crate::prelude::Did;
+
+/// A COB that can be matched by a [`Query`].
+pub trait Queryable {
+    /// A simplified state of the COB, used for filtering.
+    type Status: PartialEq;
+
+    /// The COB's state.
+    fn status(&self) -> Self::Status;
+    /// The COB's author.
+    fn author(&self) -> Did;
+    /// Whether the COB has the given label.
+    fn has_label(&self, label: &Label) -> bool;
+    /// Whether the COB is assigned to the given DID.
+    fn has_assignee(&self, did: &Did) -> bool;
+    /// When the COB was created.
+    fn timestamp(&self) -> Timestamp;
+    /// The COB's title.
+    fn title(&self) -> &str;
+    /// The COB's description.
+    fn description(&self) -> &str;
+}
+
+impl Queryable for issue::Issue {
+    type Status = issue::State;
+
+    fn status(&self) -> Self::Status {
+        *self.state()
+    }
+
+    fn author(&self) -> Did {
+        self.author().id
+    }
+
+    fn has_label(&self, label: &Label) -> bool {
+        self.labels().any(|l| l == label)
+    }
+
+    fn has_assignee(&self, did: &Did) -> bool {
+        self.assignees().any(|a| a == did)
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp()
+    }
+
+    fn title(&self) -> &str {
+        self.title()
+    }
+
+    fn description(&self) -> &str {
+        self.description()
+    }
+}
+
+impl Queryable for patch::Patch {
+    type Status = patch::Status;
+
+    fn status(&self) -> Self::Status {
+        patch::Status::from(self.state())
+    }
+
+    fn author(&self) -> Did {
+        self.author().id
+    }
+
+    fn has_label(&self, label: &Label) -> bool {
+        self.labels().any(|l| l == label)
+    }
+
+    fn has_assignee(&self, did: &Did) -> bool {
+        self.assignees().any(|a| &a == did)
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp()
+    }
+
+    fn title(&self) -> &str {
+        self.title()
+    }
+
+    fn description(&self) -> &str {
+        self.description()
+    }
+}
+
+/// A single condition of a [`Query`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter<S> {
+    /// The COB is in any of the given states.
+    State(Vec<S>),
+    /// The COB was created by the given author.
+    Author(Did),
+    /// The COB has the given label.
+    Label(Label),
+    /// The COB is assigned to the given DID.
+    Assignee(Did),
+    /// The COB was created at or after the given time.
+    Since(Timestamp),
+    /// The COB was created at or before the given time.
+    Until(Timestamp),
+    /// The COB's title or description contains the given text, ignoring case.
+    /// The text is stored in lowercase.
+    Text(String),
+}
+
+impl<S: PartialEq> Filter<S> {
+    /// Check whether the filter matches the given COB.
+    pub fn matches<T>(&self, cob: &T) -> bool
+    where
+        T: Queryable<Status = S>,
+    {
+        match self {
+            Self::State(states) => states.contains(&cob.status()),
+            Self::Author(did) => &cob.author() == did,
+            Self::Label(label) => cob.has_label(label),
+            Self::Assignee(did) => cob.has_assignee(did),
+            Self::Since(time) => &cob.timestamp() >= time,
+            Self::Until(time) => &cob.timestamp() <= time,
+            Self::Text(text) => {
+                cob.title().to_lowercase().contains(text)
+                    || cob.description().to_lowercase().contains(text)
+            }
+        }
+    }
+}
+
+/// A query over COBs with status type `S`. A COB matches the query if it
+/// matches all of its filters, so an empty query matches everything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query<S> {
+    filters: Vec<Filter<S>>,
+}
+
+impl<S> Default for Query<S> {
+    fn default() -> Self {
+        Self {
+            filters: Vec::new(),
+        }
+    }
+}
+
+impl<S: PartialEq> Query<S> {
+    /// Create an empty query.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a filter to the query.
+    pub fn filter(mut self, filter: Filter<S>) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Only match COBs in the given state.
+    pub fn state(self, state: S) -> Self {
+        self.filter(Filter::State(vec![state]))
+    }
+
+    /// Only match COBs in any of the given states.
+    pub fn states(self, states: impl IntoIterator<Item = S>) -> Self {
+        self.filter(Filter::State(states.into_iter().collect()))
+    }
+
+    /// Only match COBs created by the given author.
+    pub fn author(self, did: Did) -> Self {
+        self.filter(Filter::Author(did))
+    }
+
+    /// Only match COBs with the given label.
+    pub fn label(self, label: Label) -> Self {
+        self.filter(Filter::Label(label))
+    }
+
+    /// Only match COBs assigned to the given DID.
+    pub fn assignee(self, did: Did) -> Self {
+        self.filter(Filter::Assignee(did))
+    }
+
+    /// Only match COBs created at or after the given time.
+    pub fn since(self, time: Timestamp) -> Self {
+        self.filter(Filter::Since(time))
+    }
+
+    /// Only match COBs created at or before the given time.
+    pub fn until(self, time: Timestamp) -> Self {
+        self.filter(Filter::Until(time))
+    }
+
+    /// Only match COBs whose title or description contains the given text, ignoring case.
+    pub fn text(self, text: impl AsRef<str>) -> Self {
+        self.filter(Filter::Text(text.as_ref().to_lowercase()))
+    }
+
+    /// The filters of this query.
+    pub fn filters(&self) -> &[Filter<S>] {
+        &self.filters
+    }
+
+    /// Check whether the given COB matches all filters of the query.
+    pub fn matches<T>(&self, cob: &T) -> bool
+    where
+        T: Queryable<Status = S>,
+    {
+        self.filters.iter().all(|f| f.matches(cob))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::cob::issue::cache::Issues as _;
+    use crate::cob::issue::{Cache, CloseReason, IssueId, State};
+    use crate::cob::ActorId;
+    use crate::crypto::Signer as _;
+    use crate::test;
+    use crate::test::arbitrary;
+
+    #[test]
+    fn test_query() {
+        let test::setup::NodeWithRepo { node, repo, .. } = test::setup::NodeWithRepo::default();
+        let mut issues = Cache::no_cache(&*repo).unwrap();
+        let author = Did::from(node.signer.public_key());
+        let bob = Did::from(arbitrary::gen::<ActorId>(1));
+        let bug = Label::new("bug").unwrap();
+        let ux = Label::new("ux").unwrap();
+
+        let crash = issues
+            .create(
+                "Crash on startup",
+                "The app crashes.",
+                &[bug.clone()],
+                &[bob],
+                [],
+                &node.signer,
+            )
+            .unwrap()
+            .id;
+        let button = issues
+            .create(
+                "Button is too small",
+                "Hard to click, almost a CRASH of the UX.",
+                &[ux.clone()],
+                &[],
+                [],
+                &node.signer,
+            )
+            .unwrap()
+            .id;
+        let typo = {
+            let mut issue = issues
+                .create(
+                    "Typo in README",
+                    "Teh",
+                    &[bug.clone()],
+                    &[],
+                    [],
+                    &node.signer,
+                )
+                .unwrap();
+            issue
+                .lifecycle(
+                    State::Closed {
+                        reason: CloseReason::Solved,
+                    },
+                    &node.signer,
+                )
+                .unwrap();
+            issue.id
+        };
+
+        let all = issues
+            .list()
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect::<Vec<_>>();
+        let query = |q: Query<State>| {
+            let mut matched = all
+                .iter()
+                .filter(|(_, i)| q.matches(i))
+                .map(|(id, _)| *id)
+                .collect::<Vec<IssueId>>();
+            matched.sort();
+            matched
+        };
+        let sorted = |mut ids: Vec<IssueId>| {
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(query(Query::new()), sorted(vec![crash, button, typo]));
+        assert_eq!(
+            query(Query::new().state(State::Open)),
+            sorted(vec![crash, button])
+        );
+        assert_eq!(
+            query(Query::new().states([
+                State::Closed {
+                    reason: CloseReason::Other
+                },
+                State::Closed {
+                    reason: CloseReason::Solved
+                }
+            ])),
+            vec![typo]
+        );
+        assert_eq!(
+            query(Query::new().label(bug.clone())),
+            sorted(vec![crash, typo])
+        );
+        assert_eq!(
+            query(Query::new().label(bug).state(State::Open)),
+            vec![crash]
+        );
+        assert_eq!(query(Query::new().assignee(bob)), vec![crash]);
+        assert_eq!(query(Query::new().author(bob)), vec![]);
+        assert_eq!(
+            query(Query::new().author(author)),
+            sorted(vec![crash, button, typo])
+        );
+        assert_eq!(
+            query(Query::new().text("crash")),
+            sorted(vec![crash, button])
+        );
+        assert_eq!(query(Query::new().text("crash").label(ux)), vec![button]);
+        assert_eq!(query(Query::new().until(Timestamp::from_secs(0))), vec![]);
+        assert_eq!(
+            query(Query::new().since(Timestamp::from_secs(0))),
+            sorted(vec![crash, button, typo])
+        );
+    }
+}
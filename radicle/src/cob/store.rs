@@ -3,6 +3,7 @@
 #![allow(clippy::type_complexity)]
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::ops::ControlFlow;
 
 use nonempty::NonEmpty;
 use radicle_cob::CollaborativeObject;
@@ -98,6 +99,12 @@ pub enum Error {
         #[source]
         err: git::raw::Error,
     },
+    #[error("failed to roll back update of object `{id}`: {err}")]
+    Rollback {
+        id: ObjectId,
+        #[source]
+        err: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
 }
 
 /// Storage for collaborative objects of a specific type `T` in a single repository.
@@ -154,6 +161,8 @@ where
         let actions = actions.into();
         let related = actions.iter().flat_map(T::Action::parents).collect();
         let changes = actions.try_map(encoding::encode)?;
+        // Our head before the update, which is restored if the update can't be completed.
+        let previous = self.head(&object_id, signer)?;
         let updated = cob::update(
             self.repo,
             signer,
@@ -168,8 +177,10 @@ where
                 changes,
             },
         )?;
-        self.repo.sign_refs(signer).map_err(Error::SignRefs)?;
-
+        if let Err(err) = self.repo.sign_refs(signer) {
+            self.rollback(&object_id, previous, signer)?;
+            return Err(Error::SignRefs(err));
+        }
         Ok(updated)
     }
 
@@ -198,8 +209,10 @@ where
                 contents,
             },
         )?;
-        self.repo.sign_refs(signer).map_err(Error::SignRefs)?;
-
+        if let Err(err) = self.repo.sign_refs(signer) {
+            self.rollback(cob.id(), None, signer)?;
+            return Err(Error::SignRefs(err));
+        }
         Ok((*cob.id(), cob.object))
     }
 
@@ -222,6 +235,45 @@ where
             }),
         }
     }
+
+    /// Get the signer's current head for an object, if any.
+    fn head<G: Signer>(&self, id: &ObjectId, signer: &G) -> Result<Option<EntryId>, Error> {
+        let name = git::refs::storage::cob(signer.public_key(), T::type_name(), id);
+        match self
+            .repo
+            .reference_oid(signer.public_key(), &name.strip_namespace())
+        {
+            Ok(oid) => Ok(Some(oid)),
+            Err(err) if err.code() == git::raw::ErrorCode::NotFound => Ok(None),
+            Err(err) => Err(Error::RefLookup {
+                name: name.to_ref_string(),
+                err,
+            }),
+        }
+    }
+
+    /// Restore the signer's reference to an object to its previous head, or remove it if there
+    /// was none. Used to undo an update that could not be completed.
+    fn rollback<G: Signer>(
+        &self,
+        id: &ObjectId,
+        previous: Option<EntryId>,
+        signer: &G,
+    ) -> Result<(), Error> {
+        let remote = signer.public_key();
+
+        match previous {
+            Some(head) => {
+                cob::object::Storage::update(self.repo, remote, T::type_name(), id, &head).map_err(
+                    |err| Error::Rollback {
+                        id: *id,
+                        err: Box::new(err),
+                    },
+                )
+            }
+            None => cob::remove(self.repo, remote, T::type_name(), id).map_err(Error::from),
+        }
+    }
 }
 
 impl<'a, T, R> Store<'a, T, R>
@@ -341,6 +393,29 @@ where
 
         Ok((object, head))
     }
+
+    /// Commit transaction and apply it to the given in-memory object.
+    ///
+    /// The object is only modified once the transaction is committed. If the commit fails,
+    /// the object is left untouched and storage is rolled back, so that both keep reflecting
+    /// the state prior to the transaction.
+    pub fn apply<G: Signer>(
+        self,
+        msg: &str,
+        id: ObjectId,
+        store: &mut Store<T, R>,
+        signer: &G,
+        object: &mut T,
+    ) -> Result<EntryId, Error>
+    where
+        R: ReadRepository + SignRepository + cob::Store,
+        T::Action: Serialize + Clone,
+    {
+        let (updated, head) = self.commit(msg, id, store, signer)?;
+        *object = updated;
+
+        Ok(head)
+    }
 }
 
 /// Get an object's operations without decoding them.
@@ -358,6 +433,26 @@ pub fn ops<R: cob::Store>(
     }
 }
 
+/// Get an object's operations without decoding them, in topological order, ie. parents
+/// before children.
+pub fn log<R: cob::Store>(
+    id: &ObjectId,
+    type_name: &TypeName,
+    repo: &R,
+) -> Result<Vec<Op<Vec<u8>>>, Error> {
+    let Some(cob) = cob::get::<NonEmpty<cob::Entry>, _>(repo, type_name, id)? else {
+        return Err(Error::NotFound(type_name.clone(), *id));
+    };
+    let root = *cob.history.root().id();
+
+    Ok(cob
+        .history
+        .traverse(Vec::new(), &[root], |mut ops, _, entry| {
+            ops.push(Op::from(entry.clone()));
+            ControlFlow::Continue(ops)
+        }))
+}
+
 pub mod encoding {
     use serde::Serialize;
 
@@ -375,6 +470,64 @@ pub mod encoding {
     }
 }
 
+/// Deterministic mode, for reproducible entry and object ids.
+///
+/// **Test-only.** Entry ids depend on the commit time and on the signer, so tests that
+/// assert specific ids are brittle. In this mode, the commit time of all changes created
+/// by [`run`] is fixed to [`TIMESTAMP`], and the [`signer`] is derived from `RAD_SEED`,
+/// or [`SEED`] if it isn't set. This only has an effect in debug builds.
+#[cfg(any(test, feature = "test"))]
+pub mod deterministic {
+    use crate::crypto::test::signer::MockSigner;
+
+    /// Commit time of changes created in deterministic mode.
+    pub const TIMESTAMP: i64 = 1671125284;
+    /// Signer seed used when `RAD_SEED` isn't set.
+    pub const SEED: [u8; 32] = [0xff; 32];
+
+    /// Get the deterministic signer.
+    pub fn signer() -> MockSigner {
+        MockSigner::from_seed(crate::env::seed().unwrap_or(SEED))
+    }
+
+    /// Run `f` in deterministic mode, on the current thread.
+    pub fn run<T>(f: impl FnOnce() -> T) -> T {
+        radicle_cob::git::with_commit_time(TIMESTAMP, f)
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use crate::cob::issue::Cache;
+        use crate::test::setup::Node;
+
+        #[test]
+        fn test_deterministic_ids() {
+            let create = || {
+                let node = Node::new(tempfile::tempdir().unwrap(), signer(), "alice");
+                let repo = node.project();
+                let mut issues = Cache::no_cache(&*repo).unwrap();
+
+                run(|| {
+                    let mut issue = issues
+                        .create("Title", "Description", &[], &[], [], &node.signer)
+                        .unwrap();
+                    let id = *issue.id();
+                    let comment = issue.comment("Hello", id, [], &node.signer).unwrap();
+
+                    (repo.id, id, comment)
+                })
+            };
+            let (rid1, id1, comment1) = create();
+            let (rid2, id2, comment2) = create();
+
+            assert_eq!(rid1, rid2);
+            assert_eq!(id1, id2);
+            assert_eq!(comment1, comment2);
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod test {
     use super::*;
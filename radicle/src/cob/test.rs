@@ -231,6 +231,7 @@ impl<G: Signer> Actor<G> {
                     base,
                     oid,
                     resolves: Default::default(),
+                    squashes: Default::default(),
                 },
                 patch::Action::Edit {
                     title: title.to_string(),
@@ -11,7 +11,7 @@ use crate::cob::common::{Reaction, Timestamp, Uri};
 use crate::cob::store::Cob;
 use crate::cob::{op, ActorId, Embed, EntryId, Op};
 use crate::git;
-use crate::prelude::ReadRepository;
+use crate::prelude::{Did, ReadRepository};
 
 /// Type name of a thread, as well as the domain for all thread operations.
 /// Note that threads are not usually used standalone. They are embeded into other COBs.
@@ -52,6 +52,9 @@ pub type CommentId = EntryId;
 /// Reactions to a comment or other action.
 pub type Reactions = BTreeSet<(ActorId, Reaction)>;
 
+/// Reactions to a comment, grouped by reaction. The number of authors is the reaction count.
+pub type ReactionTally = BTreeMap<Reaction, Vec<Did>>;
+
 /// A comment edit is just some text and an edit time.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -184,8 +187,13 @@ impl<L> Comment<L> {
     }
 
     /// Return the ordered list of edits for this comment, including the original version.
-    pub fn edits(&self) -> impl Iterator<Item = &Edit> {
-        self.edits.iter()
+    pub fn edits(&self) -> &[Edit] {
+        &self.edits
+    }
+
+    /// Whether the comment was edited after it was created.
+    pub fn is_edited(&self) -> bool {
+        self.edits.len() > 1
     }
 
     /// Add an edit.
@@ -209,6 +217,26 @@ impl<L> Comment<L> {
             })
     }
 
+    /// Comment reactions, grouped by reaction, with owned author DIDs.
+    pub fn tally(&self) -> ReactionTally {
+        self.reactions
+            .iter()
+            .fold(BTreeMap::new(), |mut acc, (author, reaction)| {
+                acc.entry(*reaction).or_default().push(Did::from(*author));
+                acc
+            })
+    }
+
+    /// Number of reactions of each kind.
+    pub fn reaction_counts(&self) -> BTreeMap<Reaction, usize> {
+        self.reactions
+            .iter()
+            .fold(BTreeMap::new(), |mut acc, (_, reaction)| {
+                *acc.entry(*reaction).or_default() += 1;
+                acc
+            })
+    }
+
     /// Get comment location, if any.
     pub fn location(&self) -> Option<&L> {
         self.location.as_ref()
@@ -384,6 +412,18 @@ impl Thread {
 }
 
 impl<L> Thread<Comment<L>> {
+    /// Reactions to every comment of the thread, grouped by reaction.
+    ///
+    /// Reactions are kept as a set of `(author, reaction)` pairs per comment, which `react`
+    /// actions add to or remove from. Since actions are applied in the same causal order on
+    /// all replicas, concurrent reactions converge, and a reaction that is retracted and then
+    /// re-added by the same author is counted once.
+    pub fn reactions(&self) -> BTreeMap<CommentId, ReactionTally> {
+        self.comments()
+            .map(|(id, comment)| (*id, comment.tally()))
+            .collect()
+    }
+
     pub fn replies<'a>(
         &'a self,
         to: &'a CommentId,
@@ -729,7 +769,7 @@ mod tests {
         let t1 = Thread::from_ops([c0.clone(), c1, c2], &repo).unwrap();
 
         let comment = t1.comment(&c0.id());
-        let edits = comment.unwrap().edits().collect::<Vec<_>>();
+        let edits = comment.unwrap().edits();
 
         assert_eq!(edits[0].body.as_str(), "Hello world!");
         assert_eq!(edits[1].body.as_str(), "Goodbye world.");
@@ -805,6 +845,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_reactions_concurrent() {
+        let repo = gen::<MockRepository>(1);
+        let alice = MockSigner::default();
+        let bob = MockSigner::default();
+        let time = Timestamp::now();
+        let party = Reaction::new('🎉').unwrap();
+        let rocket = Reaction::new('🚀').unwrap();
+        let react = |to, reaction, active| Action::React {
+            to,
+            reaction,
+            active,
+        };
+
+        let mut a = test::history::<Thread, _>(
+            &[Action::Comment {
+                body: "Thread root".to_owned(),
+                reply_to: None,
+            }],
+            time,
+            &alice,
+        );
+        let root = *a.root().id();
+        let mut b = a.clone();
+
+        // Alice toggles a reaction off and on again, and adds another one.
+        a.commit(&react(root, party, true), &alice);
+        a.commit(&react(root, party, false), &alice);
+        a.commit(&react(root, party, true), &alice);
+        a.commit(&react(root, rocket, true), &alice);
+
+        // Concurrently, Bob reacts twice and retracts one of his reactions.
+        b.commit(&react(root, party, true), &bob);
+        b.commit(&react(root, rocket, true), &bob);
+        b.commit(&react(root, rocket, false), &bob);
+
+        let mut ab = a.clone();
+        ab.merge(b.clone());
+        let mut ba = b;
+        ba.merge(a);
+
+        let t1 = Thread::from_history(&ab, &repo).unwrap();
+        let t2 = Thread::from_history(&ba, &repo).unwrap();
+        let reactions = t1.reactions();
+
+        assert_eq!(reactions, t2.reactions());
+
+        let tally = reactions.get(&root).unwrap();
+        let mut partiers = vec![Did::from(*alice.public_key()), Did::from(*bob.public_key())];
+        partiers.sort();
+
+        assert_eq!(tally.get(&party), Some(&partiers));
+        assert_eq!(
+            tally.get(&rocket),
+            Some(&vec![Did::from(*alice.public_key())])
+        );
+
+        let counts = t1.comment(&root).unwrap().reaction_counts();
+        assert_eq!(counts.get(&party), Some(&2));
+        assert_eq!(counts.get(&rocket), Some(&1));
+    }
+
     #[test]
     fn test_duplicate_comments() {
         let repo = gen::<MockRepository>(1);
@@ -1,4 +1,5 @@
 use std::io;
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::process::Command;
 use std::str::FromStr;
@@ -552,16 +553,25 @@ pub fn configure_remote<'r>(
 
 /// Fetch from the given `remote`.
 pub fn fetch(repo: &git2::Repository, remote: &str) -> Result<(), git2::Error> {
-    repo.find_remote(remote)?.fetch::<&str>(
-        &[],
-        Some(
-            git2::FetchOptions::new()
-                .update_fetchhead(false)
-                .prune(git2::FetchPrune::On)
-                .download_tags(git2::AutotagOption::None),
-        ),
-        None,
-    )
+    fetch_shallow(repo, remote, None)
+}
+
+/// Fetch from the given `remote`, limiting the history fetched to `depth` commits from the
+/// tip of each reference, if a depth is given.
+pub fn fetch_shallow(
+    repo: &git2::Repository,
+    remote: &str,
+    depth: Option<NonZeroUsize>,
+) -> Result<(), git2::Error> {
+    let mut opts = git2::FetchOptions::new();
+    opts.update_fetchhead(false)
+        .prune(git2::FetchPrune::On)
+        .download_tags(git2::AutotagOption::None);
+
+    if let Some(depth) = depth {
+        opts.depth(i32::try_from(depth.get()).unwrap_or(i32::MAX));
+    }
+    repo.find_remote(remote)?.fetch::<&str>(&[], Some(&mut opts), None)
 }
 
 /// Push `refspecs` to the given `remote` using the provided `namespace`.
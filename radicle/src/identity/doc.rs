@@ -16,6 +16,7 @@ use thiserror::Error;
 
 use crate::canonical::formatter::CanonicalFormatter;
 use crate::cob::identity;
+use crate::cob::Timestamp;
 use crate::crypto;
 use crate::crypto::{Signature, Unverified, Verified};
 use crate::git;
@@ -222,6 +223,12 @@ pub struct Doc<V> {
     /// Repository visibility.
     #[serde(default, skip_serializing_if = "Visibility::is_public")]
     pub visibility: Visibility,
+    /// Revoked delegate keys, and when they were revoked.
+    ///
+    /// Revoked keys are no longer delegates, but content they signed before
+    /// their revocation can still be verified, see [`Doc::verify_signature_at`].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub revoked: BTreeMap<Did, Timestamp>,
 
     #[serde(skip)]
     verified: PhantomData<V>,
@@ -254,6 +261,29 @@ impl<V> Doc<V> {
         Ok(())
     }
 
+    /// Validate signature of content signed at the given time, against a given blob.
+    ///
+    /// Unlike [`Doc::verify_signature`], this also accepts signatures from revoked
+    /// delegates, as long as the content was signed before the key was revoked.
+    pub fn verify_signature_at(
+        &self,
+        key: &PublicKey,
+        signature: &Signature,
+        blob: Oid,
+        signed_at: Timestamp,
+    ) -> Result<(), PublicKey> {
+        match self.revoked_at(key) {
+            Some(revoked) if signed_at < revoked => {}
+            Some(_) => return Err(*key),
+            None if self.is_delegate(key) => {}
+            None => return Err(*key),
+        }
+        if key.verify(blob.as_bytes(), signature).is_err() {
+            return Err(*key);
+        }
+        Ok(())
+    }
+
     pub fn is_majority(&self, votes: usize) -> bool {
         votes >= self.majority()
     }
@@ -270,6 +300,16 @@ impl<V> Doc<V> {
     pub fn is_delegate(&self, key: &crypto::PublicKey) -> bool {
         self.delegates.contains(&key.into())
     }
+
+    /// Get the time at which the given key was revoked, if it was.
+    pub fn revoked_at(&self, key: &crypto::PublicKey) -> Option<Timestamp> {
+        self.revoked.get(&Did::from(key)).copied()
+    }
+
+    /// Check whether the given key was revoked.
+    pub fn is_revoked(&self, key: &crypto::PublicKey) -> bool {
+        self.revoked_at(key).is_some()
+    }
 }
 
 impl Doc<Verified> {
@@ -285,8 +325,12 @@ impl Doc<Verified> {
     }
 
     /// Attempt to add a new delegate to the document. Returns `true` if it wasn't there before.
+    /// Revoked keys cannot be added back.
     pub fn delegate(&mut self, key: &crypto::PublicKey) -> bool {
         let delegate = Did::from(key);
+        if self.is_revoked(key) {
+            return false;
+        }
         if self.delegates.iter().all(|id| id != &delegate) {
             self.delegates.push(delegate);
             return true;
@@ -312,6 +356,23 @@ impl Doc<Verified> {
         }
     }
 
+    /// Revoke a delegate, eg. because its key was compromised.
+    ///
+    /// The key is removed from the delegates, and can't be added back. Content signed
+    /// by the key before the revocation time remains valid, see [`Doc::verify_signature_at`].
+    pub fn revoke(
+        &mut self,
+        key: &crypto::PublicKey,
+        at: Timestamp,
+    ) -> Result<Option<Did>, DocError> {
+        let Some(delegate) = self.rescind(key)? else {
+            return Ok(None);
+        };
+        self.revoked.insert(delegate, at);
+
+        Ok(Some(delegate))
+    }
+
     /// Get the project payload, if it exists and is valid, out of this document.
     pub fn project(&self) -> Result<Project, PayloadError> {
         let value = self
@@ -397,6 +458,7 @@ impl Doc<Unverified> {
             delegates,
             threshold,
             visibility,
+            revoked: BTreeMap::new(),
             verified: PhantomData,
         }
     }
@@ -424,12 +486,16 @@ impl Doc<Unverified> {
                 "threshold cannot be zero",
             ));
         }
+        if self.delegates.iter().any(|d| self.revoked.contains_key(d)) {
+            return Err(DocError::Delegates("revoked keys cannot be delegates"));
+        }
 
         Ok(Doc {
             payload: self.payload,
             delegates: self.delegates,
             threshold: self.threshold,
             visibility: self.visibility,
+            revoked: self.revoked,
             verified: PhantomData,
         })
     }
@@ -528,6 +594,67 @@ mod test {
         assert_eq!(Doc::from_json(&bytes).unwrap().verified().unwrap(), doc);
     }
 
+    #[test]
+    fn test_revoke_delegate() {
+        let alice = MockSigner::from_seed([0xa; 32]);
+        let eve = MockSigner::from_seed([0xe; 32]);
+        let delegates = NonEmpty::from_vec(vec![
+            Did::from(alice.public_key()),
+            Did::from(eve.public_key()),
+        ])
+        .unwrap();
+        let mut doc = Doc::new(arbitrary::gen::<Project>(1), delegates, 1, Visibility::Public)
+            .verified()
+            .unwrap();
+        let revoked = Timestamp::from_secs(1_700_000_000);
+        let before = Timestamp::from_secs(1_600_000_000);
+        let after = Timestamp::from_secs(1_800_000_000);
+        let blob = arbitrary::oid();
+        let signature = eve.sign(blob.as_bytes());
+
+        assert!(doc.verify_signature(eve.public_key(), &signature, blob).is_ok());
+        assert_eq!(
+            doc.revoke(eve.public_key(), revoked).unwrap(),
+            Some(Did::from(eve.public_key()))
+        );
+        assert!(!doc.is_delegate(eve.public_key()));
+        assert!(doc.is_revoked(eve.public_key()));
+        assert_eq!(doc.revoked_at(eve.public_key()), Some(revoked));
+
+        // Content signed before the revocation is still valid.
+        assert!(doc
+            .verify_signature_at(eve.public_key(), &signature, blob, before)
+            .is_ok());
+        // Content signed after the revocation isn't.
+        assert_eq!(
+            doc.verify_signature_at(eve.public_key(), &signature, blob, after),
+            Err(*eve.public_key())
+        );
+        assert_eq!(
+            doc.verify_signature_at(eve.public_key(), &signature, blob, revoked),
+            Err(*eve.public_key())
+        );
+        assert_eq!(
+            doc.verify_signature(eve.public_key(), &signature, blob),
+            Err(*eve.public_key())
+        );
+        // Other delegates are unaffected.
+        let signature = alice.sign(blob.as_bytes());
+        assert!(doc
+            .verify_signature_at(alice.public_key(), &signature, blob, after)
+            .is_ok());
+
+        // Revoked keys can't be added back, and the revocation survives encoding.
+        assert!(!doc.delegate(eve.public_key()));
+        let (_, bytes) = doc.encode().unwrap();
+        assert_eq!(Doc::from_json(&bytes).unwrap().verified().unwrap(), doc);
+        // Revoking an unknown key does nothing.
+        assert_eq!(doc.revoke(eve.public_key(), after).unwrap(), None);
+        assert_eq!(doc.revoked_at(eve.public_key()), Some(revoked));
+        // The last delegate can't be revoked.
+        assert!(doc.revoke(alice.public_key(), after).is_err());
+    }
+
     #[test]
     fn test_visibility_json() {
         use std::str::FromStr;
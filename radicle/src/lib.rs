@@ -22,6 +22,7 @@ pub mod logger;
 pub mod node;
 pub mod profile;
 pub mod rad;
+pub mod review;
 pub mod serde_ext;
 pub mod sql;
 pub mod storage;
@@ -9,6 +9,7 @@ pub mod events;
 pub mod notifications;
 pub mod policy;
 pub mod refs;
+pub mod retry;
 pub mod routing;
 pub mod seed;
 pub mod timestamp;
@@ -28,6 +29,7 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json as json;
 
+use crate::cob;
 use crate::crypto::PublicKey;
 use crate::git;
 use crate::identity::RepoId;
@@ -123,6 +125,17 @@ impl State {
     pub fn is_connected(&self) -> bool {
         matches!(self, Self::Connected { .. })
     }
+
+    /// Average round-trip time of a connected peer, if any latencies were measured.
+    pub fn rtt(&self) -> Option<LocalDuration> {
+        match self {
+            Self::Connected { latencies, .. } if !latencies.is_empty() => {
+                let total = latencies.iter().map(|l| l.as_millis()).sum::<u128>();
+                Some(LocalDuration::from_millis(total / latencies.len() as u128))
+            }
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for State {
@@ -434,6 +447,15 @@ pub enum Command {
     #[serde(rename_all = "camelCase")]
     AnnounceRefs { rid: RepoId },
 
+    /// Announce repository references for a single collaborative object to peers.
+    #[serde(rename_all = "camelCase")]
+    AnnounceCobRefs {
+        rid: RepoId,
+        type_name: cob::TypeName,
+        #[serde(with = "crate::serde_ext::string")]
+        id: cob::ObjectId,
+    },
+
     /// Announce local repositories to peers.
     #[serde(rename_all = "camelCase")]
     AnnounceInventory,
@@ -489,9 +511,12 @@ pub enum Command {
     #[serde(rename_all = "camelCase")]
     Unfollow { nid: NodeId },
 
-    /// Get the node's status.
+    /// Get the node's status. Returns a [`Status`].
     Status,
 
+    /// Get the node's metrics, in the Prometheus text format.
+    Metrics,
+
     /// Get the node's NID.
     NodeId,
 
@@ -520,6 +545,64 @@ pub enum Link {
     Inbound,
 }
 
+/// Key identifying a peer connection.
+///
+/// Inbound connections are only known by their socket address until the handshake completes,
+/// at which point we learn the remote's Node ID. Outbound connections are known from the start,
+/// since we dial a specific node.
+///
+/// Note that a [`PeerKey::Pending`] key is *not* unique: multiple peers may share the same socket
+/// address, eg. when connecting through a NAT or a SOCKS5 proxy. It should therefore only be
+/// used to identify a connection in combination with its underlying resource.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PeerKey {
+    /// Peer whose identity is not yet known, ie. the handshake is not complete.
+    Pending(net::SocketAddr),
+    /// Peer whose identity is known.
+    Known(NodeId),
+}
+
+impl PeerKey {
+    /// Get the Node ID, if known.
+    pub fn nid(&self) -> Option<&NodeId> {
+        match self {
+            Self::Known(nid) => Some(nid),
+            Self::Pending(_) => None,
+        }
+    }
+
+    /// Check whether the peer identity is known.
+    pub fn is_known(&self) -> bool {
+        matches!(self, Self::Known(_))
+    }
+
+    /// Check whether the peer identity is still pending.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Self::Pending(_))
+    }
+}
+
+impl From<NodeId> for PeerKey {
+    fn from(nid: NodeId) -> Self {
+        Self::Known(nid)
+    }
+}
+
+impl From<net::SocketAddr> for PeerKey {
+    fn from(addr: net::SocketAddr) -> Self {
+        Self::Pending(addr)
+    }
+}
+
+impl fmt::Display for PeerKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pending(addr) => write!(f, "{addr}"),
+            Self::Known(nid) => write!(f, "{nid}"),
+        }
+    }
+}
+
 /// An established network connection with a peer.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
@@ -527,6 +610,9 @@ pub struct Session {
     pub link: Link,
     pub addr: Address,
     pub state: State,
+    /// Average round-trip time to the peer, in milliseconds, if measured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rtt: Option<u64>,
 }
 
 impl Session {
@@ -536,6 +622,25 @@ impl Session {
     }
 }
 
+/// Node health, as returned by [`Handle::status`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Status {
+    /// When the node was started.
+    pub started_at: Timestamp,
+    /// How long the node has been running for, in seconds.
+    pub uptime: u64,
+    /// Number of connected peers.
+    pub peers: usize,
+    /// Number of repositories in our inventory.
+    pub inventory: usize,
+    /// Number of ongoing and queued fetches.
+    pub pending_fetches: usize,
+    /// When our inventory was last announced, if ever.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_announcement: Option<Timestamp>,
+}
+
 /// A seed for some repository, with metadata about its status.
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -858,6 +963,10 @@ pub trait Handle: Clone + Sync + Send {
     fn listen_addrs(&self) -> Result<Vec<net::SocketAddr>, Self::Error>;
     /// Get the current node configuration.
     fn config(&self) -> Result<config::Config, Self::Error>;
+    /// Get the node's health status.
+    fn status(&self) -> Result<Status, Self::Error>;
+    /// Get the node's metrics, in the Prometheus text format.
+    fn metrics(&self) -> Result<String, Self::Error>;
     /// Connect to a peer.
     fn connect(
         &mut self,
@@ -887,6 +996,14 @@ pub trait Handle: Clone + Sync + Send {
     fn unfollow(&mut self, id: NodeId) -> Result<bool, Self::Error>;
     /// Notify the service that a project has been updated, and announce local refs.
     fn announce_refs(&mut self, id: RepoId) -> Result<RefsAt, Self::Error>;
+    /// Notify the service that a collaborative object was updated, and announce local refs.
+    /// Unlike [`Handle::announce_refs`], fails if the object doesn't exist under our namespace.
+    fn announce_cob_refs(
+        &mut self,
+        rid: RepoId,
+        type_name: cob::TypeName,
+        id: cob::ObjectId,
+    ) -> Result<RefsAt, Self::Error>;
     /// Announce local inventory.
     fn announce_inventory(&mut self) -> Result<(), Self::Error>;
     /// Notify the service that our inventory was updated with the given repository.
@@ -1046,6 +1163,20 @@ impl Handle for Node {
         true
     }
 
+    fn status(&self) -> Result<Status, Error> {
+        self.call::<Status>(Command::Status, DEFAULT_TIMEOUT)?
+            .next()
+            .ok_or(Error::EmptyResponse)?
+            .map_err(Error::from)
+    }
+
+    fn metrics(&self) -> Result<String, Error> {
+        self.call::<String>(Command::Metrics, DEFAULT_TIMEOUT)?
+            .next()
+            .ok_or(Error::EmptyResponse)?
+            .map_err(Error::from)
+    }
+
     fn config(&self) -> Result<config::Config, Error> {
         self.call::<config::Config>(Command::Config, DEFAULT_TIMEOUT)?
             .next()
@@ -1149,6 +1280,23 @@ impl Handle for Node {
         Ok(refs)
     }
 
+    fn announce_cob_refs(
+        &mut self,
+        rid: RepoId,
+        type_name: cob::TypeName,
+        id: cob::ObjectId,
+    ) -> Result<RefsAt, Error> {
+        let refs: RefsAt = self
+            .call(
+                Command::AnnounceCobRefs { rid, type_name, id },
+                DEFAULT_TIMEOUT,
+            )?
+            .next()
+            .ok_or(Error::EmptyResponse)??;
+
+        Ok(refs)
+    }
+
     fn announce_inventory(&mut self) -> Result<(), Error> {
         for line in self.call::<Success>(Command::AnnounceInventory, DEFAULT_TIMEOUT)? {
             line?;
@@ -1197,6 +1345,34 @@ impl Handle for Node {
 pub trait AliasStore {
     /// Returns alias of a `NodeId`.
     fn alias(&self, nid: &NodeId) -> Option<Alias>;
+
+    /// Returns the nodes claiming the given alias, the first one to have claimed it
+    /// coming first. Stores that can't tell return no nodes.
+    fn claimants(&self, _alias: &Alias) -> Vec<NodeId> {
+        Vec::new()
+    }
+
+    /// Returns an unambiguous name for a node, based on its alias.
+    ///
+    /// When more than one node claims the same alias, the first one to have claimed it
+    /// keeps it, while the others are disambiguated with the end of their node id,
+    /// eg. `alice#U8Vi`.
+    fn unique_alias(&self, nid: &NodeId) -> Option<String> {
+        let alias = self.alias(nid)?;
+
+        match self.claimants(&alias).first() {
+            Some(first) if first != nid => Some(disambiguate(&alias, nid)),
+            _ => Some(alias.to_string()),
+        }
+    }
+}
+
+/// Disambiguate an alias with the last characters of the node id.
+pub fn disambiguate(alias: &Alias, nid: &NodeId) -> String {
+    let nid = nid.to_human();
+    let suffix = &nid[nid.len() - 4..];
+
+    format!("{alias}#{suffix}")
 }
 
 impl AliasStore for HashMap<NodeId, Alias> {
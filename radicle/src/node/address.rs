@@ -1,4 +1,6 @@
+pub mod resolver;
 pub mod store;
+pub use resolver::{AddressResolver, ConnectTarget, ResolveError, Resolver};
 pub use store::{Error, Store};
 
 use std::cell::RefCell;
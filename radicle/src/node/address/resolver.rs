@@ -0,0 +1,212 @@
+//! Address resolution.
+//!
+//! Peer addresses come in different flavors: IP addresses can be dialed directly, DNS names
+//! have to be resolved first, and Tor onion addresses can only be reached through a SOCKS5
+//! proxy. The [`AddressResolver`] trait is the single entry point for turning an [`Address`]
+//! into something we can actually connect to.
+use std::net::ToSocketAddrs as _;
+use std::{io, net};
+
+use cyphernet::addr::HostName;
+use thiserror::Error;
+
+use crate::node::Address;
+
+/// An error resolving an address.
+#[derive(Error, Debug)]
+pub enum ResolveError {
+    /// The address can only be reached via a proxy, and none is configured.
+    #[error("address {0} requires a proxy, but none is configured")]
+    ProxyRequired(Address),
+    /// A DNS name could not be resolved.
+    #[error("failed to resolve {addr}: {err}")]
+    Dns { addr: Address, err: io::Error },
+    /// A DNS name resolved to no addresses.
+    #[error("failed to resolve {0}: no addresses found")]
+    NotFound(Address),
+    /// The address type is not supported.
+    #[error("address {0} is not supported")]
+    Unsupported(Address),
+}
+
+impl From<ResolveError> for io::Error {
+    fn from(err: ResolveError) -> Self {
+        match err {
+            ResolveError::Dns { err, .. } => err,
+            ResolveError::NotFound(_) => io::Error::new(io::ErrorKind::NotFound, err),
+            other => io::Error::new(io::ErrorKind::Unsupported, other),
+        }
+    }
+}
+
+/// The target of a connection to a peer address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectTarget {
+    /// Connect directly to the given socket address.
+    Direct(net::SocketAddr),
+    /// Connect to the given SOCKS5 proxy, and ask it to connect us to the address.
+    Proxy {
+        /// Proxy address.
+        proxy: net::SocketAddr,
+        /// Address to connect to via the proxy.
+        addr: Address,
+    },
+}
+
+impl ConnectTarget {
+    /// Whether the connection goes through a proxy.
+    pub fn is_proxied(&self) -> bool {
+        matches!(self, Self::Proxy { .. })
+    }
+
+    /// The socket address to open a TCP connection to.
+    pub fn socket_addr(&self) -> net::SocketAddr {
+        match self {
+            Self::Direct(addr) => *addr,
+            Self::Proxy { proxy, .. } => *proxy,
+        }
+    }
+}
+
+/// Resolves peer addresses into connection targets.
+pub trait AddressResolver {
+    /// Resolve an address.
+    fn resolve(&self, addr: &Address) -> Result<ConnectTarget, ResolveError>;
+}
+
+/// Default address resolver.
+///
+/// IP addresses are connected to directly, DNS names are resolved with the system resolver
+/// and Tor addresses are proxied. If `force_proxy` is set, all connections go through the
+/// proxy, including DNS names, which are then resolved by the proxy.
+#[derive(Debug, Clone, Default)]
+pub struct Resolver {
+    /// SOCKS5 proxy address.
+    pub proxy: Option<net::SocketAddr>,
+    /// Whether to route all connections through the proxy.
+    pub force_proxy: bool,
+}
+
+impl Resolver {
+    /// Create a resolver which uses the given proxy when necessary.
+    pub fn new(proxy: net::SocketAddr) -> Self {
+        Self {
+            proxy: Some(proxy),
+            force_proxy: false,
+        }
+    }
+
+    /// Route all connections through the proxy.
+    pub fn force_proxy(mut self, force: bool) -> Self {
+        self.force_proxy = force;
+        self
+    }
+
+    fn proxied(&self, addr: &Address) -> Result<ConnectTarget, ResolveError> {
+        let proxy = self
+            .proxy
+            .ok_or_else(|| ResolveError::ProxyRequired(addr.clone()))?;
+
+        Ok(ConnectTarget::Proxy {
+            proxy,
+            addr: addr.clone(),
+        })
+    }
+}
+
+impl AddressResolver for Resolver {
+    fn resolve(&self, addr: &Address) -> Result<ConnectTarget, ResolveError> {
+        if self.force_proxy {
+            return self.proxied(addr);
+        }
+        match &addr.host {
+            HostName::Ip(ip) => Ok(ConnectTarget::Direct(net::SocketAddr::new(*ip, addr.port))),
+            HostName::Dns(name) => (name.as_str(), addr.port)
+                .to_socket_addrs()
+                .map_err(|err| ResolveError::Dns {
+                    addr: addr.clone(),
+                    err,
+                })?
+                .next()
+                .map(ConnectTarget::Direct)
+                .ok_or_else(|| ResolveError::NotFound(addr.clone())),
+            HostName::Tor(_) => self.proxied(addr),
+            _ => Err(ResolveError::Unsupported(addr.clone())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::assert_matches;
+
+    const ONION: &str = "2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:8776";
+
+    fn proxy() -> net::SocketAddr {
+        net::SocketAddr::from(([127, 0, 0, 1], 9050))
+    }
+
+    #[test]
+    fn test_resolve_ip() {
+        let resolver = Resolver::new(proxy());
+        let ipv4 = Address::from_str("192.168.1.7:8776").unwrap();
+        let ipv6 = Address::from_str("[::1]:8776").unwrap();
+
+        assert_eq!(
+            resolver.resolve(&ipv4).unwrap(),
+            ConnectTarget::Direct(net::SocketAddr::from(([192, 168, 1, 7], 8776)))
+        );
+        assert_eq!(
+            resolver.resolve(&ipv6).unwrap(),
+            ConnectTarget::Direct(net::SocketAddr::from((net::Ipv6Addr::LOCALHOST, 8776)))
+        );
+    }
+
+    #[test]
+    fn test_resolve_dns() {
+        let resolver = Resolver::new(proxy());
+        let dns = Address::from_str("localhost:8776").unwrap();
+        let target = resolver.resolve(&dns).unwrap();
+
+        assert!(!target.is_proxied());
+        assert!(target.socket_addr().ip().is_loopback());
+        assert_eq!(target.socket_addr().port(), 8776);
+    }
+
+    #[test]
+    fn test_resolve_tor() {
+        let onion = Address::from_str(ONION).unwrap();
+
+        assert_eq!(
+            Resolver::new(proxy()).resolve(&onion).unwrap(),
+            ConnectTarget::Proxy {
+                proxy: proxy(),
+                addr: onion.clone()
+            }
+        );
+        assert_matches!(
+            Resolver::default().resolve(&onion),
+            Err(ResolveError::ProxyRequired(_))
+        );
+    }
+
+    #[test]
+    fn test_resolve_force_proxy() {
+        let resolver = Resolver::new(proxy()).force_proxy(true);
+
+        for addr in ["192.168.1.7:8776", "[::1]:8776", "seed.radicle.xyz:8776", ONION] {
+            let addr = Address::from_str(addr).unwrap();
+
+            assert_eq!(
+                resolver.resolve(&addr).unwrap(),
+                ConnectTarget::Proxy {
+                    proxy: proxy(),
+                    addr
+                }
+            );
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use localtime::LocalTime;
@@ -55,6 +56,11 @@ pub trait Store {
     ) -> Result<bool, Error>;
     /// Remove a node from the store.
     fn remove(&mut self, id: &NodeId) -> Result<bool, Error>;
+    /// Get the nodes claiming an alias, in the order in which they first claimed it.
+    fn claimants(&self, alias: &Alias) -> Result<Vec<NodeId>, Error>;
+    /// Get the aliases claimed by more than one node, along with their claimants, in
+    /// the order in which they first claimed it.
+    fn conflicts(&self) -> Result<BTreeMap<Alias, Vec<NodeId>>, Error>;
     /// Returns the number of addresses.
     fn len(&self) -> Result<usize, Error>;
     /// Return the number of nodes.
@@ -174,10 +180,12 @@ impl Store for Database {
     ) -> Result<bool, Error> {
         transaction(&self.db, move |db| {
             let mut stmt = db.prepare(
-                "INSERT INTO nodes (id, features, alias, pow, timestamp)
-                 VALUES (?1, ?2, ?3, ?4, ?5)
+                "INSERT INTO nodes (id, features, alias, pow, timestamp, alias_since)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?5)
                  ON CONFLICT DO UPDATE
-                 SET features = ?2, alias = ?3, pow = ?4, timestamp = ?5
+                 SET features = ?2, pow = ?4, timestamp = ?5,
+                     alias_since = CASE WHEN alias = ?3 THEN alias_since ELSE ?5 END,
+                     alias = ?3
                  WHERE timestamp < ?5",
             )?;
 
@@ -216,6 +224,39 @@ impl Store for Database {
         Ok(self.db.change_count() > 0)
     }
 
+    fn claimants(&self, alias: &Alias) -> Result<Vec<NodeId>, Error> {
+        let mut stmt = self.db.prepare(
+            "SELECT id FROM nodes
+             WHERE alias = ?1
+             ORDER BY alias_since ASC, id ASC",
+        )?;
+        stmt.bind((1, sql::Value::String(alias.to_string())))?;
+
+        let mut nodes = Vec::new();
+        for row in stmt.into_iter() {
+            nodes.push(row?.read::<NodeId, _>("id"));
+        }
+        Ok(nodes)
+    }
+
+    fn conflicts(&self) -> Result<BTreeMap<Alias, Vec<NodeId>>, Error> {
+        let stmt = self.db.prepare(
+            "SELECT id, alias FROM nodes
+             WHERE alias IN (SELECT alias FROM nodes GROUP BY alias HAVING COUNT(*) > 1)
+             ORDER BY alias_since ASC, id ASC",
+        )?;
+
+        let mut conflicts = BTreeMap::<Alias, Vec<NodeId>>::new();
+        for row in stmt.into_iter() {
+            let row = row?;
+            let id = row.read::<NodeId, _>("id");
+            let alias = Alias::from_str(row.read::<&str, _>("alias"))?;
+
+            conflicts.entry(alias).or_default().push(id);
+        }
+        Ok(conflicts)
+    }
+
     fn entries(&self) -> Result<Box<dyn Iterator<Item = AddressEntry>>, Error> {
         let mut stmt = self
             .db
@@ -331,6 +372,10 @@ where
             .map(|node| node.map(|n| n.alias))
             .unwrap_or(None)
     }
+
+    fn claimants(&self, alias: &Alias) -> Vec<NodeId> {
+        Store::claimants(self, alias).unwrap_or_default()
+    }
 }
 
 impl TryFrom<&sql::Value> for Source {
@@ -450,6 +495,53 @@ mod test {
         assert_eq!(node.alias.as_ref(), "bob");
     }
 
+    #[test]
+    fn test_alias_conflict() {
+        let alice = arbitrary::gen::<NodeId>(1);
+        let eve = arbitrary::gen::<NodeId>(1);
+        let bob = arbitrary::gen::<NodeId>(1);
+        let mut cache = Database::memory().unwrap();
+        let features = node::Features::SEED;
+        let timestamp = Timestamp::from(LocalTime::now());
+
+        cache
+            .insert(&alice, features, Alias::new("alice"), 16, timestamp, [])
+            .unwrap();
+        cache
+            .insert(&bob, features, Alias::new("bob"), 16, timestamp, [])
+            .unwrap();
+        cache
+            .insert(&eve, features, Alias::new("alice"), 16, timestamp + 1, [])
+            .unwrap();
+        // Re-announcing the same alias doesn't change when it was first claimed.
+        cache
+            .insert(&alice, features, Alias::new("alice"), 16, timestamp + 2, [])
+            .unwrap();
+
+        let alias = Alias::new("alice");
+        assert_eq!(Store::claimants(&cache, &alias).unwrap(), vec![alice, eve]);
+        assert_eq!(
+            cache.conflicts().unwrap().into_iter().collect::<Vec<_>>(),
+            vec![(alias.clone(), vec![alice, eve])]
+        );
+        assert_eq!(cache.unique_alias(&alice), Some(String::from("alice")));
+        assert_eq!(cache.unique_alias(&eve), Some(node::disambiguate(&alias, &eve)));
+        assert_eq!(cache.unique_alias(&bob), Some(String::from("bob")));
+
+        let eve_human = eve.to_human();
+        assert_eq!(
+            node::disambiguate(&alias, &eve),
+            format!("alice#{}", &eve_human[eve_human.len() - 4..])
+        );
+
+        // Once Alice changes her alias, Eve gets to keep hers.
+        cache
+            .insert(&alice, features, Alias::new("alicia"), 16, timestamp + 3, [])
+            .unwrap();
+        assert!(cache.conflicts().unwrap().is_empty());
+        assert_eq!(cache.unique_alias(&eve), Some(String::from("alice")));
+    }
+
     #[test]
     fn test_insert_and_get() {
         let alice = arbitrary::gen::<NodeId>(1);
@@ -1,18 +1,33 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::net;
 use std::ops::Deref;
 
 use cyphernet::addr::PeerAddr;
 use localtime::LocalDuration;
 
+use crate::cob::TypeName;
+use crate::git::Qualified;
 use crate::node;
 use crate::node::policy::{Policy, Scope};
 use crate::node::{Address, Alias, NodeId};
+use crate::storage::quota::Quota;
 
 /// Target number of peers to maintain connections to.
 pub const TARGET_OUTBOUND_PEERS: usize = 8;
+/// Default maximum number of connections, inbound and outbound.
+pub const DEFAULT_MAX_CONNECTIONS: usize = 144;
+/// Default minimum number of outbound connections to maintain.
+pub const DEFAULT_MIN_OUTBOUND_PEERS: usize = 4;
 /// Default number of workers to spawn.
 pub const DEFAULT_WORKERS: usize = 8;
+/// Default window within which ref announcements of a repository are coalesced.
+pub const DEFAULT_ANNOUNCE_REFS_WINDOW: LocalDuration = LocalDuration::from_secs(1);
+/// Default interval at which the inventory is announced.
+pub const DEFAULT_ANNOUNCE_INTERVAL: LocalDuration = LocalDuration::from_mins(60);
+/// Default maximum random delay added to the inventory announcement interval.
+pub const DEFAULT_ANNOUNCE_JITTER: LocalDuration = LocalDuration::from_mins(5);
+/// Default number of hops after which gossip messages are no longer relayed.
+pub const DEFAULT_GOSSIP_MAX_HOPS: u8 = 8;
 
 /// Configured public seeds.
 pub mod seeds {
@@ -102,6 +117,9 @@ pub struct Limits {
     /// How long to keep a gossip message entry before pruning it.
     #[serde(with = "crate::serde_ext::localtime::duration")]
     pub gossip_max_age: LocalDuration,
+    /// Number of hops after which a gossip message is no longer relayed.
+    #[serde(default = "defaults::gossip_max_hops")]
+    pub gossip_max_hops: u8,
     /// Maximum number of concurrent fetches per peer connection.
     pub fetch_concurrency: usize,
     /// Maximum number of open files.
@@ -112,6 +130,9 @@ pub struct Limits {
     /// Connection limits.
     #[serde(default)]
     pub connection: ConnectionLimits,
+    /// Storage quota of each remote namespace.
+    #[serde(default)]
+    pub quota: Quota,
 }
 
 impl Default for Limits {
@@ -120,10 +141,12 @@ impl Default for Limits {
             routing_max_size: 1000,
             routing_max_age: LocalDuration::from_mins(7 * 24 * 60), // One week
             gossip_max_age: LocalDuration::from_mins(2 * 7 * 24 * 60), // Two weeks
+            gossip_max_hops: DEFAULT_GOSSIP_MAX_HOPS,
             fetch_concurrency: 1,
             max_open_files: 4096,
             rate: RateLimits::default(),
             connection: ConnectionLimits::default(),
+            quota: Quota::default(),
         }
     }
 }
@@ -137,6 +160,13 @@ pub struct ConnectionLimits {
     /// Max outbound connections. Note that this is higher than the *target* number
     /// in [`TARGET_OUTBOUND_PEERS`].
     pub outbound: usize,
+    /// Max connections, inbound and outbound. When exceeded, inbound peers are evicted.
+    #[serde(default = "defaults::max_connections")]
+    pub total: usize,
+    /// Min outbound connections. When below this, peers are dialed, even if there are
+    /// enough inbound connections.
+    #[serde(default = "defaults::min_outbound")]
+    pub min_outbound: usize,
 }
 
 impl Default for ConnectionLimits {
@@ -144,6 +174,8 @@ impl Default for ConnectionLimits {
         Self {
             inbound: 128,
             outbound: 16,
+            total: DEFAULT_MAX_CONNECTIONS,
+            min_outbound: DEFAULT_MIN_OUTBOUND_PEERS,
         }
     }
 }
@@ -156,12 +188,16 @@ pub struct RateLimit {
     pub capacity: usize,
 }
 
-/// Rate limits for inbound and outbound connections.
+/// Rate limits for inbound and outbound connections, and for outgoing announcements.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RateLimits {
     pub inbound: RateLimit,
     pub outbound: RateLimit,
+    /// Announcement messages written to peers, across all connections. When exceeded,
+    /// our own announcements are sent before the ones we relay.
+    #[serde(default = "defaults::announce_rate")]
+    pub announce: RateLimit,
 }
 
 impl Default for RateLimits {
@@ -175,6 +211,7 @@ impl Default for RateLimits {
                 fill_rate: 1.0,
                 capacity: 64,
             },
+            announce: defaults::announce_rate(),
         }
     }
 }
@@ -271,6 +308,37 @@ pub struct Config {
     /// Default seeding scope.
     #[serde(default)]
     pub scope: Scope,
+    /// Window within which changes to the refs of a repository are coalesced into
+    /// a single announcement.
+    #[serde(
+        default = "defaults::announce_refs_window",
+        with = "crate::serde_ext::localtime::duration"
+    )]
+    pub announce_refs_window: LocalDuration,
+    /// Interval at which our inventory is announced to the network.
+    #[serde(
+        default = "defaults::announce_interval",
+        with = "crate::serde_ext::localtime::duration"
+    )]
+    pub announce_interval: LocalDuration,
+    /// Maximum random delay added to each inventory announcement interval, so that
+    /// nodes don't all announce at the same time.
+    #[serde(
+        default = "defaults::announce_jitter",
+        with = "crate::serde_ext::localtime::duration"
+    )]
+    pub announce_jitter: LocalDuration,
+    /// Preferred seeds, in failover order. Missing repositories are fetched from the
+    /// first of these that is connected, moving on to the next one if a fetch fails.
+    ///
+    /// These are set from the profile configuration when the node starts, and aren't
+    /// part of the node configuration.
+    #[serde(skip)]
+    pub preferred_seeds: Vec<NodeId>,
+    /// Categories of references that are not replicated when fetching.
+    /// The `rad` references of a repository are always replicated.
+    #[serde(default)]
+    pub exclude_refs: BTreeSet<RefCategory>,
 }
 
 impl Config {
@@ -294,6 +362,11 @@ impl Config {
             workers: DEFAULT_WORKERS,
             policy: Policy::default(),
             scope: Scope::default(),
+            announce_refs_window: DEFAULT_ANNOUNCE_REFS_WINDOW,
+            announce_interval: DEFAULT_ANNOUNCE_INTERVAL,
+            announce_jitter: DEFAULT_ANNOUNCE_JITTER,
+            preferred_seeds: vec![],
+            exclude_refs: BTreeSet::new(),
         }
     }
 
@@ -313,10 +386,79 @@ impl Config {
     }
 }
 
+/// Category of repository references, used to select what is replicated.
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum RefCategory {
+    /// Branches, under `refs/heads`.
+    Heads,
+    /// Tags, under `refs/tags`.
+    Tags,
+    /// Git notes, under `refs/notes`.
+    Notes,
+    /// Collaborative objects of the given type, under `refs/cobs/<type>`.
+    Cob(TypeName),
+}
+
+impl RefCategory {
+    /// Get the category of a reference, if any.
+    ///
+    /// `refs/rad` references and other unknown references don't have a category.
+    pub fn of(refname: &Qualified) -> Option<Self> {
+        match refname.non_empty_iter() {
+            ("refs", "heads", _, _) => Some(Self::Heads),
+            ("refs", "tags", _, _) => Some(Self::Tags),
+            ("refs", "notes", _, _) => Some(Self::Notes),
+            ("refs", "cobs", type_name, _) => type_name.parse().ok().map(Self::Cob),
+            _ => None,
+        }
+    }
+}
+
 /// Defaults as functions, for serde.
 mod defaults {
     /// Worker count.
     pub fn workers() -> usize {
         super::DEFAULT_WORKERS
     }
+
+    /// Ref announcement window.
+    pub fn announce_refs_window() -> localtime::LocalDuration {
+        super::DEFAULT_ANNOUNCE_REFS_WINDOW
+    }
+
+    /// Inventory announcement interval.
+    pub fn announce_interval() -> localtime::LocalDuration {
+        super::DEFAULT_ANNOUNCE_INTERVAL
+    }
+
+    /// Inventory announcement jitter.
+    pub fn announce_jitter() -> localtime::LocalDuration {
+        super::DEFAULT_ANNOUNCE_JITTER
+    }
+
+    /// Announcement rate limit.
+    pub fn announce_rate() -> super::RateLimit {
+        super::RateLimit {
+            fill_rate: 32.0,
+            capacity: 512,
+        }
+    }
+
+    /// Gossip hop limit.
+    pub fn gossip_max_hops() -> u8 {
+        super::DEFAULT_GOSSIP_MAX_HOPS
+    }
+
+    /// Maximum number of connections.
+    pub fn max_connections() -> usize {
+        super::DEFAULT_MAX_CONNECTIONS
+    }
+
+    /// Minimum number of outbound connections.
+    pub fn min_outbound() -> usize {
+        super::DEFAULT_MIN_OUTBOUND_PEERS
+    }
 }
@@ -28,6 +28,8 @@ const MIGRATIONS: &[&str] = &[
     include_str!("db/migrations/1.sql"),
     include_str!("db/migrations/2.sql"),
     include_str!("db/migrations/3.sql"),
+    include_str!("db/migrations/4.sql"),
+    include_str!("db/migrations/5.sql"),
 ];
 
 #[derive(Error, Debug)]
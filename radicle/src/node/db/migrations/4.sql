@@ -0,0 +1,15 @@
+-- Fetches to retry, eg. after a restart.
+create table if not exists "fetch-retries" (
+  -- Repository ID.
+  "repo"                 text      not null,
+  -- Node to fetch from.
+  --
+  -- Nb. We don't use a foreign key constraint because we can't guarantee
+  -- that we'll have received a node announcement from this node.
+  "node"                 text      not null,
+  -- When this entry was created or updated.
+  "timestamp"            integer   not null,
+  --
+  unique ("repo", "node")
+  --
+) strict;
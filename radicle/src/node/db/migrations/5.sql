@@ -0,0 +1,5 @@
+-- Time at which a node's current alias was first announced. When more than
+-- one node claims the same alias, the first one to claim it keeps it.
+alter table "nodes" add column "alias_since" integer not null default 0;
+
+update "nodes" set "alias_since" = "timestamp";
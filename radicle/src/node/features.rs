@@ -1,4 +1,10 @@
 //! Node features advertized on the network.
+//!
+//! Besides signaling what services a node offers, features are used as capabilities: since
+//! each peer sends us its node announcement when a session is established, the features
+//! supported by both sides are negotiated for the session, and features not supported by
+//! both sides are disabled. This allows new protocol features to be rolled out
+//! incrementally.
 use serde::{Deserialize, Serialize};
 use std::{fmt, ops};
 
@@ -14,6 +20,13 @@ impl Features {
     /// `SEED` is the base feature set all seed nodes must support.
     pub const SEED: Features = Features(0b00000001);
 
+    /// `COMPRESSION` signals support for compressed gossip and Git streams.
+    pub const COMPRESSION: Features = Features(0b00000010);
+
+    /// `PARTIAL_REPLICATION` signals support for replicating a subset of a repository's
+    /// references.
+    pub const PARTIAL_REPLICATION: Features = Features(0b00000100);
+
     /// Returns [`Features`] with the other features added.
     #[must_use]
     pub fn with(self, other: Features) -> Features {
@@ -26,6 +39,12 @@ impl Features {
         Self(self.0 ^ other.0)
     }
 
+    /// Returns the [`Features`] included in both sets.
+    #[must_use]
+    pub fn common(self, other: Features) -> Features {
+        Self(self.0 & other.0)
+    }
+
     /// Check whether [`Features`] are included.
     pub fn has(self, flags: Features) -> bool {
         (self.0 | flags.0) == self.0
@@ -94,6 +113,14 @@ impl ops::BitOrAssign for Features {
     }
 }
 
+impl ops::BitAnd for Features {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.common(rhs)
+    }
+}
+
 impl ops::BitXor for Features {
     type Output = Self;
 
@@ -128,5 +155,13 @@ mod test {
             Features::NONE.with(Features::SEED).without(Features::SEED),
             Features::NONE
         );
+
+        let local = Features::SEED | Features::COMPRESSION;
+        let remote = Features::SEED | Features::PARTIAL_REPLICATION;
+
+        assert_eq!(local.common(remote), Features::SEED);
+        assert_eq!(local & Features::NONE, Features::NONE);
+        assert!(!(local & remote).has(Features::COMPRESSION));
+        assert!(!(local & remote).has(Features::PARTIAL_REPLICATION));
     }
 }
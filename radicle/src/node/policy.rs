@@ -1,6 +1,7 @@
 pub mod config;
 pub mod store;
 
+use std::collections::BTreeSet;
 use std::fmt;
 use std::str::FromStr;
 
@@ -9,7 +10,7 @@ use thiserror::Error;
 
 use crate::prelude::RepoId;
 
-pub use super::{Alias, NodeId};
+pub use super::{Alias, NodeId, Timestamp};
 
 /// Repository seeding policy.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -27,6 +28,146 @@ pub struct FollowPolicy {
     pub policy: Policy,
 }
 
+/// A portable list of blocked nodes and repositories.
+///
+/// Used to share moderation lists between seed operators. Serialized as JSON, eg.
+///
+/// ```json
+/// { "nodes": ["z6Mk.."], "repos": ["rad:z3gq.."] }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockList {
+    /// Blocked nodes.
+    pub nodes: BTreeSet<NodeId>,
+    /// Blocked repositories.
+    pub repos: BTreeSet<RepoId>,
+}
+
+impl BlockList {
+    /// Check whether the block list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.repos.is_empty()
+    }
+
+    /// Number of entries in the block list.
+    pub fn len(&self) -> usize {
+        self.nodes.len() + self.repos.len()
+    }
+}
+
+/// Error parsing a [`BlockList`].
+#[derive(Debug, Error)]
+pub enum BlockListError {
+    /// The block list is not valid JSON, or has the wrong shape.
+    #[error("invalid block list: {0}")]
+    Json(#[from] serde_json::Error),
+    /// An entry is not a valid node id.
+    #[error("invalid node id {0:?} in block list")]
+    Node(String),
+    /// An entry is not a valid repository id.
+    #[error("invalid repository id {0:?} in block list")]
+    Repo(String),
+}
+
+impl FromStr for BlockList {
+    type Err = BlockListError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Entries are parsed one by one, so that we can report the offending entry.
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase", deny_unknown_fields)]
+        struct Raw {
+            #[serde(default)]
+            nodes: Vec<String>,
+            #[serde(default)]
+            repos: Vec<String>,
+        }
+        let raw: Raw = serde_json::from_str(s)?;
+        let nodes = raw
+            .nodes
+            .into_iter()
+            .map(|n| NodeId::from_str(&n).map_err(|_| BlockListError::Node(n)))
+            .collect::<Result<_, _>>()?;
+        let repos = raw
+            .repos
+            .into_iter()
+            .map(|r| RepoId::from_str(&r).map_err(|_| BlockListError::Repo(r)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { nodes, repos })
+    }
+}
+
+impl fmt::Display for BlockList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string_pretty(self).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+/// A change to a node's follow policies or a repository's seeding policies.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum PolicyChange {
+    /// A node was followed.
+    Follow { nid: NodeId, alias: Option<Alias> },
+    /// A node was unfollowed.
+    Unfollow { nid: NodeId },
+    /// A repository was seeded.
+    Seed { rid: RepoId, scope: Scope },
+    /// A repository was unseeded.
+    Unseed { rid: RepoId },
+    /// A node's follow policy was set.
+    FollowPolicy { nid: NodeId, policy: Policy },
+    /// A repository's seeding policy was set.
+    SeedPolicy { rid: RepoId, policy: Policy },
+}
+
+/// Where a policy change originated.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PolicySource {
+    /// The change was made by the local operator, eg. via the CLI.
+    #[default]
+    Local,
+    /// The change was made by importing a [`BlockList`].
+    Import,
+}
+
+impl fmt::Display for PolicySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local => f.write_str("local"),
+            Self::Import => f.write_str("import"),
+        }
+    }
+}
+
+impl FromStr for PolicySource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "local" => Ok(Self::Local),
+            "import" => Ok(Self::Import),
+            _ => Err(s.to_owned()),
+        }
+    }
+}
+
+/// An entry in the policy log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyLogEntry {
+    /// When the change was made.
+    pub timestamp: Timestamp,
+    /// The change made.
+    pub change: PolicyChange,
+    /// Where the change originated.
+    pub source: PolicySource,
+}
+
 /// Resource policy.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
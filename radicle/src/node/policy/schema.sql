@@ -29,3 +29,30 @@ create table if not exists "seeding" (
   "policy"             text      default 'allow'
   --
 ) strict;
+
+-- Append-only log of policy changes, for auditing.
+create table if not exists "policy_log" (
+  -- Log entry sequence number, determines the order of changes.
+  "seq"                integer   primary key autoincrement,
+  -- When the change was made, in milliseconds since epoch.
+  "timestamp"          integer   not null,
+  -- Kind of change.
+  --
+  -- Valid values are:
+  --
+  -- "follow"          node followed, `value` is the alias.
+  -- "unfollow"        node unfollowed.
+  -- "seed"            repository seeded, `value` is the scope.
+  -- "unseed"          repository unseeded.
+  -- "follow-policy"   node policy set, `value` is the policy.
+  -- "seed-policy"     repository policy set, `value` is the policy.
+  --
+  "action"             text      not null,
+  -- Node ID or Repository ID the change applies to.
+  "target"             text      not null,
+  -- Value set by the change, if any.
+  "value"              text      default '',
+  -- Where the change originated, eg. "local" or "import".
+  "source"             text      not null
+  --
+) strict;
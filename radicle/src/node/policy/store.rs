@@ -3,13 +3,17 @@ use std::marker::PhantomData;
 use std::path::Path;
 use std::{fmt, io, ops::Not as _, str::FromStr, time};
 
+use localtime::LocalTime;
 use sqlite as sql;
 use thiserror::Error;
 
-use crate::node::{Alias, AliasStore};
+use crate::node::{Alias, AliasStore, Timestamp};
 use crate::prelude::{NodeId, RepoId};
+use crate::sql::transaction;
 
-use super::{FollowPolicy, Policy, Scope, SeedPolicy};
+use super::{
+    BlockList, FollowPolicy, Policy, PolicyChange, PolicyLogEntry, PolicySource, Scope, SeedPolicy,
+};
 
 /// How long to wait for the database lock to be released before failing a read.
 const DB_READ_TIMEOUT: time::Duration = time::Duration::from_secs(3);
@@ -117,86 +121,196 @@ impl Store<Write> {
 
     /// Follow a node.
     pub fn follow(&mut self, id: &NodeId, alias: Option<&str>) -> Result<bool, Error> {
-        let mut stmt = self.db.prepare(
-            "INSERT INTO `following` (id, alias)
-             VALUES (?1, ?2)
-             ON CONFLICT DO UPDATE
-             SET alias = ?2 WHERE alias != ?2",
-        )?;
-
-        stmt.bind((1, id))?;
-        stmt.bind((2, alias.unwrap_or_default()))?;
-        stmt.next()?;
-
-        Ok(self.db.change_count() > 0)
+        transaction(&self.db, |db| {
+            let mut stmt = db.prepare(
+                "INSERT INTO `following` (id, alias)
+                 VALUES (?1, ?2)
+                 ON CONFLICT DO UPDATE
+                 SET alias = ?2 WHERE alias != ?2",
+            )?;
+
+            stmt.bind((1, id))?;
+            stmt.bind((2, alias.unwrap_or_default()))?;
+            stmt.next()?;
+
+            let updated = db.change_count() > 0;
+            if updated {
+                log(
+                    db,
+                    &PolicyChange::Follow {
+                        nid: *id,
+                        alias: alias.and_then(|a| Alias::from_str(a).ok()),
+                    },
+                    PolicySource::Local,
+                )?;
+            }
+            Ok(updated)
+        })
     }
 
     /// Seed a repository.
     pub fn seed(&mut self, id: &RepoId, scope: Scope) -> Result<bool, Error> {
-        let mut stmt = self.db.prepare(
-            "INSERT INTO `seeding` (id, scope)
-             VALUES (?1, ?2)
-             ON CONFLICT DO UPDATE
-             SET scope = ?2 WHERE scope != ?2",
-        )?;
-
-        stmt.bind((1, id))?;
-        stmt.bind((2, scope))?;
-        stmt.next()?;
-
-        Ok(self.db.change_count() > 0)
+        transaction(&self.db, |db| {
+            let mut stmt = db.prepare(
+                "INSERT INTO `seeding` (id, scope)
+                 VALUES (?1, ?2)
+                 ON CONFLICT DO UPDATE
+                 SET scope = ?2 WHERE scope != ?2",
+            )?;
+
+            stmt.bind((1, id))?;
+            stmt.bind((2, scope))?;
+            stmt.next()?;
+
+            let updated = db.change_count() > 0;
+            if updated {
+                log(
+                    db,
+                    &PolicyChange::Seed { rid: *id, scope },
+                    PolicySource::Local,
+                )?;
+            }
+            Ok(updated)
+        })
     }
 
     /// Set a node's follow policy.
     pub fn set_follow_policy(&mut self, id: &NodeId, policy: Policy) -> Result<bool, Error> {
-        let mut stmt = self.db.prepare(
-            "INSERT INTO `following` (id, policy)
-             VALUES (?1, ?2)
-             ON CONFLICT DO UPDATE
-             SET policy = ?2 WHERE policy != ?2",
-        )?;
-
-        stmt.bind((1, id))?;
-        stmt.bind((2, policy))?;
-        stmt.next()?;
-
-        Ok(self.db.change_count() > 0)
+        transaction(&self.db, |db| {
+            let mut stmt = db.prepare(
+                "INSERT INTO `following` (id, policy)
+                 VALUES (?1, ?2)
+                 ON CONFLICT DO UPDATE
+                 SET policy = ?2 WHERE policy != ?2",
+            )?;
+
+            stmt.bind((1, id))?;
+            stmt.bind((2, policy))?;
+            stmt.next()?;
+
+            let updated = db.change_count() > 0;
+            if updated {
+                log(
+                    db,
+                    &PolicyChange::FollowPolicy { nid: *id, policy },
+                    PolicySource::Local,
+                )?;
+            }
+            Ok(updated)
+        })
     }
 
     /// Set a repository's seeding policy.
     pub fn set_seed_policy(&mut self, id: &RepoId, policy: Policy) -> Result<bool, Error> {
-        let mut stmt = self.db.prepare(
-            "INSERT INTO `seeding` (id, policy)
-             VALUES (?1, ?2)
-             ON CONFLICT DO UPDATE
-             SET policy = ?2 WHERE policy != ?2",
-        )?;
-
-        stmt.bind((1, id))?;
-        stmt.bind((2, policy))?;
-        stmt.next()?;
-
-        Ok(self.db.change_count() > 0)
+        transaction(&self.db, |db| {
+            let mut stmt = db.prepare(
+                "INSERT INTO `seeding` (id, policy)
+                 VALUES (?1, ?2)
+                 ON CONFLICT DO UPDATE
+                 SET policy = ?2 WHERE policy != ?2",
+            )?;
+
+            stmt.bind((1, id))?;
+            stmt.bind((2, policy))?;
+            stmt.next()?;
+
+            let updated = db.change_count() > 0;
+            if updated {
+                log(
+                    db,
+                    &PolicyChange::SeedPolicy { rid: *id, policy },
+                    PolicySource::Local,
+                )?;
+            }
+            Ok(updated)
+        })
     }
 
     /// Unfollow a node.
     pub fn unfollow(&mut self, id: &NodeId) -> Result<bool, Error> {
-        let mut stmt = self.db.prepare("DELETE FROM `following` WHERE id = ?")?;
-
-        stmt.bind((1, id))?;
-        stmt.next()?;
-
-        Ok(self.db.change_count() > 0)
+        transaction(&self.db, |db| {
+            let mut stmt = db.prepare("DELETE FROM `following` WHERE id = ?")?;
+
+            stmt.bind((1, id))?;
+            stmt.next()?;
+
+            let updated = db.change_count() > 0;
+            if updated {
+                log(
+                    db,
+                    &PolicyChange::Unfollow { nid: *id },
+                    PolicySource::Local,
+                )?;
+            }
+            Ok(updated)
+        })
     }
 
     /// Unseed a repository.
     pub fn unseed(&mut self, id: &RepoId) -> Result<bool, Error> {
-        let mut stmt = self.db.prepare("DELETE FROM `seeding` WHERE id = ?")?;
+        transaction(&self.db, |db| {
+            let mut stmt = db.prepare("DELETE FROM `seeding` WHERE id = ?")?;
 
-        stmt.bind((1, id))?;
-        stmt.next()?;
+            stmt.bind((1, id))?;
+            stmt.next()?;
 
-        Ok(self.db.change_count() > 0)
+            let updated = db.change_count() > 0;
+            if updated {
+                log(db, &PolicyChange::Unseed { rid: *id }, PolicySource::Local)?;
+            }
+            Ok(updated)
+        })
+    }
+
+    /// Import a block list, merging it with the existing policies.
+    /// Existing policies for the listed nodes and repositories are set to 'block'.
+    /// Returns the number of policies that were updated.
+    pub fn import(&mut self, list: &BlockList) -> Result<usize, Error> {
+        transaction(&self.db, |db| {
+            let mut updated = 0;
+
+            for nid in &list.nodes {
+                let mut stmt = db.prepare(
+                    "INSERT INTO `following` (id, policy)
+                     VALUES (?1, ?2)
+                     ON CONFLICT DO UPDATE
+                     SET policy = ?2 WHERE policy != ?2",
+                )?;
+                stmt.bind((1, nid))?;
+                stmt.bind((2, Policy::Block))?;
+                stmt.next()?;
+
+                if db.change_count() > 0 {
+                    let change = PolicyChange::FollowPolicy {
+                        nid: *nid,
+                        policy: Policy::Block,
+                    };
+                    log(db, &change, PolicySource::Import)?;
+                    updated += 1;
+                }
+            }
+            for rid in &list.repos {
+                let mut stmt = db.prepare(
+                    "INSERT INTO `seeding` (id, policy)
+                     VALUES (?1, ?2)
+                     ON CONFLICT DO UPDATE
+                     SET policy = ?2 WHERE policy != ?2",
+                )?;
+                stmt.bind((1, rid))?;
+                stmt.bind((2, Policy::Block))?;
+                stmt.next()?;
+
+                if db.change_count() > 0 {
+                    let change = PolicyChange::SeedPolicy {
+                        rid: *rid,
+                        policy: Policy::Block,
+                    };
+                    log(db, &change, PolicySource::Import)?;
+                    updated += 1;
+                }
+            }
+            Ok(updated)
+        })
     }
 }
 
@@ -318,6 +432,73 @@ impl<T> Store<T> {
         }
         Ok(Box::new(entries.into_iter()))
     }
+
+    /// Get the policy log, ie. all policy changes, oldest first.
+    pub fn policy_log(&self) -> Result<Vec<PolicyLogEntry>, Error> {
+        let mut stmt = self
+            .db
+            .prepare(
+                "SELECT timestamp, action, target, value, source
+                 FROM `policy_log`
+                 ORDER BY seq",
+            )?
+            .into_iter();
+        let mut entries = Vec::new();
+
+        while let Some(row) = stmt.next() {
+            let row = row?;
+            let target = row.read::<&str, _>("target");
+            let value = row.read::<&str, _>("value");
+            let change = match row.read::<&str, _>("action") {
+                "follow" => PolicyChange::Follow {
+                    nid: parse(target)?,
+                    alias: value.is_empty().not().then(|| parse(value)).transpose()?,
+                },
+                "unfollow" => PolicyChange::Unfollow {
+                    nid: parse(target)?,
+                },
+                "seed" => PolicyChange::Seed {
+                    rid: parse(target)?,
+                    scope: parse(value)?,
+                },
+                "unseed" => PolicyChange::Unseed {
+                    rid: parse(target)?,
+                },
+                "follow-policy" => PolicyChange::FollowPolicy {
+                    nid: parse(target)?,
+                    policy: parse(value)?,
+                },
+                "seed-policy" => PolicyChange::SeedPolicy {
+                    rid: parse(target)?,
+                    policy: parse(value)?,
+                },
+                other => return Err(invalid(other).into()),
+            };
+
+            entries.push(PolicyLogEntry {
+                timestamp: row.read::<Timestamp, _>("timestamp"),
+                change,
+                source: parse(row.read::<&str, _>("source"))?,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Get the block list, ie. all blocked nodes and repositories.
+    pub fn block_list(&self) -> Result<BlockList, Error> {
+        let nodes = self
+            .follow_policies()?
+            .filter(|p| p.policy == Policy::Block)
+            .map(|p| p.nid)
+            .collect();
+        let repos = self
+            .seed_policies()?
+            .filter(|p| p.policy == Policy::Block)
+            .map(|p| p.rid)
+            .collect();
+
+        Ok(BlockList { nodes, repos })
+    }
 }
 
 impl<T> AliasStore for Store<T> {
@@ -330,11 +511,61 @@ impl<T> AliasStore for Store<T> {
     }
 }
 
+/// Append a change to the policy log.
+fn log(
+    db: &sql::Connection,
+    change: &PolicyChange,
+    source: PolicySource,
+) -> Result<(), sql::Error> {
+    let (action, target, value) = match change {
+        PolicyChange::Follow { nid, alias } => (
+            "follow",
+            nid.to_string(),
+            alias.as_ref().map(|a| a.to_string()).unwrap_or_default(),
+        ),
+        PolicyChange::Unfollow { nid } => ("unfollow", nid.to_string(), String::new()),
+        PolicyChange::Seed { rid, scope } => ("seed", rid.to_string(), scope.to_string()),
+        PolicyChange::Unseed { rid } => ("unseed", rid.to_string(), String::new()),
+        PolicyChange::FollowPolicy { nid, policy } => {
+            ("follow-policy", nid.to_string(), policy.to_string())
+        }
+        PolicyChange::SeedPolicy { rid, policy } => {
+            ("seed-policy", rid.to_string(), policy.to_string())
+        }
+    };
+    let mut stmt = db.prepare(
+        "INSERT INTO `policy_log` (timestamp, action, target, value, source)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+
+    stmt.bind((1, &Timestamp::from(LocalTime::now())))?;
+    stmt.bind((2, action))?;
+    stmt.bind((3, target.as_str()))?;
+    stmt.bind((4, value.as_str()))?;
+    stmt.bind((5, source.to_string().as_str()))?;
+    stmt.next()?;
+
+    Ok(())
+}
+
+/// Parse a value of the policy log.
+fn parse<T: FromStr>(value: &str) -> Result<T, sql::Error> {
+    T::from_str(value).map_err(|_| invalid(value))
+}
+
+fn invalid(value: &str) -> sql::Error {
+    sql::Error {
+        code: None,
+        message: Some(format!("sql: invalid policy log value {value:?}")),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::assert_matches;
 
     use super::*;
+    use crate::node::policy::BlockListError;
     use crate::test::arbitrary;
 
     #[test]
@@ -447,4 +678,121 @@ mod test {
             Policy::Block
         );
     }
+
+    #[test]
+    fn test_block_list_export_import() {
+        let nodes = arbitrary::vec::<NodeId>(3);
+        let repos = arbitrary::vec::<RepoId>(3);
+        let mut db = Store::open(":memory:").unwrap();
+
+        for nid in &nodes[..2] {
+            assert!(db.set_follow_policy(nid, Policy::Block).unwrap());
+        }
+        for rid in &repos[..2] {
+            assert!(db.set_seed_policy(rid, Policy::Block).unwrap());
+        }
+        // Allowed entries are not part of the block list.
+        assert!(db.follow(&nodes[2], None).unwrap());
+        assert!(db.seed(&repos[2], Scope::All).unwrap());
+
+        let exported = db.block_list().unwrap();
+        assert_eq!(exported.len(), 4);
+
+        let json = exported.to_string();
+        let imported = BlockList::from_str(&json).unwrap();
+        assert_eq!(imported, exported);
+
+        let mut fresh = Store::open(":memory:").unwrap();
+        assert_eq!(fresh.import(&imported).unwrap(), 4);
+        assert_eq!(fresh.block_list().unwrap(), exported);
+        // Importing again is a no-op.
+        assert_eq!(fresh.import(&imported).unwrap(), 0);
+
+        // Imported entries are merged with existing ones.
+        assert!(fresh.follow(&nodes[2], None).unwrap());
+        let other = BlockList {
+            nodes: [nodes[2]].into_iter().collect(),
+            repos: Default::default(),
+        };
+        assert_eq!(fresh.import(&other).unwrap(), 1);
+        assert_eq!(fresh.block_list().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_policy_log() {
+        let nid = arbitrary::gen::<NodeId>(1);
+        let rid = arbitrary::gen::<RepoId>(1);
+        let mut db = Store::open(":memory:").unwrap();
+
+        assert!(db.seed(&rid, Scope::All).unwrap());
+        assert!(db.follow(&nid, Some("eve")).unwrap());
+        assert!(!db.follow(&nid, Some("eve")).unwrap()); // No-op, not logged.
+        assert!(db.set_seed_policy(&rid, Policy::Block).unwrap());
+        assert!(db.unfollow(&nid).unwrap());
+        assert!(db.unseed(&rid).unwrap());
+        assert_eq!(
+            db.import(&BlockList {
+                nodes: [nid].into_iter().collect(),
+                repos: Default::default(),
+            })
+            .unwrap(),
+            1
+        );
+
+        let log = db.policy_log().unwrap();
+        let changes = log.iter().map(|e| e.change.clone()).collect::<Vec<_>>();
+        let sources = log.iter().map(|e| e.source).collect::<Vec<_>>();
+
+        assert_eq!(
+            changes,
+            vec![
+                PolicyChange::Seed {
+                    rid,
+                    scope: Scope::All
+                },
+                PolicyChange::Follow {
+                    nid,
+                    alias: Some(Alias::new("eve"))
+                },
+                PolicyChange::SeedPolicy {
+                    rid,
+                    policy: Policy::Block
+                },
+                PolicyChange::Unfollow { nid },
+                PolicyChange::Unseed { rid },
+                PolicyChange::FollowPolicy {
+                    nid,
+                    policy: Policy::Block
+                },
+            ]
+        );
+        assert_eq!(
+            sources,
+            vec![
+                PolicySource::Local,
+                PolicySource::Local,
+                PolicySource::Local,
+                PolicySource::Local,
+                PolicySource::Local,
+                PolicySource::Import,
+            ]
+        );
+        assert!(log.windows(2).all(|w| *w[0].timestamp <= *w[1].timestamp));
+    }
+
+    #[test]
+    fn test_block_list_invalid() {
+        assert_matches!(
+            BlockList::from_str(r#"{ "nodes": ["z6MkInvalid"] }"#),
+            Err(BlockListError::Node(n)) if n == "z6MkInvalid"
+        );
+        assert_matches!(
+            BlockList::from_str(r#"{ "repos": ["rad:zInvalid"] }"#),
+            Err(BlockListError::Repo(r)) if r == "rad:zInvalid"
+        );
+        assert_matches!(
+            BlockList::from_str(r#"{ "peers": [] }"#),
+            Err(BlockListError::Json(_))
+        );
+    }
 }
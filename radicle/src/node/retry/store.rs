@@ -0,0 +1,179 @@
+use std::num::TryFromIntError;
+
+use localtime::LocalTime;
+use sqlite as sql;
+use thiserror::Error;
+
+use crate::node::Database;
+use crate::node::NodeId;
+use crate::prelude::RepoId;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    /// An Internal error.
+    #[error("internal error: {0}")]
+    Internal(#[from] sql::Error),
+    /// Timestamp error.
+    #[error("invalid timestamp: {0}")]
+    Timestamp(#[from] TryFromIntError),
+    /// No rows returned in query result.
+    #[error("no rows returned")]
+    NoRows,
+}
+
+/// Fetch retry store.
+///
+/// Used to keep track of fetches that should be retried, eg. if the node is restarted before
+/// they complete.
+pub trait Store {
+    /// Add a fetch of a repository from a node, or update its timestamp if it's already there.
+    /// Only the `limit` most recent entries are kept. Returns `true` if the entry was added.
+    fn add(
+        &mut self,
+        repo: &RepoId,
+        node: &NodeId,
+        timestamp: LocalTime,
+        limit: usize,
+    ) -> Result<bool, Error>;
+    /// Remove a fetch of a repository from a node.
+    fn remove(&mut self, repo: &RepoId, node: &NodeId) -> Result<bool, Error>;
+    /// Get all fetches, oldest first.
+    fn entries(&self) -> Result<Vec<(RepoId, NodeId, LocalTime)>, Error>;
+    /// Remove fetches added before the given time. Returns the number of fetches removed.
+    fn prune(&mut self, before: LocalTime) -> Result<usize, Error>;
+    /// Return the number of fetches.
+    fn count(&self) -> Result<usize, Error>;
+}
+
+impl Store for Database {
+    fn add(
+        &mut self,
+        repo: &RepoId,
+        node: &NodeId,
+        timestamp: LocalTime,
+        limit: usize,
+    ) -> Result<bool, Error> {
+        let mut stmt = self.db.prepare(
+            "INSERT INTO `fetch-retries` (repo, node, timestamp)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT DO UPDATE
+             SET timestamp = ?3
+             WHERE timestamp < ?3",
+        )?;
+        stmt.bind((1, repo))?;
+        stmt.bind((2, node))?;
+        stmt.bind((3, i64::try_from(timestamp.as_millis())?))?;
+        stmt.next()?;
+
+        let added = self.db.change_count() > 0;
+
+        let mut stmt = self.db.prepare(
+            "DELETE FROM `fetch-retries` WHERE rowid NOT IN
+             (SELECT rowid FROM `fetch-retries` ORDER BY timestamp DESC LIMIT ?1)",
+        )?;
+        stmt.bind((1, i64::try_from(limit)?))?;
+        stmt.next()?;
+
+        Ok(added)
+    }
+
+    fn remove(&mut self, repo: &RepoId, node: &NodeId) -> Result<bool, Error> {
+        let mut stmt = self
+            .db
+            .prepare("DELETE FROM `fetch-retries` WHERE repo = ?1 AND node = ?2")?;
+
+        stmt.bind((1, repo))?;
+        stmt.bind((2, node))?;
+        stmt.next()?;
+
+        Ok(self.db.change_count() > 0)
+    }
+
+    fn entries(&self) -> Result<Vec<(RepoId, NodeId, LocalTime)>, Error> {
+        let stmt = self
+            .db
+            .prepare("SELECT repo, node, timestamp FROM `fetch-retries` ORDER BY timestamp")?;
+        let mut entries = Vec::new();
+
+        for row in stmt.into_iter() {
+            let row = row?;
+            let repo = row.try_read::<RepoId, _>("repo")?;
+            let node = row.try_read::<NodeId, _>("node")?;
+            let timestamp = row.try_read::<i64, _>("timestamp")?;
+            let timestamp = LocalTime::from_millis(timestamp as u128);
+
+            entries.push((repo, node, timestamp));
+        }
+        Ok(entries)
+    }
+
+    fn prune(&mut self, before: LocalTime) -> Result<usize, Error> {
+        let mut stmt = self
+            .db
+            .prepare("DELETE FROM `fetch-retries` WHERE timestamp < ?1")?;
+
+        stmt.bind((1, i64::try_from(before.as_millis())?))?;
+        stmt.next()?;
+
+        Ok(self.db.change_count())
+    }
+
+    fn count(&self) -> Result<usize, Error> {
+        let row = self
+            .db
+            .prepare("SELECT COUNT(*) FROM `fetch-retries`")?
+            .into_iter()
+            .next()
+            .ok_or(Error::NoRows)??;
+        let count = row.read::<i64, _>(0) as usize;
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::arbitrary;
+    use localtime::LocalDuration;
+
+    #[test]
+    fn test_add_remove() {
+        let mut db = Database::memory().unwrap();
+        let repo = arbitrary::gen::<RepoId>(1);
+        let node = arbitrary::gen::<NodeId>(1);
+        let now = LocalTime::now();
+
+        assert!(db.add(&repo, &node, now, 8).unwrap());
+        assert!(!db.add(&repo, &node, now, 8).unwrap());
+        assert!(db
+            .add(&repo, &node, now + LocalDuration::from_secs(1), 8)
+            .unwrap());
+        assert_eq!(db.count().unwrap(), 1);
+
+        assert!(db.remove(&repo, &node).unwrap());
+        assert!(!db.remove(&repo, &node).unwrap());
+        assert_eq!(db.count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_limit_and_prune() {
+        let mut db = Database::memory().unwrap();
+        let repos = arbitrary::set::<RepoId>(4..=4);
+        let node = arbitrary::gen::<NodeId>(1);
+        let now = LocalTime::from_secs(arbitrary::gen::<u32>(1) as u64);
+
+        for (i, repo) in repos.iter().enumerate() {
+            db.add(repo, &node, now + LocalDuration::from_secs(i as u64), 3)
+                .unwrap();
+        }
+        // The oldest entry was dropped.
+        let entries = db.entries().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].2, now + LocalDuration::from_secs(1));
+        assert_eq!(entries[2].2, now + LocalDuration::from_secs(3));
+
+        assert_eq!(db.prune(now + LocalDuration::from_secs(3)).unwrap(), 2);
+        assert_eq!(db.count().unwrap(), 1);
+    }
+}
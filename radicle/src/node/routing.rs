@@ -21,6 +21,17 @@ pub enum InsertResult {
     SeedAdded,
 }
 
+/// Aggregate counts of the routing table.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Stats {
+    /// Total number of routing entries.
+    pub entries: usize,
+    /// Number of distinct repositories seeded.
+    pub repos: usize,
+    /// Number of distinct seeding nodes.
+    pub nodes: usize,
+}
+
 /// An error occuring in peer-to-peer networking code.
 #[derive(Error, Debug)]
 pub enum Error {
@@ -61,6 +72,10 @@ pub trait Store {
     fn prune(&mut self, oldest: Timestamp, limit: Option<usize>) -> Result<usize, Error>;
     /// Count the number of routes for a specific repo RID.
     fn count(&self, id: &RepoId) -> Result<usize, Error>;
+    /// Count the number of repositories seeded by a specific node.
+    fn count_resources(&self, node: &NodeId) -> Result<usize, Error>;
+    /// Get aggregate counts of the routing table.
+    fn stats(&self) -> Result<Stats, Error>;
 }
 
 impl Store for Database {
@@ -216,6 +231,44 @@ impl Store for Database {
 
         Ok(count)
     }
+
+    fn count_resources(&self, node: &NodeId) -> Result<usize, Error> {
+        let mut stmt = self
+            .db
+            .prepare("SELECT COUNT(*) FROM routing WHERE node = ?")?;
+
+        stmt.bind((1, node))?;
+
+        let count: i64 = stmt
+            .into_iter()
+            .next()
+            .expect("COUNT will always return a single row")?
+            .read(0);
+
+        let count: usize = count.try_into().map_err(|_| Error::UnitOverflow)?;
+
+        Ok(count)
+    }
+
+    fn stats(&self) -> Result<Stats, Error> {
+        let row = self
+            .db
+            .prepare("SELECT COUNT(*), COUNT(DISTINCT repo), COUNT(DISTINCT node) FROM routing")?
+            .into_iter()
+            .next()
+            .expect("COUNT will always return a single row")?;
+        let count = |i: usize| -> Result<usize, Error> {
+            row.read::<i64, _>(i)
+                .try_into()
+                .map_err(|_| Error::UnitOverflow)
+        };
+
+        Ok(Stats {
+            entries: count(0)?,
+            repos: count(1)?,
+            nodes: count(2)?,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -274,6 +327,33 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_counts() {
+        let ids = Vec::from_iter(arbitrary::set::<RepoId>(3..=3));
+        let nodes = Vec::from_iter(arbitrary::set::<NodeId>(2..=2));
+        let mut db = database(":memory:");
+
+        assert_eq!(db.stats().unwrap(), Stats::default());
+
+        db.insert(&ids, nodes[0], Timestamp::EPOCH).unwrap();
+        db.insert(&ids[..1], nodes[1], Timestamp::EPOCH).unwrap();
+
+        assert_eq!(db.get(&ids[0]).unwrap(), nodes.iter().copied().collect());
+        assert_eq!(db.get(&ids[1]).unwrap(), HashSet::from([nodes[0]]));
+        assert_eq!(db.get_resources(&nodes[1]).unwrap(), HashSet::from([ids[0]]));
+        assert_eq!(db.count(&ids[0]).unwrap(), 2);
+        assert_eq!(db.count_resources(&nodes[0]).unwrap(), 3);
+        assert_eq!(db.count_resources(&nodes[1]).unwrap(), 1);
+        assert_eq!(
+            db.stats().unwrap(),
+            Stats {
+                entries: 4,
+                repos: 3,
+                nodes: 2,
+            }
+        );
+    }
+
     #[test]
     fn test_entries() {
         let ids = arbitrary::set::<RepoId>(6..9);
@@ -3,6 +3,7 @@
 //!   $RAD_HOME/                                 # Radicle home
 //!     storage/                                 # Storage root
 //!       zEQNunJUqkNahQ8VvQYuWZZV7EJB/          # Project git repository
+//!         config.json                          # Project configuration overrides
 //!       ...                                    # More projects...
 //!     keys/
 //!       radicle                                # Secret key (PKCS 8)
@@ -10,6 +11,15 @@
 //!     node/
 //!       control.sock                           # Node control socket
 //!
+//! Besides the default home, named profiles can be created under a common root, each with its
+//! own home. One of them can be made active, in which case it is used instead of the default
+//! home, unless `RAD_HOME` is set:
+//!
+//!   $HOME/.radicle/profiles/                   # Named profiles root
+//!     active                                   # Name of the active profile
+//!     alice/                                   # Home of the `alice` profile
+//!     ...                                      # More profiles...
+//!
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::{fs, io};
@@ -19,16 +29,18 @@ use thiserror::Error;
 
 use crate::crypto::ssh::agent::Agent;
 use crate::crypto::ssh::{keystore, Keystore, Passphrase};
-use crate::crypto::{PublicKey, Signer};
+use crate::crypto::{external, PublicKey, Signer};
+use crate::node::HostName;
 use crate::explorer::Explorer;
 use crate::node::policy::config::store::Read;
 use crate::node::{notifications, policy, Alias, AliasStore};
 use crate::prelude::Did;
 use crate::prelude::NodeId;
+use crate::prelude::RepoId;
 use crate::storage::git::transport;
 use crate::storage::git::Storage;
 use crate::storage::{self, ReadRepository};
-use crate::{cli, cob, git, node, web};
+use crate::{cli, cob, git, node, review, web};
 
 /// Environment variables used by radicle.
 pub mod env {
@@ -36,15 +48,37 @@ pub mod env {
 
     /// Path to the radicle home folder.
     pub const RAD_HOME: &str = "RAD_HOME";
+    /// Name of the radicle profile to use. Ignored if `RAD_HOME` is set.
+    pub const RAD_PROFILE: &str = "RAD_PROFILE";
     /// Path to the radicle node socket file.
     pub const RAD_SOCKET: &str = "RAD_SOCKET";
     /// Passphrase for the encrypted radicle secret key.
     pub const RAD_PASSPHRASE: &str = "RAD_PASSPHRASE";
+    /// Path to a file containing the passphrase for the encrypted radicle secret key.
+    pub const RAD_PASSPHRASE_FILE: &str = "RAD_PASSPHRASE_FILE";
+    /// Shell command that outputs the passphrase for the encrypted radicle secret key.
+    pub const RAD_PASSPHRASE_COMMAND: &str = "RAD_PASSPHRASE_COMMAND";
     /// RNG seed. Must be convertible to a `u64`.
     pub const RAD_RNG_SEED: &str = "RAD_RNG_SEED";
     /// Show radicle hints.
     pub const RAD_HINT: &str = "RAD_HINT";
 
+    // Configuration overrides. When set, these take precedence over the values of the
+    // configuration file, which in turn take precedence over the defaults.
+
+    /// Overrides `node.alias`.
+    pub const RAD_NODE_ALIAS: &str = "RAD_NODE_ALIAS";
+    /// Overrides `node.listen`. A comma-separated list of socket addresses.
+    pub const RAD_NODE_LISTEN: &str = "RAD_NODE_LISTEN";
+    /// Overrides `node.externalAddresses`. A comma-separated list of addresses.
+    pub const RAD_NODE_EXTERNAL_ADDRESSES: &str = "RAD_NODE_EXTERNAL_ADDRESSES";
+    /// Overrides `node.relay`. Either `true` or `false`.
+    pub const RAD_NODE_RELAY: &str = "RAD_NODE_RELAY";
+    /// Overrides `node.workers`.
+    pub const RAD_NODE_WORKERS: &str = "RAD_NODE_WORKERS";
+    /// Overrides `node.limits.fetchConcurrency`.
+    pub const RAD_NODE_FETCH_CONCURRENCY: &str = "RAD_NODE_FETCH_CONCURRENCY";
+
     /// Whether or not to show hints.
     pub fn hints() -> bool {
         var(RAD_HINT).is_ok()
@@ -119,9 +153,116 @@ pub enum ConfigError {
     Io(PathBuf, io::Error),
     #[error("failed to load configuration from {0}: {1}")]
     Load(PathBuf, serde_json::Error),
+    #[error("invalid configuration in {0}: {1}")]
+    Invalid(PathBuf, ValidationErrors),
+    #[error("invalid configuration override: {0}")]
+    Env(#[from] FieldError),
+}
+
+/// An invalid configuration field.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("`{field}` {reason}")]
+pub struct FieldError {
+    /// Path of the field, eg. `node.limits.fetchConcurrency`.
+    pub field: String,
+    /// Why the field is invalid.
+    pub reason: String,
+}
+
+impl FieldError {
+    /// Create a new field error.
+    pub fn new(field: impl ToString, reason: impl ToString) -> Self {
+        Self {
+            field: field.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// The invalid fields of a configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors(Vec<FieldError>);
+
+impl ValidationErrors {
+    /// Check a field, adding an error if the condition doesn't hold.
+    fn check(&mut self, valid: bool, field: impl ToString, reason: impl ToString) {
+        if !valid {
+            self.0.push(FieldError::new(field, reason));
+        }
+    }
+
+    /// Iterate over the field errors.
+    pub fn iter(&self) -> impl Iterator<Item = &FieldError> {
+        self.0.iter()
+    }
+
+    /// Whether there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let errors = self.0.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+
+        write!(f, "{}", errors.join("; "))
+    }
+}
+
+impl IntoIterator for ValidationErrors {
+    type Item = FieldError;
+    type IntoIter = std::vec::IntoIter<FieldError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Parse the value of an environment variable.
+fn parse<T>(name: &str, value: &str) -> Result<T, FieldError>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    value
+        .trim()
+        .parse()
+        .map_err(|e| FieldError::new(name, format!("has invalid value '{value}': {e}")))
+}
+
+/// Parse the value of an environment variable as a comma-separated list.
+fn parse_list<T>(name: &str, value: &str) -> Result<Vec<T>, FieldError>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| parse(name, v))
+        .collect()
+}
+
+/// Merge a JSON value into another. Objects are merged key by key, while any other value
+/// replaces the existing one.
+fn merge(base: &mut serde_json::Value, overrides: serde_json::Value) {
+    match (base, overrides) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                merge(base.entry(key).or_insert(serde_json::Value::Null), value);
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
 }
 
 /// Local radicle configuration.
+///
+/// Values are taken, in order of precedence, from the environment (see [`env`]), the
+/// repository overrides (see [`Config::load_repo`]), the configuration file, and finally
+/// the defaults.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Config {
@@ -138,6 +279,9 @@ pub struct Config {
     /// CLI configuration.
     #[serde(default)]
     pub cli: cli::Config,
+    /// Review configuration.
+    #[serde(default, skip_serializing_if = "review::Config::is_empty")]
+    pub review: review::Config,
     /// Node configuration.
     pub node: node::Config,
 }
@@ -152,6 +296,7 @@ impl Config {
             preferred_seeds: node.network.public_seeds(),
             web: web::Config::default(),
             cli: cli::Config::default(),
+            review: review::Config::default(),
             node,
         }
     }
@@ -164,13 +309,202 @@ impl Config {
         Ok(cfg)
     }
 
-    /// Load a configuration from the given path.
+    /// Load a configuration from the given path, apply the overrides set in the
+    /// environment, and validate it.
     pub fn load(path: &Path) -> Result<Self, ConfigError> {
-        match fs::File::open(path) {
-            Ok(cfg) => {
-                serde_json::from_reader(cfg).map_err(|e| ConfigError::Load(path.to_path_buf(), e))
-            }
-            Err(e) => Err(ConfigError::Io(path.to_path_buf(), e)),
+        Self::load_with(path, |name| env::var(name).ok())
+    }
+
+    /// Like [`Config::load`], but with the given environment lookup function.
+    fn load_with(path: &Path, var: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+        let mut cfg: Self = match fs::File::open(path) {
+            Ok(cfg) => serde_json::from_reader(cfg)
+                .map_err(|e| ConfigError::Load(path.to_path_buf(), e))?,
+            Err(e) => return Err(ConfigError::Io(path.to_path_buf(), e)),
+        };
+        cfg.apply_env(var)?;
+        cfg.validate()
+            .map_err(|e| ConfigError::Invalid(path.to_path_buf(), e))?;
+
+        Ok(cfg)
+    }
+
+    /// Get the configuration of a repository, by applying the overrides stored at the given
+    /// path on top of this configuration. If there is no such file, this configuration is
+    /// returned unchanged.
+    ///
+    /// The overrides are a partial configuration: objects are merged field by field, while
+    /// other values, including lists, replace the ones of this configuration. Node settings
+    /// apply to the whole node, and can't be overridden.
+    pub fn load_repo(&self, path: &Path) -> Result<Self, ConfigError> {
+        let overrides: serde_json::Value = match fs::File::open(path) {
+            Ok(file) => serde_json::from_reader(file)
+                .map_err(|e| ConfigError::Load(path.to_path_buf(), e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(self.clone()),
+            Err(e) => return Err(ConfigError::Io(path.to_path_buf(), e)),
+        };
+        if overrides.get("node").is_some() {
+            return Err(ConfigError::Invalid(
+                path.to_path_buf(),
+                ValidationErrors(vec![FieldError::new(
+                    "node",
+                    "can't be overridden per repository",
+                )]),
+            ));
+        }
+        let mut cfg =
+            serde_json::to_value(self).map_err(|e| ConfigError::Load(path.to_path_buf(), e))?;
+        merge(&mut cfg, overrides);
+
+        let cfg: Self =
+            serde_json::from_value(cfg).map_err(|e| ConfigError::Load(path.to_path_buf(), e))?;
+        cfg.validate()
+            .map_err(|e| ConfigError::Invalid(path.to_path_buf(), e))?;
+
+        Ok(cfg)
+    }
+
+    /// Override configuration values with the ones set in the environment, using
+    /// the given lookup function. See the `RAD_NODE_*` variables in [`env`].
+    ///
+    /// Values are only parsed here; use [`Config::validate`] to check the result.
+    pub fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), FieldError> {
+        let node = &mut self.node;
+
+        if let Some(value) = var(env::RAD_NODE_ALIAS) {
+            node.alias = parse(env::RAD_NODE_ALIAS, &value)?;
+        }
+        if let Some(value) = var(env::RAD_NODE_LISTEN) {
+            node.listen = parse_list(env::RAD_NODE_LISTEN, &value)?;
+        }
+        if let Some(value) = var(env::RAD_NODE_EXTERNAL_ADDRESSES) {
+            node.external_addresses = parse_list(env::RAD_NODE_EXTERNAL_ADDRESSES, &value)?;
+        }
+        if let Some(value) = var(env::RAD_NODE_RELAY) {
+            node.relay = parse(env::RAD_NODE_RELAY, &value)?;
+        }
+        if let Some(value) = var(env::RAD_NODE_WORKERS) {
+            node.workers = parse(env::RAD_NODE_WORKERS, &value)?;
+        }
+        if let Some(value) = var(env::RAD_NODE_FETCH_CONCURRENCY) {
+            node.limits.fetch_concurrency = parse(env::RAD_NODE_FETCH_CONCURRENCY, &value)?;
+        }
+        Ok(())
+    }
+
+    /// Check that the configuration values are within range and usable, beyond what is
+    /// checked when parsing. Returns all the invalid fields.
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        use crate::node::config::PeerConfig;
+
+        let mut errors = ValidationErrors::default();
+        let node = &self.node;
+        let limits = &node.limits;
+
+        for (i, addr) in node.listen.iter().enumerate() {
+            errors.check(
+                !node.listen[..i].contains(addr),
+                format!("node.listen[{i}]"),
+                format!("duplicates listen address {addr}"),
+            );
+        }
+        for (i, addr) in node.external_addresses.iter().enumerate() {
+            errors.check(
+                addr.port != 0,
+                format!("node.externalAddresses[{i}]"),
+                "must have a non-zero port",
+            );
+            errors.check(
+                !matches!(addr.host, HostName::Ip(ip) if ip.is_unspecified()),
+                format!("node.externalAddresses[{i}]"),
+                "must not be an unspecified address",
+            );
+        }
+        for peer in &node.connect {
+            errors.check(
+                peer.addr.port != 0,
+                "node.connect",
+                format!("address {} must have a non-zero port", peer.addr),
+            );
+        }
+        for (i, seed) in self.preferred_seeds.iter().enumerate() {
+            errors.check(
+                seed.addr.port != 0,
+                format!("preferredSeeds[{i}]"),
+                "must have a non-zero port",
+            );
+        }
+        if let PeerConfig::Dynamic { target } = node.peers {
+            errors.check(
+                target <= limits.connection.outbound,
+                "node.peers.target",
+                format!(
+                    "must not exceed the outbound connection limit ({})",
+                    limits.connection.outbound
+                ),
+            );
+        }
+        errors.check(
+            limits.connection.min_outbound <= limits.connection.outbound,
+            "node.limits.connection.minOutbound",
+            format!(
+                "must not exceed the outbound connection limit ({})",
+                limits.connection.outbound
+            ),
+        );
+        errors.check(node.workers > 0, "node.workers", "must be greater than zero");
+        errors.check(
+            limits.routing_max_size > 0,
+            "node.limits.routingMaxSize",
+            "must be greater than zero",
+        );
+        errors.check(
+            limits.fetch_concurrency > 0,
+            "node.limits.fetchConcurrency",
+            "must be greater than zero",
+        );
+        errors.check(
+            limits.max_open_files > 0,
+            "node.limits.maxOpenFiles",
+            "must be greater than zero",
+        );
+        for (name, rate) in [
+            ("inbound", &limits.rate.inbound),
+            ("outbound", &limits.rate.outbound),
+        ] {
+            errors.check(
+                rate.fill_rate.is_finite() && rate.fill_rate > 0.,
+                format!("node.limits.rate.{name}.fillRate"),
+                "must be a positive number",
+            );
+            errors.check(
+                rate.capacity > 0,
+                format!("node.limits.rate.{name}.capacity"),
+                "must be greater than zero",
+            );
+        }
+        errors.check(
+            limits.quota.refs > 0,
+            "node.limits.quota.refs",
+            "must be greater than zero",
+        );
+        errors.check(
+            limits.quota.bytes > 0,
+            "node.limits.quota.bytes",
+            "must be greater than zero",
+        );
+        if let Some(template) = &self.cli.patch_template {
+            errors.check(
+                template.is_relative(),
+                "cli.patchTemplate",
+                "must be relative to the repository root",
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
         }
     }
 
@@ -209,7 +543,38 @@ impl Profile {
     pub fn init(home: Home, alias: Alias, passphrase: Option<Passphrase>) -> Result<Self, Error> {
         let keystore = Keystore::new(&home.keys());
         let public_key = keystore.init("radicle", passphrase)?;
-        let config = Config::init(alias.clone(), home.config().as_path())?;
+        let config = Config::init(alias, home.config().as_path())?;
+
+        Self::setup(home, keystore, public_key, config)
+    }
+
+    /// Initialize a profile with an existing secret key, as exported by [`Keystore::export`].
+    ///
+    /// If a key already exists in `home`, it is only replaced if `force` is set. An
+    /// existing configuration is kept as-is, otherwise a new one is created with the
+    /// given alias.
+    pub fn import(home: Home, alias: Alias, secret: &str, force: bool) -> Result<Self, Error> {
+        let keystore = Keystore::new(&home.keys());
+        let public_key = keystore.import(secret, force)?;
+        let config = match Config::load(home.config().as_path()) {
+            Ok(config) => config,
+            Err(ConfigError::Io(_, e)) if e.kind() == io::ErrorKind::NotFound => {
+                Config::init(alias, home.config().as_path())?
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        Self::setup(home, keystore, public_key, config)
+    }
+
+    /// Create the storage and databases of a newly initialized profile.
+    fn setup(
+        home: Home,
+        keystore: Keystore,
+        public_key: PublicKey,
+        config: Config,
+    ) -> Result<Self, Error> {
+        let alias = config.alias().clone();
         let storage = Storage::open(
             home.storage(),
             git::UserInfo {
@@ -233,8 +598,18 @@ impl Profile {
         })
     }
 
+    /// Load the profile of the current home. See [`home`].
     pub fn load() -> Result<Self, Error> {
-        let home = self::home()?;
+        Self::load_from(self::home()?)
+    }
+
+    /// Load a named profile. See [`Profiles`].
+    pub fn load_named(name: &str) -> Result<Self, Error> {
+        profiles()?.load(name)
+    }
+
+    /// Load the profile stored in the given home.
+    pub fn load_from(home: Home) -> Result<Self, Error> {
         let keystore = Keystore::new(&home.keys());
         let public_key = keystore
             .public_key()?
@@ -281,7 +656,14 @@ impl Profile {
         Did::from(self.public_key)
     }
 
+    /// Get the profile's signer.
+    ///
+    /// An [`external`] signer registered for the profile's key takes precedence over the
+    /// keystore and the SSH agent.
     pub fn signer(&self) -> Result<Box<dyn Signer>, Error> {
+        if let Some(signer) = external::signer(&self.public_key) {
+            return Ok(Box::new(signer));
+        }
         if !self.keystore.is_encrypted()? {
             let signer = keystore::MemorySigner::load(&self.keystore, None)?;
             return Ok(signer.boxed());
@@ -321,6 +703,14 @@ impl Profile {
         Ok(config)
     }
 
+    /// Get the configuration of a repository, ie. the profile configuration with the
+    /// repository's overrides applied. See [`Config::load_repo`].
+    pub fn config_for(&self, rid: &RepoId) -> Result<Config, Error> {
+        let config = self.config.load_repo(&self.home.repo_config(rid))?;
+
+        Ok(config)
+    }
+
     /// Return a multi-source store for aliases.
     pub fn aliases(&self) -> Aliases {
         let policies = self.home.policies().ok();
@@ -348,6 +738,10 @@ impl AliasStore for Profile {
     fn alias(&self, nid: &NodeId) -> Option<Alias> {
         self.aliases().alias(nid)
     }
+
+    fn claimants(&self, alias: &Alias) -> Vec<NodeId> {
+        self.aliases().claimants(alias)
+    }
 }
 
 /// Holds multiple alias stores, and will try
@@ -366,14 +760,44 @@ impl AliasStore for Aliases {
             .and_then(|db| db.alias(nid))
             .or_else(|| self.db.as_ref().and_then(|db| db.alias(nid)))
     }
+
+    /// Retrieve the claimants of `alias` from `addresses.db`.
+    fn claimants(&self, alias: &Alias) -> Vec<NodeId> {
+        self.db
+            .as_ref()
+            .map(|db| AliasStore::claimants(db, alias))
+            .unwrap_or_default()
+    }
 }
 
 /// Get the path to the radicle home folder.
+///
+/// This is, in order of precedence: `RAD_HOME`, the profile named by `RAD_PROFILE`, the
+/// active named profile, and finally `$HOME/.radicle`.
 pub fn home() -> Result<Home, io::Error> {
     if let Some(home) = env::var_os(env::RAD_HOME) {
-        Ok(Home::new(PathBuf::from(home))?)
-    } else if let Some(home) = env::var_os("HOME") {
-        Ok(Home::new(PathBuf::from(home).join(".radicle"))?)
+        return Home::new(PathBuf::from(home));
+    }
+    let profiles = profiles()?;
+
+    if let Ok(name) = env::var(env::RAD_PROFILE) {
+        profiles.home(&name)
+    } else if let Some(name) = profiles.active()? {
+        profiles.home(&name)
+    } else {
+        Home::new(default_home()?)
+    }
+}
+
+/// Get the named profiles, rooted in the default home.
+pub fn profiles() -> Result<Profiles, io::Error> {
+    default_home().map(|home| Profiles::new(home.join("profiles")))
+}
+
+/// The default radicle home, `$HOME/.radicle`.
+fn default_home() -> Result<PathBuf, io::Error> {
+    if let Some(home) = env::var_os("HOME") {
+        Ok(PathBuf::from(home).join(".radicle"))
     } else {
         Err(io::Error::new(
             io::ErrorKind::NotFound,
@@ -382,6 +806,124 @@ pub fn home() -> Result<Home, io::Error> {
     }
 }
 
+/// Named profiles, each with their own [`Home`], under a common root.
+#[derive(Debug, Clone)]
+pub struct Profiles {
+    root: PathBuf,
+}
+
+impl Profiles {
+    /// Name of the file holding the name of the active profile.
+    const ACTIVE: &'static str = "active";
+
+    /// Create a new handle on the named profiles under `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Get the root of the named profiles.
+    pub fn root(&self) -> &Path {
+        self.root.as_path()
+    }
+
+    /// Get the home of a named profile, creating it if necessary.
+    pub fn home(&self, name: &str) -> Result<Home, io::Error> {
+        Home::new(self.path(name)?)
+    }
+
+    /// Check whether a named profile exists.
+    pub fn exists(&self, name: &str) -> Result<bool, io::Error> {
+        Ok(self.path(name)?.is_dir())
+    }
+
+    /// List the names of all profiles, in lexicographic order.
+    pub fn list(&self) -> Result<Vec<String>, io::Error> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+        let mut names = Vec::new();
+
+        for entry in entries {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            if let Some(name) = entry.file_name().to_str() {
+                names.push(name.to_owned());
+            }
+        }
+        names.sort();
+
+        Ok(names)
+    }
+
+    /// Get the name of the active profile, if any.
+    pub fn active(&self) -> Result<Option<String>, io::Error> {
+        match fs::read_to_string(self.root.join(Self::ACTIVE)) {
+            Ok(name) => Ok(Some(name.trim().to_owned())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Make a profile the active one. The profile must exist.
+    pub fn activate(&self, name: &str) -> Result<(), io::Error> {
+        if !self.exists(name)? {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("profile '{name}' does not exist"),
+            ));
+        }
+        fs::write(self.root.join(Self::ACTIVE), format!("{name}\n"))
+    }
+
+    /// Unset the active profile, reverting to the default home.
+    pub fn deactivate(&self) -> Result<(), io::Error> {
+        match fs::remove_file(self.root.join(Self::ACTIVE)) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Initialize a new named profile.
+    pub fn init(
+        &self,
+        name: &str,
+        alias: Alias,
+        passphrase: Option<Passphrase>,
+    ) -> Result<Profile, Error> {
+        Profile::init(self.home(name)?, alias, passphrase)
+    }
+
+    /// Load a named profile.
+    pub fn load(&self, name: &str) -> Result<Profile, Error> {
+        if !self.exists(name)? {
+            return Err(Error::NotFound(self.root.join(name)));
+        }
+        Profile::load_from(self.home(name)?)
+    }
+
+    /// Get the path of a named profile, checking that the name is valid.
+    fn path(&self, name: &str) -> Result<PathBuf, io::Error> {
+        let valid = !name.is_empty()
+            && !name.starts_with('.')
+            && name != Self::ACTIVE
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+        if !valid {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid profile name '{name}'"),
+            ));
+        }
+        Ok(self.root.join(name))
+    }
+}
+
 /// Radicle home.
 #[derive(Debug, Clone)]
 pub struct Home {
@@ -438,6 +980,11 @@ impl Home {
         self.path.join("config.json")
     }
 
+    /// Path to the configuration overrides of a repository. See [`Config::load_repo`].
+    pub fn repo_config(&self, rid: &RepoId) -> PathBuf {
+        self.storage().join(rid.canonical()).join("config.json")
+    }
+
     pub fn keys(&self) -> PathBuf {
         self.path.join("keys")
     }
@@ -562,8 +1109,14 @@ impl Home {
 #[cfg(not(target_os = "macos"))]
 mod test {
     use std::fs;
+    use std::path::PathBuf;
 
-    use super::Home;
+    use super::{env, Config, ConfigError, Home, Profiles};
+    use crate::node::config::PeerConfig;
+    use crate::node::Alias;
+    use crate::prelude::RepoId;
+    use crate::review;
+    use crate::test::arbitrary;
 
     // Checks that if we have:
     // '/run/user/1000/.tmpqfK6ih/../.tmpqfK6ih/Radicle/Home'
@@ -588,4 +1141,196 @@ mod test {
 
         assert_eq!(home.path, path);
     }
+
+    #[test]
+    fn test_named_profiles() {
+        let tmp = tempfile::tempdir().unwrap();
+        let profiles = Profiles::new(tmp.path().join("profiles"));
+
+        assert!(profiles.list().unwrap().is_empty());
+        assert_eq!(profiles.active().unwrap(), None);
+
+        let alice = profiles.init("alice", Alias::new("alice"), None).unwrap();
+        let bob = profiles.init("bob", Alias::new("bob"), None).unwrap();
+
+        assert_ne!(alice.id(), bob.id());
+        assert_eq!(profiles.list().unwrap(), vec!["alice", "bob"]);
+
+        profiles.activate("alice").unwrap();
+        let active = profiles.active().unwrap().unwrap();
+        assert_eq!(active, "alice");
+        assert_eq!(profiles.load(&active).unwrap().id(), alice.id());
+
+        profiles.activate("bob").unwrap();
+        let active = profiles.active().unwrap().unwrap();
+        assert_eq!(active, "bob");
+        assert_eq!(profiles.load(&active).unwrap().id(), bob.id());
+        assert_eq!(profiles.load(&active).unwrap().config.alias(), &Alias::new("bob"));
+
+        // Unknown and invalid profiles can't be activated or loaded.
+        profiles.activate("carol").unwrap_err();
+        profiles.load("carol").unwrap_err();
+        profiles.activate("../bob").unwrap_err();
+        assert_eq!(profiles.active().unwrap().as_deref(), Some("bob"));
+
+        profiles.deactivate().unwrap();
+        assert_eq!(profiles.active().unwrap(), None);
+    }
+
+    #[test]
+    fn test_config_validate() {
+        let config = Config::new(Alias::new("alice"));
+        assert_eq!(config.validate(), Ok(()));
+
+        let mut config = Config::new(Alias::new("alice"));
+        config.node.workers = 0;
+        config.node.peers = PeerConfig::Dynamic { target: 32 };
+        config.node.limits.rate.inbound.fill_rate = -1.;
+        config.node.listen = vec![
+            ([0, 0, 0, 0], 8776).into(),
+            ([0, 0, 0, 0], 8776).into(),
+        ];
+        config.node.external_addresses = vec!["0.0.0.0:8776".parse().unwrap()];
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(
+            errors.iter().map(|e| e.field.as_str()).collect::<Vec<_>>(),
+            vec![
+                "node.listen[1]",
+                "node.externalAddresses[0]",
+                "node.peers.target",
+                "node.workers",
+                "node.limits.rate.inbound.fillRate",
+            ]
+        );
+        assert_eq!(
+            errors.iter().next().unwrap().to_string(),
+            "`node.listen[1]` duplicates listen address 0.0.0.0:8776"
+        );
+    }
+
+    #[test]
+    fn test_config_env_override() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.json");
+        let mut config = Config::new(Alias::new("alice"));
+        config.node.workers = 4;
+        config.node.listen = vec![([0, 0, 0, 0], 8776).into()];
+        config.write(&path).unwrap();
+
+        let config = Config::load_with(&path, |name| match name {
+            env::RAD_NODE_WORKERS => Some(String::from("16")),
+            env::RAD_NODE_LISTEN => Some(String::from("127.0.0.1:9000, [::]:9000")),
+            _ => None,
+        })
+        .unwrap();
+
+        assert_eq!(config.node.workers, 16);
+        assert_eq!(
+            config.node.listen,
+            vec![
+                "127.0.0.1:9000".parse().unwrap(),
+                "[::]:9000".parse().unwrap()
+            ]
+        );
+        assert_eq!(config.node.alias, Alias::new("alice"));
+    }
+
+    #[test]
+    fn test_config_env_override_invalid() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.json");
+        Config::new(Alias::new("alice")).write(&path).unwrap();
+
+        match Config::load_with(&path, |name| {
+            (name == env::RAD_NODE_WORKERS).then(|| String::from("many"))
+        }) {
+            Err(ConfigError::Env(e)) => assert_eq!(e.field, env::RAD_NODE_WORKERS),
+            other => panic!("unexpected result: {other:?}"),
+        }
+
+        // Overrides are validated like file values.
+        match Config::load_with(&path, |name| {
+            (name == env::RAD_NODE_WORKERS).then(|| String::from("0"))
+        }) {
+            Err(ConfigError::Invalid(_, errors)) => {
+                assert_eq!(errors.to_string(), "`node.workers` must be greater than zero");
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_config_load_invalid() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.json");
+        let mut config = Config::new(Alias::new("alice"));
+        config.node.limits.fetch_concurrency = 0;
+        config.write(&path).unwrap();
+
+        match Config::load(&path) {
+            Err(ConfigError::Invalid(p, errors)) => {
+                assert_eq!(p, path);
+                assert_eq!(
+                    errors.to_string(),
+                    "`node.limits.fetchConcurrency` must be greater than zero"
+                );
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_config_repo_override() {
+        let tmp = tempfile::tempdir().unwrap();
+        let profiles = Profiles::new(tmp.path().join("profiles"));
+        let mut profile = profiles.init("alice", Alias::new("alice"), None).unwrap();
+        let rid = arbitrary::gen::<RepoId>(1);
+        let other = arbitrary::gen::<RepoId>(2);
+
+        profile.config.cli.hints = false;
+        profile.config.cli.patch_template = Some(PathBuf::from("PATCH.md"));
+        profile.config.review.rules = vec![review::Rule {
+            paths: vec![String::from("src/**")],
+            labels: vec![],
+            reviewers: vec![],
+        }];
+
+        let path = profile.home.repo_config(&rid);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            r#"{
+                "cli": { "patchTemplate": "docs/PATCH.md" },
+                "review": { "rules": [{ "paths": ["docs/**"], "reviewers": [] }] }
+            }"#,
+        )
+        .unwrap();
+
+        // The repository overrides win over the global values.
+        let config = profile.config_for(&rid).unwrap();
+        assert_eq!(config.cli.patch_template(), PathBuf::from("docs/PATCH.md"));
+        assert_eq!(config.review.rules[0].paths, vec!["docs/**"]);
+        // Values that aren't overridden are kept.
+        assert!(!config.cli.hints);
+        assert_eq!(config.alias(), &Alias::new("alice"));
+
+        // Repositories without overrides use the global values.
+        let config = profile.config_for(&other).unwrap();
+        assert_eq!(config.cli.patch_template(), PathBuf::from("PATCH.md"));
+        assert_eq!(config.review.rules[0].paths, vec!["src/**"]);
+
+        // Node settings can't be overridden.
+        fs::write(&path, r#"{ "node": { "workers": 1 } }"#).unwrap();
+        match profile.config.load_repo(&path) {
+            Err(ConfigError::Invalid(p, errors)) => {
+                assert_eq!(p, path);
+                assert_eq!(
+                    errors.to_string(),
+                    "`node` can't be overridden per repository"
+                );
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
 }
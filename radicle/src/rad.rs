@@ -1,5 +1,6 @@
 #![allow(clippy::let_unit_value)]
 use std::io;
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::str::FromStr;
 
@@ -213,6 +214,20 @@ pub fn checkout<P: AsRef<Path>, S: storage::ReadStorage>(
     remote: &RemoteId,
     path: P,
     storage: &S,
+) -> Result<git2::Repository, CheckoutError> {
+    checkout_shallow(proj, remote, path, storage, None)
+}
+
+/// Like [`checkout`], but if a depth is given, only the last `depth` commits of each branch
+/// are fetched into the working copy. This effectively does a `git clone --depth` from storage.
+///
+/// Storage itself is unaffected, and keeps the full history.
+pub fn checkout_shallow<P: AsRef<Path>, S: storage::ReadStorage>(
+    proj: RepoId,
+    remote: &RemoteId,
+    path: P,
+    storage: &S,
+    depth: Option<NonZeroUsize>,
 ) -> Result<git2::Repository, CheckoutError> {
     // TODO: Decide on whether we can use `clone_local`
     // TODO: Look into sharing object databases.
@@ -232,7 +247,7 @@ pub fn checkout<P: AsRef<Path>, S: storage::ReadStorage>(
         &url,
         &url.clone().with_namespace(*remote),
     )?;
-    git::fetch(&repo, &REMOTE_NAME).map_err(CheckoutError::Fetch)?;
+    git::fetch_shallow(&repo, &REMOTE_NAME, depth).map_err(CheckoutError::Fetch)?;
 
     {
         // Setup default branch.
@@ -0,0 +1,128 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cob::Label;
+use crate::prelude::Did;
+
+/// Review configuration.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Rules used to suggest reviewers for patches.
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+impl Config {
+    /// Whether there are no review rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Reviewers suggested by the rules for a change touching the given paths and having
+    /// the given labels. Reviewers are returned in the order of the rules, without duplicates.
+    pub fn reviewers(&self, paths: &[impl AsRef<Path>], labels: &[Label]) -> Vec<Did> {
+        let mut reviewers = Vec::new();
+
+        for rule in &self.rules {
+            if !rule.matches(paths, labels) {
+                continue;
+            }
+            for reviewer in &rule.reviewers {
+                if !reviewers.contains(reviewer) {
+                    reviewers.push(*reviewer);
+                }
+            }
+        }
+        reviewers
+    }
+}
+
+/// A review rule, mapping paths and labels to reviewers.
+///
+/// A rule matches a change if any of its path globs matches a changed path, or if any of
+/// its labels is on the change.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    /// Path globs, relative to the repository root, eg. `src/**/*.rs`.
+    ///
+    /// `*` and `?` match within a path component, while `**` matches across components.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    /// Labels.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<Label>,
+    /// Reviewers suggested when the rule matches.
+    pub reviewers: Vec<Did>,
+}
+
+impl Rule {
+    /// Check whether the rule matches a change touching the given paths and having
+    /// the given labels.
+    pub fn matches(&self, paths: &[impl AsRef<Path>], labels: &[Label]) -> bool {
+        if labels.iter().any(|l| self.labels.contains(l)) {
+            return true;
+        }
+        paths.iter().any(|path| {
+            let path = path.as_ref().to_string_lossy();
+
+            self.paths
+                .iter()
+                .any(|glob| glob_matches(glob.as_bytes(), path.as_bytes()))
+        })
+    }
+}
+
+/// Match a path against a glob.
+fn glob_matches(glob: &[u8], path: &[u8]) -> bool {
+    match glob {
+        [] => path.is_empty(),
+        // Matches zero or more leading path components.
+        [b'*', b'*', b'/', rest @ ..] => {
+            glob_matches(rest, path)
+                || (0..path.len()).any(|i| path[i] == b'/' && glob_matches(rest, &path[i + 1..]))
+        }
+        [b'*', b'*', rest @ ..] => (0..=path.len()).any(|i| glob_matches(rest, &path[i..])),
+        [b'*', rest @ ..] => (0..=path.len())
+            .take_while(|i| *i == 0 || path[i - 1] != b'/')
+            .any(|i| glob_matches(rest, &path[i..])),
+        [b'?', rest @ ..] => match path {
+            [c, tail @ ..] if *c != b'/' => glob_matches(rest, tail),
+            _ => false,
+        },
+        [c, rest @ ..] => match path {
+            [d, tail @ ..] if c == d => glob_matches(rest, tail),
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_glob_matches() {
+        let cases = [
+            ("src/*.rs", "src/lib.rs", true),
+            ("src/*.rs", "src/cob/patch.rs", false),
+            ("src/**/*.rs", "src/cob/patch.rs", true),
+            ("src/**/*.rs", "src/lib.rs", true),
+            ("**/Cargo.toml", "Cargo.toml", true),
+            ("**/Cargo.toml", "radicle/Cargo.toml", true),
+            ("docs/**", "docs/guide/intro.md", true),
+            ("docs/**", "src/docs.rs", false),
+            ("README.?d", "README.md", true),
+            ("README.?d", "README.d", false),
+        ];
+        for (glob, path, expected) in cases {
+            assert_eq!(
+                glob_matches(glob.as_bytes(), path.as_bytes()),
+                expected,
+                "{glob} {path}"
+            );
+        }
+    }
+}
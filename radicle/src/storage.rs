@@ -1,4 +1,5 @@
 pub mod git;
+pub mod quota;
 pub mod refs;
 
 use std::collections::{hash_map, BTreeSet, HashSet};
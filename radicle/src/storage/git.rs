@@ -139,26 +139,7 @@ impl ReadStorage for Storage {
     fn repositories(&self) -> Result<Vec<RepositoryInfo<Verified>>, Error> {
         let mut repos = Vec::new();
 
-        for result in fs::read_dir(&self.path)? {
-            let path = result?;
-
-            // Skip non-directories.
-            if !path.file_type()?.is_dir() {
-                continue;
-            }
-            // Skip hidden files.
-            if path.file_name().to_string_lossy().starts_with('.') {
-                continue;
-            }
-            // Skip lock files.
-            if let Some(ext) = path.path().extension() {
-                if ext == "lock" {
-                    continue;
-                }
-            }
-            let rid = RepoId::try_from(path.file_name())
-                .map_err(|_| Error::InvalidId(path.file_name()))?;
-
+        for rid in self.repository_ids()? {
             let repo = match self.repository(rid) {
                 Ok(repo) => repo,
                 Err(e) => {
@@ -298,6 +279,55 @@ impl Storage {
         Ok(())
     }
 
+    /// Verify the integrity of all repositories in storage, without modifying them.
+    ///
+    /// Checks that every repository has a readable identity, that no reference points to a
+    /// missing object, and that the latest change of every collaborative object can be loaded.
+    pub fn verify(&self) -> Vec<IntegrityError> {
+        let rids = match self.repository_ids() {
+            Ok(rids) => rids,
+            Err(err) => return vec![IntegrityError::Storage(err)],
+        };
+        let mut errors = Vec::new();
+
+        for rid in rids {
+            match self.repository(rid) {
+                Ok(repo) => errors.extend(repo.verify()),
+                Err(err) => errors.push(IntegrityError::Repository { rid, err }),
+            }
+        }
+        errors
+    }
+
+    /// Get the identifiers of all repositories in storage, including invalid ones.
+    fn repository_ids(&self) -> Result<Vec<RepoId>, Error> {
+        let mut rids = Vec::new();
+
+        for result in fs::read_dir(&self.path)? {
+            let path = result?;
+
+            // Skip non-directories.
+            if !path.file_type()?.is_dir() {
+                continue;
+            }
+            // Skip hidden files.
+            if path.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            // Skip lock files.
+            if let Some(ext) = path.path().extension() {
+                if ext == "lock" {
+                    continue;
+                }
+            }
+            let rid = RepoId::try_from(path.file_name())
+                .map_err(|_| Error::InvalidId(path.file_name()))?;
+
+            rids.push(rid);
+        }
+        Ok(rids)
+    }
+
     fn public_repositories(&self) -> Result<impl Iterator<Item = RepoId>, Error> {
         let repos = self.repositories()?;
         Ok(repos
@@ -482,6 +512,140 @@ impl Repository {
         Ok(())
     }
 
+    /// Verify the integrity of this repository, without modifying it.
+    /// See [`Storage::verify`].
+    pub fn verify(&self) -> Vec<IntegrityError> {
+        let rid = self.id;
+        let mut errors = Vec::new();
+
+        if let Err(err) = self.identity_doc() {
+            errors.push(IntegrityError::Identity { rid, err });
+        }
+        let (odb, refs) = match (self.backend.odb(), self.backend.references()) {
+            (Ok(odb), Ok(refs)) => (odb, refs),
+            (Err(err), _) | (_, Err(err)) => {
+                errors.push(IntegrityError::Git { rid, err });
+                return errors;
+            }
+        };
+
+        for r in refs {
+            let r = match r {
+                Ok(r) => r,
+                Err(err) => {
+                    errors.push(IntegrityError::Git { rid, err });
+                    continue;
+                }
+            };
+            let name = String::from_utf8_lossy(r.name_bytes()).into_owned();
+            let oid = match r.resolve() {
+                Ok(r) => r.target(),
+                Err(err) => {
+                    errors.push(IntegrityError::Ref { rid, name, err });
+                    continue;
+                }
+            };
+            let Some(oid) = oid else {
+                continue;
+            };
+            if !odb.exists(oid) {
+                errors.push(IntegrityError::DanglingRef {
+                    rid,
+                    name,
+                    oid: oid.into(),
+                });
+                continue;
+            }
+            if name.contains("/refs/cobs/") {
+                if let Err(err) = crate::cob::change::Storage::load(self, oid.into()) {
+                    errors.push(IntegrityError::Cob { rid, name, err });
+                }
+            }
+        }
+        match self.orphaned_cob_refs() {
+            Ok(orphans) => errors.extend(
+                orphans
+                    .into_iter()
+                    .map(|orphan| IntegrityError::OrphanedCobRef { rid, orphan }),
+            ),
+            Err(err) => errors.push(IntegrityError::Storage(err)),
+        }
+        errors
+    }
+
+    /// Find the collaborative object references that don't match the signed refs of their
+    /// namespace, eg. because an operation was interrupted after updating the reference, but
+    /// before signing refs. The changes these references point to can't be verified by peers.
+    pub fn orphaned_cob_refs(&self) -> Result<Vec<OrphanedRef>, Error> {
+        let mut sigrefs = HashMap::new();
+        let mut orphans = Vec::new();
+
+        for r in self.backend.references_glob("refs/namespaces/*/refs/cobs/*")? {
+            let r = r?;
+            let Some(oid) = r.target() else {
+                continue;
+            };
+            let name = r.name().ok_or(Error::InvalidRef)?;
+            let (remote, name) = git::parse_ref_namespaced::<RemoteId>(name)?;
+
+            if !sigrefs.contains_key(&remote) {
+                sigrefs.insert(remote, SignedRefsAt::load(remote, self)?);
+            }
+            let signed = sigrefs[&remote].as_ref().and_then(|s| s.get(&name));
+
+            if signed != Some(oid.into()) {
+                orphans.push(OrphanedRef {
+                    remote,
+                    name: name.to_ref_string(),
+                    oid: oid.into(),
+                    signed,
+                });
+            }
+        }
+        Ok(orphans)
+    }
+
+    /// Repair orphaned collaborative object references. See [`Repository::orphaned_cob_refs`].
+    ///
+    /// References under the signer's namespace are reattached, by signing its refs. If `remove`
+    /// is `true`, references under other namespaces are reset to their signed target, or removed
+    /// if they aren't signed; otherwise they are left untouched.
+    ///
+    /// Returns the references that were repaired.
+    pub fn repair_orphaned_cob_refs<G: Signer>(
+        &self,
+        signer: &G,
+        remove: bool,
+    ) -> Result<Vec<(OrphanedRef, Repair)>, Error> {
+        let mut repaired = Vec::new();
+        let mut reattach = false;
+
+        for orphan in self.orphaned_cob_refs()? {
+            if orphan.remote == *signer.public_key() {
+                reattach = true;
+                repaired.push((orphan, Repair::Reattached));
+                continue;
+            }
+            if !remove {
+                continue;
+            }
+            let name = format!("refs/namespaces/{}/{}", orphan.remote, orphan.name);
+
+            if let Some(signed) = orphan.signed {
+                self.backend
+                    .reference(&name, *signed, true, "repair orphaned ref (radicle)")?;
+                repaired.push((orphan, Repair::Reset(signed)));
+            } else {
+                self.backend.find_reference(&name)?.delete()?;
+                repaired.push((orphan, Repair::Removed));
+            }
+        }
+        if reattach {
+            self.sign_refs(signer)?;
+        }
+        Ok(repaired)
+    }
+
     /// Iterate over all references.
     pub fn references(
         &self,
@@ -922,6 +1086,81 @@ impl SignRepository for Repository {
     }
 }
 
+/// A collaborative object reference that doesn't match the signed refs of its namespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrphanedRef {
+    /// Namespace of the reference.
+    pub remote: RemoteId,
+    /// Name of the reference, without the namespace, eg. `refs/cobs/xyz.radicle.issue/<id>`.
+    pub name: git::RefString,
+    /// Target of the reference.
+    pub oid: Oid,
+    /// Target of the reference in the signed refs, if any.
+    pub signed: Option<Oid>,
+}
+
+/// How an orphaned reference was repaired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repair {
+    /// The reference was signed.
+    Reattached,
+    /// The reference was reset to its signed target.
+    Reset(Oid),
+    /// The reference was removed, since it wasn't signed.
+    Removed,
+}
+
+/// An integrity problem found while verifying storage.
+#[derive(Debug, Error)]
+pub enum IntegrityError {
+    /// Storage could not be read.
+    #[error("storage could not be read: {0}")]
+    Storage(#[source] Error),
+    /// Repository could not be opened.
+    #[error("repository {rid} could not be opened: {err}")]
+    Repository {
+        rid: RepoId,
+        #[source]
+        err: RepositoryError,
+    },
+    /// Repository identity is missing or invalid.
+    #[error("repository {rid} has a missing or invalid identity: {err}")]
+    Identity {
+        rid: RepoId,
+        #[source]
+        err: RepositoryError,
+    },
+    /// Repository references could not be listed.
+    #[error("repository {rid} could not be read: {err}")]
+    Git {
+        rid: RepoId,
+        #[source]
+        err: git2::Error,
+    },
+    /// Reference could not be resolved.
+    #[error("reference {name} of {rid} could not be resolved: {err}")]
+    Ref {
+        rid: RepoId,
+        name: String,
+        #[source]
+        err: git2::Error,
+    },
+    /// Reference points to a missing object.
+    #[error("reference {name} of {rid} points to missing object {oid}")]
+    DanglingRef { rid: RepoId, name: String, oid: Oid },
+    /// Collaborative object could not be loaded.
+    #[error("object {name} of {rid} could not be loaded: {err}")]
+    Cob {
+        rid: RepoId,
+        name: String,
+        #[source]
+        err: crate::cob::git::change::error::Load,
+    },
+    /// Collaborative object reference doesn't match the signed refs.
+    #[error("object {} of {rid} under {} is not signed", orphan.name, orphan.remote)]
+    OrphanedCobRef { rid: RepoId, orphan: OrphanedRef },
+}
+
 #[derive(Debug, Error)]
 pub enum QuorumError {
     #[error("no quorum was found")]
@@ -1060,6 +1299,8 @@ pub mod paths {
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
+    use std::str::FromStr;
+
     use crypto::test::signer::MockSigner;
 
     use super::*;
@@ -1312,6 +1553,85 @@ mod tests {
         assert_eq!(refs, remotes);
     }
 
+    #[test]
+    fn test_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let signer = MockSigner::default();
+        let storage = fixtures::storage(dir.path(), &signer).unwrap();
+        let rid = storage.repositories().unwrap().first().unwrap().rid;
+
+        assert!(storage.verify().is_empty());
+
+        // Corrupt a reference by pointing it to an object that doesn't exist.
+        let missing = git::Oid::from_str("ffffffffffffffffffffffffffffffffffffffff").unwrap();
+        let path = storage.path_of(&rid).join("refs").join("heads").join("corrupt");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, format!("{missing}\n")).unwrap();
+
+        let errors = storage.verify();
+        assert_eq!(errors.len(), 1);
+        assert_matches!(
+            &errors[0],
+            IntegrityError::DanglingRef { rid: r, name, oid }
+            if r == &rid && name == "refs/heads/corrupt" && oid == &missing
+        );
+    }
+
+    #[test]
+    fn test_orphaned_cob_refs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let signer = MockSigner::default();
+        let storage = Storage::open(tmp.path().join("storage"), fixtures::user()).unwrap();
+
+        transport::local::register(storage.clone());
+
+        let (rid, _, _, head) =
+            fixtures::project(tmp.path().join("project"), &storage, &signer).unwrap();
+        let repo = storage.repository(rid).unwrap();
+        let remote = arbitrary::gen::<RemoteId>(1);
+        let ours = format!(
+            "refs/namespaces/{}/refs/cobs/xyz.radicle.issue/{head}",
+            signer.public_key()
+        );
+        let theirs = format!("refs/namespaces/{remote}/refs/cobs/xyz.radicle.issue/{head}");
+
+        assert!(repo.orphaned_cob_refs().unwrap().is_empty());
+
+        // Simulate interrupted operations, which updated references without signing them.
+        for name in [&ours, &theirs] {
+            repo.backend.reference(name, head, false, "test").unwrap();
+        }
+        let orphans = repo.orphaned_cob_refs().unwrap();
+        assert_eq!(orphans.len(), 2);
+        assert!(orphans
+            .iter()
+            .all(|o| o.oid == git::Oid::from(head) && o.signed.is_none()));
+        assert_eq!(
+            repo.verify()
+                .iter()
+                .filter(|e| matches!(e, IntegrityError::OrphanedCobRef { .. }))
+                .count(),
+            2
+        );
+
+        // Our own reference is reattached, while the other one is only removed when asked to.
+        let repaired = repo.repair_orphaned_cob_refs(&signer, false).unwrap();
+        assert_eq!(repaired.len(), 1);
+        assert_eq!(repaired[0].0.remote, *signer.public_key());
+        assert_eq!(repaired[0].1, Repair::Reattached);
+
+        let orphans = repo.orphaned_cob_refs().unwrap();
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].remote, remote);
+
+        let repaired = repo.repair_orphaned_cob_refs(&signer, true).unwrap();
+        assert_eq!(repaired.len(), 1);
+        assert_eq!(repaired[0].1, Repair::Removed);
+        assert!(repo.backend.find_reference(&theirs).is_err());
+        assert!(repo.backend.find_reference(&ours).is_ok());
+        assert!(repo.orphaned_cob_refs().unwrap().is_empty());
+    }
+
     #[test]
     fn test_references_of() {
         let tmp = tempfile::tempdir().unwrap();
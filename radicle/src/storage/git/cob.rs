@@ -172,6 +172,58 @@ impl cob::object::Storage for Repository {
     }
 }
 
+impl Repository {
+    /// Fetch the reference of a single collaborative object of `remote` from the repository
+    /// at `source`, eg. another repository in storage.
+    ///
+    /// Only `refs/namespaces/<remote>/refs/cobs/<typename>/<object_id>` and the objects it
+    /// points to are fetched; all other references are skipped. This allows a single object
+    /// to be hydrated lazily, without fetching the rest of the repository.
+    ///
+    /// Returns the head of the object, or `None` if the source doesn't have it.
+    pub fn fetch_cob(
+        &self,
+        source: &Path,
+        remote: &RemoteId,
+        typename: &cob::TypeName,
+        object_id: &cob::ObjectId,
+    ) -> Result<Option<Oid>, git2::Error> {
+        let refname = git::refs::storage::cob(remote, typename, object_id);
+        let refspec = format!("+{}:{}", refname.as_str(), refname.as_str());
+        let mut remote_source = self
+            .backend
+            .remote_anonymous(&git::url::File::new(source).to_string())?;
+
+        remote_source.fetch(
+            &[refspec.as_str()],
+            Some(
+                git2::FetchOptions::new()
+                    .update_fetchhead(false)
+                    .download_tags(git2::AutotagOption::None),
+            ),
+            None,
+        )?;
+        self.cob_head(remote, typename, object_id)
+    }
+
+    /// Read the head of a single collaborative object of `remote`, without looking at any
+    /// other reference.
+    pub fn cob_head(
+        &self,
+        remote: &RemoteId,
+        typename: &cob::TypeName,
+        object_id: &cob::ObjectId,
+    ) -> Result<Option<Oid>, git2::Error> {
+        let refname = git::refs::storage::cob(remote, typename, object_id);
+
+        match self.backend.refname_to_id(refname.as_str()) {
+            Ok(oid) => Ok(Some(oid.into())),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
 /// Stores draft collaborative objects.
 ///
 // This storage backend for COBs stores changes in a `draft/cobs/*` namespace,
@@ -435,3 +487,56 @@ impl<'a, R: storage::WriteRepository> cob::object::Storage for DraftStore<'a, R>
         reference.delete()
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::cob::issue;
+    use crate::storage::WriteStorage as _;
+    use crate::test;
+
+    #[test]
+    fn test_fetch_cob() {
+        let alice = test::setup::NodeWithRepo::default();
+        let bob = test::setup::Node::default();
+        let mut issues = issue::Cache::no_cache(&*alice.repo).unwrap();
+        let wanted = *issues
+            .create("Wanted", "Blah blah blah.", &[], &[], [], &alice.signer)
+            .unwrap()
+            .id();
+        let skipped = *issues
+            .create("Skipped", "Blah blah blah.", &[], &[], [], &alice.signer)
+            .unwrap()
+            .id();
+        let remote = alice.signer.public_key();
+        let repo = bob.storage.create(alice.repo.id).unwrap();
+
+        let head = repo
+            .fetch_cob(alice.repo.path(), remote, &issue::TYPENAME, &wanted)
+            .unwrap();
+        assert_eq!(
+            head,
+            alice.repo.cob_head(remote, &issue::TYPENAME, &wanted).unwrap()
+        );
+        assert!(head.is_some());
+        assert_eq!(
+            repo.cob_head(remote, &issue::TYPENAME, &skipped).unwrap(),
+            None
+        );
+
+        // Only the targeted object's reference was materialized.
+        let refs = repo
+            .references()
+            .unwrap()
+            .map(|r| r.unwrap())
+            .map(|r| (r.namespace, r.name))
+            .collect::<Vec<_>>();
+        let wanted = git::refs::storage::cob(remote, &issue::TYPENAME, &wanted);
+
+        assert_eq!(
+            refs,
+            vec![(Some(*remote), wanted.strip_namespace().to_ref_string())]
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Storage quotas of remote namespaces.
+//!
+//! Each remote can only publish references under its own namespace, but without a limit,
+//! a remote could fill our disk by publishing an unbounded number of references or objects.
+//! A [`Quota`] bounds the number of references of a namespace, and the size of the objects
+//! that are only reachable from it.
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::git::raw as git2;
+use crate::storage::git::Repository;
+use crate::storage::refs::Refs;
+use crate::storage::RemoteId;
+
+/// Default maximum number of references of a namespace.
+pub const DEFAULT_MAX_REFS: usize = 16 * 1024;
+/// Default maximum size of the objects of a namespace, 1Gb.
+pub const DEFAULT_MAX_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Error checking a namespace against its quota.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("namespace {remote} has {refs} references, exceeding its quota of {max}")]
+    Refs {
+        remote: RemoteId,
+        refs: usize,
+        max: usize,
+    },
+    #[error("namespace {remote} has {bytes} bytes of objects, exceeding its quota of {max}")]
+    Bytes {
+        remote: RemoteId,
+        bytes: u64,
+        max: u64,
+    },
+    #[error(transparent)]
+    Git(#[from] git2::Error),
+}
+
+impl Error {
+    /// Whether the quota was exceeded, as opposed to failing to be checked.
+    pub fn is_exceeded(&self) -> bool {
+        matches!(self, Self::Refs { .. } | Self::Bytes { .. })
+    }
+}
+
+/// Storage quota of a remote namespace.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Quota {
+    /// Maximum number of references.
+    pub refs: usize,
+    /// Maximum size, in bytes, of the objects only reachable from the namespace.
+    pub bytes: u64,
+}
+
+impl Default for Quota {
+    fn default() -> Self {
+        Self {
+            refs: DEFAULT_MAX_REFS,
+            bytes: DEFAULT_MAX_BYTES,
+        }
+    }
+}
+
+impl Quota {
+    /// Check that a namespace with the given references would be within quota.
+    ///
+    /// The objects of the references must already be in the repository, but the
+    /// references themselves don't have to be.
+    pub fn check(
+        &self,
+        repo: &Repository,
+        remote: &RemoteId,
+        refs: &Refs,
+    ) -> Result<Usage, Error> {
+        if refs.len() > self.refs {
+            return Err(Error::Refs {
+                remote: *remote,
+                refs: refs.len(),
+                max: self.refs,
+            });
+        }
+        let usage = Usage::of(repo, remote, refs)?;
+
+        if usage.bytes > self.bytes {
+            return Err(Error::Bytes {
+                remote: *remote,
+                bytes: usage.bytes,
+                max: self.bytes,
+            });
+        }
+        Ok(usage)
+    }
+}
+
+/// Storage used by a namespace.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Usage {
+    /// Number of references.
+    pub refs: usize,
+    /// Size, in bytes, of the objects only reachable from the namespace.
+    pub bytes: u64,
+}
+
+impl Usage {
+    /// Compute the storage used by a namespace with the given references.
+    ///
+    /// The size is an estimate: it is the size of the commits that are not reachable from
+    /// any other namespace, or from the canonical references, along with the blobs these
+    /// commits add or modify.
+    pub fn of(repo: &Repository, remote: &RemoteId, refs: &Refs) -> Result<Self, git2::Error> {
+        let odb = repo.backend.odb()?;
+        let mut walk = repo.backend.revwalk()?;
+        let mut blobs = HashSet::new();
+        let mut bytes = 0;
+
+        for oid in refs.values() {
+            if repo.backend.find_commit(**oid).is_ok() {
+                walk.push(**oid)?;
+            } else {
+                bytes += odb.read_header(**oid)?.0 as u64;
+            }
+        }
+        // Don't count the objects shared with other namespaces, eg. the project history.
+        let namespace = format!("refs/namespaces/{remote}/");
+        for r in repo.backend.references()? {
+            let r = r?;
+            let (Some(name), Some(oid)) = (r.name(), r.target()) else {
+                continue;
+            };
+            if !name.starts_with(&namespace) && repo.backend.find_commit(oid).is_ok() {
+                walk.hide(oid)?;
+            }
+        }
+
+        for oid in walk {
+            let commit = repo.backend.find_commit(oid?)?;
+            let tree = commit.tree()?;
+            let parent = match commit.parents().next() {
+                Some(parent) => Some(parent.tree()?),
+                None => None,
+            };
+            let diff = repo
+                .backend
+                .diff_tree_to_tree(parent.as_ref(), Some(&tree), None)?;
+
+            bytes += odb.read_header(commit.id())?.0 as u64;
+
+            for delta in diff.deltas() {
+                let file = delta.new_file();
+
+                if file.exists() && blobs.insert(file.id()) {
+                    bytes += file.size();
+                }
+            }
+        }
+        Ok(Self {
+            refs: refs.len(),
+            bytes,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use super::*;
+    use crate::crypto::Signer as _;
+    use crate::storage::ReadRepository as _;
+    use crate::test::setup::NodeWithRepo;
+
+    #[test]
+    fn test_quota() {
+        let NodeWithRepo { node, repo } = NodeWithRepo::default();
+        let remote = node.signer.public_key();
+        let refs = repo.references_of(remote).unwrap();
+        let usage = Usage::of(&repo, remote, &refs).unwrap();
+
+        assert_eq!(usage.refs, refs.len());
+        assert!(usage.bytes > 0);
+        assert!(Quota::default().check(&repo, remote, &refs).is_ok());
+        assert!(matches!(
+            Quota {
+                refs: refs.len() - 1,
+                ..Quota::default()
+            }
+            .check(&repo, remote, &refs),
+            Err(Error::Refs { .. })
+        ));
+        assert!(matches!(
+            Quota {
+                bytes: usage.bytes - 1,
+                ..Quota::default()
+            }
+            .check(&repo, remote, &refs),
+            Err(Error::Bytes { .. })
+        ));
+    }
+}